@@ -17,6 +17,7 @@
 // | with Tachyomancer.  If not, see <http://www.gnu.org/licenses/>.          |
 // +--------------------------------------------------------------------------+
 
+use super::dir::{Direction, DirectionIter};
 use super::rect::{Rect, RectSize};
 use cgmath::{Point2, Vector2};
 
@@ -31,3 +32,125 @@ pub type CoordsRect = Rect<i32>;
 pub type CoordsSize = RectSize<i32>;
 
 //===========================================================================//
+
+/// Serde helpers for serializing a `Coords` compactly as an `[x, y]` pair,
+/// for use with `#[serde(with = "coords_pair")]`.  (Since `Coords` is a type
+/// alias, we can't implement the serde traits for it directly.)
+pub mod coords_pair {
+    use super::Coords;
+
+    pub fn serialize<S>(
+        coords: &Coords,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serde::Serialize::serialize(&(coords.x, coords.y), serializer)
+    }
+
+    pub fn deserialize<'d, D>(deserializer: D) -> Result<Coords, D::Error>
+    where
+        D: serde::Deserializer<'d>,
+    {
+        let (x, y) =
+            <(i32, i32) as serde::Deserialize>::deserialize(deserializer)?;
+        Ok(Coords::new(x, y))
+    }
+}
+
+//===========================================================================//
+
+pub trait CoordsExt {
+    /// Returns the coordinates adjacent to these in the given direction.
+    fn neighbor(self, dir: Direction) -> Coords;
+
+    /// Returns an iterator over each direction, paired with the coordinates
+    /// adjacent to these in that direction.
+    fn neighbors(self) -> CoordsNeighbors;
+}
+
+impl CoordsExt for Coords {
+    fn neighbor(self, dir: Direction) -> Coords {
+        self + dir.delta()
+    }
+
+    fn neighbors(self) -> CoordsNeighbors {
+        CoordsNeighbors { coords: self, dirs: Direction::all() }
+    }
+}
+
+//===========================================================================//
+
+pub struct CoordsNeighbors {
+    coords: Coords,
+    dirs: DirectionIter,
+}
+
+impl Iterator for CoordsNeighbors {
+    type Item = (Direction, Coords);
+
+    fn next(&mut self) -> Option<(Direction, Coords)> {
+        self.dirs.next().map(|dir| (dir, self.coords.neighbor(dir)))
+    }
+}
+
+//===========================================================================//
+
+#[cfg(test)]
+mod tests {
+    use super::{Coords, CoordsExt};
+    use crate::geom::Direction;
+
+    #[derive(Debug, Deserialize, PartialEq, Serialize)]
+    struct CoordsHolder {
+        #[serde(with = "super::coords_pair")]
+        coords: Coords,
+    }
+
+    #[test]
+    fn coords_serde_round_trip() {
+        for x in -3..4 {
+            for &y in &[i32::MIN, -1000, -1, 0, 1, 17, i32::MAX] {
+                let holder = CoordsHolder { coords: Coords::new(x, y) };
+                let string = toml::to_string(&holder).unwrap();
+                assert_eq!(string, format!("coords = [{}, {}]\n", x, y));
+                assert_eq!(
+                    toml::from_str::<CoordsHolder>(&string),
+                    Ok(holder)
+                );
+            }
+        }
+        assert!(toml::from_str::<CoordsHolder>("coords = [1]").is_err());
+    }
+
+    #[test]
+    fn coords_neighbor() {
+        let coords = Coords::new(3, -4);
+        assert_eq!(coords.neighbor(Direction::East), Coords::new(4, -4));
+        assert_eq!(coords.neighbor(Direction::South), Coords::new(3, -3));
+        assert_eq!(coords.neighbor(Direction::West), Coords::new(2, -4));
+        assert_eq!(coords.neighbor(Direction::North), Coords::new(3, -5));
+        for dir in Direction::all() {
+            assert_eq!(coords.neighbor(dir), coords + dir);
+            assert_eq!(coords.neighbor(dir).neighbor(-dir), coords);
+        }
+    }
+
+    #[test]
+    fn coords_neighbors() {
+        let coords = Coords::new(-1, 7);
+        let neighbors: Vec<(Direction, Coords)> = coords.neighbors().collect();
+        assert_eq!(
+            neighbors,
+            vec![
+                (Direction::East, Coords::new(0, 7)),
+                (Direction::South, Coords::new(-1, 8)),
+                (Direction::West, Coords::new(-2, 7)),
+                (Direction::North, Coords::new(-1, 6)),
+            ]
+        );
+    }
+}
+
+//===========================================================================//
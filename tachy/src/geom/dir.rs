@@ -19,12 +19,17 @@
 
 use super::coords::{Coords, CoordsDelta};
 use cgmath::{vec2, Deg};
+use serde::de::Error;
+use std::fmt;
 use std::ops;
+use std::str;
 use strum::IntoEnumIterator;
 
 //===========================================================================//
 
-#[derive(Clone, Copy, Debug, EnumIter, Eq, Hash, PartialEq)]
+#[derive(
+    Clone, Copy, Debug, EnumIter, Eq, Hash, Ord, PartialEq, PartialOrd,
+)]
 pub enum Direction {
     East,
     South,
@@ -90,6 +95,54 @@ impl Direction {
     }
 }
 
+/// Formats the direction as a single-character code (e.g. `e` for east).
+impl fmt::Display for Direction {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        let chr = match self {
+            Direction::East => 'e',
+            Direction::South => 's',
+            Direction::West => 'w',
+            Direction::North => 'n',
+        };
+        formatter.write_fmt(format_args!("{}", chr))
+    }
+}
+
+impl str::FromStr for Direction {
+    type Err = String;
+
+    fn from_str(string: &str) -> Result<Direction, String> {
+        match string {
+            "e" => Ok(Direction::East),
+            "s" => Ok(Direction::South),
+            "w" => Ok(Direction::West),
+            "n" => Ok(Direction::North),
+            _ => Err(string.to_string()),
+        }
+    }
+}
+
+impl<'d> serde::Deserialize<'d> for Direction {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'d>,
+    {
+        let string = String::deserialize(deserializer)?;
+        string.parse().map_err(|_| {
+            D::Error::custom(format!("Invalid direction: {:?}", string))
+        })
+    }
+}
+
+impl serde::Serialize for Direction {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.to_string().serialize(serializer)
+    }
+}
+
 impl ops::Add<Direction> for Coords {
     type Output = Coords;
 
@@ -165,6 +218,7 @@ pub enum DirDelta {
 mod tests {
     use super::{Coords, Direction};
     use cgmath::{Angle, Deg};
+    use std::collections::HashMap;
 
     #[test]
     fn direction_delta_angle() {
@@ -175,6 +229,32 @@ mod tests {
         }
     }
 
+    #[test]
+    fn direction_to_and_from_string() {
+        for dir in Direction::all() {
+            let string = dir.to_string();
+            assert_eq!(string.len(), 1);
+            assert_eq!(string.parse(), Ok(dir));
+        }
+        assert!("x".parse::<Direction>().is_err());
+        assert!("East".parse::<Direction>().is_err());
+    }
+
+    #[test]
+    fn direction_serde_round_trip() {
+        for dir in Direction::all() {
+            let mut map = HashMap::<String, Direction>::new();
+            map.insert("dir".to_string(), dir);
+            let string = toml::to_string(&map).unwrap();
+            assert_eq!(string, format!("dir = \"{}\"\n", dir));
+            let map: HashMap<String, Direction> =
+                toml::from_str(&string).unwrap();
+            assert_eq!(map.get("dir"), Some(&dir));
+        }
+        assert!(toml::from_str::<HashMap<String, Direction>>("dir = \"q\"")
+            .is_err());
+    }
+
     #[test]
     fn direction_flip() {
         for dir in Direction::all() {
@@ -29,7 +29,10 @@ mod rect;
 
 pub use self::cast::{AsFloat, AsInt};
 pub use self::color::{Color3, Color4};
-pub use self::coords::{Coords, CoordsDelta, CoordsRect, CoordsSize};
+pub use self::coords::{
+    coords_pair, Coords, CoordsDelta, CoordsExt, CoordsNeighbors, CoordsRect,
+    CoordsSize,
+};
 pub use self::dir::{DirDelta, Direction, DirectionIter};
 pub use self::fixed::Fixed;
 pub use self::matrix::MatrixExt;
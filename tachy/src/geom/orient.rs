@@ -22,6 +22,7 @@ use super::dir::Direction;
 use super::matrix::MatrixExt;
 use super::rect::RectSize;
 use cgmath::{Deg, Matrix4};
+use serde::de::Error;
 use std::fmt;
 use std::ops;
 use std::str;
@@ -128,6 +129,27 @@ impl str::FromStr for Orientation {
     }
 }
 
+impl<'d> serde::Deserialize<'d> for Orientation {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'d>,
+    {
+        let string = String::deserialize(deserializer)?;
+        string.parse().map_err(|_| {
+            D::Error::custom(format!("Invalid orientation: {:?}", string))
+        })
+    }
+}
+
+impl serde::Serialize for Orientation {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.to_string().serialize(serializer)
+    }
+}
+
 impl ops::Mul<Orientation> for Orientation {
     type Output = Orientation;
 
@@ -174,6 +196,18 @@ impl<T> ops::Mul<RectSize<T>> for Orientation {
 #[cfg(test)]
 mod tests {
     use super::{Direction, Orientation};
+    use std::collections::HashMap;
+
+    fn all_orientations() -> Vec<Orientation> {
+        let mut orients = Vec::new();
+        let mut orient = Orientation::default();
+        for _ in 0..4 {
+            orients.push(orient);
+            orients.push(orient.flip_vert());
+            orient = orient.rotate_cw();
+        }
+        orients
+    }
 
     #[test]
     fn orientation_to_and_from_string() {
@@ -190,6 +224,25 @@ mod tests {
         assert_eq!("t2".parse(), Ok(orient));
     }
 
+    #[test]
+    fn orientation_serde_round_trip() {
+        let orients = all_orientations();
+        for (index, &orient) in orients.iter().enumerate() {
+            assert!(!orients[..index].contains(&orient));
+            let mut map = HashMap::<String, Orientation>::new();
+            map.insert("orient".to_string(), orient);
+            let string = toml::to_string(&map).unwrap();
+            assert_eq!(string, format!("orient = \"{}\"\n", orient));
+            let map: HashMap<String, Orientation> =
+                toml::from_str(&string).unwrap();
+            assert_eq!(map.get("orient"), Some(&orient));
+        }
+        assert!(toml::from_str::<HashMap<String, Orientation>>(
+            "orient = \"f4\""
+        )
+        .is_err());
+    }
+
     #[test]
     fn orientation_times_direction() {
         let orient = Orientation::default();
@@ -55,6 +55,21 @@ impl<T: BaseNum> RectSize<T> {
     }
 }
 
+impl RectSize<i32> {
+    /// Returns the area of this size, or `None` if the area would overflow
+    /// an `i32`.  Use this instead of `area()` for sizes that may come from
+    /// untrusted data.
+    pub fn checked_area(&self) -> Option<i32> {
+        self.width.checked_mul(self.height)
+    }
+
+    /// Returns the area of this size, saturating at the numeric bounds
+    /// instead of overflowing.
+    pub fn saturating_area(&self) -> i32 {
+        self.width.saturating_mul(self.height)
+    }
+}
+
 impl AsFloat for RectSize<i32> {
     type Output32 = RectSize<f32>;
 
@@ -229,6 +244,20 @@ impl<T: BaseNum> Rect<T> {
     }
 }
 
+impl Rect<i32> {
+    /// Returns the area of this rect, or `None` if the area would overflow an
+    /// `i32`.
+    pub fn checked_area(&self) -> Option<i32> {
+        self.size().checked_area()
+    }
+
+    /// Returns the area of this rect, saturating at the numeric bounds
+    /// instead of overflowing.
+    pub fn saturating_area(&self) -> i32 {
+        self.size().saturating_area()
+    }
+}
+
 impl AsFloat for Rect<i32> {
     type Output32 = Rect<f32>;
 
@@ -347,10 +376,12 @@ impl Iterator for RectPointsIter<i32> {
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        let width = self.x_hi - self.x_lo;
-        let height = self.y_hi - self.y;
-        let size = width * height - (self.x - self.x_lo);
-        let size = size.max(0) as usize;
+        // Compute this in i64, since the area of a large rect can overflow
+        // an i32.
+        let width = (self.x_hi as i64) - (self.x_lo as i64);
+        let height = (self.y_hi as i64) - (self.y as i64);
+        let size = width * height - ((self.x as i64) - (self.x_lo as i64));
+        let size = (size.max(0) as u64).min(usize::MAX as u64) as usize;
         (size, Some(size))
     }
 }
@@ -378,6 +409,32 @@ mod tests {
         );
     }
 
+    #[test]
+    fn rect_size_checked_area() {
+        assert_eq!(RectSize::new(3, 4).checked_area(), Some(12));
+        assert_eq!(RectSize::new(3, -4).checked_area(), Some(-12));
+        assert_eq!(RectSize::new(i32::MAX, 1).checked_area(), Some(i32::MAX));
+        assert_eq!(RectSize::new(i32::MAX, 2).checked_area(), None);
+        assert_eq!(RectSize::new(i32::MAX, i32::MAX).checked_area(), None);
+        assert_eq!(RectSize::new(i32::MIN, 1).checked_area(), Some(i32::MIN));
+        assert_eq!(RectSize::new(i32::MIN, -1).checked_area(), None);
+        assert_eq!(RectSize::new(i32::MIN, i32::MIN).checked_area(), None);
+        assert_eq!(Rect::new(-5, 7, 3, 4).checked_area(), Some(12));
+        assert_eq!(Rect::new(0, 0, i32::MAX, 3).checked_area(), None);
+    }
+
+    #[test]
+    fn rect_size_saturating_area() {
+        assert_eq!(RectSize::new(3, 4).saturating_area(), 12);
+        assert_eq!(RectSize::new(i32::MAX, 2).saturating_area(), i32::MAX);
+        assert_eq!(RectSize::new(i32::MAX, -2).saturating_area(), i32::MIN);
+        assert_eq!(
+            RectSize::new(i32::MIN, i32::MIN).saturating_area(),
+            i32::MAX
+        );
+        assert_eq!(Rect::new(0, 0, i32::MAX, 3).saturating_area(), i32::MAX);
+    }
+
     #[test]
     fn rect_size_mul_div() {
         assert_eq!(
@@ -488,6 +545,17 @@ mod tests {
         assert!(iter.next().is_none());
     }
 
+    #[test]
+    fn huge_rect_iter_exact_size() {
+        let rect = Rect::new(0, 0, i32::MAX, i32::MAX);
+        let expected = (i32::MAX as u64) * (i32::MAX as u64);
+        assert_eq!(rect.into_iter().len() as u64, expected);
+        let rect = Rect::new(-1, 0, i32::MAX, i32::MAX);
+        let mut iter = rect.into_iter();
+        assert!(iter.next().is_some());
+        assert_eq!(iter.len() as u64, expected - 1);
+    }
+
     #[test]
     fn negative_rect_iter() {
         let rect = Rect::new(1, 2, -3, 4);
@@ -17,8 +17,10 @@
 // | with Tachyomancer.  If not, see <http://www.gnu.org/licenses/>.          |
 // +--------------------------------------------------------------------------+
 
+extern crate base64;
 extern crate cgmath;
 extern crate downcast_rs;
+extern crate flate2;
 extern crate indexmap;
 extern crate num_integer;
 extern crate pathfinding;
@@ -39,6 +41,7 @@ extern crate toml;
 mod macros;
 
 pub mod geom;
+pub mod log;
 pub mod save;
 pub mod state;
 
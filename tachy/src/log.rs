@@ -0,0 +1,69 @@
+// +--------------------------------------------------------------------------+
+// | Copyright 2018 Matthew D. Steele <mdsteele@alum.mit.edu>                 |
+// |                                                                          |
+// | This file is part of Tachyomancer.                                       |
+// |                                                                          |
+// | Tachyomancer is free software: you can redistribute it and/or modify it  |
+// | under the terms of the GNU General Public License as published by the    |
+// | Free Software Foundation, either version 3 of the License, or (at your   |
+// | option) any later version.                                               |
+// |                                                                          |
+// | Tachyomancer is distributed in the hope that it will be useful, but      |
+// | WITHOUT ANY WARRANTY; without even the implied warranty of               |
+// | MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU        |
+// | General Public License for details.                                      |
+// |                                                                          |
+// | You should have received a copy of the GNU General Public License along  |
+// | with Tachyomancer.  If not, see <http://www.gnu.org/licenses/>.          |
+// +--------------------------------------------------------------------------+
+
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+//===========================================================================//
+
+// How many recent log lines to keep in memory:
+const MAX_RECENT_LINES: usize = 200;
+
+static RECENT_LINES: Mutex<VecDeque<String>> = Mutex::new(VecDeque::new());
+
+//===========================================================================//
+
+/// Records a line of log output, so that it can later be included in an issue
+/// report.  Only the most recent lines are kept.
+pub fn record_line(line: String) {
+    if let Ok(mut lines) = RECENT_LINES.lock() {
+        if lines.len() >= MAX_RECENT_LINES {
+            lines.pop_front();
+        }
+        lines.push_back(line);
+    }
+}
+
+/// Returns the most recently recorded log lines, oldest first.
+pub fn recent_lines() -> Vec<String> {
+    match RECENT_LINES.lock() {
+        Ok(lines) => lines.iter().cloned().collect(),
+        Err(_) => Vec::new(),
+    }
+}
+
+//===========================================================================//
+
+#[cfg(test)]
+mod tests {
+    use super::{recent_lines, record_line, MAX_RECENT_LINES};
+
+    #[test]
+    fn recent_lines_are_bounded() {
+        for index in 0..(MAX_RECENT_LINES + 10) {
+            record_line(format!("test line {}", index));
+        }
+        let lines = recent_lines();
+        assert!(lines.len() <= MAX_RECENT_LINES);
+        assert!(lines.contains(&format!("test line {}", MAX_RECENT_LINES + 9)));
+        assert!(!lines.contains(&"test line 0".to_string()));
+    }
+}
+
+//===========================================================================//
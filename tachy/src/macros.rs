@@ -17,24 +17,30 @@
 // | with Tachyomancer.  If not, see <http://www.gnu.org/licenses/>.          |
 // +--------------------------------------------------------------------------+
 
+/// Logs a message to stderr in debug builds.  The message is recorded for
+/// inclusion in issue reports in all builds.
 #[macro_export]
 macro_rules! debug_log {
-    ($e:expr) => {
-        (if cfg!(debug_assertions) { eprintln!($e); })
-    };
-    ($fmt:expr, $($arg:tt)+) => {
-        (if cfg!(debug_assertions) { eprintln!($fmt, $($arg)+); })
-    };
+    ($($arg:tt)+) => {{
+        let line = format!($($arg)+);
+        if cfg!(debug_assertions) {
+            eprintln!("{}", line);
+        }
+        $crate::log::record_line(line);
+    }};
 }
 
+/// Logs a warning to stderr in debug builds.  Warnings are recorded for
+/// inclusion in issue reports in all builds.
 #[macro_export]
 macro_rules! debug_warn {
-    ($($arg:tt)+) => {
-        (if cfg!(debug_assertions) {
-            eprint!("\x1b[31mWARNING:\x1b[m ");
-            debug_log!($($arg)+);
-        })
-    };
+    ($($arg:tt)+) => {{
+        let line = format!($($arg)+);
+        if cfg!(debug_assertions) {
+            eprintln!("\x1b[31mWARNING:\x1b[m {}", line);
+        }
+        $crate::log::record_line(format!("WARNING: {}", line));
+    }};
 }
 
 //===========================================================================//
@@ -30,19 +30,55 @@ use toml;
 
 //===========================================================================//
 
+/// The maximum number of characters permitted in a wire label.
+pub const MAX_WIRE_LABEL_CHARS: usize = 16;
+
+/// The maximum number of characters permitted in each of a circuit's notes.
+pub const MAX_CIRCUIT_NOTE_CHARS: usize = 500;
+
+//===========================================================================//
+
+/// The saved form of a circuit.  To keep circuit files (and network payloads)
+/// small, the serialized form omits empty sections and default chip
+/// orientations; these are filled back in on deserialization.
 #[derive(Clone, Deserialize, Serialize)]
 pub struct CircuitData {
     pub size: CoordsSize,
+    #[serde(default, skip_serializing_if = "CircuitChipData::is_empty")]
     pub chips: CircuitChipData,
+    #[serde(default, skip_serializing_if = "CircuitWireData::is_empty")]
     pub wires: CircuitWireData,
+    /// Purely cosmetic labels attached to wire fragments.  These have no
+    /// effect on evaluation, and are omitted from the file when empty.
+    #[serde(default, skip_serializing_if = "CircuitWireLabelData::is_empty")]
+    pub labels: CircuitWireLabelData,
+    /// Freeform notes about the circuit, for when it is shared.  Like wire
+    /// labels, these have no effect on evaluation, and are omitted from the
+    /// file when empty.
+    #[serde(default, skip_serializing_if = "CircuitNotes::is_empty")]
+    pub notes: CircuitNotes,
 }
 
 impl CircuitData {
     pub fn new(width: i32, height: i32) -> CircuitData {
         CircuitData {
             size: CoordsSize::new(width, height),
-            chips: CircuitChipData(BTreeMap::new()),
+            chips: CircuitChipData(BTreeMap::new(), BTreeMap::new()),
             wires: CircuitWireData(BTreeMap::new()),
+            labels: CircuitWireLabelData::default(),
+            notes: CircuitNotes::default(),
+        }
+    }
+
+    /// Returns a copy of this circuit with the same size, chips, and notes,
+    /// but with all wire fragments (and wire labels) removed.
+    pub fn without_wires(&self) -> CircuitData {
+        CircuitData {
+            size: self.size,
+            chips: self.chips.clone(),
+            wires: CircuitWireData(BTreeMap::new()),
+            labels: CircuitWireLabelData::default(),
+            notes: self.notes.clone(),
         }
     }
 
@@ -82,8 +118,15 @@ impl CircuitData {
 
 //===========================================================================//
 
-#[derive(Clone)]
-pub struct CircuitChipData(BTreeMap<(i32, i32), (ChipType, Orientation)>);
+/// The chips in a circuit.  Chips whose type can't be parsed (e.g. because
+/// the circuit file was hand-edited or written by a newer version of the
+/// game) are kept separately, so that the rest of the circuit can still be
+/// loaded; they are dropped if the data is serialized again.
+#[derive(Clone, Default)]
+pub struct CircuitChipData(
+    BTreeMap<(i32, i32), (ChipType, Orientation)>,
+    BTreeMap<(i32, i32), String>,
+);
 
 impl CircuitChipData {
     pub fn insert(
@@ -95,9 +138,23 @@ impl CircuitChipData {
         self.0.insert((delta.x, delta.y), (ctype, orient));
     }
 
+    /// Returns true if there are no (known) chips in the circuit.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
     pub fn iter(&self) -> CircuitChipDataIter {
         CircuitChipDataIter { inner: self.0.iter() }
     }
+
+    /// Returns the locations and type strings of any chips whose type could
+    /// not be parsed during deserialization.
+    pub fn unknown(&self) -> Vec<(CoordsDelta, &str)> {
+        self.1
+            .iter()
+            .map(|(&(x, y), name)| (CoordsDelta::new(x, y), name.as_str()))
+            .collect()
+    }
 }
 
 impl<'d> serde::Deserialize<'d> for CircuitChipData {
@@ -107,32 +164,23 @@ impl<'d> serde::Deserialize<'d> for CircuitChipData {
     {
         let map = BTreeMap::<&str, &str>::deserialize(deserializer)?;
         let mut chips = BTreeMap::new();
+        let mut unknown = BTreeMap::new();
         for (key, chip_str) in map.into_iter() {
             let coords = key_string_delta(key).ok_or_else(|| {
                 D::Error::custom(format!("Invalid coords key: {:?}", key))
             })?;
-            let mut items = chip_str.splitn(2, '-');
-            let orient_str = items.next().ok_or_else(|| {
-                D::Error::custom(format!("Invalid chip spec: {:?}", chip_str))
-            })?;
-            let orient = orient_str.parse::<Orientation>().map_err(|_| {
-                D::Error::custom(format!("Invalid chip spec: {:?}", chip_str))
-            })?;
-            let ctype_str = items.next().ok_or_else(|| {
-                D::Error::custom(format!("Invalid chip spec: {:?}", chip_str))
-            })?;
-            let ctype = ctype_str.parse::<ChipType>().map_err(|_| {
-                D::Error::custom(format!("Invalid chip spec: {:?}", chip_str))
-            })?;
-            if items.next().is_some() {
-                return Err(D::Error::custom(format!(
-                    "Invalid chip spec: {:?}",
-                    chip_str
-                )));
+            let (orient, ctype_str) = split_chip_spec(chip_str);
+            match ctype_str.parse::<ChipType>() {
+                Ok(ctype) => {
+                    chips.insert((coords.x, coords.y), (ctype, orient));
+                }
+                Err(_) => {
+                    unknown
+                        .insert((coords.x, coords.y), ctype_str.to_string());
+                }
             }
-            chips.insert((coords.x, coords.y), (ctype, orient));
         }
-        Ok(CircuitChipData(chips))
+        Ok(CircuitChipData(chips, unknown))
     }
 }
 
@@ -144,16 +192,30 @@ impl serde::Serialize for CircuitChipData {
         self.0
             .iter()
             .map(|(&(x, y), &(ctype, orient))| {
-                (
-                    delta_key_string(CoordsDelta::new(x, y)),
-                    format!("{}-{}", orient, ctype),
-                )
+                let chip_str = if orient == Orientation::default() {
+                    ctype.to_string()
+                } else {
+                    format!("{}-{}", orient, ctype)
+                };
+                (delta_key_string(CoordsDelta::new(x, y)), chip_str)
             })
             .collect::<BTreeMap<String, String>>()
             .serialize(serializer)
     }
 }
 
+/// Splits a serialized chip spec (e.g. `"t1-Not"`) into its orientation and
+/// chip type string.  The orientation prefix is omitted for chips with the
+/// default orientation (e.g. `"Not"`).
+fn split_chip_spec(chip_str: &str) -> (Orientation, &str) {
+    if let Some(index) = chip_str.find('-') {
+        if let Ok(orient) = chip_str[..index].parse::<Orientation>() {
+            return (orient, &chip_str[(index + 1)..]);
+        }
+    }
+    (Orientation::default(), chip_str)
+}
+
 pub struct CircuitChipDataIter<'a> {
     inner: btree_map::Iter<'a, (i32, i32), (ChipType, Orientation)>,
 }
@@ -174,10 +236,14 @@ impl<'a> Iterator for CircuitChipDataIter<'a> {
 
 //===========================================================================//
 
-#[derive(Clone)]
+#[derive(Clone, Default)]
 pub struct CircuitWireData(BTreeMap<(i32, i32, OrderedDir), WireShape>);
 
 impl CircuitWireData {
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
     pub fn insert(
         &mut self,
         delta: CoordsDelta,
@@ -244,6 +310,111 @@ impl<'a> Iterator for CircuitWireDataIter<'a> {
 
 //===========================================================================//
 
+/// Freeform notes attached to a circuit.  Unlike Comment chips, these aren't
+/// shown on the board; they are meant for describing a circuit file that is
+/// being shared with other players.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct CircuitNotes {
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub description: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub author: String,
+}
+
+impl CircuitNotes {
+    pub fn is_empty(&self) -> bool {
+        self.description.is_empty() && self.author.is_empty()
+    }
+
+    /// Returns a copy of these notes with surrounding whitespace trimmed, and
+    /// each note cut down to at most `MAX_CIRCUIT_NOTE_CHARS` characters.
+    pub fn truncated(&self) -> CircuitNotes {
+        let truncate = |note: &str| -> String {
+            note.trim().chars().take(MAX_CIRCUIT_NOTE_CHARS).collect()
+        };
+        CircuitNotes {
+            description: truncate(&self.description),
+            author: truncate(&self.author),
+        }
+    }
+}
+
+//===========================================================================//
+
+#[derive(Clone, Default)]
+pub struct CircuitWireLabelData(BTreeMap<(i32, i32, OrderedDir), String>);
+
+impl CircuitWireLabelData {
+    pub fn insert(
+        &mut self,
+        delta: CoordsDelta,
+        dir: Direction,
+        label: String,
+    ) {
+        self.0.insert((delta.x, delta.y, OrderedDir(dir)), label);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn iter(&self) -> CircuitWireLabelDataIter<'_> {
+        CircuitWireLabelDataIter { inner: self.0.iter() }
+    }
+}
+
+impl<'d> serde::Deserialize<'d> for CircuitWireLabelData {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'d>,
+    {
+        let map = BTreeMap::<&str, String>::deserialize(deserializer)?;
+        let mut labels = BTreeMap::new();
+        for (key, label) in map.into_iter() {
+            let (delta, dir) = key_string_location(key).ok_or_else(|| {
+                D::Error::custom(format!("Invalid location key: {:?}", key))
+            })?;
+            labels.insert((delta.x, delta.y, OrderedDir(dir)), label);
+        }
+        Ok(CircuitWireLabelData(labels))
+    }
+}
+
+impl serde::Serialize for CircuitWireLabelData {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.0
+            .iter()
+            .map(|(&(x, y, OrderedDir(dir)), label)| {
+                (location_key_string(CoordsDelta::new(x, y), dir), label)
+            })
+            .collect::<BTreeMap<String, &String>>()
+            .serialize(serializer)
+    }
+}
+
+pub struct CircuitWireLabelDataIter<'a> {
+    inner: btree_map::Iter<'a, (i32, i32, OrderedDir), String>,
+}
+
+impl<'a> Iterator for CircuitWireLabelDataIter<'a> {
+    type Item = (CoordsDelta, Direction, &'a str);
+
+    fn next(&mut self) -> Option<(CoordsDelta, Direction, &'a str)> {
+        self.inner.next().map(|(&(x, y, OrderedDir(dir)), label)| {
+            (CoordsDelta::new(x, y), dir, label.as_str())
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+//===========================================================================//
+
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 struct OrderedDir(Direction);
 
@@ -308,23 +479,17 @@ pub(super) fn key_string_delta(key: &str) -> Option<CoordsDelta> {
 }
 
 fn location_key_string(delta: CoordsDelta, dir: Direction) -> String {
-    let dir_chr = match dir {
-        Direction::East => 'e',
-        Direction::South => 's',
-        Direction::West => 'w',
-        Direction::North => 'n',
-    };
-    format!("{}{}", delta_key_string(delta), dir_chr)
+    format!("{}{}", delta_key_string(delta), dir)
 }
 
 fn key_string_location(key: &str) -> Option<(CoordsDelta, Direction)> {
     let mut string = key.to_string();
     let dir = match string.pop() {
-        Some('e') => Direction::East,
-        Some('s') => Direction::South,
-        Some('w') => Direction::West,
-        Some('n') => Direction::North,
-        _ => return None,
+        Some(chr) => match chr.to_string().parse::<Direction>() {
+            Ok(dir) => dir,
+            Err(_) => return None,
+        },
+        None => return None,
     };
     if let Some(delta) = key_string_delta(&string) {
         Some((delta, dir))
@@ -337,7 +502,10 @@ fn key_string_location(key: &str) -> Option<(CoordsDelta, Direction)> {
 
 #[cfg(test)]
 mod tests {
-    use super::{ChipType, CircuitData, OrderedDir, WireShape};
+    use super::{
+        ChipType, CircuitData, CircuitNotes, OrderedDir, WireShape,
+        MAX_CIRCUIT_NOTE_CHARS,
+    };
     use crate::geom::{CoordsDelta, CoordsSize, Direction, Orientation};
     use toml;
 
@@ -376,13 +544,109 @@ mod tests {
              [chips]\n\
              p1p3 = \"t0-Button\"\n\
              p1p4 = \"f3-Comment('Press')\"\n\
-             p2p3 = \"f0-Break(true)\"\n\n\
+             p2p3 = \"Break(true)\"\n\n\
              [wires]\n\
              p1p3e = \"Stub\"\n\
              p2p3w = \"Stub\"\n"
         );
     }
 
+    #[test]
+    fn circuit_data_wire_labels_round_trip() {
+        let mut data = CircuitData::new(8, 5);
+        data.wires.insert(
+            CoordsDelta::new(1, 3),
+            Direction::East,
+            WireShape::Stub,
+        );
+        data.labels.insert(
+            CoordsDelta::new(1, 3),
+            Direction::East,
+            "Data Bus".to_string(),
+        );
+        let string = data.serialize_to_string().unwrap();
+        assert_eq!(
+            string,
+            "size = [8, 5]\n\n\
+             [wires]\n\
+             p1p3e = \"Stub\"\n\n\
+             [labels]\n\
+             p1p3e = \"Data Bus\"\n"
+        );
+        let data = CircuitData::deserialize_from_string(&string).unwrap();
+        assert_eq!(
+            data.labels.iter().collect::<Vec<_>>(),
+            vec![(CoordsDelta::new(1, 3), Direction::East, "Data Bus")]
+        );
+        // Circuits saved without any labels should still load.
+        let data = CircuitData::deserialize_from_string(
+            "size = [8, 5]\n[chips]\n[wires]\n",
+        )
+        .unwrap();
+        assert!(data.labels.is_empty());
+    }
+
+    #[test]
+    fn circuit_data_notes_round_trip() {
+        let mut data = CircuitData::new(8, 5);
+        data.notes.description = "A \"fast\" adder.\nTwo lines.".to_string();
+        data.notes.author = "Jane".to_string();
+        let string = data.serialize_to_string().unwrap();
+        assert_eq!(
+            string,
+            "size = [8, 5]\n\n\
+             [notes]\n\
+             description = \"A \\\"fast\\\" adder.\\nTwo lines.\"\n\
+             author = \"Jane\"\n"
+        );
+        let loaded = CircuitData::deserialize_from_string(&string).unwrap();
+        assert_eq!(loaded.notes, data.notes);
+        // Circuits saved without notes should still load.
+        let data =
+            CircuitData::deserialize_from_string("size = [8, 5]\n").unwrap();
+        assert!(data.notes.is_empty());
+    }
+
+    #[test]
+    fn circuit_notes_truncated() {
+        let notes = CircuitNotes {
+            description: format!("  {}  ", "x".repeat(2000)),
+            author: " Jane ".to_string(),
+        };
+        let truncated = notes.truncated();
+        assert_eq!(
+            truncated.description.chars().count(),
+            MAX_CIRCUIT_NOTE_CHARS
+        );
+        assert_eq!(truncated.author, "Jane");
+    }
+
+    #[test]
+    fn circuit_data_without_wires() {
+        let mut data = CircuitData::new(8, 5);
+        data.chips.insert(
+            CoordsDelta::new(2, 3),
+            ChipType::Break(true),
+            Orientation::default(),
+        );
+        data.wires.insert(
+            CoordsDelta::new(2, 3),
+            Direction::West,
+            WireShape::Stub,
+        );
+        let stripped = data.without_wires();
+        assert_eq!(stripped.size, data.size);
+        assert_eq!(stripped.chips.0, data.chips.0);
+        assert_eq!(stripped.chips.1, data.chips.1);
+        assert_eq!(stripped.wires.iter().count(), 0);
+        assert_eq!(
+            stripped.serialize_to_string().unwrap(),
+            "size = [8, 5]\n\n\
+             [chips]\n\
+             p2p3 = \"Break(true)\"\n"
+        );
+    }
+
     #[test]
     fn deserialize_circuit_data() {
         let toml = "size = [8, 5]\n\n\
@@ -427,6 +691,66 @@ mod tests {
             .collect()
         );
     }
+
+    #[test]
+    fn deserialize_circuit_data_with_unknown_chip() {
+        let toml = "size = [8, 5]\n\n\
+                    [chips]\n\
+                    p1p3 = \"t0-Button\"\n\
+                    p2p3 = \"f0-Flux(Capacitor)\"\n\n\
+                    [wires]\n";
+        let data: CircuitData = toml::from_slice(toml.as_bytes()).unwrap();
+        assert_eq!(
+            data.chips.iter().collect::<Vec<_>>(),
+            vec![(
+                CoordsDelta::new(1, 3),
+                ChipType::Button(None),
+                Orientation::default().flip_vert()
+            )]
+        );
+        assert_eq!(
+            data.chips.unknown(),
+            vec![(CoordsDelta::new(2, 3), "Flux(Capacitor)")]
+        );
+        let output = data.serialize_to_string().unwrap();
+        assert!(!output.contains("Flux"));
+    }
+
+    #[test]
+    fn compact_circuit_data_round_trip() {
+        let mut data = CircuitData::new(8, 5);
+        data.chips.insert(
+            CoordsDelta::new(2, 3),
+            ChipType::Break(true),
+            Orientation::default(),
+        );
+        data.chips.insert(
+            CoordsDelta::new(1, 3),
+            ChipType::Button(None),
+            Orientation::default().flip_vert(),
+        );
+        data.chips.insert(
+            CoordsDelta::new(4, 1),
+            ChipType::Comment(*b"t1-Or"),
+            Orientation::default(),
+        );
+        let string = data.serialize_to_string().unwrap();
+        assert!(!string.contains("f0-"));
+        assert!(!string.contains("[wires]"));
+        let loaded = CircuitData::deserialize_from_string(&string).unwrap();
+        assert_eq!(loaded.size, data.size);
+        assert_eq!(loaded.chips.0, data.chips.0);
+        assert!(loaded.chips.unknown().is_empty());
+        assert!(loaded.wires.is_empty());
+        assert_eq!(loaded.serialize_to_string().unwrap(), string);
+        // An entirely empty circuit is just its size.
+        let empty = CircuitData::new(8, 5);
+        let string = empty.serialize_to_string().unwrap();
+        assert_eq!(string, "size = [8, 5]\n");
+        let loaded = CircuitData::deserialize_from_string(&string).unwrap();
+        assert!(loaded.chips.is_empty());
+        assert!(loaded.wires.is_empty());
+    }
 }
 
 //===========================================================================//
@@ -22,21 +22,30 @@ mod circuit;
 mod converse;
 mod hotkey;
 mod puzzle;
+mod reference;
 mod score;
 mod set;
+mod share;
 mod size;
 mod solution;
 mod wire;
 
 pub use self::chip::{ChipSet, ChipType, CHIP_CATEGORIES, MAX_COMMENT_CHARS};
-pub use self::circuit::CircuitData;
+pub use self::circuit::{
+    CircuitData, CircuitNotes, CircuitWireLabelData, MAX_CIRCUIT_NOTE_CHARS,
+    MAX_WIRE_LABEL_CHARS,
+};
 pub use self::converse::{
     Chapter, Conversation, ConversationIter, ConversationProgress, Prereq,
 };
 pub use self::hotkey::HotkeyCode;
 pub use self::puzzle::{Puzzle, PuzzleIter, PuzzleKind, ScoreUnits};
-pub use self::score::{ScoreCurve, ScoreCurveMap};
+pub use self::reference::ReferenceSpec;
+pub use self::score::{PuzzleOptimality, ScoreCurve, ScoreCurveMap};
 pub use self::set::PuzzleSet;
+pub use self::share::{
+    decode_share_code, encode_share_code, MAX_SHARE_CODE_CHARS,
+};
 pub use self::size::{WireSize, WireSizeInterval};
 pub use self::solution::{InputsData, SolutionData};
 pub use self::wire::WireShape;
@@ -162,6 +162,12 @@ impl Puzzle {
         self.data().graph_bounds
     }
 
+    /// Returns the author-provided (area, score) target for this puzzle, if
+    /// any, for showing players roughly how close they are to optimal.
+    pub fn target_score(self) -> Option<(i32, u32)> {
+        self.data().target_score
+    }
+
     pub fn description(self) -> &'static str {
         self.data().description
     }
@@ -170,6 +176,17 @@ impl Puzzle {
         self.data().instructions
     }
 
+    /// Returns the optional hint for this puzzle, to be shown on request to
+    /// players who are stuck on it.
+    pub fn hint(self) -> Option<&'static str> {
+        let hint = self.data().hint;
+        if hint.is_empty() {
+            None
+        } else {
+            Some(hint)
+        }
+    }
+
     pub fn allows_analog(self) -> bool {
         self.data().allow == AllowColors::All
     }
@@ -192,6 +209,7 @@ impl Puzzle {
                 init_size: (8, 6),
                 score_units: ScoreUnits::Time,
                 graph_bounds: (200, 300),
+                target_score: None,
                 description:
                     "Adjust the beacon transmission angle to track the \
                      detected signal.",
@@ -202,6 +220,7 @@ impl Puzzle {
                      * $!The current position is given by the motor interface \
                      on the right side of the board.\n\
                      * $!The closer the current position is to optimal, TODO.",
+                hint: "",
             },
             Puzzle::AutomateCollector => &PuzzleData {
                 title: "Collector",
@@ -210,8 +229,10 @@ impl Puzzle {
                 init_size: (8, 6),
                 score_units: ScoreUnits::Time,
                 graph_bounds: (500, 500),
+                target_score: None,
                 description: "TODO",
                 instructions: "TODO",
+                hint: "",
             },
             Puzzle::AutomateCryocycler => &PuzzleData {
                 title: "Cryocycler",
@@ -220,8 +241,10 @@ impl Puzzle {
                 init_size: (9, 5),
                 score_units: ScoreUnits::Time,
                 graph_bounds: (500, 500),
+                target_score: None,
                 description: "TODO",
                 instructions: "TODO",
+                hint: "",
             },
             Puzzle::AutomateDrillingRig => &PuzzleData {
                 title: "Drilling Rig",
@@ -230,10 +253,12 @@ impl Puzzle {
                 init_size: (5, 7),
                 score_units: ScoreUnits::Time,
                 graph_bounds: (100, 200),
+                target_score: None,
                 description:
                     "Regulate the speed of the drill to avoid breaking the \
                      drill head.",
                 instructions: "TODO",
+                hint: "",
             },
             Puzzle::AutomateEnrichment => &PuzzleData {
                 title: "U-235 Enrichment",
@@ -242,6 +267,7 @@ impl Puzzle {
                 init_size: (10, 7),
                 score_units: ScoreUnits::Time,
                 graph_bounds: (500, 300),
+                target_score: None,
                 description:
                     "Transfer fissile material between cascade centrifuges \
                      via intermediate storage tanks.",
@@ -258,6 +284,7 @@ impl Puzzle {
                      ready to be moved out of a centrifuge; use a series of \
                      transfers through intermediate tanks to bring it to its \
                      destination.",
+                hint: "",
             },
             Puzzle::AutomateFuelSynthesis => &PuzzleData {
                 title: "Fuel Synthesis",
@@ -266,6 +293,7 @@ impl Puzzle {
                 init_size: (8, 6),
                 score_units: ScoreUnits::Time,
                 graph_bounds: (500, 400),
+                target_score: None,
                 description:
                     "Control the intake valves and mixing chamber for the \
                      hyperfuel synthesis process.",
@@ -278,6 +306,7 @@ impl Puzzle {
                      * $!It is an error to allow either of the tanks to \
                      overflow.\n\
                      * $!Your goal is to produce 8 batches of fuel.",
+                hint: "",
             },
             Puzzle::AutomateGeigerCounter => &PuzzleData {
                 title: "Geiger Counter",
@@ -286,10 +315,12 @@ impl Puzzle {
                 init_size: (10, 9),
                 score_units: ScoreUnits::Cycles,
                 graph_bounds: (500, 5000),
+                target_score: None,
                 description:
                     "Calculate the number of radiation particles detected \
                      over a sliding window of time.",
                 instructions: "TODO",
+                hint: "",
             },
             Puzzle::AutomateGrapple => &PuzzleData {
                 title: "Grapple Launcher",
@@ -298,10 +329,12 @@ impl Puzzle {
                 init_size: (6, 8),
                 score_units: ScoreUnits::Time,
                 graph_bounds: (200, 200),
+                target_score: None,
                 description:
                     "Regulate the discharge of the grapple launcher's \
                      magnetic coils.",
                 instructions: "TODO",
+                hint: "",
             },
             Puzzle::AutomateGuidance => &PuzzleData {
                 title: "Guidance System",
@@ -310,10 +343,12 @@ impl Puzzle {
                 init_size: (5, 9),
                 score_units: ScoreUnits::Time,
                 graph_bounds: (500, 300),
+                target_score: None,
                 description:
                     "Navigate the guided thermonuclear torpedo around enemy \
                      fighters in order to strike the mothership.",
                 instructions: "TODO",
+                hint: "",
             },
             Puzzle::AutomateHeliostat => &PuzzleData {
                 title: "Heliostat",
@@ -322,6 +357,7 @@ impl Puzzle {
                 init_size: (6, 5),
                 score_units: ScoreUnits::Time,
                 graph_bounds: (100, 200),
+                target_score: None,
                 description:
                     "Position the ship's heliostat to reflect sunlight \
                      onto the solar panels at the optimal angle.",
@@ -335,6 +371,7 @@ impl Puzzle {
                      interface on the right side of the board.\n\
                      * $!The closer the current position is to optimal, the \
                      more energy will be produced.",
+                hint: "",
             },
             Puzzle::AutomateIncubator => &PuzzleData {
                 title: "Incubator",
@@ -343,6 +380,7 @@ impl Puzzle {
                 init_size: (9, 6),
                 score_units: ScoreUnits::Time,
                 graph_bounds: (500, 500),
+                target_score: None,
                 description:
                     "Incubate the eggs of flora native to this planet in \
                      order to render them edible for Ichthyans.",
@@ -356,6 +394,7 @@ impl Puzzle {
                      necessarily contiguous), then unloaded.\n\
                      * $!Loading or unloading an egg takes a few time \
                      steps.  The heater must be off during this time.",
+                hint: "",
             },
             Puzzle::AutomateInjector => &PuzzleData {
                 title: "Plasma Injector",
@@ -364,11 +403,13 @@ impl Puzzle {
                 init_size: (9, 5),
                 score_units: ScoreUnits::Time,
                 graph_bounds: (500, 500),
+                target_score: None,
                 description:
                     "Control the main reactor's primary plasma injection head \
                      in order to maintain a steady Low-Temperature Fusion \
                      (LTF) reaction.",
                 instructions: "TODO",
+                hint: "",
             },
             Puzzle::AutomateMiningRobot => &PuzzleData {
                 title: "Mining Robot",
@@ -377,6 +418,7 @@ impl Puzzle {
                 init_size: (8, 6),
                 score_units: ScoreUnits::Time,
                 graph_bounds: (100, 500),
+                target_score: None,
                 description:
                     "Program the scout robot to pick up ore deposits and \
                      carry them back to the base.",
@@ -390,6 +432,7 @@ impl Puzzle {
                      is an error to try to carry more than that.\n\
                      * $!When the robot returns to the base, it will \
                      automatically dump its ore and depart again.",
+                hint: "",
             },
             Puzzle::AutomateReactor => &PuzzleData {
                 title: "Backup Reactor",
@@ -398,10 +441,12 @@ impl Puzzle {
                 init_size: (6, 8),
                 score_units: ScoreUnits::Time,
                 graph_bounds: (400, 500),
+                target_score: None,
                 description:
                     "Manipulate the reactor's control rods to adjust the \
                      power output to the desired level.",
                 instructions: "TODO",
+                hint: "",
             },
             Puzzle::AutomateResonator => &PuzzleData {
                 title: "Strike Resonator",
@@ -410,10 +455,12 @@ impl Puzzle {
                 init_size: (9, 7),
                 score_units: ScoreUnits::Time,
                 graph_bounds: (500, 500),
+                target_score: None,
                 description:
                     "Deliver timed pulses to a resonator crystal in order to \
                      charge up the STRIKE beam weapon.",
                 instructions: "TODO",
+                hint: "",
             },
             Puzzle::AutomateRobotArm => &PuzzleData {
                 title: "Manipulator Arm",
@@ -422,9 +469,11 @@ impl Puzzle {
                 init_size: (8, 6),
                 score_units: ScoreUnits::Time,
                 graph_bounds: (300, 500),
+                target_score: None,
                 description:
                     "Operate a robotic arm in response to radio commands.",
                 instructions: "TODO",
+                hint: "",
             },
             Puzzle::AutomateSensors => &PuzzleData {
                 title: "Main Sensors",
@@ -433,6 +482,7 @@ impl Puzzle {
                 init_size: (6, 7),
                 score_units: ScoreUnits::Time,
                 graph_bounds: (150, 150),
+                target_score: None,
                 description:
                     "Narrow the sensor sweep to zero in on a given signal of \
                      interest.",
@@ -450,6 +500,7 @@ impl Puzzle {
                      value to terminate the scan.\n\
                      * $!Note that ($/x$/ AND 1) is 0 when $/x$/ is even, \
                      and 1 when $/x$/ is odd.",
+                hint: "",
             },
             Puzzle::AutomateSonar => &PuzzleData {
                 title: "Sonar Navigation",
@@ -458,10 +509,12 @@ impl Puzzle {
                 init_size: (9, 7),
                 score_units: ScoreUnits::Time,
                 graph_bounds: (500, 1000),
+                target_score: None,
                 description:
                     "Use sonar to guide an autonomous underwater vehicle \
                      through a submarine canyon.",
                 instructions: "TODO",
+                hint: "",
             },
             Puzzle::AutomateStorageDepot => &PuzzleData {
                 title: "Storage Depot",
@@ -470,10 +523,12 @@ impl Puzzle {
                 init_size: (8, 6),
                 score_units: ScoreUnits::Time,
                 graph_bounds: (500, 1000),
+                target_score: None,
                 description:
                     "Store crates within a warehouse and retrieve them again \
                      on demand.",
                 instructions: "TODO",
+                hint: "",
             },
             Puzzle::AutomateTranslator => &PuzzleData {
                 title: "Translator",
@@ -482,9 +537,11 @@ impl Puzzle {
                 init_size: (8, 5),
                 score_units: ScoreUnits::Time,
                 graph_bounds: (500, 500),
+                target_score: None,
                 description: "Translate a passage of Ichthyan text using a \
                               word-for-word dictionary database.",
                 instructions: "TODO",
+                hint: "",
             },
             Puzzle::AutomateXUnit => &PuzzleData {
                 title: "X-Unit",
@@ -493,6 +550,7 @@ impl Puzzle {
                 init_size: (12, 9),
                 score_units: ScoreUnits::Time,
                 graph_bounds: (500, 300),
+                target_score: None,
                 description:
                     "Ensure that all the implosion charges surrounding the \
                      fissile core detonate simultaneously.",
@@ -507,6 +565,7 @@ impl Puzzle {
                      test signal to the Ping port and waiting for an echo on \
                      the Pong port.  The echo will take exactly twice that \
                      charge's delay to come back.",
+                hint: "",
             },
             Puzzle::CommandLander => &PuzzleData {
                 title: "Orbital Lander",
@@ -515,10 +574,12 @@ impl Puzzle {
                 init_size: (6, 8),
                 score_units: ScoreUnits::ManualInputs,
                 graph_bounds: (200, 100),
+                target_score: None,
                 description:
                     "Operate the lander thrusters during descent for a soft \
                      landing.",
                 instructions: "TODO",
+                hint: "",
             },
             Puzzle::CommandSapper => &PuzzleData {
                 title: "Sapper Drone",
@@ -527,11 +588,13 @@ impl Puzzle {
                 init_size: (9, 7),
                 score_units: ScoreUnits::ManualInputs,
                 graph_bounds: (500, 500),
+                target_score: None,
                 description:
                     "Pilot a remote drone through the minefield to each of \
                      the control satellites to enable the mines' cloaking \
                      devices.",
                 instructions: "TODO",
+                hint: "",
             },
             Puzzle::CommandShields => &PuzzleData {
                 title: "Deflector Shields",
@@ -540,10 +603,12 @@ impl Puzzle {
                 init_size: (9, 7),
                 score_units: ScoreUnits::ManualInputs,
                 graph_bounds: (500, 100),
+                target_score: None,
                 description:
                     "Control the ship's shields to block incoming enemy \
                      torpedoes, then return fire.",
                 instructions: "TODO",
+                hint: "",
             },
             Puzzle::CommandTurret => &PuzzleData {
                 title: "Defense Turret",
@@ -552,10 +617,12 @@ impl Puzzle {
                 init_size: (9, 7),
                 score_units: ScoreUnits::ManualInputs,
                 graph_bounds: (500, 100),
+                target_score: None,
                 description:
                     "Aim and fire the pulse cannon turret to fend off waves \
                      of enemy attackers.",
                 instructions: "TODO",
+                hint: "",
             },
             Puzzle::FabricateCounter => &PuzzleData {
                 title: "Counter",
@@ -564,12 +631,14 @@ impl Puzzle {
                 init_size: (7, 5),
                 score_units: ScoreUnits::WireLength,
                 graph_bounds: (150, 150),
+                target_score: None,
                 description:
                     "Build a memory chip that can increment or decrement its \
                      value in response to events.\n\n\
                      Once this task is completed, you will be able to use \
                      $*Counter$* chips in future tasks.",
                 instructions: "TODO",
+                hint: "",
             },
             Puzzle::FabricateEggTimer => &PuzzleData {
                 title: "Egg Timer",
@@ -578,12 +647,14 @@ impl Puzzle {
                 init_size: (7, 7),
                 score_units: ScoreUnits::WireLength,
                 graph_bounds: (150, 200),
+                target_score: None,
                 description:
                     "Build a timing chip that counts down from a given time \
                      and then fires an event at zero.\n\n\
                      Once this task is completed, you will be able to use \
                      $*Egg Timer$* chips in future tasks.",
                 instructions: "TODO",
+                hint: "",
             },
             Puzzle::FabricateHalve => &PuzzleData {
                 title: "Halver",
@@ -592,6 +663,7 @@ impl Puzzle {
                 init_size: (7, 5),
                 score_units: ScoreUnits::WireLength,
                 graph_bounds: (100, 100),
+                target_score: None,
                 description:
                     "Build a 4-bit halver using packers and unpackers.\n\n\
                      Once this task is completed, you will be able to use \
@@ -602,6 +674,7 @@ impl Puzzle {
                      * $!This can be achieved by unpacking the input into \
                      four 1-bit wires, then packing the highest three wires \
                      of the input into the lowest three wires of the output.",
+                hint: "",
             },
             Puzzle::FabricateInc => &PuzzleData {
                 title: "Incrementor",
@@ -610,6 +683,7 @@ impl Puzzle {
                 init_size: (5, 5),
                 score_units: ScoreUnits::WireLength,
                 graph_bounds: (100, 100),
+                target_score: None,
                 description:
                     "Build a 4-bit incrementor using more basic event and \
                      behavior chips.\n\n\
@@ -620,6 +694,7 @@ impl Puzzle {
                      * $!When an input event arrives, the circuit should \
                      add the input behavior value to the event value, \
                      and emit an output event with the sum.",
+                hint: "",
             },
             Puzzle::FabricateLatch => &PuzzleData {
                 title: "Latch",
@@ -628,6 +703,7 @@ impl Puzzle {
                 init_size: (5, 5),
                 score_units: ScoreUnits::WireLength,
                 graph_bounds: (100, 100),
+                target_score: None,
                 description:
                     "Build a memory chip that can flip-flop between two \
                      states.\n\n\
@@ -639,6 +715,7 @@ impl Puzzle {
                      should be reset back to 0.\n\
                      * $!If a $*Set$* and $*Rst$* event arrive \
                      simultaneously, the current output should be toggled.",
+                hint: "",
             },
             Puzzle::FabricateMul => &PuzzleData {
                 title: "Multiplier",
@@ -647,6 +724,7 @@ impl Puzzle {
                 init_size: (7, 7),
                 score_units: ScoreUnits::WireLength,
                 graph_bounds: (150, 200),
+                target_score: None,
                 description:
                     "Build an 8-bit multiplier using 4-bit multipliers.\n\n\
                      Once this task is completed, you will be able to use \
@@ -655,6 +733,7 @@ impl Puzzle {
                     "* $!Your goal is to construct an 8-bit multiplier.\n\
                      * $!The output should be the product of $*In1$* and \
                      $*In2$*.  This product will never be more than 255.",
+                hint: "",
             },
             Puzzle::FabricateQueue => &PuzzleData {
                 title: "Queue Memory",
@@ -663,12 +742,14 @@ impl Puzzle {
                 init_size: (7, 6),
                 score_units: ScoreUnits::WireLength,
                 graph_bounds: (500, 500),
+                target_score: None,
                 description:
                     "Construct a simple first-in, first-out queue memory \
                      module.\n\n\
                      Once this task is completed, you will be able to use \
                      $*Queue$* chips in future tasks.",
                 instructions: "TODO",
+                hint: "",
             },
             Puzzle::FabricateStack => &PuzzleData {
                 title: "Stack Memory",
@@ -677,12 +758,14 @@ impl Puzzle {
                 init_size: (7, 6),
                 score_units: ScoreUnits::WireLength,
                 graph_bounds: (500, 500),
+                target_score: None,
                 description:
                     "Construct a simple last-in, first-out stack memory \
                      module.\n\n\
                      Once this task is completed, you will be able to use \
                      $*Stack$* chips in future tasks.",
                 instructions: "TODO",
+                hint: "",
             },
             Puzzle::FabricateStopwatch => &PuzzleData {
                 title: "Stopwatch",
@@ -691,12 +774,14 @@ impl Puzzle {
                 init_size: (7, 7),
                 score_units: ScoreUnits::WireLength,
                 graph_bounds: (150, 200),
+                target_score: None,
                 description:
                     "Build a timing chip that counts upwards from zero, and \
                      that can be paused or reset.\n\n\
                      Once this task is completed, you will be able to use \
                      $*Stopwatch$* chips in future tasks.",
                 instructions: "TODO",
+                hint: "",
             },
             Puzzle::FabricateXor => &PuzzleData {
                 title: "XOR Gate",
@@ -705,6 +790,7 @@ impl Puzzle {
                 init_size: (5, 5),
                 score_units: ScoreUnits::WireLength,
                 graph_bounds: (100, 100),
+                target_score: None,
                 description: "Build a 1-bit $*XOR$* gate out of $*AND$*, \
                      $*OR$*, and $*NOT$* gates.\n\n\
                      Once this task is completed, you will be able to use \
@@ -715,6 +801,10 @@ impl Puzzle {
                      be 1 if exactly one input is 1, but not both.\n\
                      * $!Note that ($/a$/ XOR $/b$/) is equivalent to \
                      ($/a$/ OR $/b$/) AND NOT ($/a$/ AND $/b$/).",
+                hint: "Build ($/a$/ OR $/b$/) and ($/a$/ AND $/b$/) side by \
+                     side, send the $*AND$* result through a $*NOT$* \
+                     gate, and then combine the two with one more \
+                     $*AND$* gate.",
             },
             Puzzle::SandboxAnalog => &PuzzleData {
                 title: "Analog Lab",
@@ -723,12 +813,14 @@ impl Puzzle {
                 init_size: (8, 6),
                 score_units: ScoreUnits::Time,
                 graph_bounds: (100, 100),
+                target_score: None,
                 description:
                     "Build any circuits you want using all behavior, event, \
                      and analog chips that are currently available.  You can \
                      use this area for prototyping, experimentation, or \
                      freeform design.",
                 instructions: "",
+                hint: "",
             },
             Puzzle::SandboxBehavior => &PuzzleData {
                 title: "Behavior Lab",
@@ -737,11 +829,13 @@ impl Puzzle {
                 init_size: (8, 6),
                 score_units: ScoreUnits::Time,
                 graph_bounds: (100, 100),
+                target_score: None,
                 description:
                     "Build any circuits you want using all behavior chips \
                      that are currently available.  You can use this area \
                      for prototyping, experimentation, or freeform design.",
                 instructions: "",
+                hint: "",
             },
             Puzzle::SandboxEvent => &PuzzleData {
                 title: "Event Lab",
@@ -750,12 +844,14 @@ impl Puzzle {
                 init_size: (8, 6),
                 score_units: ScoreUnits::Time,
                 graph_bounds: (100, 100),
+                target_score: None,
                 description:
                     "Build any circuits you want using all behavior and \
                      event chips that are currently available.  You can \
                      use this area for prototyping, experimentation, or \
                      freeform design.",
                 instructions: "",
+                hint: "",
             },
             Puzzle::TutorialAdc => &PuzzleData {
                 title: "ADC",
@@ -764,10 +860,12 @@ impl Puzzle {
                 init_size: (7, 5),
                 score_units: ScoreUnits::WireLength,
                 graph_bounds: (200, 300),
+                target_score: None,
                 description:
                     "Build a 2-bit analog-to-digital converter using basic \
                      analog components.",
                 instructions: "TODO",
+                hint: "",
             },
             Puzzle::TutorialAdd => &PuzzleData {
                 title: "Adder",
@@ -776,6 +874,7 @@ impl Puzzle {
                 init_size: (5, 5),
                 score_units: ScoreUnits::WireLength,
                 graph_bounds: (100, 100),
+                target_score: None,
                 description:
                     "Build a 4-bit adder using 2-bit adders, packers, and \
                      unpackers.\n\n\
@@ -788,6 +887,11 @@ impl Puzzle {
                      4-bit inputs into hi and lo 2-bit values that the \
                      2-bit adders can accept.  Remember to handle carry \
                      bits appropriately.",
+                hint: "Add the lo halves of the two inputs with one 2-bit \
+                     adder, and the hi halves with another.  Then use a \
+                     third adder to add the carry from the lo sum into the \
+                     hi sum, and pack the lo sum and final hi sum \
+                     together.",
             },
             Puzzle::TutorialAmp => &PuzzleData {
                 title: "Signal Amplifier",
@@ -796,6 +900,7 @@ impl Puzzle {
                 init_size: (6, 5),
                 score_units: ScoreUnits::WireLength,
                 graph_bounds: (150, 150),
+                target_score: None,
                 description:
                     "Construct a signal amplifier with an automatic safety \
                      cutoff for high-intensity signals.",
@@ -805,6 +910,7 @@ impl Puzzle {
                      board.\n\
                      * $!However, if the doubled value is greater than 10, no \
                      event should be sent.",
+                hint: "",
             },
             Puzzle::TutorialClock => &PuzzleData {
                 title: "Noise Filter",
@@ -813,6 +919,7 @@ impl Puzzle {
                 init_size: (6, 5),
                 score_units: ScoreUnits::WireLength,
                 graph_bounds: (150, 200),
+                target_score: None,
                 description:
                     "Construct a simple signal processor that filters out \
                      overly-noisy inputs.",
@@ -822,6 +929,7 @@ impl Puzzle {
                      two time steps.\n\
                      * $!If another event arrives in the middle of this delay \
                      period, it should be ignored.",
+                hint: "",
             },
             Puzzle::TutorialDemux => &PuzzleData {
                 title: "Four-Way Demux",
@@ -830,10 +938,12 @@ impl Puzzle {
                 init_size: (6, 4),
                 score_units: ScoreUnits::WireLength,
                 graph_bounds: (150, 200),
+                target_score: None,
                 description:
                     "Route incoming events to one of four destinations, based \
                      on a 2-bit control value.",
                 instructions: "TODO",
+                hint: "",
             },
             Puzzle::TutorialIntegrate => &PuzzleData {
                 title: "Integrator",
@@ -842,12 +952,14 @@ impl Puzzle {
                 init_size: (7, 5),
                 score_units: ScoreUnits::WireLength,
                 graph_bounds: (200, 300),
+                target_score: None,
                 description:
                     "Build an analog voltage integrator from other analog \
                      components.\n\n\
                      Once this task is completed, you will be able to use \
                      $*Integrate$* chips in future tasks.",
                 instructions: "TODO",
+                hint: "",
             },
             Puzzle::TutorialMux => &PuzzleData {
                 title: "Two-Way Mux",
@@ -856,6 +968,7 @@ impl Puzzle {
                 init_size: (5, 5),
                 score_units: ScoreUnits::WireLength,
                 graph_bounds: (100, 100),
+                target_score: None,
                 description:
                     "Build a 1-bit multiplexer using other logic gates.\n\n\
                      Once this task is completed, you will be able to use \
@@ -866,6 +979,11 @@ impl Puzzle {
                      * $!If $/a$/ and $/b$/ are the inputs and $/c$/ is \
                      the control, then a Mux is    \
                      ($/a$/ AND NOT $/c$/) OR ($/b$/ AND $/c$/).",
+                hint: "Use a $*NOT$* gate to invert $*ctrl$*, and then use \
+                     two $*AND$* gates: one combining $*in0$* with the \
+                     inverted control, and one combining $*in1$* with the \
+                     original control.  At most one of these can be 1, so \
+                     an $*OR$* gate can combine them into the output.",
             },
             Puzzle::TutorialOr => &PuzzleData {
                 title: "OR Gate",
@@ -874,6 +992,7 @@ impl Puzzle {
                 init_size: (5, 5),
                 score_units: ScoreUnits::WireLength,
                 graph_bounds: (50, 50),
+                target_score: None,
                 description:
                     "Build a 1-bit $*OR$* gate out of $*AND$* and $*NOT$* \
                      gates.\n\n\
@@ -885,6 +1004,10 @@ impl Puzzle {
                      be 1 if either input is 1, or 0 if both inputs are 0.\n\
                      * $!Note that ($/a$/ OR $/b$/) is equivalent to \
                      NOT ((NOT $/a$/) AND (NOT $/b$/)).",
+                hint: "Send each input through its own $*NOT$* gate, combine \
+                     the two results with an $*AND$* gate, and then send \
+                     the $*AND$* gate's output through one more $*NOT$* \
+                     gate.",
             },
             Puzzle::TutorialRam => &PuzzleData {
                 title: "Repeat Filter",
@@ -893,10 +1016,12 @@ impl Puzzle {
                 init_size: (6, 5),
                 score_units: ScoreUnits::WireLength,
                 graph_bounds: (200, 200),
+                target_score: None,
                 description:
                     "Construct a simple signal processor that filters out \
                      repeated inputs.",
                 instructions: "TODO",
+                hint: "",
             },
             Puzzle::TutorialSum => &PuzzleData {
                 title: "Running Total",
@@ -905,6 +1030,7 @@ impl Puzzle {
                 init_size: (7, 7),
                 score_units: ScoreUnits::WireLength,
                 graph_bounds: (150, 200),
+                target_score: None,
                 description:
                     "Build a circuit for tracking a running total and \
                      resetting it back to zero.",
@@ -918,6 +1044,7 @@ impl Puzzle {
                      the reset should occur just $/before$/ the input is \
                      added in, so that the output for that time step is \
                      the value of the input event.",
+                hint: "",
             },
         }
     }
@@ -932,15 +1059,17 @@ struct PuzzleData {
     init_size: (i32, i32),
     score_units: ScoreUnits,
     graph_bounds: (i32, u32),
+    target_score: Option<(i32, u32)>,
     description: &'static str,
     instructions: &'static str,
+    hint: &'static str,
 }
 
 //===========================================================================//
 
 #[cfg(test)]
 mod tests {
-    use super::Puzzle;
+    use super::{Puzzle, PuzzleKind};
     use std::str::FromStr;
 
     #[test]
@@ -959,6 +1088,26 @@ mod tests {
             assert!(name.starts_with(kind.as_str()));
         }
     }
+
+    #[test]
+    fn target_scores_are_within_graph_bounds() {
+        for puzzle in Puzzle::all() {
+            if let Some((area, score)) = puzzle.target_score() {
+                let (max_area, max_score) = puzzle.graph_bounds();
+                assert!(area > 0 && area <= max_area, "{:?}", puzzle);
+                assert!(score <= max_score, "{:?}", puzzle);
+            }
+        }
+    }
+
+    #[test]
+    fn sandbox_puzzles_have_no_hints() {
+        for puzzle in Puzzle::all() {
+            if puzzle.kind() == PuzzleKind::Sandbox {
+                assert_eq!(puzzle.hint(), None, "{:?}", puzzle);
+            }
+        }
+    }
 }
 
 //===========================================================================//
@@ -0,0 +1,111 @@
+// +--------------------------------------------------------------------------+
+// | Copyright 2018 Matthew D. Steele <mdsteele@alum.mit.edu>                 |
+// |                                                                          |
+// | This file is part of Tachyomancer.                                       |
+// |                                                                          |
+// | Tachyomancer is free software: you can redistribute it and/or modify it  |
+// | under the terms of the GNU General Public License as published by the    |
+// | Free Software Foundation, either version 3 of the License, or (at your   |
+// | option) any later version.                                               |
+// |                                                                          |
+// | Tachyomancer is distributed in the hope that it will be useful, but      |
+// | WITHOUT ANY WARRANTY; without even the implied warranty of               |
+// | MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU        |
+// | General Public License for details.                                      |
+// |                                                                          |
+// | You should have received a copy of the GNU General Public License along  |
+// | with Tachyomancer.  If not, see <http://www.gnu.org/licenses/>.          |
+// +--------------------------------------------------------------------------+
+
+use std::fs;
+use std::path::Path;
+use toml;
+
+//===========================================================================//
+
+/// The largest value that can be sent from a sandbox input port.
+const MAX_REFERENCE_INPUT: u32 = 0xff;
+
+//===========================================================================//
+
+/// A user-supplied specification of how a sandbox circuit should behave,
+/// for checking a circuit against a reference without it being a graded
+/// puzzle.  Each row of the table gives values for the sandbox's A and B
+/// input ports along with the value expected on the wire labeled `output`.
+#[derive(Deserialize, Serialize)]
+pub struct ReferenceSpec {
+    pub output: String,
+    pub table: Vec<[u32; 3]>,
+}
+
+impl ReferenceSpec {
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<ReferenceSpec, String> {
+        ReferenceSpec::load_path(path.as_ref())
+    }
+
+    fn load_path(path: &Path) -> Result<ReferenceSpec, String> {
+        let string = fs::read_to_string(path).map_err(|err| {
+            format!("Could not read reference from {:?}: {}", path, err)
+        })?;
+        ReferenceSpec::deserialize_from_string(&string)
+    }
+
+    pub fn deserialize_from_string(
+        string: &str,
+    ) -> Result<ReferenceSpec, String> {
+        let spec: ReferenceSpec = toml::from_str(string).map_err(|err| {
+            format!("Could not deserialize reference: {}", err)
+        })?;
+        spec.validate()?;
+        Ok(spec)
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.output.is_empty() {
+            return Err("Reference output wire label is empty".to_string());
+        }
+        if self.table.is_empty() {
+            return Err("Reference table is empty".to_string());
+        }
+        for (index, &[a, b, _]) in self.table.iter().enumerate() {
+            if a > MAX_REFERENCE_INPUT || b > MAX_REFERENCE_INPUT {
+                return Err(format!(
+                    "Reference table row {} has an input value above {}",
+                    index + 1,
+                    MAX_REFERENCE_INPUT
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+//===========================================================================//
+
+#[cfg(test)]
+mod tests {
+    use super::ReferenceSpec;
+
+    #[test]
+    fn deserialize_reference_spec() {
+        let toml = "output = \"Sum\"\n\
+                    table = [[1, 2, 3], [10, 20, 30]]\n";
+        let spec = ReferenceSpec::deserialize_from_string(toml).unwrap();
+        assert_eq!(spec.output, "Sum");
+        assert_eq!(spec.table, vec![[1, 2, 3], [10, 20, 30]]);
+    }
+
+    #[test]
+    fn reject_invalid_reference_specs() {
+        let empty_output = "output = \"\"\ntable = [[1, 2, 3]]\n";
+        assert!(ReferenceSpec::deserialize_from_string(empty_output).is_err());
+        let empty_table = "output = \"Sum\"\ntable = []\n";
+        assert!(ReferenceSpec::deserialize_from_string(empty_table).is_err());
+        let big_input = "output = \"Sum\"\ntable = [[256, 0, 0]]\n";
+        assert!(ReferenceSpec::deserialize_from_string(big_input).is_err());
+        let short_row = "output = \"Sum\"\ntable = [[1, 2]]\n";
+        assert!(ReferenceSpec::deserialize_from_string(short_row).is_err());
+    }
+}
+
+//===========================================================================//
@@ -25,6 +25,22 @@ use std::str::FromStr;
 
 //===========================================================================//
 
+/// How well a player has solved a puzzle, compared to the best known scores.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PuzzleOptimality {
+    /// The player hasn't solved the puzzle yet.
+    Unsolved,
+    /// The player has solved the puzzle, but every one of their solutions is
+    /// beaten by some known solution (or there are no known solutions to
+    /// compare against).
+    Solved,
+    /// At least one of the player's solutions lies on the frontier of best
+    /// known solutions.
+    Optimal,
+}
+
+//===========================================================================//
+
 #[derive(Clone)]
 pub struct ScoreCurve {
     scores: Vec<(i32, u32)>,
@@ -55,6 +71,30 @@ impl ScoreCurve {
         ScoreCurve::fix(&mut self.scores);
     }
 
+    /// Returns true if at least one point on this curve is not beaten by
+    /// any point on the `frontier` curve.  A point is beaten by another if
+    /// the other point is no worse in both area and score, and is better in
+    /// at least one of them.
+    pub fn reaches_frontier(&self, frontier: &ScoreCurve) -> bool {
+        self.scores.iter().any(|&(area, score)| {
+            !frontier.scores.iter().any(|&(other_area, other_score)| {
+                other_area <= area
+                    && other_score <= score
+                    && (other_area, other_score) != (area, score)
+            })
+        })
+    }
+
+    /// Returns the (area, score) point combining the best area and the best
+    /// score anywhere on this curve, or `None` if the curve is empty.  No
+    /// single solution need achieve this point.
+    pub fn best_area_and_score(&self) -> Option<(i32, u32)> {
+        match (self.scores.first(), self.scores.last()) {
+            (Some(&(area, _)), Some(&(_, score))) => Some((area, score)),
+            _ => None,
+        }
+    }
+
     fn fix(points: &mut Vec<(i32, u32)>) {
         points.sort();
         let mut best_score = i64::MAX;
@@ -227,6 +267,28 @@ mod tests {
         assert_eq!(scores, vec![(9, 60)]);
     }
 
+    #[test]
+    fn reaches_frontier() {
+        let global = ScoreCurve::with_scores(vec![(9, 50), (16, 35)]);
+        let tied = ScoreCurve::with_scores(vec![(12, 60), (16, 35)]);
+        assert!(tied.reaches_frontier(&global));
+        let better = ScoreCurve::with_scores(vec![(20, 30)]);
+        assert!(better.reaches_frontier(&global));
+        let beaten = ScoreCurve::with_scores(vec![(9, 55), (20, 35)]);
+        assert!(!beaten.reaches_frontier(&global));
+        assert!(!ScoreCurve::new().reaches_frontier(&global));
+        assert!(better.reaches_frontier(&ScoreCurve::new()));
+    }
+
+    #[test]
+    fn best_area_and_score() {
+        assert_eq!(ScoreCurve::new().best_area_and_score(), None);
+        let single = ScoreCurve::with_scores(vec![(20, 30)]);
+        assert_eq!(single.best_area_and_score(), Some((20, 30)));
+        let curve = ScoreCurve::with_scores(vec![(16, 35), (9, 50), (20, 30)]);
+        assert_eq!(curve.best_area_and_score(), Some((9, 30)));
+    }
+
     #[test]
     fn serialize_score_curve() {
         let scores = ScoreCurve::with_scores(vec![(16, 85), (20, 43)]);
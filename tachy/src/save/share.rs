@@ -0,0 +1,181 @@
+// +--------------------------------------------------------------------------+
+// | Copyright 2018 Matthew D. Steele <mdsteele@alum.mit.edu>                 |
+// |                                                                          |
+// | This file is part of Tachyomancer.                                       |
+// |                                                                          |
+// | Tachyomancer is free software: you can redistribute it and/or modify it  |
+// | under the terms of the GNU General Public License as published by the    |
+// | Free Software Foundation, either version 3 of the License, or (at your   |
+// | option) any later version.                                               |
+// |                                                                          |
+// | Tachyomancer is distributed in the hope that it will be useful, but      |
+// | WITHOUT ANY WARRANTY; without even the implied warranty of               |
+// | MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU        |
+// | General Public License for details.                                      |
+// |                                                                          |
+// | You should have received a copy of the GNU General Public License along  |
+// | with Tachyomancer.  If not, see <http://www.gnu.org/licenses/>.          |
+// +--------------------------------------------------------------------------+
+
+use super::circuit::CircuitData;
+use super::puzzle::Puzzle;
+use flate2::read::DeflateDecoder;
+use flate2::write::DeflateEncoder;
+use flate2::Compression;
+use std::io::{Read, Write};
+use toml;
+
+//===========================================================================//
+
+/// The maximum length of a circuit share code.  Circuits whose codes would
+/// be longer than this can't be shared this way, since codes that long
+/// wouldn't be practical to post or paste.
+pub const MAX_SHARE_CODE_CHARS: usize = 8000;
+
+/// Share codes start with this prefix, so that we can recognize them (and
+/// change the encoding in the future if needed).
+const SHARE_CODE_PREFIX: &str = "TM1.";
+
+/// Upper bound on the decompressed size of a share code, to guard against
+/// codes that would decompress to an enormous amount of data.
+const MAX_DECOMPRESSED_BYTES: u64 = 1 << 20;
+
+//===========================================================================//
+
+#[derive(Deserialize, Serialize)]
+struct SharedCircuitData {
+    puzzle: Puzzle,
+    circuit: CircuitData,
+}
+
+/// Encodes a circuit (along with the puzzle it is for) as a compact,
+/// URL-safe string that can be copied and pasted between players.
+pub fn encode_share_code(
+    puzzle: Puzzle,
+    circuit: &CircuitData,
+) -> Result<String, String> {
+    let data = SharedCircuitData { puzzle, circuit: circuit.clone() };
+    let toml = toml::to_string(&data)
+        .map_err(|err| format!("Could not serialize circuit: {}", err))?;
+    let mut encoder = DeflateEncoder::new(Vec::new(), Compression::best());
+    encoder
+        .write_all(toml.as_bytes())
+        .map_err(|err| format!("Could not compress circuit: {}", err))?;
+    let bytes = encoder
+        .finish()
+        .map_err(|err| format!("Could not compress circuit: {}", err))?;
+    let code = format!(
+        "{}{}",
+        SHARE_CODE_PREFIX,
+        base64::encode_config(&bytes, base64::URL_SAFE_NO_PAD)
+    );
+    if code.len() > MAX_SHARE_CODE_CHARS {
+        return Err(format!(
+            "Circuit is too large to share (code would be {} characters, \
+             but the limit is {})",
+            code.len(),
+            MAX_SHARE_CODE_CHARS
+        ));
+    }
+    Ok(code)
+}
+
+/// Decodes a string produced by `encode_share_code`.  Surrounding whitespace
+/// is ignored.  Note that the returned circuit is not checked against its
+/// puzzle; that is up to the caller.
+pub fn decode_share_code(code: &str) -> Result<(Puzzle, CircuitData), String> {
+    let code = code.trim();
+    if code.len() > MAX_SHARE_CODE_CHARS {
+        return Err(format!(
+            "Share code is too long ({} characters, but the limit is {})",
+            code.len(),
+            MAX_SHARE_CODE_CHARS
+        ));
+    }
+    if !code.starts_with(SHARE_CODE_PREFIX) {
+        return Err("Not a circuit share code".to_string());
+    }
+    let bytes = base64::decode_config(
+        &code[SHARE_CODE_PREFIX.len()..],
+        base64::URL_SAFE_NO_PAD,
+    )
+    .map_err(|err| format!("Invalid share code: {}", err))?;
+    let mut toml = String::new();
+    DeflateDecoder::new(bytes.as_slice())
+        .take(MAX_DECOMPRESSED_BYTES)
+        .read_to_string(&mut toml)
+        .map_err(|err| format!("Invalid share code: {}", err))?;
+    let data: SharedCircuitData = toml::from_str(&toml)
+        .map_err(|err| format!("Invalid share code: {}", err))?;
+    Ok((data.puzzle, data.circuit))
+}
+
+//===========================================================================//
+
+#[cfg(test)]
+mod tests {
+    use super::{decode_share_code, encode_share_code, MAX_SHARE_CODE_CHARS};
+    use crate::geom::{CoordsDelta, CoordsSize, Direction, Orientation};
+    use crate::save::{ChipType, CircuitData, Puzzle, WireShape};
+
+    #[test]
+    fn share_code_round_trip() {
+        let mut circuit = CircuitData::new(8, 6);
+        circuit.chips.insert(
+            CoordsDelta::new(2, 3),
+            ChipType::Not,
+            Orientation::default(),
+        );
+        circuit.wires.insert(
+            CoordsDelta::new(2, 3),
+            Direction::East,
+            WireShape::Stub,
+        );
+        circuit.wires.insert(
+            CoordsDelta::new(3, 3),
+            Direction::West,
+            WireShape::Straight,
+        );
+        let puzzle = Puzzle::SandboxBehavior;
+        let code = encode_share_code(puzzle, &circuit).unwrap();
+        assert!(code
+            .chars()
+            .all(|chr| chr.is_ascii_alphanumeric() || "-_.".contains(chr)));
+        let (decoded_puzzle, decoded) =
+            decode_share_code(&format!("  {}\n", code)).unwrap();
+        assert_eq!(decoded_puzzle, puzzle);
+        assert_eq!(decoded.size, CoordsSize::new(8, 6));
+        assert_eq!(
+            decoded.serialize_to_string().unwrap(),
+            circuit.serialize_to_string().unwrap()
+        );
+    }
+
+    #[test]
+    fn invalid_share_codes() {
+        assert!(decode_share_code("").is_err());
+        assert!(decode_share_code("hello").is_err());
+        assert!(decode_share_code("TM1.!!!").is_err());
+        assert!(decode_share_code("TM1.aGVsbG8").is_err());
+        let too_long = format!("TM1.{}", "A".repeat(MAX_SHARE_CODE_CHARS));
+        assert!(decode_share_code(&too_long).is_err());
+    }
+
+    #[test]
+    fn huge_circuit_is_too_large_to_share() {
+        let mut circuit = CircuitData::new(200, 200);
+        for x in 0..200 {
+            for y in 0..200 {
+                let ctype = ChipType::Const((x * 7 + y * 13) as u8);
+                circuit.chips.insert(
+                    CoordsDelta::new(x, y),
+                    ctype,
+                    Orientation::default(),
+                );
+            }
+        }
+        assert!(encode_share_code(Puzzle::SandboxEvent, &circuit).is_err());
+    }
+}
+
+//===========================================================================//
@@ -147,6 +147,10 @@ impl WireSizeInterval {
         WireSizeInterval::new(size, WireSize::max_value())
     }
 
+    pub fn at_most(size: WireSize) -> WireSizeInterval {
+        WireSizeInterval::new(WireSize::min_value(), size)
+    }
+
     pub fn is_empty(&self) -> bool {
         self.lo > self.hi
     }
@@ -155,6 +159,22 @@ impl WireSizeInterval {
         self.lo < self.hi
     }
 
+    /// Returns a human-readable description of the sizes in this interval,
+    /// e.g. "4-bit" or "at least 2-bit".
+    pub fn description(&self) -> String {
+        if self.is_empty() {
+            "no valid size".to_string()
+        } else if self.lo == self.hi {
+            format!("{}-bit", self.lo.num_bits())
+        } else if self.hi == WireSize::max_value() {
+            format!("at least {}-bit", self.lo.num_bits())
+        } else if self.lo == WireSize::min_value() {
+            format!("at most {}-bit", self.hi.num_bits())
+        } else {
+            format!("{}- to {}-bit", self.lo.num_bits(), self.hi.num_bits())
+        }
+    }
+
     pub fn lower_bound(&self) -> Option<WireSize> {
         if self.is_empty() {
             None
@@ -217,8 +217,6 @@ mod tests {
              time_steps = 4\n\n\
              [circuit]\n\
              size = [4, 3]\n\n\
-             [circuit.chips]\n\n\
-             [circuit.wires]\n\n\
              [inputs]\n\
              t00002_001_p3p2_0 = 2\n"
         );
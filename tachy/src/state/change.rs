@@ -37,6 +37,9 @@ pub enum GridChange {
     RemoveChip(Coords, ChipType, Orientation),
     /// Change the bounds rect from the first rect to the second.
     SetBounds(CoordsRect, CoordsRect),
+    /// Changes the cosmetic label on the wire fragment at the given location
+    /// from the first string to the second (an empty string means no label).
+    SetWireLabel((Coords, Direction), String, String),
 }
 
 impl GridChange {
@@ -105,6 +108,8 @@ impl GridChange {
                             }
                         }
                         GridChange::SetBounds(r1, r2) if r1 == r2 => {}
+                        GridChange::SetWireLabel(_, ref old, ref new)
+                            if old == new => {}
                         _ => new_changes.push(change2),
                     }
                 }
@@ -125,6 +130,9 @@ impl GridChange {
             GridChange::AddChip(c, t, o) => GridChange::RemoveChip(c, t, o),
             GridChange::RemoveChip(c, t, o) => GridChange::AddChip(c, t, o),
             GridChange::SetBounds(old, new) => GridChange::SetBounds(new, old),
+            GridChange::SetWireLabel(loc, old, new) => {
+                GridChange::SetWireLabel(loc, new, old)
+            }
         }
     }
 }
@@ -57,8 +57,10 @@ impl WireId {
 pub enum WireError {
     MultipleSources(WireId),
     PortColorMismatch(WireId),
-    NoValidSize(WireId),
-    UnbrokenLoop(Vec<WireId>, bool),
+    NoValidSize(WireId, Option<SizeConflict>),
+    /// A combinational loop: the wires involved, whether any of them are
+    /// event wires, and the chip ports through which the loop passes.
+    UnbrokenLoop(Vec<WireId>, bool, Vec<(Coords, Direction)>),
 }
 
 impl WireError {
@@ -66,14 +68,53 @@ impl WireError {
         match self {
             WireError::MultipleSources(wire_id)
             | WireError::PortColorMismatch(wire_id)
-            | WireError::NoValidSize(wire_id) => vec![*wire_id],
-            WireError::UnbrokenLoop(wire_ids, _) => wire_ids.clone(),
+            | WireError::NoValidSize(wire_id, _) => vec![*wire_id],
+            WireError::UnbrokenLoop(wire_ids, _, _) => wire_ids.clone(),
+        }
+    }
+
+    /// Returns the locations of the specific ports responsible for this
+    /// error, if known.
+    pub fn port_locs(&self) -> Vec<(Coords, Direction)> {
+        match self {
+            WireError::NoValidSize(_, Some(conflict)) => conflict.port_locs(),
+            WireError::UnbrokenLoop(_, _, ports) => ports.clone(),
+            _ => vec![],
         }
     }
 }
 
 //===========================================================================//
 
+/// Two port constraints that, together, rule out every possible size for a
+/// wire.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct SizeConflict {
+    /// The port that last narrowed down the wire's possible sizes before the
+    /// conflict, along with the sizes that it allowed.  This is `None` if
+    /// the wire's sizes were only limited by its color (e.g. analog wires
+    /// are always 2-bit).
+    pub first: Option<((Coords, Direction), WireSizeInterval)>,
+    /// The port that ruled out the wire's remaining sizes, along with the
+    /// sizes that it allowed.
+    pub second: ((Coords, Direction), WireSizeInterval),
+}
+
+impl SizeConflict {
+    pub fn port_locs(&self) -> Vec<(Coords, Direction)> {
+        let mut locs = Vec::with_capacity(2);
+        if let Some((loc, _)) = self.first {
+            locs.push(loc);
+        }
+        if !locs.contains(&self.second.0) {
+            locs.push(self.second.0);
+        }
+        locs
+    }
+}
+
+//===========================================================================//
+
 #[derive(Debug)]
 pub struct WireInfo {
     pub fragments: HashSet<(Coords, Direction)>,
@@ -100,13 +141,26 @@ impl WireInfo {
 
 //===========================================================================//
 
+/// Returns a key for sorting port/fragment locations, so that we can visit
+/// them in a deterministic order rather than in `HashMap` iteration order
+/// (which varies from run to run).
+pub fn loc_sort_key(loc: &(Coords, Direction)) -> (i32, i32, Direction) {
+    let &(coords, dir) = loc;
+    (coords.x, coords.y, dir)
+}
+
+//===========================================================================//
+
 pub fn group_wires(
     all_ports: &HashMap<(Coords, Direction), (PortFlow, PortColor)>,
     all_fragments: &mut HashMap<(Coords, Direction), (WireShape, WireId)>,
 ) -> Vec<WireInfo> {
     // TODO: Allow more limited starts for incremental typechecking.
-    let mut starts: IndexMap<(Coords, Direction), WireShape> =
+    let mut starts: Vec<((Coords, Direction), WireShape)> =
         all_fragments.iter().map(|(&k, &(v, _))| (k, v)).collect();
+    starts.sort_by_key(|(loc, _)| loc_sort_key(loc));
+    let mut starts: IndexMap<(Coords, Direction), WireShape> =
+        starts.into_iter().collect();
 
     // Collect fragments into wires:
     let mut wires = Vec::<WireInfo>::new();
@@ -169,12 +223,17 @@ pub fn group_wires(
     }
 
     // Add fragment-less wires for any ports that don't have a wire yet.
-    for (&loc, &port) in all_ports.iter() {
-        if !all_fragments.contains_key(&loc) {
-            let mut wire_ports = HashMap::with_capacity(1);
-            wire_ports.insert(loc, port);
-            wires.push(WireInfo::new(HashSet::new(), wire_ports));
-        }
+    let mut lone_ports: Vec<((Coords, Direction), (PortFlow, PortColor))> =
+        all_ports
+            .iter()
+            .filter(|&(loc, _)| !all_fragments.contains_key(loc))
+            .map(|(&loc, &port)| (loc, port))
+            .collect();
+    lone_ports.sort_by_key(|(loc, _)| loc_sort_key(loc));
+    for (loc, port) in lone_ports {
+        let mut wire_ports = HashMap::with_capacity(1);
+        wire_ports.insert(loc, port);
+        wires.push(WireInfo::new(HashSet::new(), wire_ports));
     }
 
     wires
@@ -248,28 +307,41 @@ pub fn determine_wire_sizes(
     wires_for_ports: &HashMap<(Coords, Direction), WireId>,
     mut constraints: Vec<PortConstraint>,
 ) -> Vec<WireError> {
+    let mut history = vec![SizeHistory::default(); wires.len()];
     let mut changed = true;
     while changed {
         changed = false;
         constraints.retain(|&constraint| {
             match constraint {
                 PortConstraint::Exact(loc, size) => {
-                    let wire = &mut wires[wires_for_ports[&loc].0];
-                    let new_size = wire
-                        .size
-                        .intersection(WireSizeInterval::exactly(size));
-                    if new_size != wire.size {
-                        wire.size = new_size;
-                        changed = true;
-                    }
+                    let id = wires_for_ports[&loc];
+                    changed |= narrow_wire(
+                        wires,
+                        &mut history,
+                        id,
+                        loc,
+                        WireSizeInterval::exactly(size),
+                    );
                 }
                 PortConstraint::AtLeast(loc, size) => {
-                    let wire = &mut wires[wires_for_ports[&loc].0];
-                    changed |= wire.size.make_at_least(size);
+                    let id = wires_for_ports[&loc];
+                    changed |= narrow_wire(
+                        wires,
+                        &mut history,
+                        id,
+                        loc,
+                        WireSizeInterval::at_least(size),
+                    );
                 }
                 PortConstraint::AtMost(loc, size) => {
-                    let wire = &mut wires[wires_for_ports[&loc].0];
-                    changed |= wire.size.make_at_most(size);
+                    let id = wires_for_ports[&loc];
+                    changed |= narrow_wire(
+                        wires,
+                        &mut history,
+                        id,
+                        loc,
+                        WireSizeInterval::at_most(size),
+                    );
                 }
                 PortConstraint::Equal(loc1, loc2) => {
                     let id1 = wires_for_ports[&loc1];
@@ -278,13 +350,21 @@ pub fn determine_wire_sizes(
                         let size1 = wires[id1.0].size;
                         let size2 = wires[id2.0].size;
                         if !size1.is_empty() && !size2.is_empty() {
-                            let new_size = size1.intersection(size2);
-                            changed = changed
-                                || new_size != size1
-                                || new_size != size2;
-                            wires[id1.0].size = new_size;
-                            wires[id2.0].size = new_size;
-                            return new_size.is_ambiguous();
+                            changed |= narrow_wire(
+                                wires,
+                                &mut history,
+                                id1,
+                                loc2,
+                                size2,
+                            );
+                            changed |= narrow_wire(
+                                wires,
+                                &mut history,
+                                id2,
+                                loc1,
+                                size1,
+                            );
+                            return size1.intersection(size2).is_ambiguous();
                         }
                     }
                 }
@@ -292,19 +372,38 @@ pub fn determine_wire_sizes(
                     let id1 = wires_for_ports[&loc1];
                     let id2 = wires_for_ports[&loc2];
                     if id1 == id2 {
-                        let wire = &mut wires[id1.0];
-                        changed |= !wire.size.is_empty();
-                        wire.size = WireSizeInterval::empty();
+                        let index = id1.0;
+                        let size = wires[index].size;
+                        if !size.is_empty() {
+                            if history[index].conflict.is_none() {
+                                history[index].conflict = Some(SizeConflict {
+                                    first: Some((loc1, size)),
+                                    second: (loc2, size.half()),
+                                });
+                            }
+                            wires[index].size = WireSizeInterval::empty();
+                            changed = true;
+                        }
                     } else {
                         let size1 = wires[id1.0].size;
                         let size2 = wires[id2.0].size;
                         if !size1.is_empty() && !size2.is_empty() {
+                            changed |= narrow_wire(
+                                wires,
+                                &mut history,
+                                id1,
+                                loc2,
+                                size2.double(),
+                            );
+                            changed |= narrow_wire(
+                                wires,
+                                &mut history,
+                                id2,
+                                loc1,
+                                size1.half(),
+                            );
                             let new_size1 = size1.intersection(size2.double());
                             let new_size2 = size2.intersection(size1.half());
-                            changed |=
-                                new_size1 != size1 || new_size2 != size2;
-                            wires[id1.0].size = new_size1;
-                            wires[id2.0].size = new_size2;
                             return new_size1.is_ambiguous()
                                 || new_size2.is_ambiguous();
                         }
@@ -319,12 +418,53 @@ pub fn determine_wire_sizes(
     for (index, wire) in wires.iter_mut().enumerate() {
         if wire.color != WireColor::Unknown && wire.size.is_empty() {
             wire.has_error = true;
-            errors.push(WireError::NoValidSize(WireId(index)));
+            errors.push(WireError::NoValidSize(
+                WireId(index),
+                history[index].conflict,
+            ));
         }
     }
     errors
 }
 
+/// Tracks how a wire's possible sizes were narrowed down, so that size
+/// errors can point to the ports responsible.
+#[derive(Clone, Default)]
+struct SizeHistory {
+    /// The port that most recently narrowed down the wire's size, along with
+    /// the sizes that it allowed.
+    narrowed_by: Option<((Coords, Direction), WireSizeInterval)>,
+    /// The first conflict (if any) that left the wire with no valid size.
+    conflict: Option<SizeConflict>,
+}
+
+/// Narrows the wire's size to the sizes `allowed` by the port at `loc`,
+/// recording a conflict if this leaves the wire with no valid size.
+/// Returns true if the wire's size changed.
+fn narrow_wire(
+    wires: &mut [WireInfo],
+    history: &mut [SizeHistory],
+    id: WireId,
+    loc: (Coords, Direction),
+    allowed: WireSizeInterval,
+) -> bool {
+    let wire = &mut wires[id.0];
+    let history = &mut history[id.0];
+    let new_size = wire.size.intersection(allowed);
+    if new_size == wire.size {
+        return false;
+    }
+    if new_size.is_empty() && history.conflict.is_none() {
+        history.conflict = Some(SizeConflict {
+            first: history.narrowed_by,
+            second: (loc, allowed),
+        });
+    }
+    wire.size = new_size;
+    history.narrowed_by = Some((loc, allowed));
+    true
+}
+
 //===========================================================================//
 
 pub fn detect_loops(
@@ -335,7 +475,7 @@ pub fn detect_loops(
     let wire_ids: Vec<WireId> = (0..wires.len()).map(WireId).collect();
     let mut wire_successors: HashMap<WireId, Vec<WireId>> =
         wire_ids.iter().map(|&id| (id, Vec::new())).collect();
-    for dependency in dependencies.into_iter() {
+    for dependency in dependencies.iter() {
         let sink = wires_for_ports[&dependency.sink];
         let source = wires_for_ports[&dependency.source];
         wire_successors.get_mut(&sink).unwrap().push(source);
@@ -366,7 +506,22 @@ pub fn detect_loops(
                     contains_events |= wires[index].color == WireColor::Event;
                     wires[index].has_error = true;
                 }
-                errors.push(WireError::UnbrokenLoop(comp, contains_events))
+                // Report the chip ports that the loop passes through, so that
+                // the player can see where to break it.
+                let mut ports = Vec::<(Coords, Direction)>::new();
+                for dependency in dependencies.iter() {
+                    if comp.contains(&wires_for_ports[&dependency.sink])
+                        && comp.contains(&wires_for_ports[&dependency.source])
+                    {
+                        ports.push(dependency.sink);
+                        ports.push(dependency.source);
+                    }
+                }
+                errors.push(WireError::UnbrokenLoop(
+                    comp,
+                    contains_events,
+                    ports,
+                ))
             }
             Err(errors)
         }
@@ -436,6 +591,62 @@ mod tests {
         assert_eq!(WireColor::Event, wires[1].color);
     }
 
+    #[test]
+    fn group_wires_deterministically() {
+        let mut results = Vec::new();
+        for _ in 0..20 {
+            // Each new HashMap gets a different random hash seed, so these
+            // maps will have different iteration orders each time.
+            let mut ports =
+                HashMap::<(Coords, Direction), (PortFlow, PortColor)>::new();
+            let mut frags =
+                HashMap::<(Coords, Direction), (WireShape, WireId)>::new();
+            for x in 0..8 {
+                ports.insert(
+                    ((2 * x, 0).into(), Direction::East),
+                    (PortFlow::Source, PortColor::Event),
+                );
+                ports.insert(
+                    ((2 * x + 1, 0).into(), Direction::West),
+                    (PortFlow::Source, PortColor::Behavior),
+                );
+                ports.insert(
+                    ((2 * x, 5).into(), Direction::South),
+                    (PortFlow::Sink, PortColor::Analog),
+                );
+                frags.insert(
+                    ((2 * x, 0).into(), Direction::East),
+                    (WireShape::Stub, WireId::NULL),
+                );
+                frags.insert(
+                    ((2 * x + 1, 0).into(), Direction::West),
+                    (WireShape::Stub, WireId::NULL),
+                );
+            }
+            let mut wires = group_wires(&ports, &mut frags);
+            let errors = recolor_wires(&mut wires);
+            let wire_locs: Vec<Vec<(Coords, Direction)>> = wires
+                .iter()
+                .map(|wire| {
+                    let mut locs: Vec<(Coords, Direction)> =
+                        wire.ports.keys().copied().collect();
+                    locs.sort_by_key(loc_sort_key);
+                    locs
+                })
+                .collect();
+            let mut frag_ids: Vec<((Coords, Direction), WireId)> = frags
+                .iter()
+                .map(|(&loc, &(_, wire_id))| (loc, wire_id))
+                .collect();
+            frag_ids.sort_by_key(|(loc, _)| loc_sort_key(loc));
+            results.push((wire_locs, frag_ids, errors));
+        }
+        assert!(!results[0].2.is_empty());
+        for result in results.iter() {
+            assert_eq!(result, &results[0]);
+        }
+    }
+
     #[test]
     fn typecheck_no_wires() {
         let mut wires = vec![];
@@ -492,10 +703,49 @@ mod tests {
         let wires_for_ports = map_ports_to_wires(&wires);
         let errors =
             determine_wire_sizes(&mut wires, &wires_for_ports, constraints);
-        assert_eq!(vec![WireError::NoValidSize(WireId(0))], errors);
+        let conflict = SizeConflict {
+            first: Some((loc1, WireSizeInterval::exactly(WireSize::Four))),
+            second: (loc2, WireSizeInterval::exactly(WireSize::Eight)),
+        };
+        assert_eq!(
+            vec![WireError::NoValidSize(WireId(0), Some(conflict))],
+            errors
+        );
         assert!(wires[0].size.is_empty());
     }
 
+    #[test]
+    fn typecheck_size_conflict_reports_ports() {
+        let loc1: (Coords, Direction) = ((0, 0).into(), Direction::East);
+        let loc2: (Coords, Direction) = ((1, 0).into(), Direction::West);
+        let mut ports = HashMap::new();
+        ports.insert(loc1, (PortFlow::Source, PortColor::Behavior));
+        ports.insert(loc2, (PortFlow::Sink, PortColor::Behavior));
+        let mut wires = vec![WireInfo {
+            fragments: HashSet::new(),
+            ports,
+            color: WireColor::Behavior,
+            size: WireSizeInterval::full(),
+            has_error: false,
+        }];
+        let constraints = vec![
+            PortConstraint::AtMost(loc1, WireSize::Two),
+            PortConstraint::AtLeast(loc2, WireSize::Four),
+        ];
+        let wires_for_ports = map_ports_to_wires(&wires);
+        let errors =
+            determine_wire_sizes(&mut wires, &wires_for_ports, constraints);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].port_locs(), vec![loc1, loc2]);
+        if let WireError::NoValidSize(_, Some(ref conflict)) = errors[0] {
+            let first = conflict.first.unwrap().1;
+            assert_eq!(first.description(), "at most 2-bit");
+            assert_eq!(conflict.second.1.description(), "at least 4-bit");
+        } else {
+            panic!("Unexpected error: {:?}", errors[0]);
+        }
+    }
+
     #[test]
     fn typecheck_two_wires_success() {
         let loc1: (Coords, Direction) = ((0, 0).into(), Direction::East);
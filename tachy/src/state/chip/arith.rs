@@ -460,3 +460,225 @@ impl ChipEval for SubChipEval {
 }
 
 //===========================================================================//
+
+#[cfg(test)]
+mod tests {
+    use super::super::super::eval::{ChipEval, CircuitState};
+    use super::{
+        Add2BitChipEval, AddChipEval, HalveChipEval, IncChipEval,
+        Mul4BitChipEval, MulChipEval, SubChipEval,
+    };
+    use crate::save::WireSize;
+    use crate::state::WireId;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+    use std::collections::HashSet;
+
+    type NewEvals =
+        fn(&[(WireId, WireSize)]) -> Vec<(usize, Box<dyn ChipEval>)>;
+
+    const NUM_TRIALS: usize = 200;
+
+    const SIZES: &[WireSize] =
+        &[WireSize::One, WireSize::Two, WireSize::Four, WireSize::Eight];
+
+    fn test_rng() -> StdRng {
+        StdRng::seed_from_u64(0x7ac4_10ac)
+    }
+
+    /// Builds a chip whose ports are all of the given size, sets its input
+    /// wires to the given values (marking each as changed, even if the value
+    /// is zero), evaluates the chip once, and returns the resulting state.
+    fn eval_chip(
+        new_evals: NewEvals,
+        sizes: &[WireSize],
+        inputs: &[u32],
+    ) -> CircuitState {
+        let slots: Vec<(WireId, WireSize)> = sizes
+            .iter()
+            .enumerate()
+            .map(|(index, &size)| (WireId(index), size))
+            .collect();
+        let mut state = CircuitState::new(slots.len(), HashSet::new());
+        for (index, &value) in inputs.iter().enumerate() {
+            state.send_event(WireId(index), value);
+        }
+        for (_, mut chip_eval) in new_evals(&slots) {
+            chip_eval.eval(&mut state);
+        }
+        state
+    }
+
+    fn random_value<R: Rng>(rng: &mut R, size: WireSize) -> u32 {
+        rng.gen::<u32>() & size.mask()
+    }
+
+    // Add wraps around modulo 2^N for an N-bit wire.
+    #[test]
+    fn add_wraps_within_wire_size() {
+        let mut rng = test_rng();
+        for &size in SIZES {
+            for _ in 0..NUM_TRIALS {
+                let a = random_value(&mut rng, size);
+                let b = random_value(&mut rng, size);
+                let state = eval_chip(
+                    AddChipEval::new_evals,
+                    &[size, size, size],
+                    &[a, b],
+                );
+                let expected = (a + b) % (1 << size.num_bits());
+                assert_eq!(
+                    state.recv_behavior(WireId(2)),
+                    expected,
+                    "{} + {} ({:?})",
+                    a,
+                    b,
+                    size
+                );
+            }
+        }
+    }
+
+    // Sub never wraps; it outputs the absolute difference of its inputs,
+    // which always fits in the wire size.
+    #[test]
+    fn sub_gives_absolute_difference() {
+        let mut rng = test_rng();
+        for &size in SIZES {
+            for _ in 0..NUM_TRIALS {
+                let a = random_value(&mut rng, size);
+                let b = random_value(&mut rng, size);
+                let state = eval_chip(
+                    SubChipEval::new_evals,
+                    &[size, size, size],
+                    &[a, b],
+                );
+                let expected = a.max(b) - a.min(b);
+                assert_eq!(
+                    state.recv_behavior(WireId(2)),
+                    expected,
+                    "|{} - {}| ({:?})",
+                    a,
+                    b,
+                    size
+                );
+                assert!(expected <= size.mask());
+            }
+        }
+    }
+
+    // Mul keeps only the low N bits of the product for an N-bit wire.
+    #[test]
+    fn mul_wraps_within_wire_size() {
+        let mut rng = test_rng();
+        for &size in SIZES {
+            for _ in 0..NUM_TRIALS {
+                let a = random_value(&mut rng, size);
+                let b = random_value(&mut rng, size);
+                let state = eval_chip(
+                    MulChipEval::new_evals,
+                    &[size, size, size],
+                    &[a, b],
+                );
+                let expected = (a * b) % (1 << size.num_bits());
+                assert_eq!(
+                    state.recv_behavior(WireId(2)),
+                    expected,
+                    "{} * {} ({:?})",
+                    a,
+                    b,
+                    size
+                );
+            }
+        }
+    }
+
+    // Inc adds its behavior input to each incoming event, wrapping around
+    // modulo 2^N for an N-bit wire.
+    #[test]
+    fn inc_wraps_within_wire_size() {
+        let mut rng = test_rng();
+        for &size in SIZES {
+            for _ in 0..NUM_TRIALS {
+                let event = random_value(&mut rng, size);
+                let delta = random_value(&mut rng, size);
+                let state = eval_chip(
+                    IncChipEval::new_evals,
+                    &[size, size, size],
+                    &[event, delta],
+                );
+                let expected = (event + delta) % (1 << size.num_bits());
+                assert_eq!(
+                    state.recv_event(WireId(2)),
+                    Some(expected),
+                    "{} + {} ({:?})",
+                    event,
+                    delta,
+                    size
+                );
+            }
+        }
+    }
+
+    // Halve rounds down, and so can never overflow.
+    #[test]
+    fn halve_rounds_down() {
+        let mut rng = test_rng();
+        for &size in SIZES {
+            for _ in 0..NUM_TRIALS {
+                let a = random_value(&mut rng, size);
+                let state =
+                    eval_chip(HalveChipEval::new_evals, &[size, size], &[a]);
+                assert_eq!(
+                    state.recv_behavior(WireId(1)),
+                    a / 2,
+                    "{} / 2 ({:?})",
+                    a,
+                    size
+                );
+            }
+        }
+    }
+
+    // Add2Bit splits the 3-bit sum of two 2-bit inputs into a low 2-bit
+    // output and a high 2-bit carry, so no information is lost.
+    #[test]
+    fn add_2bit_splits_sum_into_output_and_carry() {
+        let size = WireSize::Two;
+        for a in 0..4 {
+            for b in 0..4 {
+                let state = eval_chip(
+                    Add2BitChipEval::new_evals,
+                    &[size, size, size, size],
+                    &[a, b],
+                );
+                let lo = state.recv_behavior(WireId(2));
+                let hi = state.recv_behavior(WireId(3));
+                assert!(lo <= size.mask() && hi <= size.mask());
+                assert_eq!((hi << 2) | lo, a + b, "{} + {}", a, b);
+            }
+        }
+    }
+
+    // Mul4Bit splits the 8-bit product of two 4-bit inputs into a low 4-bit
+    // output and a high 4-bit carry, so no information is lost.
+    #[test]
+    fn mul_4bit_splits_product_into_output_and_carry() {
+        let size = WireSize::Four;
+        for a in 0..16 {
+            for b in 0..16 {
+                let state = eval_chip(
+                    Mul4BitChipEval::new_evals,
+                    &[size, size, size, size],
+                    &[a, b],
+                );
+                let lo = state.recv_behavior(WireId(2));
+                let hi = state.recv_behavior(WireId(3));
+                assert!(lo <= size.mask() && hi <= size.mask());
+                assert_eq!((hi << 4) | lo, a * b, "{} * {}", a, b);
+            }
+        }
+    }
+}
+
+//===========================================================================//
@@ -144,6 +144,10 @@ impl ChipEval for ButtonChipEval {
     fn on_press(&mut self, _sublocation: u32, num_times: u32) {
         self.press_count = self.press_count.saturating_add(num_times);
     }
+
+    fn is_interactive_input(&self) -> bool {
+        true
+    }
 }
 
 //===========================================================================//
@@ -218,6 +222,10 @@ impl ChipEval for ToggleChipEval {
         }
         self.toggle_count = self.toggle_count.saturating_add(num_times);
     }
+
+    fn is_interactive_input(&self) -> bool {
+        true
+    }
 }
 
 //===========================================================================//
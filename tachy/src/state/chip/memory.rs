@@ -510,6 +510,10 @@ impl ChipEval for ScreenChipEval {
     fn on_press(&mut self, sublocation: u32, _num_times: u32) {
         self.pressed = Some(sublocation);
     }
+
+    fn is_interactive_input(&self) -> bool {
+        true
+    }
 }
 
 //===========================================================================//
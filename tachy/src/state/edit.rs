@@ -18,18 +18,23 @@
 // +--------------------------------------------------------------------------+
 
 use super::change::GridChange;
-use super::check::{self, WireColor, WireError, WireId, WireInfo};
+use super::check::{
+    self, SizeConflict, WireColor, WireError, WireId, WireInfo,
+};
 use super::chip::{new_chip_evals, ChipExt};
 use super::eval::{ChipEval, CircuitEval};
 use super::interface::Interface;
 use super::port::{PortColor, PortConstraint, PortDependency, PortFlow};
 use super::puzzle::{new_puzzle_eval, PuzzleExt};
+use super::verify::count_wire_fragments;
 use crate::geom::{
     Coords, CoordsDelta, CoordsRect, CoordsSize, Direction, Orientation, Rect,
 };
 use crate::save::{
-    ChipSet, ChipType, CircuitData, Puzzle, PuzzleSet, WireShape, WireSize,
+    ChipSet, ChipType, CircuitData, CircuitNotes, Puzzle, PuzzleSet,
+    WireShape, WireSize, MAX_WIRE_LABEL_CHARS,
 };
+use pathfinding::prelude::astar;
 use std::collections::{hash_map, hash_set, HashMap, HashSet};
 use std::mem;
 use std::time::{Duration, Instant};
@@ -47,18 +52,89 @@ enum ChipCell {
 
 //===========================================================================//
 
+/// A chip from a saved circuit that could not be placed into an `EditGrid`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum RejectedChip {
+    /// The chip's type could not be parsed.
+    Unknown(Coords, String),
+    /// The chip's type is not allowed in this puzzle.
+    NotAllowed(Coords, ChipType),
+}
+
+impl RejectedChip {
+    pub fn coords(&self) -> Coords {
+        match *self {
+            RejectedChip::Unknown(coords, _) => coords,
+            RejectedChip::NotAllowed(coords, _) => coords,
+        }
+    }
+}
+
+//===========================================================================//
+
+/// Summary statistics about a circuit, independent of how it is scored.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct CircuitStats {
+    /// The number of chips in the circuit, not counting comments.
+    pub num_chips: usize,
+    /// The total length of all wires in the circuit, measured the same way
+    /// as for puzzles scored by wire length.
+    pub wire_length: usize,
+    /// The area of the circuit's bounds.
+    pub area: i32,
+}
+
+//===========================================================================//
+
+/// The parts of preparing a circuit for evaluation that depend only on the
+/// circuit's structure.  These are cached between evaluations, and discarded
+/// whenever the grid changes.
+struct CompiledCircuit {
+    wire_length: usize,
+    null_wires: HashSet<WireId>,
+    chips: Vec<CompiledChip>,
+    interface_slots: Vec<Vec<((Coords, Direction), WireId)>>,
+}
+
+struct CompiledChip {
+    ctype: ChipType,
+    coords: Coords,
+    /// The wire attached to each of the chip's ports, with its size.
+    wires: Vec<(WireId, WireSize)>,
+    /// For each of the chip's ports, the index of the wire group that the
+    /// port's wire belongs to (only present for source ports).
+    groups: Vec<Option<usize>>,
+}
+
+//===========================================================================//
+
 pub struct EditGrid {
     puzzle: Puzzle,
     allowed_chips: ChipSet,
     bounds: CoordsRect,
     interfaces: &'static [Interface],
     fragments: HashMap<(Coords, Direction), (WireShape, WireId)>,
+    // Every label is attached to an existing fragment; removing a fragment
+    // also removes its label (see `label_removals`).
+    wire_labels: HashMap<(Coords, Direction), String>,
     chips: HashMap<Coords, ChipCell>,
     wires: Vec<WireInfo>,
     wires_for_ports: HashMap<(Coords, Direction), WireId>,
+    // Wires in topological (evaluation) order, divided into groups that can
+    // be evaluated in parallel.  This is recomputed by typecheck_wires only
+    // when the grid changes, and is empty if the grid has errors.
     wire_groups: Vec<Vec<WireId>>,
     errors: Vec<WireError>,
+    unconnected_ports: Vec<(Coords, Direction)>,
+    rejected_chips: Vec<RejectedChip>,
+    // Notes from the circuit data, kept so that they survive being saved
+    // again; they are not editable on the board.
+    notes: CircuitNotes,
     eval: Option<CircuitEval>,
+    compiled: Option<CompiledCircuit>,
+    // Values chosen for the sandbox input ports; these aren't saved with the
+    // circuit.
+    sandbox_inputs: Vec<u32>,
     undo_stack: Vec<Vec<GridChange>>,
     redo_stack: Vec<Vec<GridChange>>,
     provisional_changes: Vec<GridChange>,
@@ -84,12 +160,18 @@ impl EditGrid {
             bounds: CoordsRect::with_size(origin, data.size),
             interfaces: puzzle.interfaces(),
             fragments: HashMap::new(),
+            wire_labels: HashMap::new(),
             chips: HashMap::new(),
             wires: Vec::new(),
             wires_for_ports: HashMap::new(),
             wire_groups: Vec::new(),
             errors: Vec::new(),
+            unconnected_ports: Vec::new(),
+            rejected_chips: Vec::new(),
+            notes: data.notes.truncated(),
             eval: None,
+            compiled: None,
+            sandbox_inputs: Vec::new(),
             undo_stack: Vec::new(),
             redo_stack: Vec::new(),
             provisional_changes: Vec::new(),
@@ -102,7 +184,18 @@ impl EditGrid {
         grid.bounds.height = grid.bounds.height.max(min_size.height);
 
         // Chips:
+        for (delta, name) in data.chips.unknown() {
+            let rejected = RejectedChip::Unknown(origin + delta, name.into());
+            debug_warn!("from_circuit_data: rejected {:?}", rejected);
+            grid.rejected_chips.push(rejected);
+        }
         for (delta, ctype, orient) in data.chips.iter() {
+            if !grid.allowed_chips.contains(ctype) {
+                let rejected = RejectedChip::NotAllowed(origin + delta, ctype);
+                debug_warn!("from_circuit_data: rejected {:?}", rejected);
+                grid.rejected_chips.push(rejected);
+                continue;
+            }
             let change = GridChange::AddChip(origin + delta, ctype, orient);
             if !grid.mutate_one(&change) {
                 debug_log!("from_circuit_data: {:?} had no effect", change);
@@ -211,12 +304,23 @@ impl EditGrid {
             grid.set_frag(coords, dir, WireShape::Stub);
         }
 
+        // Wire labels:
+        for (delta, dir, label) in data.labels.iter() {
+            let loc = (origin + delta, dir);
+            let label: String =
+                label.trim().chars().take(MAX_WIRE_LABEL_CHARS).collect();
+            if !label.is_empty() && grid.fragments.contains_key(&loc) {
+                grid.wire_labels.insert(loc, label);
+            }
+        }
+
         grid.typecheck_wires();
         grid
     }
 
     pub fn to_circuit_data(&self) -> CircuitData {
         let mut data = CircuitData::new(self.bounds.width, self.bounds.height);
+        data.notes = self.notes.clone();
         let origin = self.bounds.top_left();
         for (coords, ctype, orient) in self.chips() {
             data.chips.insert(coords - origin, ctype, orient);
@@ -248,6 +352,9 @@ impl EditGrid {
             }
             data.wires.insert(coords - origin, dir, shape);
         }
+        for ((coords, dir), label) in self.wire_labels() {
+            data.labels.insert(coords - origin, dir, label.to_string());
+        }
         data
     }
 
@@ -309,6 +416,21 @@ impl EditGrid {
         return true;
     }
 
+    pub fn stats(&self) -> CircuitStats {
+        let mut num_chips = 0;
+        for (_, ctype, _) in self.chips() {
+            if let ChipType::Comment(_) = ctype {
+                continue;
+            }
+            num_chips += 1;
+        }
+        CircuitStats {
+            num_chips,
+            wire_length: count_wire_fragments(self.fragments.keys().copied()),
+            area: self.bounds.saturating_area(),
+        }
+    }
+
     pub fn interfaces(&self) -> &[Interface] {
         &self.interfaces
     }
@@ -334,6 +456,31 @@ impl EditGrid {
         }
     }
 
+    /// Returns an iterator over the distinct wires in the grid (skipping any
+    /// "null" wires that have no fragments), in unspecified order.  See
+    /// `WireSummary` for what is reported about each wire.
+    pub fn wires(&self) -> WiresIter<'_> {
+        WiresIter { inner: self.wires.iter().enumerate(), grid: self }
+    }
+
+    /// Returns the cosmetic label attached to the wire fragment at the given
+    /// location, if any.
+    pub fn wire_label_at(
+        &self,
+        coords: Coords,
+        dir: Direction,
+    ) -> Option<&str> {
+        self.wire_labels.get(&(coords, dir)).map(String::as_str)
+    }
+
+    /// Returns an iterator over `((Coords, Direction), &str)` pairs for all
+    /// labeled wire fragments, in unspecified order.
+    pub fn wire_labels(
+        &self,
+    ) -> impl Iterator<Item = ((Coords, Direction), &str)> {
+        self.wire_labels.iter().map(|(&loc, label)| (loc, label.as_str()))
+    }
+
     pub fn wire_index_group(&self, group_index: usize) -> &[WireId] {
         if group_index < self.wire_groups.len() {
             &self.wire_groups[group_index]
@@ -350,6 +497,181 @@ impl EditGrid {
         &self.errors
     }
 
+    /// If the grid has an event wire loop that could be broken by replacing
+    /// a straight piece of wire next to one of the loop's chip ports with a
+    /// `Delay` chip, returns the location for the `Delay` chip and the
+    /// changes needed to insert it (to be applied as a single `try_mutate`).
+    pub fn loop_breaking_delay(&self) -> Option<(Coords, Vec<GridChange>)> {
+        if !self.allowed_chips.contains(ChipType::Delay) {
+            return None;
+        }
+        for error in self.errors.iter() {
+            let ports = match error {
+                WireError::UnbrokenLoop(_, true, ports) => ports,
+                _ => continue,
+            };
+            for &(coords, dir) in ports.iter() {
+                let flow = match self.chip_at(coords) {
+                    Some((chip_coords, ctype, orient)) => ctype
+                        .ports(chip_coords, orient)
+                        .into_iter()
+                        .find(|port| port.loc() == (coords, dir))
+                        .map(|port| port.flow),
+                    None => None,
+                };
+                // The Delay chip's output must face the chip's input port,
+                // or its input must face the chip's output port.
+                let output_dir = match flow {
+                    Some(PortFlow::Sink) => -dir,
+                    Some(PortFlow::Source) => dir,
+                    None => continue,
+                };
+                let delay_coords = coords + dir;
+                if let Some(changes) =
+                    self.delay_insertion(delay_coords, output_dir)
+                {
+                    return Some((delay_coords, changes));
+                }
+            }
+        }
+        None
+    }
+
+    /// Returns the changes needed to replace a straight event wire passing
+    /// through `coords` with a `Delay` chip whose output faces `output_dir`,
+    /// or `None` if that isn't possible.
+    fn delay_insertion(
+        &self,
+        coords: Coords,
+        output_dir: Direction,
+    ) -> Option<Vec<GridChange>> {
+        if !self.bounds.contains_point(coords)
+            || self.chip_at(coords).is_some()
+        {
+            return None;
+        }
+        for dir in Direction::all() {
+            let expected = if dir == output_dir || dir == -output_dir {
+                Some(WireShape::Straight)
+            } else {
+                None
+            };
+            if self.wire_shape_at(coords, dir) != expected {
+                return None;
+            }
+        }
+        let &(_, wire_id) = self.fragments.get(&(coords, output_dir))?;
+        if self.wires[wire_id.0].color != WireColor::Event {
+            return None;
+        }
+        let mut orient = Orientation::default();
+        while orient * Direction::East != output_dir {
+            orient = orient.rotate_cw();
+        }
+        let locs = [(coords, output_dir), (coords, -output_dir)];
+        let old_wires =
+            locs.iter().map(|&loc| (loc, WireShape::Straight)).collect();
+        let new_wires =
+            locs.iter().map(|&loc| (loc, WireShape::Stub)).collect();
+        Some(vec![
+            GridChange::ReplaceWires(old_wires, new_wires),
+            GridChange::AddChip(coords, ChipType::Delay, orient),
+        ])
+    }
+
+    /// Returns the changes needed to connect the two given port locations
+    /// with a new wire that doesn't pass through any chip or existing wire
+    /// (to be applied as a single `try_mutate`), or `None` if neither port
+    /// is free or there is no such route.
+    pub fn auto_route_wire(
+        &self,
+        start: (Coords, Direction),
+        end: (Coords, Direction),
+    ) -> Option<Vec<GridChange>> {
+        if start == end
+            || self.has_frag(start.0, start.1)
+            || self.has_frag(end.0, end.1)
+        {
+            return None;
+        }
+        let is_free = |coords: Coords| {
+            self.bounds.contains_point(coords)
+                && !self.chips.contains_key(&coords)
+                && Direction::all().all(|dir| !self.has_frag(coords, dir))
+        };
+        let first = start.0 + start.1;
+        let last = end.0 + end.1;
+        let last_exit = -end.1;
+        if !is_free(first) || !is_free(last) {
+            return None;
+        }
+        // Each search node is a cell on the route, along with the side of
+        // that cell that the wire enters through.  Turns cost a little extra,
+        // so that we prefer routes with long straight runs.
+        let (path, _) = astar(
+            &(first, -start.1),
+            |&(coords, entry)| {
+                Direction::all()
+                    .filter(|&exit| exit != entry)
+                    .map(|exit| {
+                        let cost = if exit == -entry { 2 } else { 3 };
+                        ((coords + exit, -exit), cost)
+                    })
+                    .filter(|&((next, _), _)| is_free(next))
+                    .collect::<Vec<_>>()
+            },
+            |&(coords, _)| {
+                2 * ((coords.x - last.x).abs() + (coords.y - last.y).abs())
+            },
+            |&(coords, entry)| coords == last && entry != last_exit,
+        )?;
+        let mut new_wires = HashMap::new();
+        new_wires.insert(start, WireShape::Stub);
+        new_wires.insert(end, WireShape::Stub);
+        for (index, &(coords, entry)) in path.iter().enumerate() {
+            let exit = match path.get(index + 1) {
+                Some(&(_, next_entry)) => -next_entry,
+                None => last_exit,
+            };
+            let shapes = if exit == -entry {
+                [(entry, WireShape::Straight), (exit, WireShape::Straight)]
+            } else if exit == entry.rotate_cw() {
+                [(entry, WireShape::TurnLeft), (exit, WireShape::TurnRight)]
+            } else {
+                [(entry, WireShape::TurnRight), (exit, WireShape::TurnLeft)]
+            };
+            for &(dir, shape) in shapes.iter() {
+                if new_wires.insert((coords, dir), shape).is_some() {
+                    debug_warn!(
+                        "Auto-routed wire crosses itself at {:?}",
+                        coords
+                    );
+                    return None;
+                }
+            }
+        }
+        Some(vec![GridChange::ReplaceWires(HashMap::new(), new_wires)])
+    }
+
+    /// Returns the chips from the circuit data that this grid was created from
+    /// that could not be loaded because their type was unknown or not allowed
+    /// in this puzzle.
+    pub fn rejected_chips(&self) -> &[RejectedChip] {
+        &self.rejected_chips
+    }
+
+    /// Returns the freeform notes (description and author) that were stored
+    /// with the circuit data that this grid was created from.
+    pub fn notes(&self) -> &CircuitNotes {
+        &self.notes
+    }
+
+    /// Returns the locations of chip ports whose wire doesn't connect them
+    /// to any other port.  This is updated whenever the grid changes.
+    pub fn unconnected_ports(&self) -> &[(Coords, Direction)] {
+        &self.unconnected_ports
+    }
+
     pub fn has_chip_at(&self, coords: Coords) -> bool {
         self.chips.contains_key(&coords)
     }
@@ -394,6 +716,27 @@ impl EditGrid {
         return None;
     }
 
+    /// Returns true if there is a chip or interface port at the given
+    /// location.
+    pub fn has_port_at(&self, loc: (Coords, Direction)) -> bool {
+        let (coords, _) = loc;
+        if let Some((chip_coords, ctype, orient)) = self.chip_at(coords) {
+            ctype
+                .ports(chip_coords, orient)
+                .iter()
+                .any(|port| port.loc() == loc)
+        } else if let Some((top_left, _, interface)) =
+            self.interface_at(coords)
+        {
+            interface
+                .ports_with_top_left(top_left)
+                .iter()
+                .any(|(_, port)| port.loc() == loc)
+        } else {
+            false
+        }
+    }
+
     pub fn wire_id_at(
         &self,
         coords: Coords,
@@ -402,6 +745,15 @@ impl EditGrid {
         self.fragments.get(&(coords, dir)).map(|&(_, id)| id)
     }
 
+    /// Returns the color of the given wire, or `WireColor::Unknown` if there
+    /// is no such wire.
+    pub fn wire_color(&self, wire_id: WireId) -> WireColor {
+        self.wires
+            .get(wire_id.0)
+            .map(|wire| wire.color)
+            .unwrap_or(WireColor::Unknown)
+    }
+
     pub fn wire_shape_at(
         &self,
         coords: Coords,
@@ -476,6 +828,52 @@ impl EditGrid {
         }
     }
 
+    /// Replaces the bounds, chips, wires, and wire labels of this grid with
+    /// those of `other` (which must be for the same puzzle), as a single
+    /// change that can be undone.  The grid's notes are left as they are.
+    /// Returns false, leaving the grid unchanged, if the replacement isn't
+    /// possible (e.g. because the grid is being evaluated).
+    #[must_use = "must not ignore try_replace_with failure"]
+    pub fn try_replace_with(&mut self, other: &EditGrid) -> bool {
+        if self.puzzle != other.puzzle {
+            return false;
+        }
+        let old_wires: HashMap<(Coords, Direction), WireShape> = self
+            .fragments
+            .iter()
+            .map(|(&loc, &(shape, _))| (loc, shape))
+            .collect();
+        let new_wires: HashMap<(Coords, Direction), WireShape> = other
+            .fragments
+            .iter()
+            .map(|(&loc, &(shape, _))| (loc, shape))
+            .collect();
+        let mut changes = Vec::<GridChange>::new();
+        if !old_wires.is_empty() {
+            changes.push(GridChange::ReplaceWires(old_wires, HashMap::new()));
+        }
+        for (coords, ctype, orient) in self.chips() {
+            changes.push(GridChange::RemoveChip(coords, ctype, orient));
+        }
+        if self.bounds != other.bounds {
+            changes.push(GridChange::SetBounds(self.bounds, other.bounds));
+        }
+        for (coords, ctype, orient) in other.chips() {
+            changes.push(GridChange::AddChip(coords, ctype, orient));
+        }
+        if !new_wires.is_empty() {
+            changes.push(GridChange::ReplaceWires(HashMap::new(), new_wires));
+        }
+        for (&loc, label) in other.wire_labels.iter() {
+            changes.push(GridChange::SetWireLabel(
+                loc,
+                String::new(),
+                label.clone(),
+            ));
+        }
+        self.try_mutate(changes)
+    }
+
     #[must_use = "must not ignore try_mutate_provisionally failure"]
     pub fn try_mutate_provisionally(
         &mut self,
@@ -520,25 +918,61 @@ impl EditGrid {
         return true;
     }
 
+    /// Replaces any provisional changes (such as a manually-dragged wire) with
+    /// an automatically-routed wire between the two given port locations (see
+    /// `auto_route_wire`), committed as a single undoable change.  If no
+    /// route can be found, the provisional changes are kept and false is
+    /// returned.
+    #[must_use = "must not ignore try_auto_route_wire failure"]
+    pub fn try_auto_route_wire(
+        &mut self,
+        start: (Coords, Direction),
+        end: (Coords, Direction),
+    ) -> bool {
+        if self.eval.is_some() {
+            return false;
+        }
+        let provisional = self.provisional_changes.clone();
+        self.roll_back_provisional_changes();
+        if let Some(changes) = self.auto_route_wire(start, end) {
+            if self.try_mutate(changes) {
+                return true;
+            }
+        }
+        if !self.try_mutate_provisionally(provisional) {
+            debug_warn!("failed to restore provisional changes");
+        }
+        false
+    }
+
     #[must_use = "must not ignore try_mutate_internal result"]
     fn try_mutate_internal(
         &mut self,
-        mut changes: Vec<GridChange>,
+        changes: Vec<GridChange>,
     ) -> Option<Vec<GridChange>> {
         if self.eval.is_some() {
             return None;
         }
-        let num_changes: usize = changes.len();
-        let mut num_succeeded: usize = 0;
-        for change in changes.iter() {
-            if !self.mutate_one(change) {
+        let mut applied = Vec::<GridChange>::with_capacity(changes.len());
+        let mut succeeded = true;
+        'changes: for change in changes.into_iter() {
+            // Labels on removed fragments are cleared first, as separate
+            // changes, so that undoing the removal restores them.
+            for removal in self.label_removals(&change) {
+                if !self.mutate_one(&removal) {
+                    succeeded = false;
+                    break 'changes;
+                }
+                applied.push(removal);
+            }
+            if !self.mutate_one(&change) {
+                succeeded = false;
                 break;
             }
-            num_succeeded += 1;
+            applied.push(change);
         }
-        let changes = if num_succeeded < num_changes {
-            changes.truncate(num_succeeded);
-            for change in GridChange::invert_group(changes) {
+        let changes = if !succeeded {
+            for change in GridChange::invert_group(applied) {
                 if !self.mutate_one(&change) {
                     debug_warn!("failed to roll back {:?}", change);
                 }
@@ -547,12 +981,35 @@ impl EditGrid {
         } else {
             self.redo_stack.clear();
             self.mark_modified();
-            Some(changes)
+            Some(applied)
         };
         self.typecheck_wires();
         return changes;
     }
 
+    /// Returns the `SetWireLabel` changes needed to clear the labels on any
+    /// wire fragments that the given change would remove without replacing.
+    fn label_removals(&self, change: &GridChange) -> Vec<GridChange> {
+        match *change {
+            GridChange::ReplaceWires(ref old_wires, ref new_wires) => {
+                old_wires
+                    .keys()
+                    .filter(|loc| !new_wires.contains_key(loc))
+                    .filter_map(|&loc| {
+                        self.wire_labels.get(&loc).map(|label| {
+                            GridChange::SetWireLabel(
+                                loc,
+                                label.clone(),
+                                String::new(),
+                            )
+                        })
+                    })
+                    .collect()
+            }
+            _ => Vec::new(),
+        }
+    }
+
     #[must_use = "must not ignore mutate_one failure"]
     fn mutate_one(&mut self, change: &GridChange) -> bool {
         debug_assert!(self.eval.is_none());
@@ -769,6 +1226,21 @@ impl EditGrid {
                     return false;
                 }
             }
+            GridChange::SetWireLabel(loc, ref old_label, ref new_label) => {
+                let current = self.wire_label_at(loc.0, loc.1).unwrap_or("");
+                if current != old_label.as_str()
+                    || new_label.chars().count() > MAX_WIRE_LABEL_CHARS
+                    || (!new_label.is_empty()
+                        && !self.fragments.contains_key(&loc))
+                {
+                    return false;
+                }
+                if new_label.is_empty() {
+                    self.wire_labels.remove(&loc);
+                } else {
+                    self.wire_labels.insert(loc, new_label.clone());
+                }
+            }
         }
         return true;
     }
@@ -786,6 +1258,7 @@ impl EditGrid {
         self.wires_for_ports = HashMap::new();
         self.wire_groups = Vec::new();
         self.eval = None;
+        self.compiled = None;
         self.validate_wire_fragments();
 
         let mut all_ports =
@@ -795,7 +1268,12 @@ impl EditGrid {
                 all_ports.insert(port.loc(), (port.flow, port.color));
             }
         }
-        for (coords, ctype, orient) in self.chips() {
+        // Visit chips in a consistent order, so that wire IDs, wire sizes,
+        // and errors don't depend on HashMap iteration order.
+        let mut chips: Vec<(Coords, ChipType, Orientation)> =
+            self.chips().collect();
+        chips.sort_by_key(|&(coords, _, _)| (coords.x, coords.y));
+        for &(coords, ctype, orient) in chips.iter() {
             for port in ctype.ports(coords, orient) {
                 all_ports.insert(port.loc(), (port.flow, port.color));
             }
@@ -804,12 +1282,21 @@ impl EditGrid {
         self.wires = check::group_wires(&all_ports, &mut self.fragments);
         self.errors = check::recolor_wires(&mut self.wires);
         self.wires_for_ports = check::map_ports_to_wires(&self.wires);
+        self.unconnected_ports = chips
+            .iter()
+            .flat_map(|&(coords, ctype, orient)| ctype.ports(coords, orient))
+            .map(|port| port.loc())
+            .filter(|loc| match self.wires_for_ports.get(loc) {
+                Some(wire_id) => self.wires[wire_id.0].ports.len() < 2,
+                None => true,
+            })
+            .collect();
 
         let constraints: Vec<PortConstraint> = self
             .interfaces
             .iter()
             .flat_map(|interface| interface.constraints(self.bounds))
-            .chain(self.chips().flat_map(|(coords, ctype, orient)| {
+            .chain(chips.iter().flat_map(|&(coords, ctype, orient)| {
                 ctype.constraints(coords, orient)
             }))
             .collect();
@@ -819,9 +1306,9 @@ impl EditGrid {
             constraints,
         ));
 
-        let dependencies: Vec<PortDependency> = self
-            .chips()
-            .flat_map(|(coords, ctype, orient)| {
+        let dependencies: Vec<PortDependency> = chips
+            .iter()
+            .flat_map(|&(coords, ctype, orient)| {
                 ctype.dependencies(coords, orient)
             })
             .collect();
@@ -845,6 +1332,24 @@ impl EditGrid {
         }
     }
 
+    /// Returns the value chosen for the given sandbox input port (or zero if
+    /// none has been chosen).
+    pub fn sandbox_input(&self, index: usize) -> u32 {
+        self.sandbox_inputs.get(index).copied().unwrap_or(0)
+    }
+
+    /// Sets the value that the given sandbox input port sends each time step,
+    /// both for the current evaluation (if any) and for future ones.
+    pub fn set_sandbox_input(&mut self, index: usize, value: u32) {
+        if self.sandbox_inputs.len() <= index {
+            self.sandbox_inputs.resize(index + 1, 0);
+        }
+        self.sandbox_inputs[index] = value;
+        if let Some(ref mut eval) = self.eval {
+            eval.set_sandbox_input(index, value);
+        }
+    }
+
     pub fn eval(&self) -> Option<&CircuitEval> {
         self.eval.as_ref()
     }
@@ -857,7 +1362,50 @@ impl EditGrid {
         if !self.errors.is_empty() {
             return false;
         }
+        let start_time = Instant::now();
+        let was_cached = self.compiled.is_some();
+        if !was_cached {
+            self.compiled = Some(self.compile());
+        }
+        let compiled = self.compiled.as_ref().unwrap();
+
+        let mut chip_evals: Vec<Vec<Box<dyn ChipEval>>> =
+            (0..self.wire_groups.len()).map(|_| vec![]).collect();
+        for chip in compiled.chips.iter() {
+            for (port_index, chip_eval) in
+                new_chip_evals(chip.ctype, chip.coords, &chip.wires)
+            {
+                let group_index = chip.groups[port_index].unwrap();
+                chip_evals[group_index].push(chip_eval);
+            }
+        }
+        let puzzle_eval =
+            new_puzzle_eval(self.puzzle, compiled.interface_slots.clone());
 
+        let mut eval = CircuitEval::new(
+            compiled.wire_length,
+            self.wires.len(),
+            compiled.null_wires.clone(),
+            chip_evals,
+            puzzle_eval,
+            self.puzzle.score_units(),
+        );
+        for (index, &value) in self.sandbox_inputs.iter().enumerate() {
+            eval.set_sandbox_input(index, value);
+        }
+        self.eval = Some(eval);
+        debug_log!(
+            "Starting evaluation (took {:?}, analysis {})",
+            start_time.elapsed(),
+            if was_cached { "cached" } else { "compiled" }
+        );
+        return true;
+    }
+
+    /// Performs the evaluation-independent setup work for `start_eval`.
+    /// This must only be called when the grid has no errors.
+    fn compile(&self) -> CompiledCircuit {
+        debug_assert!(self.errors.is_empty());
         let mut wires_for_ports =
             HashMap::<(Coords, Direction), WireId>::new();
         let mut groups_for_ports =
@@ -880,57 +1428,49 @@ impl EditGrid {
             }
         }
 
-        let mut chip_evals: Vec<Vec<Box<dyn ChipEval>>> =
-            (0..self.wire_groups.len()).map(|_| vec![]).collect();
-        for (coords, ctype, orient) in self.chips() {
-            let ports = ctype.ports(coords, orient);
-            let wires: Vec<(WireId, WireSize)> = ports
-                .iter()
-                .map(|port| {
-                    let wire_id = wires_for_ports[&port.loc()];
-                    let wire = &self.wires[wire_id.0];
-                    debug_assert!(!wire.has_error);
-                    debug_assert!(!wire.size.is_empty());
-                    (wire_id, wire.size.lower_bound().unwrap())
-                })
-                .collect();
-            for (port_index, chip_eval) in
-                new_chip_evals(ctype, coords, &wires)
-            {
-                let port = &ports[port_index];
-                let group_index = groups_for_ports[&port.loc()];
-                chip_evals[group_index].push(chip_eval);
-            }
-        }
+        let chips = self
+            .chips()
+            .map(|(coords, ctype, orient)| {
+                let ports = ctype.ports(coords, orient);
+                let wires = ports
+                    .iter()
+                    .map(|port| {
+                        let wire_id = wires_for_ports[&port.loc()];
+                        let wire = &self.wires[wire_id.0];
+                        debug_assert!(!wire.has_error);
+                        debug_assert!(!wire.size.is_empty());
+                        (wire_id, wire.size.lower_bound().unwrap())
+                    })
+                    .collect();
+                let groups = ports
+                    .iter()
+                    .map(|port| groups_for_ports.get(&port.loc()).copied())
+                    .collect();
+                CompiledChip { ctype, coords, wires, groups }
+            })
+            .collect();
 
-        let puzzle_eval = {
-            let slots: Vec<Vec<((Coords, Direction), WireId)>> = self
-                .interfaces
-                .iter()
-                .map(|interface| {
-                    interface
-                        .ports(self.bounds)
-                        .into_iter()
-                        .map(|(_, port)| {
-                            let loc = port.loc();
-                            (loc, wires_for_ports[&loc])
-                        })
-                        .collect()
-                })
-                .collect();
-            new_puzzle_eval(self.puzzle, slots)
-        };
+        let interface_slots = self
+            .interfaces
+            .iter()
+            .map(|interface| {
+                interface
+                    .ports(self.bounds)
+                    .into_iter()
+                    .map(|(_, port)| {
+                        let loc = port.loc();
+                        (loc, wires_for_ports[&loc])
+                    })
+                    .collect()
+            })
+            .collect();
 
-        self.eval = Some(CircuitEval::new(
-            self.fragments.len(),
-            self.wires.len(),
+        CompiledCircuit {
+            wire_length: self.stats().wire_length,
             null_wires,
-            chip_evals,
-            puzzle_eval,
-            self.puzzle.score_units(),
-        ));
-        debug_log!("Starting evaluation");
-        return true;
+            chips,
+            interface_slots,
+        }
     }
 
     pub fn stop_eval(&mut self) {
@@ -1020,22 +1560,37 @@ impl EditGrid {
                          same type.",
                     );
                 }
-                WireError::NoValidSize(id) if id == wire_id => {
-                    // TODO: Make this message more helpful.  For example, if
-                    //   the wire must be exactly 2 bits on one side and 4 bits
-                    //   on the other, we should give those values.
+                WireError::NoValidSize(id, ref conflict) if id == wire_id => {
                     fmt.push_str(
                         "\n\n$RError:$D This wire is connecting \
                          mismatching bit sizes.",
                     );
+                    match *conflict {
+                        Some(SizeConflict { first: Some(first), second }) => {
+                            fmt.push_str(&format!(
+                                "  One port requires it to be {}, but \
+                                 another requires it to be {}.",
+                                first.1.description(),
+                                second.1.description()
+                            ));
+                        }
+                        Some(SizeConflict { first: None, second }) => {
+                            fmt.push_str(&format!(
+                                "  One port requires it to be {}, which \
+                                 isn't possible for this type of wire.",
+                                second.1.description()
+                            ));
+                        }
+                        None => {}
+                    }
                 }
-                WireError::UnbrokenLoop(ref ids, contains_events)
+                WireError::UnbrokenLoop(ref ids, contains_events, _)
                     if ids.contains(&wire_id) =>
                 {
                     // TODO: Fix this message to account for analog wires.
                     fmt.push_str(
                         "\n\n$RError:$D This wire forms a closed \
-                         loop",
+                         loop through the marked chip ports",
                     );
                     if contains_events {
                         fmt.push_str(
@@ -1277,3 +1832,708 @@ impl<'a> ExactSizeIterator for WireFragmentsForWireIter<'a> {
 }
 
 //===========================================================================//
+
+/// A summary of one distinct wire in an `EditGrid`, as returned by
+/// `EditGrid::wires`.
+pub struct WireSummary<'a> {
+    pub id: WireId,
+    /// The inferred size of the wire, or `None` if no size is consistent with
+    /// the ports it connects (in which case `has_error` will be true).
+    pub size: Option<WireSize>,
+    pub color: WireColor,
+    pub has_error: bool,
+    /// The chip/interface ports that the wire connects to, as `((Coords,
+    /// Direction), PortFlow)` pairs, in unspecified order.
+    pub endpoints: Vec<((Coords, Direction), PortFlow)>,
+    /// The wire's fragments, in unspecified order.
+    pub fragments: WireFragmentsForWireIter<'a>,
+}
+
+pub struct WiresIter<'a> {
+    inner: std::iter::Enumerate<std::slice::Iter<'a, WireInfo>>,
+    grid: &'a EditGrid,
+}
+
+impl<'a> Iterator for WiresIter<'a> {
+    type Item = WireSummary<'a>;
+
+    fn next(&mut self) -> Option<WireSummary<'a>> {
+        for (index, wire) in &mut self.inner {
+            if wire.fragments.is_empty() {
+                continue;
+            }
+            let id = WireId(index);
+            return Some(WireSummary {
+                id,
+                size: wire.size.lower_bound(),
+                color: wire.color,
+                has_error: wire.has_error,
+                endpoints: wire
+                    .ports
+                    .iter()
+                    .map(|(&loc, &(flow, _))| (loc, flow))
+                    .collect(),
+                fragments: self.grid.wire_fragments_for_wire_id(id),
+            });
+        }
+        None
+    }
+}
+
+//===========================================================================//
+
+#[cfg(test)]
+mod tests {
+    use super::{CircuitStats, EditGrid, RejectedChip};
+    use crate::geom::{Coords, CoordsDelta, Direction, Orientation};
+    use crate::save::{
+        ChipType, CircuitData, Puzzle, PuzzleSet, WireShape, WireSize,
+    };
+    use crate::state::{
+        ChipExt, EvalResult, GridChange, PortFlow, WireColor, WireError,
+        WireId,
+    };
+    use std::collections::{HashMap, HashSet};
+
+    #[test]
+    fn unconnected_ports_update_on_change() {
+        let mut data = CircuitData::new(10, 10);
+        data.chips.insert(
+            CoordsDelta::new(2, 2),
+            ChipType::Not,
+            Orientation::default(),
+        );
+        data.wires.insert(
+            CoordsDelta::new(2, 2),
+            Direction::East,
+            WireShape::Stub,
+        );
+        data.wires.insert(
+            CoordsDelta::new(3, 2),
+            Direction::West,
+            WireShape::Straight,
+        );
+        data.wires.insert(
+            CoordsDelta::new(3, 2),
+            Direction::East,
+            WireShape::Straight,
+        );
+        data.wires.insert(
+            CoordsDelta::new(4, 2),
+            Direction::West,
+            WireShape::Stub,
+        );
+        let mut grid = EditGrid::from_circuit_data(
+            Puzzle::TutorialOr,
+            &PuzzleSet::new(),
+            &data,
+        );
+        let unconnected: HashSet<(Coords, Direction)> =
+            grid.unconnected_ports().iter().copied().collect();
+        assert_eq!(
+            unconnected,
+            vec![
+                (Coords::new(2, 2), Direction::East),
+                (Coords::new(2, 2), Direction::West),
+            ]
+            .into_iter()
+            .collect::<HashSet<_>>()
+        );
+
+        let change = GridChange::AddChip(
+            Coords::new(4, 2),
+            ChipType::Not,
+            Orientation::default(),
+        );
+        assert!(grid.try_mutate(vec![change]));
+        let unconnected: HashSet<(Coords, Direction)> =
+            grid.unconnected_ports().iter().copied().collect();
+        assert_eq!(
+            unconnected,
+            vec![
+                (Coords::new(2, 2), Direction::West),
+                (Coords::new(4, 2), Direction::East),
+            ]
+            .into_iter()
+            .collect::<HashSet<_>>()
+        );
+    }
+
+    #[test]
+    fn auto_route_wire_avoids_chips() {
+        let mut data = CircuitData::new(10, 10);
+        for &x in &[1, 4, 7, 8] {
+            data.chips.insert(
+                CoordsDelta::new(x, 2),
+                ChipType::Not,
+                Orientation::default(),
+            );
+        }
+        let mut grid = EditGrid::from_circuit_data(
+            Puzzle::TutorialOr,
+            &PuzzleSet::new(),
+            &data,
+        );
+        let start = (Coords::new(1, 2), Direction::East);
+        let end = (Coords::new(7, 2), Direction::West);
+        let changes = grid.auto_route_wire(start, end).unwrap();
+        assert!(grid.try_mutate(changes));
+        assert!(!grid.unconnected_ports().contains(&start));
+        assert!(!grid.unconnected_ports().contains(&end));
+        assert_eq!(
+            grid.wire_id_at(start.0, start.1),
+            grid.wire_id_at(end.0, end.1)
+        );
+        for dir in Direction::all() {
+            assert_eq!(grid.wire_shape_at(Coords::new(4, 2), dir), None);
+        }
+
+        // Ports that are already wired, or that face another chip, can't be
+        // routed to.
+        let blocked = (Coords::new(8, 2), Direction::West);
+        let other = (Coords::new(4, 2), Direction::East);
+        assert!(grid.auto_route_wire(other, blocked).is_none());
+        assert!(grid.auto_route_wire(other, end).is_none());
+    }
+
+    #[test]
+    fn failed_auto_route_keeps_provisional_changes() {
+        let mut data = CircuitData::new(10, 10);
+        data.chips.insert(
+            CoordsDelta::new(2, 2),
+            ChipType::Not,
+            Orientation::default(),
+        );
+        let mut grid = EditGrid::from_circuit_data(
+            Puzzle::TutorialOr,
+            &PuzzleSet::new(),
+            &data,
+        );
+        let start = (Coords::new(2, 2), Direction::East);
+        let mut stubs = HashMap::new();
+        stubs.insert(start, WireShape::Stub);
+        stubs.insert((Coords::new(3, 2), Direction::West), WireShape::Stub);
+        let change = GridChange::ReplaceWires(HashMap::new(), stubs);
+        assert!(grid.try_mutate_provisionally(vec![change]));
+        assert!(!grid.try_auto_route_wire(start, start));
+        assert!(grid.has_provisional_changes());
+        assert_eq!(
+            grid.wire_shape_at(start.0, start.1),
+            Some(WireShape::Stub)
+        );
+
+        let end = (Coords::new(2, 2), Direction::West);
+        assert!(grid.try_auto_route_wire(start, end));
+        assert!(!grid.has_provisional_changes());
+        assert_eq!(
+            grid.wire_shape_at(Coords::new(3, 2), Direction::West),
+            Some(WireShape::TurnRight)
+        );
+        assert!(grid.undo());
+        assert_eq!(grid.wire_shape_at(start.0, start.1), None);
+    }
+
+    #[test]
+    fn compiled_circuit_is_cached_until_grid_changes() {
+        let mut grid =
+            EditGrid::new(Puzzle::SandboxBehavior, &PuzzleSet::new());
+        assert!(grid.compiled.is_none());
+        assert!(grid.start_eval());
+        grid.stop_eval();
+        assert!(grid.compiled.is_some());
+        assert!(grid.start_eval());
+        grid.stop_eval();
+        assert!(grid.compiled.is_some());
+
+        let change = GridChange::AddChip(
+            Coords::new(2, 2),
+            ChipType::Not,
+            Orientation::default(),
+        );
+        assert!(grid.try_mutate(vec![change]));
+        assert!(grid.compiled.is_none());
+        assert!(grid.start_eval());
+        assert_eq!(grid.eval().unwrap().errors().len(), 0);
+    }
+
+    #[test]
+    fn circuit_notes_survive_editing() {
+        let mut data = CircuitData::new(8, 6);
+        data.notes.description = "  Inverts its input.  ".to_string();
+        data.notes.author = "Jane".to_string();
+        let mut grid = EditGrid::from_circuit_data(
+            Puzzle::SandboxBehavior,
+            &PuzzleSet::with_everything_solved(),
+            &data,
+        );
+        assert_eq!(grid.notes().description, "Inverts its input.");
+        assert!(grid.try_mutate(vec![GridChange::AddChip(
+            Coords::new(2, 2),
+            ChipType::Not,
+            Orientation::default(),
+        )]));
+        let saved = grid.to_circuit_data();
+        assert_eq!(saved.notes.description, "Inverts its input.");
+        assert_eq!(saved.notes.author, "Jane");
+    }
+
+    #[test]
+    fn loop_breaking_delay_replaces_straight_event_wire() {
+        // An event wire loops from a Discard chip's output back to its input.
+        let mut data = CircuitData::new(5, 2);
+        data.chips.insert(
+            CoordsDelta::new(2, 0),
+            ChipType::Discard,
+            Orientation::default(),
+        );
+        let wires = [
+            ((0, 0), Direction::South, WireShape::TurnRight),
+            ((1, 0), Direction::East, WireShape::Straight),
+            ((2, 0), Direction::East, WireShape::Stub),
+            ((3, 0), Direction::East, WireShape::Straight),
+            ((4, 0), Direction::West, WireShape::TurnRight),
+            ((4, 1), Direction::North, WireShape::TurnRight),
+            ((0, 1), Direction::East, WireShape::TurnRight),
+            ((1, 1), Direction::East, WireShape::Straight),
+            ((2, 1), Direction::East, WireShape::Straight),
+            ((3, 1), Direction::East, WireShape::Straight),
+        ];
+        for &((x, y), dir, shape) in wires.iter() {
+            data.wires.insert(CoordsDelta::new(x, y), dir, shape);
+        }
+        let mut grid = EditGrid::from_circuit_data(
+            Puzzle::SandboxEvent,
+            &PuzzleSet::with_everything_solved(),
+            &data,
+        );
+        assert!(grid
+            .errors()
+            .iter()
+            .any(|error| matches!(error, WireError::UnbrokenLoop(..))));
+
+        let (coords, changes) = grid.loop_breaking_delay().unwrap();
+        assert_eq!(coords, Coords::new(1, 0));
+        assert!(grid.try_mutate(changes));
+        assert_eq!(
+            grid.chip_at(coords),
+            Some((coords, ChipType::Delay, Orientation::default()))
+        );
+        assert!(!grid
+            .errors()
+            .iter()
+            .any(|error| matches!(error, WireError::UnbrokenLoop(..))));
+        assert_eq!(grid.loop_breaking_delay(), None);
+    }
+
+    #[test]
+    fn compiled_chips_are_in_topological_order() {
+        // A chain of chips: Const -> Not -> Not -> Not.
+        let mut data = CircuitData::new(8, 4);
+        data.chips.insert(
+            CoordsDelta::new(1, 1),
+            ChipType::Const(1),
+            Orientation::default(),
+        );
+        for x in 2..5 {
+            data.chips.insert(
+                CoordsDelta::new(x, 1),
+                ChipType::Not,
+                Orientation::default(),
+            );
+        }
+        for x in 1..4 {
+            data.wires.insert(
+                CoordsDelta::new(x, 1),
+                Direction::East,
+                WireShape::Stub,
+            );
+            data.wires.insert(
+                CoordsDelta::new(x + 1, 1),
+                Direction::West,
+                WireShape::Stub,
+            );
+        }
+        let mut grid = EditGrid::from_circuit_data(
+            Puzzle::SandboxBehavior,
+            &PuzzleSet::with_everything_solved(),
+            &data,
+        );
+        assert!(grid.errors().is_empty());
+        assert!(grid.start_eval());
+
+        let mut group_for_wire = HashMap::<WireId, usize>::new();
+        for (index, group) in grid.wire_groups.iter().enumerate() {
+            for &wire_id in group.iter() {
+                group_for_wire.insert(wire_id, index);
+            }
+        }
+        let compiled = grid.compiled.as_ref().unwrap();
+        let mut chain_groups = Vec::<usize>::new();
+        for chip in compiled.chips.iter() {
+            let ports = chip.ctype.ports(chip.coords, Orientation::default());
+            for dep in
+                chip.ctype.dependencies(chip.coords, Orientation::default())
+            {
+                let sink_index =
+                    ports.iter().position(|p| p.loc() == dep.sink).unwrap();
+                let source_index =
+                    ports.iter().position(|p| p.loc() == dep.source).unwrap();
+                let sink_group = group_for_wire[&chip.wires[sink_index].0];
+                let source_group = chip.groups[source_index].unwrap();
+                assert!(
+                    sink_group < source_group,
+                    "{:?} at {:?} evaluates its output before its input",
+                    chip.ctype,
+                    chip.coords
+                );
+                chain_groups.push(source_group);
+            }
+        }
+        chain_groups.sort();
+        assert_eq!(chain_groups.len(), 3);
+        chain_groups.dedup();
+        assert_eq!(chain_groups.len(), 3);
+    }
+
+    #[test]
+    fn wires_report_fragments_endpoints_and_sizes() {
+        let mut data = CircuitData::new(10, 10);
+        data.chips.insert(
+            CoordsDelta::new(2, 2),
+            ChipType::Const(5),
+            Orientation::default(),
+        );
+        data.wires.insert(
+            CoordsDelta::new(2, 2),
+            Direction::East,
+            WireShape::Stub,
+        );
+        data.wires.insert(
+            CoordsDelta::new(3, 2),
+            Direction::West,
+            WireShape::Straight,
+        );
+        data.wires.insert(
+            CoordsDelta::new(3, 2),
+            Direction::East,
+            WireShape::Straight,
+        );
+        data.wires.insert(
+            CoordsDelta::new(4, 2),
+            Direction::West,
+            WireShape::Stub,
+        );
+        let grid = EditGrid::from_circuit_data(
+            Puzzle::SandboxBehavior,
+            &PuzzleSet::with_everything_solved(),
+            &data,
+        );
+        let loc = (Coords::new(2, 2), Direction::East);
+        let wires: Vec<_> = grid
+            .wires()
+            .filter(|wire| wire.endpoints.iter().any(|&(l, _)| l == loc))
+            .collect();
+        assert_eq!(wires.len(), 1);
+        let wire = wires.into_iter().next().unwrap();
+        assert_eq!(grid.wire_id_at(loc.0, loc.1), Some(wire.id));
+        assert_eq!(wire.size, Some(WireSize::Four));
+        assert_eq!(wire.color, WireColor::Behavior);
+        assert!(!wire.has_error);
+        assert_eq!(wire.endpoints, vec![(loc, PortFlow::Source)]);
+        let fragments: HashSet<(Coords, Direction)> =
+            wire.fragments.map(|(loc, _)| loc).collect();
+        assert_eq!(
+            fragments,
+            vec![
+                (Coords::new(2, 2), Direction::East),
+                (Coords::new(3, 2), Direction::West),
+                (Coords::new(3, 2), Direction::East),
+                (Coords::new(4, 2), Direction::West),
+            ]
+            .into_iter()
+            .collect::<HashSet<_>>()
+        );
+        assert!(grid.wires().all(|wire| wire.fragments.count() > 0));
+    }
+
+    #[test]
+    fn sandbox_inputs_drive_input_interface_ports() {
+        let mut grid = EditGrid::new(
+            Puzzle::SandboxBehavior,
+            &PuzzleSet::with_everything_solved(),
+        );
+        let bounds = grid.bounds();
+        let ports: Vec<(Coords, Direction)> = grid
+            .interfaces()
+            .last()
+            .unwrap()
+            .ports(bounds)
+            .into_iter()
+            .map(|(_, port)| port.loc())
+            .collect();
+        assert_eq!(ports.len(), 2);
+        grid.set_sandbox_input(1, 42);
+        assert!(grid.start_eval());
+        let _ = grid.eval_mut().unwrap().step_time();
+        assert_eq!(grid.port_value(ports[0]), Some(0));
+        assert_eq!(grid.port_value(ports[1]), Some(42));
+        grid.set_sandbox_input(0, 7);
+        let _ = grid.eval_mut().unwrap().step_time();
+        assert_eq!(grid.port_value(ports[0]), Some(7));
+        assert_eq!(grid.sandbox_input(0), 7);
+        assert_eq!(grid.sandbox_input(5), 0);
+    }
+
+    #[test]
+    fn wire_labels_round_trip_and_undo() {
+        let mut data = CircuitData::new(10, 10);
+        data.wires.insert(
+            CoordsDelta::new(2, 2),
+            Direction::East,
+            WireShape::Stub,
+        );
+        data.labels.insert(
+            CoordsDelta::new(2, 2),
+            Direction::East,
+            "Bus".to_string(),
+        );
+        // Labels not attached to any fragment are dropped when loading.
+        data.labels.insert(
+            CoordsDelta::new(5, 5),
+            Direction::North,
+            "Nowhere".to_string(),
+        );
+        let mut grid = EditGrid::from_circuit_data(
+            Puzzle::SandboxEvent,
+            &PuzzleSet::new(),
+            &data,
+        );
+        let loc = (Coords::new(2, 2), Direction::East);
+        assert_eq!(grid.wire_label_at(loc.0, loc.1), Some("Bus"));
+        assert_eq!(grid.wire_labels().count(), 1);
+        let stats = grid.stats();
+
+        let change = GridChange::SetWireLabel(
+            loc,
+            "Bus".to_string(),
+            "Clock".to_string(),
+        );
+        assert!(grid.try_mutate(vec![change]));
+        assert_eq!(grid.wire_label_at(loc.0, loc.1), Some("Clock"));
+        assert_eq!(grid.stats(), stats);
+        let data = grid.to_circuit_data();
+        let labels: Vec<_> = data.labels.iter().collect();
+        assert_eq!(labels, vec![(CoordsDelta::new(2, 2), loc.1, "Clock")]);
+
+        assert!(grid.undo());
+        assert_eq!(grid.wire_label_at(loc.0, loc.1), Some("Bus"));
+
+        // Labels can only be added to existing fragments.
+        let change = GridChange::SetWireLabel(
+            (Coords::new(5, 5), Direction::North),
+            String::new(),
+            "Nope".to_string(),
+        );
+        assert!(!grid.try_mutate(vec![change]));
+    }
+
+    #[test]
+    fn replacing_whole_grid_can_be_undone() {
+        let mut data = CircuitData::new(8, 6);
+        data.chips.insert(
+            CoordsDelta::new(1, 1),
+            ChipType::Not,
+            Orientation::default(),
+        );
+        data.wires.insert(
+            CoordsDelta::new(1, 1),
+            Direction::East,
+            WireShape::Stub,
+        );
+        data.labels.insert(
+            CoordsDelta::new(1, 1),
+            Direction::East,
+            "Out".to_string(),
+        );
+        let solved = PuzzleSet::new();
+        let mut grid =
+            EditGrid::from_circuit_data(Puzzle::SandboxEvent, &solved, &data);
+        let original = grid.to_circuit_data().serialize_to_string().unwrap();
+
+        let mut data = CircuitData::new(10, 9);
+        data.chips.insert(
+            CoordsDelta::new(4, 4),
+            ChipType::And,
+            Orientation::default(),
+        );
+        data.wires.insert(
+            CoordsDelta::new(4, 4),
+            Direction::West,
+            WireShape::Stub,
+        );
+        let other =
+            EditGrid::from_circuit_data(Puzzle::SandboxEvent, &solved, &data);
+        let replacement =
+            other.to_circuit_data().serialize_to_string().unwrap();
+
+        assert!(grid.try_replace_with(&other));
+        assert_eq!(
+            grid.to_circuit_data().serialize_to_string().unwrap(),
+            replacement
+        );
+        assert!(grid.undo());
+        assert_eq!(
+            grid.to_circuit_data().serialize_to_string().unwrap(),
+            original
+        );
+        assert!(grid.redo());
+        assert_eq!(
+            grid.to_circuit_data().serialize_to_string().unwrap(),
+            replacement
+        );
+    }
+
+    #[test]
+    fn removing_wire_removes_its_label_until_undo() {
+        let mut data = CircuitData::new(10, 10);
+        data.wires.insert(
+            CoordsDelta::new(2, 2),
+            Direction::East,
+            WireShape::Stub,
+        );
+        data.labels.insert(
+            CoordsDelta::new(2, 2),
+            Direction::East,
+            "Bus".to_string(),
+        );
+        let mut grid = EditGrid::from_circuit_data(
+            Puzzle::SandboxEvent,
+            &PuzzleSet::new(),
+            &data,
+        );
+        let loc = (Coords::new(2, 2), Direction::East);
+        let mut stubs = HashMap::new();
+        stubs.insert(loc, WireShape::Stub);
+        stubs.insert((Coords::new(3, 2), Direction::West), WireShape::Stub);
+        let remove = GridChange::ReplaceWires(stubs.clone(), HashMap::new());
+        assert!(grid.try_mutate(vec![remove]));
+        assert_eq!(grid.wire_labels().count(), 0);
+
+        // A new wire drawn in the same place doesn't pick up the old label.
+        let add = GridChange::ReplaceWires(HashMap::new(), stubs);
+        assert!(grid.try_mutate(vec![add]));
+        assert_eq!(grid.wire_label_at(loc.0, loc.1), None);
+        assert!(grid.to_circuit_data().labels.iter().next().is_none());
+
+        assert!(grid.undo());
+        assert!(grid.undo());
+        assert_eq!(grid.wire_label_at(loc.0, loc.1), Some("Bus"));
+        assert!(grid.redo());
+        assert_eq!(grid.wire_label_at(loc.0, loc.1), None);
+    }
+
+    #[test]
+    fn stats_ignore_comments() {
+        let mut data = CircuitData::new(6, 4);
+        data.chips.insert(
+            CoordsDelta::new(1, 1),
+            ChipType::Not,
+            Orientation::default(),
+        );
+        data.chips.insert(
+            CoordsDelta::new(3, 1),
+            ChipType::Comment(*b"#    "),
+            Orientation::default(),
+        );
+        data.wires.insert(
+            CoordsDelta::new(1, 1),
+            Direction::East,
+            WireShape::Stub,
+        );
+        data.wires.insert(
+            CoordsDelta::new(2, 1),
+            Direction::West,
+            WireShape::Stub,
+        );
+        let grid = EditGrid::from_circuit_data(
+            Puzzle::TutorialOr,
+            &PuzzleSet::new(),
+            &data,
+        );
+        assert_eq!(
+            grid.stats(),
+            CircuitStats { num_chips: 1, wire_length: 2, area: 24 }
+        );
+    }
+
+    #[test]
+    fn stats_for_huge_bounds() {
+        let data = CircuitData::new(i32::MAX, i32::MAX);
+        let grid = EditGrid::from_circuit_data(
+            Puzzle::SandboxEvent,
+            &PuzzleSet::new(),
+            &data,
+        );
+        assert_eq!(grid.stats().area, i32::MAX);
+    }
+
+    #[test]
+    fn frozen_inputs_ignore_presses() {
+        let mut data = CircuitData::new(10, 10);
+        data.chips.insert(
+            CoordsDelta::new(2, 2),
+            ChipType::Toggle(false),
+            Orientation::default(),
+        );
+        let mut grid = EditGrid::from_circuit_data(
+            Puzzle::SandboxBehavior,
+            &PuzzleSet::new(),
+            &data,
+        );
+        assert!(grid.rejected_chips().is_empty());
+        assert!(grid.start_eval());
+        let top_left = grid.bounds().top_left();
+        let eval = grid.eval_mut().unwrap();
+        eval.set_inputs_frozen(true);
+        eval.press_button(Coords::new(2, 2), 0, 1);
+        match eval.step_time() {
+            EvalResult::Continue => {}
+            result => panic!("Unexpected result: {:?}", result),
+        }
+        assert!(eval.recorded_inputs(top_left).is_none());
+        eval.set_inputs_frozen(false);
+        eval.press_button(Coords::new(2, 2), 0, 1);
+        match eval.step_time() {
+            EvalResult::Continue => {}
+            result => panic!("Unexpected result: {:?}", result),
+        }
+        assert!(eval.recorded_inputs(top_left).is_some());
+    }
+
+    #[test]
+    fn disallowed_chips_are_rejected() {
+        let mut data = CircuitData::new(10, 10);
+        data.chips.insert(
+            CoordsDelta::new(2, 2),
+            ChipType::Not,
+            Orientation::default(),
+        );
+        data.chips.insert(
+            CoordsDelta::new(5, 5),
+            ChipType::Ram,
+            Orientation::default(),
+        );
+        let grid = EditGrid::from_circuit_data(
+            Puzzle::TutorialOr,
+            &PuzzleSet::new(),
+            &data,
+        );
+        assert!(!grid.allowed_chips().contains(ChipType::Ram));
+        assert_eq!(
+            grid.rejected_chips(),
+            &[RejectedChip::NotAllowed(Coords::new(5, 5), ChipType::Ram)]
+        );
+        assert!(grid.chip_at(Coords::new(5, 5)).is_none());
+        assert!(grid.chip_at(Coords::new(2, 2)).is_some());
+    }
+}
@@ -115,6 +115,12 @@ impl CircuitEval {
         self.puzzle_eval.downcast_ref::<T>().unwrap()
     }
 
+    /// Returns the PuzzleEval object if it has the specified type, or `None`
+    /// otherwise.
+    pub fn try_puzzle_eval<T: PuzzleEval>(&self) -> Option<&T> {
+        self.puzzle_eval.downcast_ref::<T>()
+    }
+
     pub fn errors(&self) -> &[EvalError] {
         &self.errors
     }
@@ -126,12 +132,36 @@ impl CircuitEval {
         times: u32,
     ) {
         if let Some(&(group, index)) = self.coords_map.get(&coords) {
-            self.chips[group][index].on_press(sublocation, times);
+            let chip = &mut self.chips[group][index];
+            if self.state.frozen_time_step.is_none()
+                || !chip.is_interactive_input()
+            {
+                chip.on_press(sublocation, times);
+            }
         }
     }
 
     pub fn press_hotkey(&mut self, code: HotkeyCode) {
-        self.state.press_hotkey(code);
+        if self.state.frozen_time_step.is_none() {
+            self.state.press_hotkey(code);
+        }
+    }
+
+    pub fn inputs_frozen(&self) -> bool {
+        self.state.inputs_frozen()
+    }
+
+    /// Freezes or unfreezes the circuit's inputs.  While inputs are frozen,
+    /// presses on interactive input chips (and their hotkeys) are ignored,
+    /// and puzzle evals that support it (e.g. sandbox puzzles) hold their
+    /// inputs at the values they had when the inputs were frozen.  This is a
+    /// debugging aid for probing a circuit while stepping the clock.
+    pub fn set_inputs_frozen(&mut self, frozen: bool) {
+        if !frozen {
+            self.state.frozen_time_step = None;
+        } else if self.state.frozen_time_step.is_none() {
+            self.state.frozen_time_step = Some(self.state.time_step);
+        }
     }
 
     fn num_recorded_inputs(&self) -> u32 {
@@ -171,6 +201,16 @@ impl CircuitEval {
         self.state.values[wire_id.0].1
     }
 
+    /// Sets the value that the given sandbox input port will send, starting
+    /// with the next time step.
+    pub fn set_sandbox_input(&mut self, index: usize, value: u32) {
+        let inputs = &mut self.state.sandbox_inputs;
+        if inputs.len() <= index {
+            inputs.resize(index + 1, 0);
+        }
+        inputs[index] = value;
+    }
+
     /// Returns display data for the chip at the given coordinates, if any.
     pub fn display_data(&self, coords: Coords) -> &[u8] {
         if let Some(&(group, index)) = self.coords_map.get(&coords) {
@@ -323,11 +363,16 @@ pub struct CircuitState {
     breakpoints: Vec<Coords>,
     hotkey_presses: HashMap<HotkeyCode, u32>,
     recorded_inputs: Vec<(u32, u32, Coords, u32, u32)>,
+    frozen_time_step: Option<u32>,
+    sandbox_inputs: Vec<u32>,
     changed: bool,
 }
 
 impl CircuitState {
-    fn new(num_values: usize, null_wires: HashSet<WireId>) -> CircuitState {
+    pub(crate) fn new(
+        num_values: usize,
+        null_wires: HashSet<WireId>,
+    ) -> CircuitState {
         CircuitState {
             time_step: 0,
             cycle: 0,
@@ -336,6 +381,8 @@ impl CircuitState {
             breakpoints: vec![],
             hotkey_presses: HashMap::new(),
             recorded_inputs: Vec::new(),
+            frozen_time_step: None,
+            sandbox_inputs: Vec::new(),
             changed: false,
         }
     }
@@ -348,6 +395,23 @@ impl CircuitState {
         self.cycle
     }
 
+    pub fn inputs_frozen(&self) -> bool {
+        self.frozen_time_step.is_some()
+    }
+
+    /// Returns the time step that time-varying puzzle inputs should reflect.
+    /// This is normally the current time step, but while inputs are frozen,
+    /// it is the time step at which they were frozen.
+    pub fn input_time_step(&self) -> u32 {
+        self.frozen_time_step.unwrap_or(self.time_step)
+    }
+
+    /// Returns the value chosen for the given sandbox input port (or zero if
+    /// none has been chosen).
+    pub fn sandbox_input(&self, index: usize) -> u32 {
+        self.sandbox_inputs.get(index).copied().unwrap_or(0)
+    }
+
     pub fn is_null_wire(&self, slot: WireId) -> bool {
         self.null_wires.contains(&slot)
     }
@@ -557,6 +621,13 @@ pub trait ChipEval {
     /// clickable parts.  For example, the `Screen` chip uses the sublocation
     /// to know which cell of the screen was clicked.
     fn on_press(&mut self, _sublocation: u32, _num_times: u32) {}
+
+    /// Returns true if presses on this chip are player inputs (which get
+    /// recorded with the solution), and so should be ignored while inputs
+    /// are frozen.  The default implementation returns false.
+    fn is_interactive_input(&self) -> bool {
+        false
+    }
 }
 
 //===========================================================================//
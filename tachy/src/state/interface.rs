@@ -44,6 +44,8 @@ pub struct InterfacePort {
 
 pub struct Interface {
     pub(super) name: &'static str,
+    /// A short label drawn next to the interface on the board edge.
+    pub(super) label: &'static str,
     pub(super) description: &'static str,
     pub(super) side: Direction,
     pub(super) pos: InterfacePosition,
@@ -119,10 +121,18 @@ impl Interface {
         size.into()
     }
 
+    pub fn label(&self) -> &'static str {
+        self.label
+    }
+
     pub fn side(&self) -> Direction {
         self.side
     }
 
+    pub fn port_names(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.ports.iter().map(|port| port.name)
+    }
+
     pub fn ports(&self, bounds: CoordsRect) -> Vec<(&'static str, PortSpec)> {
         self.ports_with_top_left(self.top_left(bounds))
     }
@@ -168,44 +178,20 @@ impl Interface {
 
     pub fn tooltip_format(&self) -> String {
         if self.ports.len() == 1 && self.ports[0].description.is_empty() {
-            let port = &self.ports[0];
-            if port.color == PortColor::Analog {
-                format!(
-                    "$*{}$>({} {})$<$*\n{}",
-                    self.name,
-                    port.color.tooltip_format(),
-                    port.flow.tooltip_format(),
-                    self.description
-                )
-            } else {
-                format!(
-                    "$*{}$>({}-bit {} {})$<$*\n{}",
-                    self.name,
-                    port.size.num_bits(),
-                    port.color.tooltip_format(),
-                    port.flow.tooltip_format(),
-                    self.description
-                )
-            }
+            format!(
+                "$*{}$>({})$<$*\n{}",
+                self.name,
+                self.ports[0].type_format(),
+                self.description
+            )
         } else {
             let mut fmt = format!("$*{}$*\n{}\n", self.name, self.description);
             for port in self.ports.iter() {
-                if port.color == PortColor::Analog {
-                    fmt.push_str(&format!(
-                        "\n$*{}$>({} {})$<$*",
-                        port.name,
-                        port.color.tooltip_format(),
-                        port.flow.tooltip_format(),
-                    ));
-                } else {
-                    fmt.push_str(&format!(
-                        "\n$*{}$>({}-bit {} {})$<$*",
-                        port.name,
-                        port.size.num_bits(),
-                        port.color.tooltip_format(),
-                        port.flow.tooltip_format(),
-                    ));
-                }
+                fmt.push_str(&format!(
+                    "\n$*{}$>({})$<$*",
+                    port.name,
+                    port.type_format()
+                ));
                 if !port.description.is_empty() {
                     fmt.push_str(&format!("\n  $!{}", port.description));
                 }
@@ -213,6 +199,45 @@ impl Interface {
             fmt
         }
     }
+
+    /// Returns a compact, read-only summary of this interface's ports (their
+    /// names, flows, and wire sizes), suitable for a reference panel.
+    pub fn spec_format(&self) -> String {
+        let side = match self.side {
+            Direction::East => "right",
+            Direction::South => "bottom",
+            Direction::West => "left",
+            Direction::North => "top",
+        };
+        let mut fmt = format!("$*{}$* ({} edge)", self.name, side);
+        for port in self.ports.iter() {
+            fmt.push_str(&format!(
+                "\n  {}: {}",
+                port.name,
+                port.type_format()
+            ));
+        }
+        fmt
+    }
+}
+
+impl InterfacePort {
+    fn type_format(&self) -> String {
+        if self.color == PortColor::Analog {
+            format!(
+                "{} {}",
+                self.color.tooltip_format(),
+                self.flow.tooltip_format()
+            )
+        } else {
+            format!(
+                "{}-bit {} {}",
+                self.size.num_bits(),
+                self.color.tooltip_format(),
+                self.flow.tooltip_format()
+            )
+        }
+    }
 }
 
 //===========================================================================//
@@ -228,6 +253,7 @@ mod tests {
     fn interface_positioning() {
         let mut interface = Interface {
             name: "Foobar",
+            label: "foobar",
             description: "",
             side: Direction::North,
             pos: InterfacePosition::Center,
@@ -287,6 +313,39 @@ mod tests {
         assert_eq!(interface.size(), CoordsSize::new(2, 1));
     }
 
+    #[test]
+    fn interface_spec_format() {
+        let interface = Interface {
+            name: "Foobar",
+            label: "foobar",
+            description: "Ignored.",
+            side: Direction::West,
+            pos: InterfacePosition::Center,
+            ports: &[
+                InterfacePort {
+                    name: "Foo",
+                    description: "Also ignored.",
+                    flow: PortFlow::Source,
+                    color: PortColor::Event,
+                    size: WireSize::Four,
+                },
+                InterfacePort {
+                    name: "Bar",
+                    description: "",
+                    flow: PortFlow::Sink,
+                    color: PortColor::Analog,
+                    size: WireSize::Zero,
+                },
+            ],
+        };
+        assert_eq!(
+            interface.spec_format(),
+            "$*Foobar$* (left edge)\n  \
+             Foo: 4-bit $Cevent$D source\n  \
+             Bar: $Ganalog$D sink"
+        );
+    }
+
     #[test]
     fn interface_min_bounds_size() {
         let ports = &[
@@ -308,6 +367,7 @@ mod tests {
         let interfaces = vec![
             Interface {
                 name: "Spam",
+                label: "spam",
                 description: "",
                 side: Direction::North,
                 pos: InterfacePosition::Right(0),
@@ -315,6 +375,7 @@ mod tests {
             },
             Interface {
                 name: "Eggs",
+                label: "eggs",
                 description: "",
                 side: Direction::North,
                 pos: InterfacePosition::Center,
@@ -322,6 +383,7 @@ mod tests {
             },
             Interface {
                 name: "Bacon",
+                label: "bacon",
                 description: "",
                 side: Direction::North,
                 pos: InterfacePosition::Left(0),
@@ -329,6 +391,7 @@ mod tests {
             },
             Interface {
                 name: "Beans",
+                label: "beans",
                 description: "",
                 side: Direction::West,
                 pos: InterfacePosition::Right(1),
@@ -336,6 +399,7 @@ mod tests {
             },
             Interface {
                 name: "Sausage",
+                label: "sausage",
                 description: "",
                 side: Direction::West,
                 pos: InterfacePosition::Left(0),
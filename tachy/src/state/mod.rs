@@ -30,16 +30,23 @@ mod verify;
 pub use self::change::GridChange;
 pub use self::check::{
     detect_loops, determine_wire_sizes, group_wires, map_ports_to_wires,
-    recolor_wires, WireColor, WireError, WireId, WireInfo,
+    recolor_wires, SizeConflict, WireColor, WireError, WireId, WireInfo,
 };
 pub use self::chip::ChipExt;
-pub use self::edit::{ChipsIter, EditGrid, WireFragmentsIter};
+pub use self::edit::{
+    ChipsIter, CircuitStats, EditGrid, RejectedChip, WireFragmentsForWireIter,
+    WireFragmentsIter, WireSummary, WiresIter,
+};
 pub use self::eval::{CircuitEval, EvalError, EvalResult};
 pub use self::interface::{Interface, InterfacePort};
 pub use self::port::{
     PortColor, PortConstraint, PortDependency, PortFlow, PortSpec,
 };
 pub use self::puzzle::*;
-pub use self::verify::verify_solution;
+pub use self::verify::{
+    check_shared_circuit, replay_inputs_to_time_step,
+    replay_solution_to_time_step, solve_circuit, verify_against_reference,
+    verify_circuit, verify_solution, wire_length_score, ReferenceMismatch,
+};
 
 //===========================================================================//
@@ -31,6 +31,7 @@ use num_integer::Roots;
 pub const INTERFACES: &[Interface] = &[
     Interface {
         name: "Sensor Interface",
+        label: "sensor",
         description:
             "Connects to a subspace sensor array that determines the ideal \
              position for the beacon dish.  Use the motor interface to move \
@@ -56,6 +57,7 @@ pub const INTERFACES: &[Interface] = &[
     },
     Interface {
         name: "Motor Interface",
+        label: "motor",
         description:
             "Connects to a stepper motor that controls the position of the \
              beacon mirror.",
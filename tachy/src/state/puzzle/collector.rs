@@ -45,6 +45,7 @@ const POSITIONS: &[(u32, u32)] = &[
 pub const INTERFACES: &[Interface] = &[
     Interface {
         name: "Radio Interface",
+        label: "radio",
         description: "Connects to a radio antenna.",
         side: Direction::West,
         pos: InterfacePosition::Left(1),
@@ -62,6 +63,7 @@ pub const INTERFACES: &[Interface] = &[
     },
     Interface {
         name: "X-Movement Interface",
+        label: "x-movement",
         description: "Connects to the X-axis actuator.",
         side: Direction::East,
         pos: InterfacePosition::Left(0),
@@ -92,6 +94,7 @@ pub const INTERFACES: &[Interface] = &[
     },
     Interface {
         name: "Y-Movement Interface",
+        label: "y-movement",
         description: "Connects to the Y-axis actuator.",
         side: Direction::North,
         pos: InterfacePosition::Left(0),
@@ -122,6 +125,7 @@ pub const INTERFACES: &[Interface] = &[
     },
     Interface {
         name: "Collection Interface",
+        label: "collection",
         description: "Connects to the collector arm.",
         side: Direction::South,
         pos: InterfacePosition::Left(1),
@@ -53,6 +53,7 @@ const CYCLES: &[(u32, u32)] = &[
 pub const INTERFACES: &[Interface] = &[
     Interface {
         name: "Scheduler Interface",
+        label: "scheduler",
         description:
             "Connects to the cryopod power cycle scheduler.  Whenever a pair \
              of cryopods is ready, each port sends an event with the number \
@@ -78,6 +79,7 @@ pub const INTERFACES: &[Interface] = &[
     },
     Interface {
         name: "Thermal Control Interface",
+        label: "thermal control",
         description: "Connects to the cryopod thermal controller.",
         side: Direction::East,
         pos: InterfacePosition::Center,
@@ -48,6 +48,7 @@ const JOLTS: &[u8; DEPTH_FOR_VICTORY as usize] = &[
 
 pub const INTERFACES: &[Interface] = &[Interface {
     name: "Drill Interface",
+    label: "drill",
     description: "Connects to the drill's motor and sensors.",
     side: Direction::South,
     pos: InterfacePosition::Center,
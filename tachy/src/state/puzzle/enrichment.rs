@@ -57,6 +57,7 @@ const TRANSFERS: &[(u8, u32, u32)] = &[
 pub const INTERFACES: &[Interface] = &[
     Interface {
         name: "Catalog Interface",
+        label: "catalog",
         description:
             "Connects to a database cataloging the connections between \
              storage tanks.  You can send one or more tank IDs here at any \
@@ -87,6 +88,7 @@ pub const INTERFACES: &[Interface] = &[
     },
     Interface {
         name: "Control Interface",
+        label: "control",
         description:
             "Connects to the cascade control system.  When material is ready \
              to be moved from one centrifuge to another, the two ports will \
@@ -113,6 +115,7 @@ pub const INTERFACES: &[Interface] = &[
     },
     Interface {
         name: "Valve Interface",
+        label: "valve",
         description:
             "Controls the tank valves.  Send the IDs of two connected tanks \
              to transfer the contents of one to the other.  The transfer \
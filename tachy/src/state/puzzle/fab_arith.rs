@@ -33,6 +33,7 @@ pub const FABRICATE_XOR_DATA: &FabricationData = &FabricationData {
 pub(super) const XOR_INTERFACES: &[Interface] = &[
     Interface {
         name: "In1",
+        label: "IN1",
         description: "First input (0 or 1).",
         side: Direction::West,
         pos: InterfacePosition::Center,
@@ -46,6 +47,7 @@ pub(super) const XOR_INTERFACES: &[Interface] = &[
     },
     Interface {
         name: "In2",
+        label: "IN2",
         description: "Second input (0 or 1).",
         side: Direction::South,
         pos: InterfacePosition::Center,
@@ -59,6 +61,7 @@ pub(super) const XOR_INTERFACES: &[Interface] = &[
     },
     Interface {
         name: "Out",
+        label: "OUT",
         description: "Should be 1 if exactly one input is 1.\n\
                       Should be 0 if the inputs are both 0 or both 1.",
         side: Direction::East,
@@ -91,6 +94,7 @@ pub const FABRICATE_MUL_DATA: &FabricationData = &FabricationData {
 pub(super) const MUL_INTERFACES: &[Interface] = &[
     Interface {
         name: "In1",
+        label: "IN1",
         description: "First input (from 0 to 255).",
         side: Direction::West,
         pos: InterfacePosition::Center,
@@ -104,6 +108,7 @@ pub(super) const MUL_INTERFACES: &[Interface] = &[
     },
     Interface {
         name: "In2",
+        label: "IN2",
         description: "Second input (from 0 to 255).",
         side: Direction::South,
         pos: InterfacePosition::Center,
@@ -117,6 +122,7 @@ pub(super) const MUL_INTERFACES: &[Interface] = &[
     },
     Interface {
         name: "Out",
+        label: "OUT",
         description:
             "Should be the product of the two inputs (which will never be \
              more than 255 for this task).",
@@ -156,6 +162,7 @@ pub const FABRICATE_HALVE_DATA: &FabricationData = &FabricationData {
 pub(super) const HALVE_INTERFACES: &[Interface] = &[
     Interface {
         name: "In",
+        label: "IN",
         description: "Input (from 0 to 15).",
         side: Direction::West,
         pos: InterfacePosition::Center,
@@ -169,6 +176,7 @@ pub(super) const HALVE_INTERFACES: &[Interface] = &[
     },
     Interface {
         name: "Out",
+        label: "OUT",
         description: "Should be half the value of the input, rounded down.",
         side: Direction::East,
         pos: InterfacePosition::Center,
@@ -33,6 +33,7 @@ pub const FABRICATE_EGG_TIMER_DATA: &FabricationData = &FabricationData {
 pub(super) const EGG_TIMER_INTERFACES: &[Interface] = &[
     Interface {
         name: "Set",
+        label: "SET",
         description:
             "When an event value arrives here, the timer should be set to go \
              off in that many time steps.",
@@ -48,6 +49,7 @@ pub(super) const EGG_TIMER_INTERFACES: &[Interface] = &[
     },
     Interface {
         name: "Remain",
+        label: "REMAIN",
         description:
             "Should be the number of time steps before the timer goes off.",
         side: Direction::North,
@@ -62,6 +64,7 @@ pub(super) const EGG_TIMER_INTERFACES: &[Interface] = &[
     },
     Interface {
         name: "Alarm",
+        label: "ALARM",
         description: "Send an event here when the timer goes off.",
         side: Direction::North,
         pos: InterfacePosition::Right(0),
@@ -109,6 +112,7 @@ pub const FABRICATE_STOPWATCH_DATA: &FabricationData = &FabricationData {
 pub(super) const STOPWATCH_INTERFACES: &[Interface] = &[
     Interface {
         name: "Start",
+        label: "START",
         description:
             "When an event value arrives here, the timer should start \
              counting up from its current value.",
@@ -124,6 +128,7 @@ pub(super) const STOPWATCH_INTERFACES: &[Interface] = &[
     },
     Interface {
         name: "Stop",
+        label: "STOP",
         description:
             "When an event value arrives here, the timer should pause.",
         side: Direction::North,
@@ -138,6 +143,7 @@ pub(super) const STOPWATCH_INTERFACES: &[Interface] = &[
     },
     Interface {
         name: "Reset",
+        label: "RESET",
         description:
             "When an event value arrives here, the timer value should be \
              reset to zero.",
@@ -153,6 +159,7 @@ pub(super) const STOPWATCH_INTERFACES: &[Interface] = &[
     },
     Interface {
         name: "Time",
+        label: "TIME",
         description: "Should be the current timer value, starting at zero.",
         side: Direction::North,
         pos: InterfacePosition::Right(0),
@@ -33,6 +33,7 @@ pub const FABRICATE_COUNTER_DATA: &FabricationData = &FabricationData {
 pub(super) const COUNTER_INTERFACES: &[Interface] = &[
     Interface {
         name: "Set",
+        label: "SET",
         description:
             "When an event is sent from here, the counter should be set to \
              that value.",
@@ -48,6 +49,7 @@ pub(super) const COUNTER_INTERFACES: &[Interface] = &[
     },
     Interface {
         name: "Inc",
+        label: "INC",
         description:
             "When an event is sent from here, the counter value should be \
              incremented by one.",
@@ -63,6 +65,7 @@ pub(super) const COUNTER_INTERFACES: &[Interface] = &[
     },
     Interface {
         name: "Dec",
+        label: "DEC",
         description:
             "When an event is sent from here, the counter value should be \
              decremented by one.",
@@ -78,6 +81,7 @@ pub(super) const COUNTER_INTERFACES: &[Interface] = &[
     },
     Interface {
         name: "Out",
+        label: "OUT",
         description:
             "Should be equal to the current counter value (initially zero).",
         side: Direction::North,
@@ -126,6 +130,7 @@ pub const FABRICATE_INC_DATA: &FabricationData = &FabricationData {
 pub(super) const INC_INTERFACES: &[Interface] = &[
     Interface {
         name: "InE",
+        label: "INE",
         description: "Input events arrive here.",
         side: Direction::West,
         pos: InterfacePosition::Center,
@@ -139,6 +144,7 @@ pub(super) const INC_INTERFACES: &[Interface] = &[
     },
     Interface {
         name: "InB",
+        label: "INB",
         description: "Provides the value that should be added to each event.",
         side: Direction::South,
         pos: InterfacePosition::Center,
@@ -152,6 +158,7 @@ pub(super) const INC_INTERFACES: &[Interface] = &[
     },
     Interface {
         name: "Out",
+        label: "OUT",
         description:
             "Whenever an input event arrives, send an event here with the sum \
              of the two input values.",
@@ -189,6 +196,7 @@ pub const FABRICATE_LATCH_DATA: &FabricationData = &FabricationData {
 pub(super) const LATCH_INTERFACES: &[Interface] = &[
     Interface {
         name: "Set",
+        label: "SET",
         description:
             "When an event arrives here, the output should be set to 1.",
         side: Direction::West,
@@ -203,6 +211,7 @@ pub(super) const LATCH_INTERFACES: &[Interface] = &[
     },
     Interface {
         name: "Rst",
+        label: "RST",
         description:
             "When an event arrives here, the output should be reset to 0.",
         side: Direction::South,
@@ -217,6 +226,7 @@ pub(super) const LATCH_INTERFACES: &[Interface] = &[
     },
     Interface {
         name: "Out",
+        label: "OUT",
         description: "Should be set to the current output (initially zero).",
         side: Direction::East,
         pos: InterfacePosition::Center,
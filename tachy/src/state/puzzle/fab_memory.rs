@@ -33,6 +33,7 @@ pub const FABRICATE_QUEUE_DATA: &FabricationData = &FabricationData {
 pub(super) const QUEUE_INTERFACES: &[Interface] = &[
     Interface {
         name: "Push",
+        label: "PUSH",
         description:
             "When an event arrives here, that value should be pushed into the \
              back of the queue.",
@@ -48,6 +49,7 @@ pub(super) const QUEUE_INTERFACES: &[Interface] = &[
     },
     Interface {
         name: "Pop",
+        label: "POP",
         description:
             "When an event arrives here, the value at the front of the queue \
              should be popped off and sent to the $*Out$* port.",
@@ -63,6 +65,7 @@ pub(super) const QUEUE_INTERFACES: &[Interface] = &[
     },
     Interface {
         name: "Out",
+        label: "OUT",
         description: "Values popped from the queue should be sent here.",
         side: Direction::East,
         pos: InterfacePosition::Right(0),
@@ -76,6 +79,7 @@ pub(super) const QUEUE_INTERFACES: &[Interface] = &[
     },
     Interface {
         name: "Count",
+        label: "COUNT",
         description:
             "Should be equal to the number of values currently in the queue.",
         side: Direction::West,
@@ -124,6 +128,7 @@ pub const FABRICATE_STACK_DATA: &FabricationData = &FabricationData {
 pub(super) const STACK_INTERFACES: &[Interface] = &[
     Interface {
         name: "Push",
+        label: "PUSH",
         description:
             "When an event arrives here, that value should be pushed onto \
              the top of the stack.",
@@ -139,6 +144,7 @@ pub(super) const STACK_INTERFACES: &[Interface] = &[
     },
     Interface {
         name: "Pop",
+        label: "POP",
         description:
             "When an event arrives here, the top value of the stack should \
              be popped off and sent to the $*Out$* port.",
@@ -154,6 +160,7 @@ pub(super) const STACK_INTERFACES: &[Interface] = &[
     },
     Interface {
         name: "Out",
+        label: "OUT",
         description: "Values popped off the stack should be sent here.",
         side: Direction::East,
         pos: InterfacePosition::Right(0),
@@ -167,6 +174,7 @@ pub(super) const STACK_INTERFACES: &[Interface] = &[
     },
     Interface {
         name: "Count",
+        label: "COUNT",
         description:
             "Should be equal to the number of values currently in the stack.",
         side: Direction::West,
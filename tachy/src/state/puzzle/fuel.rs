@@ -39,6 +39,7 @@ const PUMP_OFFSETS: [u32; NUM_TANKS] = [0, 5];
 pub const INTERFACES: &[Interface] = &[
     Interface {
         name: "Intake #1 Interface",
+        label: "intake #1",
         description: "Connects to the intake valve/pump for reagent tank #1.",
         side: Direction::North,
         pos: InterfacePosition::Right(0),
@@ -46,6 +47,7 @@ pub const INTERFACES: &[Interface] = &[
     },
     Interface {
         name: "Intake #2 Interface",
+        label: "intake #2",
         description: "Connects to the intake valve/pump for reagent tank #2.",
         side: Direction::North,
         pos: InterfacePosition::Left(0),
@@ -53,6 +55,7 @@ pub const INTERFACES: &[Interface] = &[
     },
     Interface {
         name: "Mixer Interface",
+        label: "mixer",
         description: "Connects to the mixer unit.",
         side: Direction::South,
         pos: InterfacePosition::Center,
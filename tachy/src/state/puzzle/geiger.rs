@@ -44,6 +44,7 @@ const CLICKS: &[u8] = &[
 pub const INTERFACES: &[Interface] = &[
     Interface {
         name: "Detector Interface",
+        label: "detector",
         description: "Connects to the particle detector.",
         side: Direction::West,
         pos: InterfacePosition::Center,
@@ -59,6 +60,7 @@ pub const INTERFACES: &[Interface] = &[
     },
     Interface {
         name: "Display Interface",
+        label: "display",
         description: "Connects to the device display.",
         side: Direction::North,
         pos: InterfacePosition::Center,
@@ -48,6 +48,7 @@ const STARTING_CHARGES: &[(u32, u32)] = &[
 pub const INTERFACES: &[Interface] = &[
     Interface {
         name: "Port Coil Interface",
+        label: "port coil",
         description:
             "Controls the port-side magnetic coil for the grapple launcher.",
         side: Direction::West,
@@ -76,6 +77,7 @@ pub const INTERFACES: &[Interface] = &[
     },
     Interface {
         name: "Starboard Coil Interface",
+        label: "starboard coil",
         description:
             "Controls the starboard-side magnetic coil for the grapple \
              launcher.",
@@ -45,6 +45,7 @@ const ENEMIES: &[(i32, u32)] = &[
 pub const INTERFACES: &[Interface] = &[
     Interface {
         name: "Radar Interface",
+        label: "radar",
         description: "Connects to the torpedo's radar receiver.",
         side: Direction::North,
         pos: InterfacePosition::Center,
@@ -60,6 +61,7 @@ pub const INTERFACES: &[Interface] = &[
     },
     Interface {
         name: "Port Thruster Interface",
+        label: "port thruster",
         description: "Connects to the torpedo's port-side turning thruster.",
         side: Direction::South,
         pos: InterfacePosition::Left(0),
@@ -76,6 +78,7 @@ pub const INTERFACES: &[Interface] = &[
     },
     Interface {
         name: "Gyro Interface",
+        label: "gyro",
         description: "Connects to the torpedo's dead-reckoning gyro.",
         side: Direction::South,
         pos: InterfacePosition::Center,
@@ -89,6 +92,7 @@ pub const INTERFACES: &[Interface] = &[
     },
     Interface {
         name: "Starboard Thruster Interface",
+        label: "starboard thruster",
         description:
             "Connects to the torpedo's starboard-side turning thruster.",
         side: Direction::South,
@@ -37,6 +37,7 @@ const ORBIT_DEGREES_PER_TIME_STEP: i32 = 5;
 pub const INTERFACES: &[Interface] = &[
     Interface {
         name: "Sensor Interface",
+        label: "sensor",
         description:
             "Connects to a subspace sensor array that determines the ideal \
              position for the heliostat mirror.  Use the motor interface to \
@@ -65,6 +66,7 @@ pub const INTERFACES: &[Interface] = &[
     },
     Interface {
         name: "Motor Interface",
+        label: "motor",
         description:
             "Connects to a stepper motor that controls the position of the \
              heliostat mirror.",
@@ -37,6 +37,7 @@ const RIGHT_EGG_DELAYS: &[u32] = &[5, 3, 5, 9, 2];
 pub const INTERFACES: &[Interface] = &[
     Interface {
         name: "Heater Interface",
+        label: "heater",
         description: "Connects to the incubator heat lamp.",
         side: Direction::North,
         pos: InterfacePosition::Center,
@@ -53,6 +54,7 @@ pub const INTERFACES: &[Interface] = &[
     },
     Interface {
         name: "Left Slot Interface",
+        label: "left slot",
         description:
             "Connects to the loading slot for the left-hand side of the \
              incubator.",
@@ -62,6 +64,7 @@ pub const INTERFACES: &[Interface] = &[
     },
     Interface {
         name: "Right Slot Interface",
+        label: "right slot",
         description:
             "Connects to the loading slot for the right-hand side of the \
              incubator.",
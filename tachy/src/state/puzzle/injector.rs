@@ -39,6 +39,7 @@ const ARRAYS: &[[u32; NUM_POSITIONS]] = &[
 pub const INTERFACES: &[Interface] = &[
     Interface {
         name: "Injection Interface",
+        label: "injection",
         description: "Connects to the plasma injection head.",
         side: Direction::South,
         pos: InterfacePosition::Center,
@@ -74,6 +75,7 @@ pub const INTERFACES: &[Interface] = &[
     },
     Interface {
         name: "Sensor Interface",
+        label: "sensor",
         description: "Connects to the feedback sensor array.",
         side: Direction::West,
         pos: InterfacePosition::Right(0),
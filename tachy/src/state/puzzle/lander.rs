@@ -44,6 +44,7 @@ const MAX_LANDING_ANGLE: i32 = 95;
 pub const INTERFACES: &[Interface] = &[
     Interface {
         name: "Instruments Interface",
+        label: "instruments",
         description: "Connects to the lander's instruments panel.",
         side: Direction::West,
         pos: InterfacePosition::Center,
@@ -77,6 +78,7 @@ pub const INTERFACES: &[Interface] = &[
     },
     Interface {
         name: "Thruster Interface",
+        label: "thruster",
         description:
             "Connects to the lander's descent thrusters.  Increase the value \
              for each side to increase thrust, at the cost of more fuel.",
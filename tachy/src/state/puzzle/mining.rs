@@ -38,6 +38,7 @@ const MAX_ORE_CARRIED: u32 = 15;
 pub const INTERFACES: &[Interface] = &[
     Interface {
         name: "Nav Interface",
+        label: "nav",
         description: "Connects to the robot's automatic navigation system.",
         side: Direction::East,
         pos: InterfacePosition::Right(1),
@@ -62,6 +63,7 @@ pub const INTERFACES: &[Interface] = &[
     },
     Interface {
         name: "Digger Interface",
+        label: "digger",
         description: "Connects to the robot's ore digging/hauling equipment.",
         side: Direction::South,
         pos: InterfacePosition::Center,
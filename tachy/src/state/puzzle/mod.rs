@@ -81,11 +81,14 @@ pub use self::lander::LanderEval;
 pub use self::mining::MiningRobotEval;
 pub use self::reactor::ReactorEval;
 pub use self::resonator::ResonatorEval;
+pub(super) use self::rng::SimpleRng;
 pub use self::robotarm::RobotArmEval;
+pub use self::sandbox::sandbox_input_names;
 pub use self::sapper::SapperEval;
 pub use self::sensors::SensorsEval;
 pub use self::shared::{
-    FabricationData, FabricationEval, TutorialBubblePosition,
+    FabricationData, FabricationEval, FabricationMismatch,
+    TutorialBubblePosition,
 };
 pub use self::shields::ShieldsEval;
 pub use self::sonar::SonarEval;
@@ -116,6 +119,7 @@ use crate::save::{
 
 pub trait PuzzleExt {
     fn origin_conversations(&self) -> &'static [Conversation];
+    fn first_solve_conversation(&self) -> Option<Conversation>;
     fn allowed_chips(&self, solved: &PuzzleSet) -> ChipSet;
     fn interfaces(&self) -> &'static [Interface];
     fn tutorial_bubbles(
@@ -167,6 +171,18 @@ impl PuzzleExt for Puzzle {
         }
     }
 
+    fn first_solve_conversation(&self) -> Option<Conversation> {
+        match self {
+            Puzzle::AutomateFuelSynthesis => Some(Conversation::MakingFuel),
+            Puzzle::AutomateHeliostat => Some(Conversation::RestorePower),
+            Puzzle::AutomateReactor => Some(Conversation::MorePower),
+            Puzzle::AutomateSensors => Some(Conversation::WhereAreWe),
+            Puzzle::CommandLander => Some(Conversation::Descent),
+            Puzzle::CommandTurret => Some(Conversation::UnexpectedCompany),
+            _ => None,
+        }
+    }
+
     fn allowed_chips(&self, solved: &PuzzleSet) -> ChipSet {
         let mut allowed = ChipSet::new();
         for &(_, ctypes) in CHIP_CATEGORIES.iter() {
@@ -179,6 +195,9 @@ impl PuzzleExt for Puzzle {
         allowed
     }
 
+    // Every puzzle must be listed explicitly here (no wildcard arm), so that
+    // adding a new puzzle is a compile error until its interfaces exist.
+    #[deny(clippy::wildcard_enum_match_arm)]
     fn interfaces(&self) -> &'static [Interface] {
         match self {
             Puzzle::AutomateBeacon => self::beacon::INTERFACES,
@@ -298,6 +317,9 @@ fn is_chip_allowed_in(
 
 //===========================================================================//
 
+// As with `PuzzleExt::interfaces`, every puzzle must be listed explicitly
+// here, so that a new puzzle can't silently end up without an eval.
+#[deny(clippy::wildcard_enum_match_arm)]
 pub(super) fn new_puzzle_eval(
     puzzle: Puzzle,
     slots: Vec<Vec<((Coords, Direction), WireId)>>,
@@ -405,3 +427,54 @@ pub(super) fn new_puzzle_eval(
 }
 
 //===========================================================================//
+
+#[cfg(test)]
+mod tests {
+    use super::PuzzleExt;
+    use crate::save::{Puzzle, PuzzleSet};
+    use crate::state::EditGrid;
+
+    #[test]
+    fn all_puzzles_have_interfaces() {
+        for puzzle in Puzzle::all() {
+            let interfaces = puzzle.interfaces();
+            assert!(!interfaces.is_empty(), "{:?} has no interfaces", puzzle);
+            for interface in interfaces.iter() {
+                assert!(
+                    !interface.ports.is_empty(),
+                    "{:?} interface {:?} has no ports",
+                    puzzle,
+                    interface.name
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn first_solve_conversations_are_origin_conversations() {
+        for puzzle in Puzzle::all() {
+            if let Some(conv) = puzzle.first_solve_conversation() {
+                assert!(
+                    puzzle.origin_conversations().contains(&conv),
+                    "{:?} isn't an origin conversation for {:?}",
+                    conv,
+                    puzzle
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn all_puzzles_have_evals() {
+        for puzzle in Puzzle::all() {
+            let mut grid = EditGrid::new(puzzle, &PuzzleSet::new());
+            assert!(grid.start_eval(), "{:?} failed to start eval", puzzle);
+            // Stepping the (empty) circuit exercises the puzzle eval's
+            // handling of its interface slots.
+            let _ = grid.eval_mut().unwrap().step_time();
+            grid.stop_eval();
+        }
+    }
+}
+
+//===========================================================================//
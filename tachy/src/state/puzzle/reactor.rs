@@ -37,6 +37,7 @@ const TARGET_HOLD_TIME: i32 = 5;
 pub const INTERFACES: &[Interface] = &[
     Interface {
         name: "Thermostat Interface",
+        label: "thermostat",
         description:
             "Connects to sensors in the ship's power grid that determine the \
              current and desired power outputs of the backup reactor (from 0 \
@@ -62,6 +63,7 @@ pub const INTERFACES: &[Interface] = &[
     },
     Interface {
         name: "Control Rod Interface",
+        label: "control rod",
         description:
             "Connects to an array of actuators that move the reactor's three \
              control rods.  Send higher values to retract a rod (increasing \
@@ -40,6 +40,7 @@ const INIT_SPEED_INDEX: usize = 2;
 pub const INTERFACES: &[Interface] = &[
     Interface {
         name: "Rear Detector",
+        label: "rear detector",
         description:
             "Connects to the radiation wave detector at the rear of the \
              resonator crystal.",
@@ -57,6 +58,7 @@ pub const INTERFACES: &[Interface] = &[
     },
     Interface {
         name: "Forward Detector",
+        label: "forward detector",
         description:
             "Connects to the radiation wave detector at the front of the \
              resonator crystal.",
@@ -74,6 +76,7 @@ pub const INTERFACES: &[Interface] = &[
     },
     Interface {
         name: "Pulse Emitter",
+        label: "pulse emitter",
         description: "Connects to the crystal's pulse emitter.",
         side: Direction::North,
         pos: InterfacePosition::Center,
@@ -40,6 +40,7 @@ const COMMANDS: &[u32] = &[3, 5, 2, 4, 0, 7, 1, 6, 3, 7, 1, 6, 2, 5];
 pub const INTERFACES: &[Interface] = &[
     Interface {
         name: "Radio Interface",
+        label: "radio",
         description: "Connects to a radio antenna.",
         side: Direction::West,
         pos: InterfacePosition::Right(1),
@@ -66,6 +67,7 @@ pub const INTERFACES: &[Interface] = &[
     },
     Interface {
         name: "Arm Interface",
+        label: "arm",
         description:
             "Connects to the sensors and servo motors of the robot arm.",
         side: Direction::East,
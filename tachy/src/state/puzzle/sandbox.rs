@@ -37,9 +37,37 @@ const STARTUP_PORTS: &[InterfacePort] = &[InterfacePort {
     size: WireSize::Zero,
 }];
 
+const INPUT_PORTS: &[InterfacePort] = &[
+    InterfacePort {
+        name: "A",
+        description: "Outputs the value chosen for A in the input panel.",
+        flow: PortFlow::Source,
+        color: PortColor::Behavior,
+        size: WireSize::Eight,
+    },
+    InterfacePort {
+        name: "B",
+        description: "Outputs the value chosen for B in the input panel.",
+        flow: PortFlow::Source,
+        color: PortColor::Behavior,
+        size: WireSize::Eight,
+    },
+];
+
+const INPUT_INTERFACE: Interface = Interface {
+    name: "Input Interface",
+    label: "input",
+    description: "Connects to the sandbox input panel, which lets you choose \
+                  the value sent from each port.",
+    side: Direction::East,
+    pos: InterfacePosition::Right(0),
+    ports: INPUT_PORTS,
+};
+
 pub const ANALOG_INTERFACES: &[Interface] = &[
     Interface {
         name: "Startup Interface",
+        label: "startup",
         description: "Connects to the power supply.",
         side: Direction::North,
         pos: InterfacePosition::Right(0),
@@ -47,6 +75,7 @@ pub const ANALOG_INTERFACES: &[Interface] = &[
     },
     Interface {
         name: "Timer Interface",
+        label: "timer",
         description: "Connects to a digital timer.",
         side: Direction::West,
         pos: InterfacePosition::Right(0),
@@ -76,25 +105,31 @@ pub const ANALOG_INTERFACES: &[Interface] = &[
             },
         ],
     },
+    INPUT_INTERFACE,
 ];
 
-pub const BEHAVIOR_INTERFACES: &[Interface] = &[Interface {
-    name: "Timer Interface",
-    description: "Connects to a digital timer.",
-    side: Direction::West,
-    pos: InterfacePosition::Right(0),
-    ports: &[InterfacePort {
-        name: "Time",
-        description: "Outputs the current time step.",
-        flow: PortFlow::Source,
-        color: PortColor::Behavior,
-        size: WireSize::Eight,
-    }],
-}];
+pub const BEHAVIOR_INTERFACES: &[Interface] = &[
+    Interface {
+        name: "Timer Interface",
+        label: "timer",
+        description: "Connects to a digital timer.",
+        side: Direction::West,
+        pos: InterfacePosition::Right(0),
+        ports: &[InterfacePort {
+            name: "Time",
+            description: "Outputs the current time step.",
+            flow: PortFlow::Source,
+            color: PortColor::Behavior,
+            size: WireSize::Eight,
+        }],
+    },
+    INPUT_INTERFACE,
+];
 
 pub const EVENT_INTERFACES: &[Interface] = &[
     Interface {
         name: "Startup Interface",
+        label: "startup",
         description: "Connects to the power supply.",
         side: Direction::North,
         pos: InterfacePosition::Right(0),
@@ -102,6 +137,7 @@ pub const EVENT_INTERFACES: &[Interface] = &[
     },
     Interface {
         name: "Timer Interface",
+        label: "timer",
         description: "Connects to a digital timer.",
         side: Direction::West,
         pos: InterfacePosition::Right(0),
@@ -123,22 +159,43 @@ pub const EVENT_INTERFACES: &[Interface] = &[
             },
         ],
     },
+    INPUT_INTERFACE,
 ];
 
 //===========================================================================//
 
+/// Returns the names of the ports on each sandbox puzzle's input interface,
+/// in the order of their indices for `EditGrid::set_sandbox_input`.
+pub fn sandbox_input_names() -> Vec<&'static str> {
+    INPUT_PORTS.iter().map(|port| port.name).collect()
+}
+
+fn input_wires(slots: &[((Coords, Direction), WireId)]) -> Vec<WireId> {
+    debug_assert_eq!(slots.len(), INPUT_PORTS.len());
+    slots.iter().map(|&(_, wire)| wire).collect()
+}
+
+fn send_inputs(input_wires: &[WireId], state: &mut CircuitState) {
+    for (index, &wire) in input_wires.iter().enumerate() {
+        state.send_behavior(wire, state.sandbox_input(index) & 0xff);
+    }
+}
+
+//===========================================================================//
+
 pub struct SandboxAnalogEval {
     init_wire: WireId,
     time_wire: WireId,
     tick_wire: WireId,
     sine_wire: WireId,
+    input_wires: Vec<WireId>,
 }
 
 impl SandboxAnalogEval {
     pub fn new(
         slots: Vec<Vec<((Coords, Direction), WireId)>>,
     ) -> SandboxAnalogEval {
-        debug_assert_eq!(slots.len(), 2);
+        debug_assert_eq!(slots.len(), 3);
         debug_assert_eq!(slots[0].len(), 1);
         debug_assert_eq!(slots[1].len(), 3);
         SandboxAnalogEval {
@@ -146,13 +203,15 @@ impl SandboxAnalogEval {
             time_wire: slots[1][0].1,
             tick_wire: slots[1][1].1,
             sine_wire: slots[1][2].1,
+            input_wires: input_wires(&slots[2]),
         }
     }
 
     fn send_sine(&self, state: &mut CircuitState) {
-        let time = (state.time_step() % SINE_PERIOD)
+        let cycle = if state.inputs_frozen() { 0 } else { state.cycle() };
+        let time = (state.input_time_step() % SINE_PERIOD)
             * MAX_CYCLES_PER_TIME_STEP
-            + state.cycle();
+            + cycle;
         let dtheta = std::f64::consts::TAU
             / ((SINE_PERIOD * MAX_CYCLES_PER_TIME_STEP) as f64);
         let theta = dtheta * (time as f64);
@@ -169,9 +228,10 @@ impl PuzzleEval for SandboxAnalogEval {
         if state.time_step() == 0 {
             state.send_event(self.init_wire, 0);
         }
-        state.send_behavior(self.time_wire, state.time_step() & 0xff);
+        state.send_behavior(self.time_wire, state.input_time_step() & 0xff);
         state.send_event(self.tick_wire, 0);
         self.send_sine(state);
+        send_inputs(&self.input_wires, state);
     }
 
     fn begin_additional_cycle(&mut self, state: &mut CircuitState) {
@@ -180,6 +240,7 @@ impl PuzzleEval for SandboxAnalogEval {
 
     fn needs_another_cycle(&self, state: &CircuitState) -> bool {
         state.cycle() + 1 < MAX_CYCLES_PER_TIME_STEP
+            && !state.inputs_frozen()
             && !state.is_null_wire(self.sine_wire)
     }
 }
@@ -188,15 +249,19 @@ impl PuzzleEval for SandboxAnalogEval {
 
 pub struct SandboxBehaviorEval {
     time_wire: WireId,
+    input_wires: Vec<WireId>,
 }
 
 impl SandboxBehaviorEval {
     pub fn new(
         slots: Vec<Vec<((Coords, Direction), WireId)>>,
     ) -> SandboxBehaviorEval {
-        debug_assert_eq!(slots.len(), 1);
+        debug_assert_eq!(slots.len(), 2);
         debug_assert_eq!(slots[0].len(), 1);
-        SandboxBehaviorEval { time_wire: slots[0][0].1 }
+        SandboxBehaviorEval {
+            time_wire: slots[0][0].1,
+            input_wires: input_wires(&slots[1]),
+        }
     }
 }
 
@@ -206,7 +271,8 @@ impl PuzzleEval for SandboxBehaviorEval {
     }
 
     fn begin_time_step(&mut self, state: &mut CircuitState) {
-        state.send_behavior(self.time_wire, state.time_step() & 0xff);
+        state.send_behavior(self.time_wire, state.input_time_step() & 0xff);
+        send_inputs(&self.input_wires, state);
     }
 }
 
@@ -216,19 +282,21 @@ pub struct SandboxEventEval {
     init_wire: WireId,
     time_wire: WireId,
     tick_wire: WireId,
+    input_wires: Vec<WireId>,
 }
 
 impl SandboxEventEval {
     pub fn new(
         slots: Vec<Vec<((Coords, Direction), WireId)>>,
     ) -> SandboxEventEval {
-        debug_assert_eq!(slots.len(), 2);
+        debug_assert_eq!(slots.len(), 3);
         debug_assert_eq!(slots[0].len(), 1);
         debug_assert_eq!(slots[1].len(), 2);
         SandboxEventEval {
             init_wire: slots[0][0].1,
             time_wire: slots[1][0].1,
             tick_wire: slots[1][1].1,
+            input_wires: input_wires(&slots[2]),
         }
     }
 }
@@ -242,8 +310,9 @@ impl PuzzleEval for SandboxEventEval {
         if state.time_step() == 0 {
             state.send_event(self.init_wire, 0);
         }
-        state.send_behavior(self.time_wire, state.time_step() & 0xff);
+        state.send_behavior(self.time_wire, state.input_time_step() & 0xff);
         state.send_event(self.tick_wire, 0);
+        send_inputs(&self.input_wires, state);
     }
 }
 
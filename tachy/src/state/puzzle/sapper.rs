@@ -58,6 +58,7 @@ const MAZE: [[i8; MAZE_SIZE]; MAZE_SIZE] = [
 pub const INTERFACES: &[Interface] = &[
     Interface {
         name: "Scanner Interface",
+        label: "scanner",
         description: "Connects to the drone's forward sensors.",
         side: Direction::North,
         pos: InterfacePosition::Center,
@@ -76,6 +77,7 @@ pub const INTERFACES: &[Interface] = &[
     },
     Interface {
         name: "Navigation Interface",
+        label: "navigation",
         description: "Connects to the drone's navigation sensors.",
         side: Direction::West,
         pos: InterfacePosition::Center,
@@ -107,6 +109,7 @@ pub const INTERFACES: &[Interface] = &[
     },
     Interface {
         name: "Engine Interface",
+        label: "engine",
         description: "Connects to the ship's defensive beam weapon.",
         side: Direction::South,
         pos: InterfacePosition::Center,
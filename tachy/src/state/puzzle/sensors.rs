@@ -28,6 +28,7 @@ use crate::state::{PortColor, PortFlow, WireId};
 pub const INTERFACES: &[Interface] = &[
     Interface {
         name: "Upper",
+        label: "UPPER",
         description:
             "Indicates the current upper bound of the scan range (inclusive).",
         side: Direction::West,
@@ -42,6 +43,7 @@ pub const INTERFACES: &[Interface] = &[
     },
     Interface {
         name: "Lower",
+        label: "LOWER",
         description:
             "Indicates the current lower bound of the scan range (inclusive).",
         side: Direction::West,
@@ -56,6 +58,7 @@ pub const INTERFACES: &[Interface] = &[
     },
     Interface {
         name: "Out",
+        label: "OUT",
         description: "Controls where the scan range will be subdivided.",
         side: Direction::East,
         pos: InterfacePosition::Center,
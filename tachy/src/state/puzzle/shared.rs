@@ -65,6 +65,21 @@ impl FabricationData {
 
 //===========================================================================//
 
+/// Describes the first place where a fabrication circuit's output differed
+/// from the expected table.  As in the table itself, `expected` and `actual`
+/// are `NIL` for "no event", and analog values are encoded `Fixed` values.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FabricationMismatch {
+    pub time_step: u32,
+    pub column: usize,
+    pub port_name: &'static str,
+    pub expected: u32,
+    pub actual: u32,
+    pub message: String,
+}
+
+//===========================================================================//
+
 pub struct FabricationEval {
     interfaces: &'static [Interface],
     slots: Vec<Vec<((Coords, Direction), WireId)>>,
@@ -72,6 +87,7 @@ pub struct FabricationEval {
     expected_table_values: &'static [u32],
     table_values: Vec<u32>,
     has_received_events: HashSet<WireId>,
+    first_mismatch: Option<FabricationMismatch>,
 }
 
 impl FabricationEval {
@@ -93,12 +109,49 @@ impl FabricationEval {
             expected_table_values: data.expected_table_values,
             table_values: data.expected_table_values.to_vec(),
             has_received_events: HashSet::new(),
+            first_mismatch: None,
         }
     }
 
     pub fn table_values(&self) -> &[u32] {
         &self.table_values
     }
+
+    /// Returns details about the first mismatch between the circuit's output
+    /// and the expected table, if there has been one so far.
+    pub fn first_mismatch(&self) -> Option<&FabricationMismatch> {
+        self.first_mismatch.as_ref()
+    }
+
+    /// Returns an error for the given port, recording it as the first
+    /// mismatch if there hasn't been one yet.
+    fn mismatch_error(
+        &mut self,
+        state: &CircuitState,
+        loc: (Coords, Direction),
+        column: usize,
+        expected: u32,
+        actual: u32,
+        message: String,
+    ) -> EvalError {
+        if self.first_mismatch.is_none() {
+            let port_name = self
+                .interfaces
+                .iter()
+                .flat_map(|interface| interface.ports.iter())
+                .nth(column)
+                .map_or("", |port| port.name);
+            self.first_mismatch = Some(FabricationMismatch {
+                time_step: state.time_step(),
+                column,
+                port_name,
+                expected,
+                actual,
+                message: message.clone(),
+            });
+        }
+        state.port_error(loc, message)
+    }
 }
 
 impl PuzzleEval for FabricationEval {
@@ -175,21 +228,42 @@ impl PuzzleEval for FabricationEval {
                                     port.name, actual
                                 )
                             };
-                            errors.push(state.port_error(loc, msg));
+                            errors.push(self.mismatch_error(
+                                state,
+                                loc,
+                                column_index,
+                                expected,
+                                actual,
+                                msg,
+                            ));
                         } else if self.has_received_events.contains(&wire) {
                             let msg = format!(
                                 "Expected only one event for {}, \
                                  but got more than one.",
                                 port.name
                             );
-                            errors.push(state.port_error(loc, msg));
+                            errors.push(self.mismatch_error(
+                                state,
+                                loc,
+                                column_index,
+                                expected,
+                                actual,
+                                msg,
+                            ));
                         } else if actual != expected {
                             let msg = format!(
                                 "Expected event value of {} for {}, \
                                  but got event value of {}.",
                                 expected, port.name, actual
                             );
-                            errors.push(state.port_error(loc, msg));
+                            errors.push(self.mismatch_error(
+                                state,
+                                loc,
+                                column_index,
+                                expected,
+                                actual,
+                                msg,
+                            ));
                         }
                         self.has_received_events.insert(wire);
                     }
@@ -224,7 +298,14 @@ impl PuzzleEval for FabricationEval {
                                      but got value of {}.",
                                     expected, port.name, actual
                                 );
-                                errors.push(state.port_error(loc, msg));
+                                errors.push(self.mismatch_error(
+                                    state,
+                                    loc,
+                                    column_index,
+                                    expected,
+                                    actual,
+                                    msg,
+                                ));
                             }
                         }
                         PortColor::Event => {
@@ -244,7 +325,14 @@ impl PuzzleEval for FabricationEval {
                                             expected, port.name
                                         )
                                     };
-                                    errors.push(state.port_error(loc, msg));
+                                    errors.push(self.mismatch_error(
+                                        state,
+                                        loc,
+                                        column_index,
+                                        expected,
+                                        NIL,
+                                        msg,
+                                    ));
                                 }
                             }
                         }
@@ -260,7 +348,14 @@ impl PuzzleEval for FabricationEval {
                                      but got value of {}.",
                                     expected_fixed, port.name, actual_fixed
                                 );
-                                errors.push(state.port_error(loc, msg));
+                                errors.push(self.mismatch_error(
+                                    state,
+                                    loc,
+                                    column_index,
+                                    expected,
+                                    actual_fixed.to_encoded(),
+                                    msg,
+                                ));
                             }
                         }
                     }
@@ -98,6 +98,7 @@ fn enemy_dist_for_time_step(time_step: u32) -> u32 {
 pub const INTERFACES: &[Interface] = &[
     Interface {
         name: "Sensors Interface",
+        label: "sensors",
         description: "Connects to the ship's tactical sensors.",
         side: Direction::North,
         pos: InterfacePosition::Right(0),
@@ -125,6 +126,7 @@ pub const INTERFACES: &[Interface] = &[
     },
     Interface {
         name: "Beam Interface",
+        label: "beam",
         description: "Connects to the ship's defensive beam weapon.",
         side: Direction::North,
         pos: InterfacePosition::Left(0),
@@ -142,6 +144,7 @@ pub const INTERFACES: &[Interface] = &[
     },
     Interface {
         name: "Shields Interface",
+        label: "shields",
         description: "Connects to the ship's deflector shields.",
         side: Direction::East,
         pos: InterfacePosition::Center,
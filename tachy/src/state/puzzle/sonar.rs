@@ -67,6 +67,7 @@ const STBD_WALLS: &[i32; NUM_WALLS] = &[
 pub const INTERFACES: &[Interface] = &[
     Interface {
         name: "Port Sonar Interface",
+        label: "port sonar",
         description: "Connects to the port-side sonar.",
         side: Direction::West,
         pos: InterfacePosition::Left(1),
@@ -74,6 +75,7 @@ pub const INTERFACES: &[Interface] = &[
     },
     Interface {
         name: "Starboard Sonar Interface",
+        label: "starboard sonar",
         description: "Connects to the starboard-side sonar.",
         side: Direction::East,
         pos: InterfacePosition::Right(1),
@@ -81,6 +83,7 @@ pub const INTERFACES: &[Interface] = &[
     },
     Interface {
         name: "Propulsion Interface",
+        label: "propulsion",
         description: "Connects to the propellers and rudder.",
         side: Direction::South,
         pos: InterfacePosition::Center,
@@ -98,6 +101,7 @@ pub const INTERFACES: &[Interface] = &[
     },
     Interface {
         name: "Instruments Interface",
+        label: "instruments",
         description: "Connects to the dead-reckoning positioning system.",
         side: Direction::North,
         pos: InterfacePosition::Center,
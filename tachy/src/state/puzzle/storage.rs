@@ -74,6 +74,7 @@ const DELAYS_AND_COMMANDS: &[(u32, Command)] = &[
 pub const INTERFACES: &[Interface] = &[
     Interface {
         name: "Radio Interface",
+        label: "radio",
         description: "Connects to a radio antenna.",
         side: Direction::West,
         pos: InterfacePosition::Left(1),
@@ -102,6 +103,7 @@ pub const INTERFACES: &[Interface] = &[
     },
     Interface {
         name: "Sensor Interface",
+        label: "sensor",
         description: "Connects to the sensors on the robot arm.",
         side: Direction::East,
         pos: InterfacePosition::Right(0),
@@ -127,6 +129,7 @@ pub const INTERFACES: &[Interface] = &[
     },
     Interface {
         name: "Motor Interface",
+        label: "motor",
         description: "Connects to the servo motors of the robot arm.",
         side: Direction::East,
         pos: InterfacePosition::Left(0),
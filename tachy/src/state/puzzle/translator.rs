@@ -115,6 +115,7 @@ fn translate_alien_word(mut alien: &[u8]) -> (u32, Vec<u8>) {
 pub const INTERFACES: &[Interface] = &[
     Interface {
         name: "Reader Interface",
+        label: "reader",
         description: "Connects to an OCR scanner for reading alien text.",
         side: Direction::West,
         pos: InterfacePosition::Center,
@@ -130,6 +131,7 @@ pub const INTERFACES: &[Interface] = &[
     },
     Interface {
         name: "Printer Interface",
+        label: "printer",
         description: "Connects to a printer for printing out translated text.",
         side: Direction::East,
         pos: InterfacePosition::Center,
@@ -145,6 +147,7 @@ pub const INTERFACES: &[Interface] = &[
     },
     Interface {
         name: "Translation Interface",
+        label: "translation",
         description: "Connects to a word-for-word translation database.",
         side: Direction::South,
         pos: InterfacePosition::Center,
@@ -51,6 +51,7 @@ const ENEMIES: &[(u32, u32, u32)] = &[
 pub const INTERFACES: &[Interface] = &[
     Interface {
         name: "Radar Interface",
+        label: "radar",
         description: "Connects to the base's radar dish.",
         side: Direction::West,
         pos: InterfacePosition::Left(1),
@@ -78,6 +79,7 @@ pub const INTERFACES: &[Interface] = &[
     },
     Interface {
         name: "Cannon Interface",
+        label: "cannon",
         description: "Connects to the pulse cannon mounted on the turret.",
         side: Direction::East,
         pos: InterfacePosition::Right(1),
@@ -103,6 +105,7 @@ pub const INTERFACES: &[Interface] = &[
     },
     Interface {
         name: "Turret Interface",
+        label: "turret",
         description: "Connects to the motor on the turret base.",
         side: Direction::South,
         pos: InterfacePosition::Center,
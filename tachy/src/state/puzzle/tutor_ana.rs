@@ -39,6 +39,7 @@ pub const TUTORIAL_ADC_DATA: &FabricationData = &FabricationData {
 pub(super) const ADC_INTERFACES: &[Interface] = &[
     Interface {
         name: "In",
+        label: "IN",
         description: "The analog input voltage to be converted.",
         side: Direction::North,
         pos: InterfacePosition::Center,
@@ -52,6 +53,7 @@ pub(super) const ADC_INTERFACES: &[Interface] = &[
     },
     Interface {
         name: "Sample",
+        label: "SAMPLE",
         description:
             "When an event arrives here, sample the analog input, and if it's \
              non-negative, send a 2-bit representation of the voltage to \
@@ -68,6 +70,7 @@ pub(super) const ADC_INTERFACES: &[Interface] = &[
     },
     Interface {
         name: "Out",
+        label: "OUT",
         description: "Send digitized voltage values here:\n    \
                       Send 0 for 0.0 to +0.25\n    \
                       Send 1 for +0.25 to +0.5\n    \
@@ -118,6 +121,7 @@ pub const TUTORIAL_INTEGRATE_DATA: &FabricationData = &FabricationData {
 pub(super) const INTEGRATE_INTERFACES: &[Interface] = &[
     Interface {
         name: "In",
+        label: "IN",
         description: "The analog input voltage to be integrated.",
         side: Direction::West,
         pos: InterfacePosition::Center,
@@ -131,6 +135,7 @@ pub(super) const INTEGRATE_INTERFACES: &[Interface] = &[
     },
     Interface {
         name: "Reset",
+        label: "RESET",
         description:
             "When an event arrives here, the output voltage should be reset \
              to the current $*IC$* voltage.",
@@ -146,6 +151,7 @@ pub(super) const INTEGRATE_INTERFACES: &[Interface] = &[
     },
     Interface {
         name: "IC",
+        label: "IC",
         description:
             "The \"initial condition\" voltage to reset to when a $*Reset$* \
              event arrives.",
@@ -161,6 +167,7 @@ pub(super) const INTEGRATE_INTERFACES: &[Interface] = &[
     },
     Interface {
         name: "Out",
+        label: "OUT",
         description:
             "This should start at zero, and sum up the input voltages over \
              time.",
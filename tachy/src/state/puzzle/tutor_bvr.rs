@@ -33,6 +33,7 @@ pub const TUTORIAL_OR_DATA: &FabricationData = &FabricationData {
 pub(super) const OR_INTERFACES: &[Interface] = &[
     Interface {
         name: "In1",
+        label: "IN1",
         description: "First input (0 or 1).",
         side: Direction::West,
         pos: InterfacePosition::Center,
@@ -46,6 +47,7 @@ pub(super) const OR_INTERFACES: &[Interface] = &[
     },
     Interface {
         name: "In2",
+        label: "IN2",
         description: "Second input (0 or 1).",
         side: Direction::South,
         pos: InterfacePosition::Center,
@@ -59,6 +61,7 @@ pub(super) const OR_INTERFACES: &[Interface] = &[
     },
     Interface {
         name: "Out",
+        label: "OUT",
         description: "Should be 1 if either input is 1.\n\
                       Should be 0 if both inputs are 0.",
         side: Direction::East,
@@ -107,6 +110,7 @@ pub const TUTORIAL_MUX_DATA: &FabricationData = &FabricationData {
 pub(super) const MUX_INTERFACES: &[Interface] = &[
     Interface {
         name: "In0",
+        label: "IN0",
         description: "The input to use when the control value is 0.",
         side: Direction::West,
         pos: InterfacePosition::Center,
@@ -120,6 +124,7 @@ pub(super) const MUX_INTERFACES: &[Interface] = &[
     },
     Interface {
         name: "In1",
+        label: "IN1",
         description: "The input to use when the control value is 1.",
         side: Direction::South,
         pos: InterfacePosition::Center,
@@ -133,6 +138,7 @@ pub(super) const MUX_INTERFACES: &[Interface] = &[
     },
     Interface {
         name: "Ctrl",
+        label: "CTRL",
         description: "Indicates which input should be sent to the output.",
         side: Direction::North,
         pos: InterfacePosition::Center,
@@ -146,6 +152,7 @@ pub(super) const MUX_INTERFACES: &[Interface] = &[
     },
     Interface {
         name: "Out",
+        label: "OUT",
         description: "Should be the value of $*In0$* if $*Ctrl$* is 0, or \
                       of $*In1$* if $*Ctrl$* is 1.",
         side: Direction::East,
@@ -194,6 +201,7 @@ pub const TUTORIAL_ADD_DATA: &FabricationData = &FabricationData {
 pub(super) const ADD_INTERFACES: &[Interface] = &[
     Interface {
         name: "In1",
+        label: "IN1",
         description: "First input (from 0 to 15).",
         side: Direction::West,
         pos: InterfacePosition::Center,
@@ -207,6 +215,7 @@ pub(super) const ADD_INTERFACES: &[Interface] = &[
     },
     Interface {
         name: "In2",
+        label: "IN2",
         description: "Second input (from 0 to 15).",
         side: Direction::South,
         pos: InterfacePosition::Center,
@@ -220,6 +229,7 @@ pub(super) const ADD_INTERFACES: &[Interface] = &[
     },
     Interface {
         name: "Out",
+        label: "OUT",
         description:
             "Should be the sum of the two inputs (which will never be more \
              than 15 for this task).",
@@ -33,6 +33,7 @@ pub const TUTORIAL_DEMUX_DATA: &FabricationData = &FabricationData {
 pub(super) const DEMUX_INTERFACES: &[Interface] = &[
     Interface {
         name: "In",
+        label: "IN",
         description: "Input events arrive here.",
         side: Direction::West,
         pos: InterfacePosition::Center,
@@ -46,6 +47,7 @@ pub(super) const DEMUX_INTERFACES: &[Interface] = &[
     },
     Interface {
         name: "Ctrl",
+        label: "CTRL",
         description:
             "Indicates which output (0-3) the event should be sent to.",
         side: Direction::North,
@@ -60,6 +62,7 @@ pub(super) const DEMUX_INTERFACES: &[Interface] = &[
     },
     Interface {
         name: "Out",
+        label: "OUT",
         description:
             "Input events should be sent to $*Out0$* when $*Ctrl$* is 0, to \
              $*Out1$* when $*Ctrl$* is 1, and so on.",
@@ -133,6 +136,7 @@ pub const TUTORIAL_AMP_DATA: &FabricationData = &FabricationData {
 pub(super) const AMP_INTERFACES: &[Interface] = &[
     Interface {
         name: "In",
+        label: "IN",
         description: "Input events arrive here.",
         side: Direction::West,
         pos: InterfacePosition::Center,
@@ -146,6 +150,7 @@ pub(super) const AMP_INTERFACES: &[Interface] = &[
     },
     Interface {
         name: "Out",
+        label: "OUT",
         description:
             "Output events should be sent here.  The output value should be \
              twice the input value, unless that would be more than 10, in \
@@ -204,6 +209,7 @@ pub const TUTORIAL_SUM_DATA: &FabricationData = &FabricationData {
 pub(super) const SUM_INTERFACES: &[Interface] = &[
     Interface {
         name: "Reset",
+        label: "RESET",
         description: "When an event arrives here, the output sum should be \
                       reset to zero.",
         side: Direction::North,
@@ -218,6 +224,7 @@ pub(super) const SUM_INTERFACES: &[Interface] = &[
     },
     Interface {
         name: "In",
+        label: "IN",
         description: "Input events arrive here.",
         side: Direction::West,
         pos: InterfacePosition::Center,
@@ -231,6 +238,7 @@ pub(super) const SUM_INTERFACES: &[Interface] = &[
     },
     Interface {
         name: "Total",
+        label: "TOTAL",
         description:
             "Should equal the sum of all input events since the last \
              reset.",
@@ -33,6 +33,7 @@ pub const TUTORIAL_RAM_DATA: &FabricationData = &FabricationData {
 pub(super) const RAM_INTERFACES: &[Interface] = &[
     Interface {
         name: "In",
+        label: "IN",
         description: "Input events arrive here.",
         side: Direction::West,
         pos: InterfacePosition::Center,
@@ -46,6 +47,7 @@ pub(super) const RAM_INTERFACES: &[Interface] = &[
     },
     Interface {
         name: "Out",
+        label: "OUT",
         description: "Output events should be sent here.",
         side: Direction::East,
         pos: InterfacePosition::Center,
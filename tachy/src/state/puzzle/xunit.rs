@@ -55,6 +55,7 @@ fn delay_for(charge: u32) -> u32 {
 pub const INTERFACES: &[Interface] = &[
     Interface {
         name: "Proximity Interface",
+        label: "proximity",
         description: "Connects to the missile's proximity sensor.",
         side: Direction::West,
         pos: InterfacePosition::Center,
@@ -70,6 +71,7 @@ pub const INTERFACES: &[Interface] = &[
     },
     Interface {
         name: "Detonator Interface",
+        label: "detonator",
         description:
             "Connects to the detonators for all of the warhead's explosive \
              charges.",
@@ -17,10 +17,81 @@
 // | with Tachyomancer.  If not, see <http://www.gnu.org/licenses/>.          |
 // +--------------------------------------------------------------------------+
 
-use crate::geom::Coords;
-use crate::save::{PuzzleSet, SolutionData};
-use crate::state::{EditGrid, EvalResult, WireError};
-use std::collections::HashMap;
+use super::puzzle::SimpleRng;
+use crate::geom::{Coords, CoordsRect, Direction};
+use crate::save::{
+    CircuitData, InputsData, Puzzle, PuzzleKind, PuzzleSet, ReferenceSpec,
+    SolutionData, WireShape,
+};
+use crate::state::{
+    EditGrid, EvalResult, Interface, PuzzleExt, WireColor, WireError,
+};
+use std::collections::{HashMap, HashSet};
+
+//===========================================================================//
+
+/// How many randomly-chosen rows of a reference table to check a sandbox
+/// circuit against.
+const REFERENCE_SAMPLE_COUNT: usize = 100;
+
+/// The fixed seed for choosing reference table rows, so that repeated checks
+/// of the same circuit give the same results.
+const REFERENCE_SEED: u64 = 0x5eed_ba5e;
+
+//===========================================================================//
+
+/// A time step on which a sandbox circuit's output differed from what its
+/// reference table expected.
+#[derive(Debug, Eq, PartialEq)]
+pub struct ReferenceMismatch {
+    pub time_step: u32,
+    pub inputs: (u32, u32),
+    pub expected: u32,
+    pub actual: u32,
+}
+
+//===========================================================================//
+
+/// Returns the wire length of a circuit, as used for scoring puzzles whose
+/// score units are wire length.  This expands the compact form in which
+/// fragments are stored in circuit data, and then counts them with
+/// `count_wire_fragments`, just as `EditGrid::stats` does for a grid.
+pub fn wire_length_score(circuit: &CircuitData) -> u32 {
+    let origin = Coords::new(0, 0);
+    let mut fragments = Vec::<(Coords, Direction)>::new();
+    for (delta, dir, shape) in circuit.wires.iter() {
+        let coords = origin + delta;
+        let dirs = match shape {
+            WireShape::Stub => vec![dir],
+            WireShape::Straight => vec![dir, -dir],
+            WireShape::TurnLeft => vec![dir, dir.rotate_cw()],
+            WireShape::TurnRight => vec![dir, dir.rotate_ccw()],
+            WireShape::SplitTee => {
+                vec![dir, dir.rotate_cw(), dir.rotate_ccw()]
+            }
+            WireShape::SplitLeft => vec![dir, dir.rotate_cw(), -dir],
+            WireShape::SplitRight => vec![dir, dir.rotate_ccw(), -dir],
+            WireShape::Cross => Direction::all().collect(),
+        };
+        for dir in dirs {
+            fragments.push((coords, dir));
+            // Stubs on the far side of each fragment are implied rather than
+            // stored (see EditGrid::to_circuit_data).
+            fragments.push((coords + dir, -dir));
+        }
+    }
+    count_wire_fragments(fragments) as u32
+}
+
+/// Counts the distinct wire fragments (that is, each cell side that a wire
+/// passes through) among the given `(coords, dir)` pairs.  This is the one
+/// definition of wire length shared by the editor and the score server.
+pub(crate) fn count_wire_fragments<I>(fragments: I) -> usize
+where
+    I: IntoIterator<Item = (Coords, Direction)>,
+{
+    fragments.into_iter().collect::<HashSet<_>>().len()
+}
 
 //===========================================================================//
 
@@ -32,37 +103,10 @@ pub fn verify_solution(data: &SolutionData) -> Vec<String> {
     );
     let mut errors = Vec::<String>::new();
     if !grid.start_eval() {
-        for error in grid.errors() {
-            errors.push(match error {
-                WireError::MultipleSources(id) => {
-                    format!("Wire {} has multiple sources", id.0)
-                }
-                WireError::PortColorMismatch(id) => {
-                    format!("Wire {} has a color mismatch", id.0)
-                }
-                WireError::NoValidSize(id) => {
-                    format!("Wire {} has a size mismatch", id.0)
-                }
-                WireError::UnbrokenLoop(ids, _) => {
-                    format!("Wires {:?} form a loop", ids)
-                }
-            });
-        }
-        errors.push("Circuit had errors".to_string());
-        return errors;
-    }
-
-    let mut all_inputs = HashMap::<(u32, u32), Vec<(Coords, u32, u32)>>::new();
-    if let Some(ref inputs) = data.inputs {
-        let origin = grid.bounds().top_left();
-        for (time_step, cycle, delta, subloc, count) in inputs.iter() {
-            all_inputs
-                .entry((time_step, cycle))
-                .or_insert_with(Vec::new)
-                .push((origin + delta, subloc, count));
-        }
+        return grid_errors(&grid);
     }
 
+    let mut all_inputs = recorded_inputs_by_cycle(data.inputs.as_ref(), &grid);
     let eval = grid.eval_mut().unwrap();
     loop {
         let time_step = eval.time_step();
@@ -107,4 +151,319 @@ pub fn verify_solution(data: &SolutionData) -> Vec<String> {
     errors
 }
 
+/// Re-simulates a solution from the start, replaying its recorded inputs,
+/// until evaluation reaches the beginning of the given time step (or the
+/// evaluation ends, whichever comes first).  Since evaluation is
+/// deterministic, this can be used to jump to an arbitrary point in a
+/// solution's run, including backwards.  Returns the grid (with evaluation
+/// still active) along with the result of the last cycle evaluated, or the
+/// grid's errors if evaluation couldn't be started.
+pub fn replay_solution_to_time_step(
+    data: &SolutionData,
+    time_step: u32,
+) -> Result<(EditGrid, EvalResult), Vec<String>> {
+    let mut grid = EditGrid::from_circuit_data(
+        data.puzzle,
+        &PuzzleSet::with_everything_solved(),
+        &data.circuit,
+    );
+    match replay_inputs_to_time_step(
+        &mut grid,
+        data.inputs.as_ref(),
+        time_step,
+    ) {
+        Some(result) => Ok((grid, result)),
+        None => Err(grid_errors(&grid)),
+    }
+}
+
+/// Restarts evaluation of the grid from the beginning, then replays the
+/// given recorded inputs (as from `CircuitEval::recorded_inputs`) until
+/// evaluation reaches the beginning of the given time step (or the
+/// evaluation ends, whichever comes first).  Returns the result of the last
+/// cycle evaluated, or `None` if evaluation couldn't be started.
+pub fn replay_inputs_to_time_step(
+    grid: &mut EditGrid,
+    inputs: Option<&InputsData>,
+    time_step: u32,
+) -> Option<EvalResult> {
+    grid.stop_eval();
+    if !grid.start_eval() {
+        return None;
+    }
+    let mut all_inputs = recorded_inputs_by_cycle(inputs, grid);
+    let eval = grid.eval_mut().unwrap();
+    let mut result = EvalResult::Continue;
+    while eval.time_step() < time_step {
+        let key = (eval.time_step(), eval.cycle());
+        if let Some(inputs) = all_inputs.remove(&key) {
+            for (coords, subloc, count) in inputs {
+                eval.press_button(coords, subloc, count);
+            }
+        }
+        result = eval.step_cycle();
+        match result {
+            EvalResult::Continue | EvalResult::Breakpoint(_) => {}
+            EvalResult::Failure | EvalResult::Victory(_) => break,
+        }
+    }
+    Some(result)
+}
+
+/// Button presses to replay, as (coords, subloc, count), keyed by
+/// (time step, cycle).
+type InputsByCycle = HashMap<(u32, u32), Vec<(Coords, u32, u32)>>;
+
+fn recorded_inputs_by_cycle(
+    inputs: Option<&InputsData>,
+    grid: &EditGrid,
+) -> InputsByCycle {
+    let mut all_inputs = InputsByCycle::new();
+    if let Some(inputs) = inputs {
+        let origin = grid.bounds().top_left();
+        for (time_step, cycle, delta, subloc, count) in inputs.iter() {
+            all_inputs
+                .entry((time_step, cycle))
+                .or_insert_with(Vec::new)
+                .push((origin + delta, subloc, count));
+        }
+    }
+    all_inputs
+}
+
+/// Checks that a circuit received from another player (e.g. via a share
+/// code) can be loaded as-is for the given puzzle: its bounds must be large
+/// enough for the puzzle's interfaces, and every chip must be known, allowed
+/// for the puzzle, and within the bounds.
+pub fn check_shared_circuit(
+    puzzle: Puzzle,
+    solved: &PuzzleSet,
+    circuit: &CircuitData,
+) -> Result<(), String> {
+    let min_size = Interface::min_bounds_size(puzzle.interfaces());
+    if circuit.size.width < min_size.width
+        || circuit.size.height < min_size.height
+    {
+        return Err(format!(
+            "Circuit size {}x{} is smaller than the minimum of {}x{}",
+            circuit.size.width,
+            circuit.size.height,
+            min_size.width,
+            min_size.height
+        ));
+    }
+    if let Some((_, name)) = circuit.chips.unknown().first() {
+        return Err(format!("Unknown chip type: {:?}", name));
+    }
+    let allowed = puzzle.allowed_chips(solved);
+    let bounds = CoordsRect::with_size(Coords::new(0, 0), circuit.size);
+    for (delta, ctype, orient) in circuit.chips.iter() {
+        if !allowed.contains(ctype) {
+            return Err(format!(
+                "{} chips are not allowed in this puzzle",
+                ctype
+            ));
+        }
+        let rect = CoordsRect::with_size(
+            Coords::new(0, 0) + delta,
+            orient * ctype.size(),
+        );
+        if !bounds.contains_rect(rect) {
+            return Err(format!("{} chip is out of bounds", ctype));
+        }
+    }
+    Ok(())
+}
+
+/// Checks a sandbox circuit against a user-supplied reference table.  Runs
+/// the circuit for a number of time steps, each time sending the inputs from
+/// a randomly-chosen row of the table, and compares the value on the wire
+/// labeled with the reference's output name against the row's expected
+/// value.  Returns all mismatches found (empty if the circuit matches the
+/// reference), or a list of errors if the check could not be run.
+pub fn verify_against_reference(
+    puzzle: Puzzle,
+    circuit: &CircuitData,
+    spec: &ReferenceSpec,
+) -> Result<Vec<ReferenceMismatch>, Vec<String>> {
+    if puzzle.kind() != PuzzleKind::Sandbox {
+        return Err(vec![format!("{:?} is not a sandbox puzzle", puzzle)]);
+    }
+    let mut grid = EditGrid::from_circuit_data(
+        puzzle,
+        &PuzzleSet::with_everything_solved(),
+        circuit,
+    );
+    let output_wire = grid
+        .wire_labels()
+        .find(|&(_, label)| label == spec.output)
+        .and_then(|((coords, dir), _)| grid.wire_id_at(coords, dir));
+    let output_wire = match output_wire {
+        Some(wire) => wire,
+        None => {
+            return Err(vec![format!("No wire is labeled {:?}", spec.output)]);
+        }
+    };
+    if grid.wire_color(output_wire) != WireColor::Behavior {
+        return Err(vec![format!(
+            "The wire labeled {:?} is not a behavior wire",
+            spec.output
+        )]);
+    }
+    if !grid.start_eval() {
+        return Err(grid_errors(&grid));
+    }
+    let mut rng = SimpleRng::new(REFERENCE_SEED);
+    let mut mismatches = Vec::new();
+    let eval = grid.eval_mut().unwrap();
+    for _ in 0..REFERENCE_SAMPLE_COUNT {
+        let row = rng.rand_int(0, (spec.table.len() - 1) as u32) as usize;
+        let [a, b, expected] = spec.table[row];
+        eval.set_sandbox_input(0, a);
+        eval.set_sandbox_input(1, b);
+        let time_step = eval.time_step();
+        while eval.time_step() == time_step {
+            match eval.step_time() {
+                EvalResult::Continue | EvalResult::Breakpoint(_) => {}
+                EvalResult::Failure | EvalResult::Victory(_) => {
+                    return Err(eval
+                        .errors()
+                        .iter()
+                        .map(|error| {
+                            format!(
+                                "Time step {}: {}",
+                                error.time_step, error.message
+                            )
+                        })
+                        .collect());
+                }
+            }
+        }
+        let actual = eval.wire_value(output_wire);
+        if actual != expected {
+            mismatches.push(ReferenceMismatch {
+                time_step,
+                inputs: (a, b),
+                expected,
+                actual,
+            });
+        }
+    }
+    Ok(mismatches)
+}
+
+/// Runs a saved circuit for the given puzzle without any player input.
+/// Returns the score if the circuit completes the puzzle within
+/// `max_time_steps` time steps, or a list of errors otherwise.
+pub fn verify_circuit(
+    puzzle: Puzzle,
+    solved: &PuzzleSet,
+    circuit: &CircuitData,
+    max_time_steps: u32,
+) -> Result<u32, Vec<String>> {
+    run_circuit(puzzle, solved, circuit, max_time_steps)
+        .map(|(score, _)| score)
+}
+
+/// Like `verify_circuit`, but on success returns solution data for the
+/// circuit (with no recorded inputs) rather than just the score.
+pub fn solve_circuit(
+    puzzle: Puzzle,
+    solved: &PuzzleSet,
+    circuit: &CircuitData,
+    max_time_steps: u32,
+    install_id: Option<u64>,
+) -> Result<SolutionData, Vec<String>> {
+    let (score, time_steps) =
+        run_circuit(puzzle, solved, circuit, max_time_steps)?;
+    Ok(SolutionData {
+        install_id,
+        puzzle,
+        score,
+        time_steps,
+        circuit: circuit.clone(),
+        inputs: None,
+    })
+}
+
+/// Runs the circuit without player input, and returns the score and the
+/// number of time steps taken on victory.
+fn run_circuit(
+    puzzle: Puzzle,
+    solved: &PuzzleSet,
+    circuit: &CircuitData,
+    max_time_steps: u32,
+) -> Result<(u32, u32), Vec<String>> {
+    let mut grid = EditGrid::from_circuit_data(puzzle, solved, circuit);
+    if !grid.start_eval() {
+        return Err(grid_errors(&grid));
+    }
+    let eval = grid.eval_mut().unwrap();
+    loop {
+        match eval.step_time() {
+            EvalResult::Continue | EvalResult::Breakpoint(_) => {
+                if eval.time_step() >= max_time_steps {
+                    return Err(vec![format!(
+                        "Evaluation did not finish within {} time steps",
+                        max_time_steps
+                    )]);
+                }
+            }
+            EvalResult::Failure => {
+                return Err(eval
+                    .errors()
+                    .iter()
+                    .map(|error| {
+                        format!(
+                            "Time step {}: {}",
+                            error.time_step, error.message
+                        )
+                    })
+                    .collect());
+            }
+            EvalResult::Victory(score) => {
+                return Ok((score, eval.time_step()));
+            }
+        }
+    }
+}
+
+fn grid_errors(grid: &EditGrid) -> Vec<String> {
+    let mut errors: Vec<String> = grid
+        .errors()
+        .iter()
+        .map(|error| match error {
+            WireError::MultipleSources(id) => {
+                format!("Wire {} has multiple sources", id.0)
+            }
+            WireError::PortColorMismatch(id) => {
+                format!("Wire {} has a color mismatch", id.0)
+            }
+            WireError::NoValidSize(id, None) => {
+                format!("Wire {} has a size mismatch", id.0)
+            }
+            WireError::NoValidSize(id, Some(conflict)) => format!(
+                "Wire {} has a size mismatch at ports {:?}",
+                id.0,
+                conflict.port_locs()
+            ),
+            WireError::UnbrokenLoop(ids, _, ports) => {
+                let ports: Vec<String> = ports
+                    .iter()
+                    .map(|(coords, dir)| {
+                        format!("({}, {}) {:?}", coords.x, coords.y, dir)
+                    })
+                    .collect();
+                format!(
+                    "Wires {:?} form a combinational loop through ports {}",
+                    ids.iter().map(|id| id.0).collect::<Vec<usize>>(),
+                    ports.join(", ")
+                )
+            }
+        })
+        .collect();
+    errors.push("Circuit had errors".to_string());
+    errors
+}
+
 //===========================================================================//
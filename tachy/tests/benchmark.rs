@@ -0,0 +1,136 @@
+// +--------------------------------------------------------------------------+
+// | Copyright 2018 Matthew D. Steele <mdsteele@alum.mit.edu>                 |
+// |                                                                          |
+// | This file is part of Tachyomancer.                                       |
+// |                                                                          |
+// | Tachyomancer is free software: you can redistribute it and/or modify it  |
+// | under the terms of the GNU General Public License as published by the    |
+// | Free Software Foundation, either version 3 of the License, or (at your   |
+// | option) any later version.                                               |
+// |                                                                          |
+// | Tachyomancer is distributed in the hope that it will be useful, but      |
+// | WITHOUT ANY WARRANTY; without even the implied warranty of               |
+// | MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU        |
+// | General Public License for details.                                      |
+// |                                                                          |
+// | You should have received a copy of the GNU General Public License along  |
+// | with Tachyomancer.  If not, see <http://www.gnu.org/licenses/>.          |
+// +--------------------------------------------------------------------------+
+
+extern crate tachy;
+
+use std::time::Instant;
+use tachy::geom::{CoordsDelta, Direction, Orientation};
+use tachy::save::{ChipType, CircuitData, Puzzle, PuzzleSet, WireShape};
+use tachy::state::{EditGrid, EvalResult};
+
+//===========================================================================//
+
+// The size of the synthetic workload used by `evaluator_throughput`.  These
+// are fixed so that steps-per-second measurements stay comparable over time.
+const BENCHMARK_ROWS: i32 = 64;
+const BENCHMARK_COLUMNS: i32 = 64;
+const BENCHMARK_TIME_STEPS: u32 = 20_000;
+const BENCHMARK_RESTARTS: u32 = 100;
+
+//===========================================================================//
+
+#[test]
+fn stress_circuit_evaluates_without_errors() {
+    let data = stress_circuit(4, 8);
+    assert_eq!(data.chips.iter().count(), 32);
+    run_stress_circuit(&data, 100);
+}
+
+/// Measures evaluator throughput on a large circuit of chained chips.  This
+/// is too slow to run by default; use
+/// `cargo test --release --test benchmark -- --ignored --nocapture`.
+#[test]
+#[ignore]
+fn evaluator_throughput() {
+    let data = stress_circuit(BENCHMARK_ROWS, BENCHMARK_COLUMNS);
+    let start = Instant::now();
+    run_stress_circuit(&data, BENCHMARK_TIME_STEPS);
+    let seconds = start.elapsed().as_secs_f64();
+    println!(
+        "Evaluated {} chips for {} time steps in {:.3}s ({:.1} steps/sec)",
+        BENCHMARK_ROWS * BENCHMARK_COLUMNS,
+        BENCHMARK_TIME_STEPS,
+        seconds,
+        (BENCHMARK_TIME_STEPS as f64) / seconds
+    );
+}
+
+/// Measures how long it takes to start evaluating a large circuit for the
+/// first time, versus restarting it after the analysis has been cached.  Run
+/// this the same way as `evaluator_throughput`.
+#[test]
+#[ignore]
+fn evaluation_restart_time() {
+    let data = stress_circuit(BENCHMARK_ROWS, BENCHMARK_COLUMNS);
+    let mut grid = EditGrid::from_circuit_data(
+        Puzzle::SandboxBehavior,
+        &PuzzleSet::with_everything_solved(),
+        &data,
+    );
+    assert!(grid.errors().is_empty());
+    let start = Instant::now();
+    assert!(grid.start_eval());
+    let first_seconds = start.elapsed().as_secs_f64();
+    grid.stop_eval();
+    let start = Instant::now();
+    for _ in 0..BENCHMARK_RESTARTS {
+        assert!(grid.start_eval());
+        grid.stop_eval();
+    }
+    let restart_seconds =
+        start.elapsed().as_secs_f64() / (BENCHMARK_RESTARTS as f64);
+    println!(
+        "Started {} chips in {:.3}ms, then restarted in {:.3}ms ({:.1}x)",
+        BENCHMARK_ROWS * BENCHMARK_COLUMNS,
+        first_seconds * 1000.0,
+        restart_seconds * 1000.0,
+        first_seconds / restart_seconds
+    );
+}
+
+//===========================================================================//
+
+/// Builds a sandbox circuit made of `rows` independent chains, each of which
+/// is `columns` NOT chips long, with each chip's output wired directly into
+/// the next chip's input.
+fn stress_circuit(rows: i32, columns: i32) -> CircuitData {
+    let mut data = CircuitData::new(columns + 2, rows + 2);
+    for row in 0..rows {
+        for col in 0..columns {
+            let delta = CoordsDelta::new(col + 1, row + 1);
+            data.chips.insert(delta, ChipType::Not, Orientation::default());
+            if col + 1 < columns {
+                data.wires.insert(delta, Direction::East, WireShape::Stub);
+                let next = CoordsDelta::new(col + 2, row + 1);
+                data.wires.insert(next, Direction::West, WireShape::Stub);
+            }
+        }
+    }
+    data
+}
+
+fn run_stress_circuit(data: &CircuitData, time_steps: u32) {
+    let mut grid = EditGrid::from_circuit_data(
+        Puzzle::SandboxBehavior,
+        &PuzzleSet::with_everything_solved(),
+        data,
+    );
+    assert!(grid.errors().is_empty());
+    assert!(grid.start_eval());
+    let eval = grid.eval_mut().unwrap();
+    while eval.time_step() < time_steps {
+        match eval.step_time() {
+            EvalResult::Continue | EvalResult::Breakpoint(_) => {}
+            result => panic!("Unexpected result: {:?}", result),
+        }
+    }
+    assert!(eval.errors().is_empty());
+}
+
+//===========================================================================//
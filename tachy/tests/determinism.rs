@@ -0,0 +1,124 @@
+// +--------------------------------------------------------------------------+
+// | Copyright 2018 Matthew D. Steele <mdsteele@alum.mit.edu>                 |
+// |                                                                          |
+// | This file is part of Tachyomancer.                                       |
+// |                                                                          |
+// | Tachyomancer is free software: you can redistribute it and/or modify it  |
+// | under the terms of the GNU General Public License as published by the    |
+// | Free Software Foundation, either version 3 of the License, or (at your   |
+// | option) any later version.                                               |
+// |                                                                          |
+// | Tachyomancer is distributed in the hope that it will be useful, but      |
+// | WITHOUT ANY WARRANTY; without even the implied warranty of               |
+// | MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU        |
+// | General Public License for details.                                      |
+// |                                                                          |
+// | You should have received a copy of the GNU General Public License along  |
+// | with Tachyomancer.  If not, see <http://www.gnu.org/licenses/>.          |
+// +--------------------------------------------------------------------------+
+
+extern crate tachy;
+
+use std::collections::HashMap;
+use std::fs;
+use tachy::geom::{Coords, Fixed};
+use tachy::save::{PuzzleSet, SolutionData};
+use tachy::state::{EditGrid, EvalResult, WireId};
+
+//===========================================================================//
+
+/// The state of every wire in a circuit at the end of one cycle, as (value,
+/// event, analog) triples in wire ID order.
+type WireSnapshot = Vec<(u32, Option<u32>, Fixed)>;
+
+//===========================================================================//
+
+/// Runs every golden solution twice and checks that both runs produce
+/// exactly the same sequence of wire states, to catch any nondeterminism in
+/// circuit evaluation (e.g. from HashMap iteration order).  Puzzles that use
+/// randomness seed it with a fixed per-puzzle seed, so two runs of the same
+/// solution should always be identical.
+#[test]
+fn golden_solutions_evaluate_deterministically() {
+    for entry in fs::read_dir("tests/solutions").unwrap() {
+        let path = entry.unwrap().path();
+        let data = SolutionData::load(&path).unwrap();
+        let first = run_with_snapshots(&data);
+        assert!(!first.is_empty(), "No cycles were run for {:?}", path);
+        let second = run_with_snapshots(&data);
+        assert_eq!(
+            first.len(),
+            second.len(),
+            "Run length differs: {:?}",
+            path
+        );
+        for (index, (snap1, snap2)) in
+            first.iter().zip(second.iter()).enumerate()
+        {
+            assert_eq!(
+                snap1, snap2,
+                "Runs of {:?} diverged at cycle #{}",
+                path, index
+            );
+        }
+    }
+}
+
+//===========================================================================//
+
+/// Evaluates a solution (replaying its recorded inputs), recording the
+/// result of each cycle along with a snapshot of all wires afterwards.
+fn run_with_snapshots(data: &SolutionData) -> Vec<(String, WireSnapshot)> {
+    let mut grid = EditGrid::from_circuit_data(
+        data.puzzle,
+        &PuzzleSet::with_everything_solved(),
+        &data.circuit,
+    );
+    let wire_ids: Vec<WireId> = grid.wires().map(|wire| wire.id).collect();
+    let origin = grid.bounds().top_left();
+    let mut all_inputs = HashMap::<(u32, u32), Vec<(Coords, u32, u32)>>::new();
+    if let Some(ref inputs) = data.inputs {
+        for (time_step, cycle, delta, subloc, count) in inputs.iter() {
+            all_inputs.entry((time_step, cycle)).or_default().push((
+                origin + delta,
+                subloc,
+                count,
+            ));
+        }
+    }
+    assert!(grid.start_eval(), "Solution has errors: {:?}", data.puzzle);
+    let eval = grid.eval_mut().unwrap();
+    let mut snapshots = Vec::new();
+    loop {
+        let time_step = eval.time_step();
+        if let Some(inputs) = all_inputs.remove(&(time_step, eval.cycle())) {
+            for (coords, subloc, count) in inputs {
+                eval.press_button(coords, subloc, count);
+            }
+        }
+        let result = eval.step_cycle();
+        let done = match result {
+            EvalResult::Continue | EvalResult::Breakpoint(_) => {
+                time_step > data.time_steps
+            }
+            EvalResult::Failure | EvalResult::Victory(_) => true,
+        };
+        let snapshot = wire_ids
+            .iter()
+            .map(|&id| {
+                (
+                    eval.wire_value(id),
+                    eval.wire_event(id),
+                    eval.wire_analog(id),
+                )
+            })
+            .collect();
+        snapshots.push((format!("{:?}", result), snapshot));
+        if done {
+            break;
+        }
+    }
+    snapshots
+}
+
+//===========================================================================//
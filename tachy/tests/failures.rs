@@ -19,11 +19,25 @@
 
 extern crate tachy;
 
-use tachy::save::SolutionData;
-use tachy::state::verify_solution;
+use tachy::save::{PuzzleSet, SolutionData};
+use tachy::state::{
+    verify_solution, EditGrid, EvalResult, FabricationEval,
+    FabricationMismatch,
+};
 
 //===========================================================================//
 
+#[test]
+fn combinational_loop() {
+    let actual = test_failure("combinational_loop");
+    let expected = vec![
+        "Wires [0] form a combinational loop through ports \
+         (1, 0) West, (1, 0) East",
+        "Circuit had errors",
+    ];
+    assert_eq!(actual, expected);
+}
+
 #[test]
 fn cryocycler_already_thawed() {
     let actual = test_failure("cryocycler_already_thawed");
@@ -73,6 +87,37 @@ fn fab_behavior_wrong() {
     assert_eq!(actual, expected);
 }
 
+#[test]
+fn fab_behavior_wrong_first_mismatch() {
+    let data =
+        SolutionData::load("tests/failures/fab_behavior_wrong.toml").unwrap();
+    let mut grid = EditGrid::from_circuit_data(
+        data.puzzle,
+        &PuzzleSet::with_everything_solved(),
+        &data.circuit,
+    );
+    assert!(grid.start_eval());
+    let eval = grid.eval_mut().unwrap();
+    loop {
+        match eval.step_cycle() {
+            EvalResult::Continue | EvalResult::Breakpoint(_) => {}
+            EvalResult::Failure => break,
+            EvalResult::Victory(_) => panic!("unexpected victory"),
+        }
+    }
+    let fab_eval = eval.try_puzzle_eval::<FabricationEval>().unwrap();
+    let expected = FabricationMismatch {
+        time_step: 3,
+        column: 2,
+        port_name: "Out",
+        expected: 0,
+        actual: 1,
+        message: "Expected value of 0 for Out, but got value of 1."
+            .to_string(),
+    };
+    assert_eq!(fab_eval.first_mismatch(), Some(&expected));
+}
+
 #[test]
 fn fuel_synth_intakes_open_while_mixing() {
     let actual = test_failure("fuel_synth_intakes_open_while_mixing");
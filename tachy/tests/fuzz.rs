@@ -0,0 +1,155 @@
+// +--------------------------------------------------------------------------+
+// | Copyright 2018 Matthew D. Steele <mdsteele@alum.mit.edu>                 |
+// |                                                                          |
+// | This file is part of Tachyomancer.                                       |
+// |                                                                          |
+// | Tachyomancer is free software: you can redistribute it and/or modify it  |
+// | under the terms of the GNU General Public License as published by the    |
+// | Free Software Foundation, either version 3 of the License, or (at your   |
+// | option) any later version.                                               |
+// |                                                                          |
+// | Tachyomancer is distributed in the hope that it will be useful, but      |
+// | WITHOUT ANY WARRANTY; without even the implied warranty of               |
+// | MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU        |
+// | General Public License for details.                                      |
+// |                                                                          |
+// | You should have received a copy of the GNU General Public License along  |
+// | with Tachyomancer.  If not, see <http://www.gnu.org/licenses/>.          |
+// +--------------------------------------------------------------------------+
+
+extern crate rand;
+extern crate tachy;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::fs;
+use tachy::save::SolutionData;
+
+//===========================================================================//
+
+// How many mutated inputs to try for each seed solution.  This is kept small
+// enough that the tests stay fast; bump it up locally when hunting for bugs.
+const NUM_MUTATIONS: usize = 200;
+
+// Fragments that are likely to exercise interesting parsing paths when
+// spliced into an otherwise-valid solution file.
+const DICTIONARY: &[&str] = &[
+    "\n",
+    "=",
+    "\"",
+    "[",
+    "]",
+    ",",
+    "_",
+    "-",
+    "m",
+    "p",
+    "t",
+    "0",
+    "-1",
+    "4294967295",
+    "4294967296",
+    "2147483647",
+    "2147483648",
+    "-2147483648",
+    "18446744073709551615",
+    "1e999",
+    "nan",
+    "[circuit]",
+    "[circuit.chips]",
+    "[circuit.wires]",
+    "[inputs]",
+    "size = [",
+    "puzzle = ",
+    "score = ",
+    "f0-And",
+    "t99999_999_",
+    "m2147483648p0",
+    "p2147483647m2147483647",
+    "\u{e9}",
+    "\u{1f4a5}",
+];
+
+//===========================================================================//
+
+#[test]
+fn deserialize_extreme_sizes() {
+    let toml = |size: &str| {
+        format!(
+            "puzzle = \"TutorialOr\"\n\
+             score = 14\n\
+             time_steps = 4\n\n\
+             [circuit]\n\
+             size = {}\n\n\
+             [circuit.chips]\n\n\
+             [circuit.wires]\n",
+            size
+        )
+    };
+    for size in &["[2147483647, 2147483647]", "[-2147483648, -2147483648]"] {
+        assert!(SolutionData::deserialize_from_string(&toml(size)).is_ok());
+    }
+    for size in &["[2147483648, 1]", "[1, -2147483649]", "[1]", "[1, 2, 3]"] {
+        assert!(SolutionData::deserialize_from_string(&toml(size)).is_err());
+    }
+}
+
+#[test]
+fn deserialize_random_strings() {
+    let mut rng = StdRng::seed_from_u64(0x5010_7104);
+    for _ in 0..(10 * NUM_MUTATIONS) {
+        let len = rng.gen_range(0, 200);
+        let bytes: Vec<u8> = (0..len).map(|_| rng.gen()).collect();
+        try_deserialize(&bytes);
+    }
+}
+
+#[test]
+fn deserialize_mutated_solutions() {
+    let mut rng = StdRng::seed_from_u64(0xf022_ed01);
+    for entry in fs::read_dir("tests/solutions").unwrap() {
+        let original = fs::read(entry.unwrap().path()).unwrap();
+        try_deserialize(&original);
+        for _ in 0..NUM_MUTATIONS {
+            let mut bytes = original.clone();
+            for _ in 0..rng.gen_range(1, 8) {
+                mutate(&mut rng, &mut bytes);
+            }
+            try_deserialize(&bytes);
+        }
+    }
+}
+
+//===========================================================================//
+
+fn mutate<R: Rng>(rng: &mut R, bytes: &mut Vec<u8>) {
+    let index = rng.gen_range(0, bytes.len() + 1);
+    match rng.gen_range(0, 4) {
+        0 if index < bytes.len() => {
+            bytes[index] = rng.gen();
+        }
+        1 if index < bytes.len() => {
+            let end = rng.gen_range(index, bytes.len() + 1);
+            bytes.drain(index..end);
+        }
+        2 => {
+            let len = bytes.len();
+            let start = rng.gen_range(0, len + 1);
+            let end = rng.gen_range(start, len + 1);
+            let chunk: Vec<u8> = bytes[start..end].to_vec();
+            bytes.splice(index..index, chunk);
+        }
+        _ => {
+            let word = DICTIONARY[rng.gen_range(0, DICTIONARY.len())];
+            bytes.splice(index..index, word.bytes());
+        }
+    }
+}
+
+fn try_deserialize(bytes: &[u8]) {
+    let string = String::from_utf8_lossy(bytes);
+    // We only care that this doesn't panic; errors are expected.
+    let _ = SolutionData::deserialize_from_string(&string);
+}
+
+//===========================================================================//
@@ -0,0 +1,110 @@
+// +--------------------------------------------------------------------------+
+// | Copyright 2018 Matthew D. Steele <mdsteele@alum.mit.edu>                 |
+// |                                                                          |
+// | This file is part of Tachyomancer.                                       |
+// |                                                                          |
+// | Tachyomancer is free software: you can redistribute it and/or modify it  |
+// | under the terms of the GNU General Public License as published by the    |
+// | Free Software Foundation, either version 3 of the License, or (at your   |
+// | option) any later version.                                               |
+// |                                                                          |
+// | Tachyomancer is distributed in the hope that it will be useful, but      |
+// | WITHOUT ANY WARRANTY; without even the implied warranty of               |
+// | MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU        |
+// | General Public License for details.                                      |
+// |                                                                          |
+// | You should have received a copy of the GNU General Public License along  |
+// | with Tachyomancer.  If not, see <http://www.gnu.org/licenses/>.          |
+// +--------------------------------------------------------------------------+
+
+extern crate tachy;
+
+use tachy::geom::{CoordsDelta, Direction};
+use tachy::save::{CircuitData, Puzzle, ReferenceSpec, WireShape};
+use tachy::state::{verify_against_reference, ReferenceMismatch};
+
+//===========================================================================//
+
+/// Returns a SandboxBehavior circuit with a labeled wire connected directly
+/// to the input interface's A port.
+fn pass_through_circuit(label: &str) -> CircuitData {
+    // The input interface is on the east side of the board, with port A in
+    // the top row.
+    let mut data = CircuitData::new(4, 3);
+    data.wires.insert(
+        CoordsDelta::new(3, 0),
+        Direction::East,
+        WireShape::Stub,
+    );
+    data.labels.insert(
+        CoordsDelta::new(3, 0),
+        Direction::East,
+        label.to_string(),
+    );
+    data
+}
+
+fn spec(output: &str, table: Vec<[u32; 3]>) -> ReferenceSpec {
+    ReferenceSpec { output: output.to_string(), table }
+}
+
+//===========================================================================//
+
+#[test]
+fn matching_circuit_has_no_mismatches() {
+    let circuit = pass_through_circuit("Out");
+    let table = vec![[1, 7, 1], [5, 0, 5], [200, 9, 200]];
+    let result = verify_against_reference(
+        Puzzle::SandboxBehavior,
+        &circuit,
+        &spec("Out", table),
+    );
+    assert_eq!(result, Ok(vec![]));
+}
+
+#[test]
+fn mismatched_rows_are_reported() {
+    let circuit = pass_through_circuit("Out");
+    // The circuit outputs A, but this reference expects B.
+    let table = vec![[3, 4, 4]];
+    let mismatches = verify_against_reference(
+        Puzzle::SandboxBehavior,
+        &circuit,
+        &spec("Out", table),
+    )
+    .unwrap();
+    assert!(!mismatches.is_empty());
+    assert_eq!(
+        mismatches[0],
+        ReferenceMismatch {
+            time_step: 0,
+            inputs: (3, 4),
+            expected: 4,
+            actual: 3,
+        }
+    );
+}
+
+#[test]
+fn missing_output_label_is_an_error() {
+    let circuit = pass_through_circuit("Out");
+    let result = verify_against_reference(
+        Puzzle::SandboxBehavior,
+        &circuit,
+        &spec("Sum", vec![[1, 2, 3]]),
+    );
+    assert!(result.is_err());
+}
+
+#[test]
+fn non_sandbox_puzzle_is_an_error() {
+    let circuit = pass_through_circuit("Out");
+    let result = verify_against_reference(
+        Puzzle::TutorialOr,
+        &circuit,
+        &spec("Out", vec![[1, 2, 3]]),
+    );
+    assert!(result.is_err());
+}
+
+//===========================================================================//
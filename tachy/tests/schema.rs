@@ -0,0 +1,194 @@
+// +--------------------------------------------------------------------------+
+// | Copyright 2018 Matthew D. Steele <mdsteele@alum.mit.edu>                 |
+// |                                                                          |
+// | This file is part of Tachyomancer.                                       |
+// |                                                                          |
+// | Tachyomancer is free software: you can redistribute it and/or modify it  |
+// | under the terms of the GNU General Public License as published by the    |
+// | Free Software Foundation, either version 3 of the License, or (at your   |
+// | option) any later version.                                               |
+// |                                                                          |
+// | Tachyomancer is distributed in the hope that it will be useful, but      |
+// | WITHOUT ANY WARRANTY; without even the implied warranty of               |
+// | MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU        |
+// | General Public License for details.                                      |
+// |                                                                          |
+// | You should have received a copy of the GNU General Public License along  |
+// | with Tachyomancer.  If not, see <http://www.gnu.org/licenses/>.          |
+// +--------------------------------------------------------------------------+
+
+//! Checks the serialized shape of `SolutionData` (and the `CircuitData`
+//! within it) against a committed JSON-schema-like snapshot, so that
+//! unintended changes to the file format get caught.  The snapshot can be
+//! used by third-party tools that read or write solution files.  To update it
+//! after an intentional format change, run:
+//!
+//!     TACHY_UPDATE_SCHEMA=1 cargo test -p tachy --test schema
+
+extern crate tachy;
+extern crate toml;
+
+use std::collections::BTreeSet;
+use std::fs;
+use tachy::geom::{CoordsDelta, Direction, Orientation};
+use tachy::save::{
+    ChipType, CircuitData, InputsData, Puzzle, SolutionData, WireShape,
+};
+use toml::Value;
+
+//===========================================================================//
+
+const SNAPSHOT_PATH: &str = "tests/schema/solution.json";
+
+// Tables whose keys are data (e.g. encoded coordinates) rather than field
+// names, along with a description of their keys:
+const MAP_TABLES: &[(&str, &str)] = &[
+    ("circuit.chips", "chip position, e.g. \"p1m2\" for (1, -2)"),
+    ("circuit.labels", "wire fragment, e.g. \"p1m2e\" for the east side"),
+    ("circuit.wires", "wire fragment, e.g. \"p1m2e\" for the east side"),
+    ("inputs", "time step, cycle, position, and sublocation of a press"),
+];
+
+//===========================================================================//
+
+#[test]
+fn solution_schema_matches_snapshot() {
+    let schema = solution_schema();
+    if std::env::var_os("TACHY_UPDATE_SCHEMA").is_some() {
+        fs::write(SNAPSHOT_PATH, &schema).unwrap();
+        return;
+    }
+    let snapshot = fs::read_to_string(SNAPSHOT_PATH).unwrap();
+    assert!(
+        snapshot == schema,
+        "SolutionData format has changed; expected:\n{}\nactual:\n{}",
+        snapshot,
+        schema
+    );
+}
+
+//===========================================================================//
+
+/// Generates the schema by serializing two sample solutions: one with every
+/// optional field present, and one with none of them.  Fields that only
+/// appear in the former are considered optional.
+fn solution_schema() -> String {
+    let full = to_value(&full_solution());
+    let minimal = to_value(&minimal_solution());
+    let mut out = String::new();
+    write_schema(&mut out, 0, "", &full, Some(&minimal));
+    out.push('\n');
+    out
+}
+
+fn minimal_solution() -> SolutionData {
+    SolutionData {
+        install_id: None,
+        puzzle: Puzzle::TutorialOr,
+        score: 12,
+        time_steps: 4,
+        circuit: CircuitData::new(3, 3),
+        inputs: None,
+    }
+}
+
+fn full_solution() -> SolutionData {
+    let mut circuit = CircuitData::new(3, 3);
+    let delta = CoordsDelta::new(1, -2);
+    circuit.chips.insert(delta, ChipType::Not, Orientation::default());
+    circuit.wires.insert(delta, Direction::East, WireShape::Stub);
+    circuit.labels.insert(delta, Direction::East, "foo".to_string());
+    let mut inputs = InputsData::new();
+    inputs.insert(1, 2, delta, 0, 1);
+    SolutionData {
+        install_id: Some(1234),
+        inputs: Some(inputs),
+        circuit,
+        ..minimal_solution()
+    }
+}
+
+fn to_value(solution: &SolutionData) -> Value {
+    let string = solution.serialize_to_string().unwrap();
+    string.parse::<Value>().unwrap()
+}
+
+fn write_schema(
+    out: &mut String,
+    indent: usize,
+    path: &str,
+    value: &Value,
+    minimal: Option<&Value>,
+) {
+    let pad = "  ".repeat(indent);
+    match value {
+        Value::String(_) => out.push_str("{\"type\": \"string\"}"),
+        Value::Integer(_) => out.push_str("{\"type\": \"integer\"}"),
+        Value::Float(_) => out.push_str("{\"type\": \"number\"}"),
+        Value::Boolean(_) => out.push_str("{\"type\": \"boolean\"}"),
+        Value::Datetime(_) => out.push_str("{\"type\": \"datetime\"}"),
+        Value::Array(items) => {
+            out.push_str("{\n");
+            out.push_str(&format!("{}  \"type\": \"array\",\n", pad));
+            out.push_str(&format!("{}  \"items\": ", pad));
+            match items.first() {
+                Some(item) => {
+                    write_schema(out, indent + 1, path, item, None);
+                }
+                None => out.push_str("{}"),
+            }
+            out.push_str(&format!("\n{}}}", pad));
+        }
+        Value::Table(table) => {
+            out.push_str("{\n");
+            out.push_str(&format!("{}  \"type\": \"object\",\n", pad));
+            if let Some(&(_, keys)) =
+                MAP_TABLES.iter().find(|&&(map_path, _)| map_path == path)
+            {
+                out.push_str(&format!("{}  \"keys\": {:?},\n", pad, keys));
+                out.push_str(&format!("{}  \"values\": ", pad));
+                let first = table.values().next().unwrap();
+                write_schema(out, indent + 1, path, first, None);
+                out.push_str(&format!("\n{}}}", pad));
+                return;
+            }
+            let required: BTreeSet<&String> = match minimal {
+                Some(Value::Table(minimal)) => minimal.keys().collect(),
+                _ => table.keys().collect(),
+            };
+            let required: Vec<String> =
+                required.iter().map(|key| format!("{:?}", key)).collect();
+            out.push_str(&format!(
+                "{}  \"required\": [{}],\n",
+                pad,
+                required.join(", ")
+            ));
+            out.push_str(&format!("{}  \"properties\": {{", pad));
+            for (index, (key, field)) in table.iter().enumerate() {
+                if index > 0 {
+                    out.push(',');
+                }
+                out.push_str(&format!("\n{}    {:?}: ", pad, key));
+                let field_path = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", path, key)
+                };
+                let minimal_field = match minimal {
+                    Some(Value::Table(minimal)) => minimal.get(key),
+                    _ => None,
+                };
+                write_schema(
+                    out,
+                    indent + 2,
+                    &field_path,
+                    field,
+                    minimal_field,
+                );
+            }
+            out.push_str(&format!("\n{}  }}\n{}}}", pad, pad));
+        }
+    }
+}
+
+//===========================================================================//
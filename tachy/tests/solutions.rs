@@ -19,8 +19,16 @@
 
 extern crate tachy;
 
-use tachy::save::SolutionData;
-use tachy::state::verify_solution;
+use std::collections::HashSet;
+use std::fs;
+use tachy::save::{
+    decode_share_code, encode_share_code, Puzzle, PuzzleKind, PuzzleSet,
+    ScoreUnits, SolutionData,
+};
+use tachy::state::{
+    check_shared_circuit, replay_solution_to_time_step, solve_circuit,
+    verify_circuit, verify_solution, wire_length_score, EditGrid, EvalResult,
+};
 
 //===========================================================================//
 
@@ -269,6 +277,150 @@ fn tutorial_sum() {
     test_solution("tutorial_sum");
 }
 
+#[test]
+fn verify_circuit_without_inputs() {
+    let path = "tests/solutions/automate_collector.toml";
+    let data = SolutionData::load(path).unwrap();
+    let solved = PuzzleSet::with_everything_solved();
+    assert_eq!(
+        verify_circuit(data.puzzle, &solved, &data.circuit, 1000),
+        Ok(data.score)
+    );
+    assert!(verify_circuit(data.puzzle, &solved, &data.circuit, 10).is_err());
+}
+
+#[test]
+fn solve_circuit_without_inputs() {
+    let path = "tests/solutions/automate_collector.toml";
+    let data = SolutionData::load(path).unwrap();
+    let solved = PuzzleSet::with_everything_solved();
+    let solution =
+        solve_circuit(data.puzzle, &solved, &data.circuit, 1000, Some(17))
+            .unwrap();
+    assert_eq!(solution.install_id, Some(17));
+    assert_eq!(solution.score, data.score);
+    assert_eq!(solution.time_steps, data.time_steps);
+    assert!(solution.inputs.is_none());
+    assert_eq!(verify_solution(&solution), Vec::<String>::new());
+}
+
+#[test]
+fn wire_length_score_for_known_circuit() {
+    let path = "tests/solutions/tutorial_or.toml";
+    let data = SolutionData::load(path).unwrap();
+    assert_eq!(wire_length_score(&data.circuit), 12);
+}
+
+#[test]
+fn wire_length_score_matches_solution_scores() {
+    for entry in fs::read_dir("tests/solutions").unwrap() {
+        let path = entry.unwrap().path();
+        let data = SolutionData::load(&path).unwrap();
+        if data.puzzle.score_units() == ScoreUnits::WireLength {
+            assert_eq!(
+                wire_length_score(&data.circuit),
+                data.score,
+                "Wrong wire length for {:?}",
+                path
+            );
+        }
+    }
+}
+
+#[test]
+fn replay_solution_to_middle_time_step() {
+    let path = "tests/solutions/command_lander_manual.toml";
+    let data = SolutionData::load(path).unwrap();
+    assert!(data.inputs.is_some());
+    for &time_step in &[60, 10, 0] {
+        let (grid, result) =
+            replay_solution_to_time_step(&data, time_step).unwrap();
+        assert!(matches!(result, EvalResult::Continue), "{:?}", result);
+        let eval = grid.eval().unwrap();
+        assert_eq!(eval.time_step(), time_step);
+        assert_eq!(eval.cycle(), 0);
+    }
+}
+
+#[test]
+fn replay_solution_past_end_stops_at_victory() {
+    let path = "tests/solutions/command_lander_manual.toml";
+    let data = SolutionData::load(path).unwrap();
+    let (grid, result) =
+        replay_solution_to_time_step(&data, data.time_steps + 100).unwrap();
+    match result {
+        EvalResult::Victory(score) => assert_eq!(score, data.score),
+        result => panic!("Replay ended with {:?}", result),
+    }
+    let eval = grid.eval().unwrap();
+    assert!(eval.time_step() <= data.time_steps);
+    assert!(eval.errors().is_empty());
+}
+
+#[test]
+fn share_code_round_trip_preserves_solution() {
+    let path = "tests/solutions/automate_collector.toml";
+    let data = SolutionData::load(path).unwrap();
+    let code = encode_share_code(data.puzzle, &data.circuit).unwrap();
+    let (puzzle, circuit) = decode_share_code(&code).unwrap();
+    assert_eq!(puzzle, data.puzzle);
+    let solved = PuzzleSet::with_everything_solved();
+    assert_eq!(check_shared_circuit(puzzle, &solved, &circuit), Ok(()));
+    assert_eq!(
+        verify_circuit(puzzle, &solved, &circuit, 1000),
+        Ok(data.score)
+    );
+}
+
+#[test]
+fn check_shared_circuit_rejects_invalid_circuits() {
+    let path = "tests/solutions/automate_collector.toml";
+    let data = SolutionData::load(path).unwrap();
+    let solved = PuzzleSet::with_everything_solved();
+    // Not all chips in this circuit are allowed in the first tutorial.
+    let puzzle = Puzzle::TutorialOr;
+    assert_eq!(
+        check_shared_circuit(puzzle, &solved, &data.circuit),
+        Err("Discard chips are not allowed in this puzzle".to_string())
+    );
+    // The circuit is too small for the puzzle's interfaces.
+    let mut circuit = data.circuit.clone();
+    circuit.size.width = 1;
+    assert!(check_shared_circuit(data.puzzle, &solved, &circuit).is_err());
+}
+
+#[test]
+fn wire_length_score_matches_edit_grid_stats() {
+    let solved = PuzzleSet::with_everything_solved();
+    for entry in fs::read_dir("tests/solutions").unwrap() {
+        let path = entry.unwrap().path();
+        let data = SolutionData::load(&path).unwrap();
+        let grid =
+            EditGrid::from_circuit_data(data.puzzle, &solved, &data.circuit);
+        assert_eq!(
+            grid.stats().wire_length,
+            wire_length_score(&data.circuit) as usize,
+            "Mismatched wire length for {:?}",
+            path
+        );
+    }
+}
+
+#[test]
+fn tutorial_puzzles_have_solutions() {
+    let mut solved = HashSet::<Puzzle>::new();
+    for entry in fs::read_dir("tests/solutions").unwrap() {
+        let path = entry.unwrap().path();
+        let data = SolutionData::load(&path).unwrap();
+        solved.insert(data.puzzle);
+    }
+    let missing: Vec<Puzzle> = Puzzle::all()
+        .filter(|puzzle| puzzle.kind() == PuzzleKind::Tutorial)
+        .filter(|puzzle| !solved.contains(puzzle))
+        .collect();
+    assert!(missing.is_empty(), "No solutions for: {:?}", missing);
+}
+
 //===========================================================================//
 
 fn test_solution(name: &str) {
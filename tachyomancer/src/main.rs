@@ -45,20 +45,44 @@ mod mancer;
 
 use self::mancer::gui::{GuiContext, Window, WindowOptions};
 use self::mancer::mode::{self, ModeChange};
-use self::mancer::save::{Prefs, SaveDir};
+use self::mancer::save::{encode_name, Prefs, SaveDir};
 use self::mancer::state::GameState;
-use std::path::PathBuf;
+use self::mancer::view::AssetPreloader;
+use std::ffi::OsString;
+use std::fs;
+use std::path::{Path, PathBuf};
 use tachy::geom::RectSize;
+use tachy::save::{Puzzle, PuzzleKind};
+use tachy::state::{solve_circuit, verify_circuit, verify_solution};
 
 //===========================================================================//
 
 // TODO: Change this once we have a real score server.
 const DEFAULT_SCORE_SERVER_ADDR: &str = "http://127.0.0.1:8080";
 
+// How many time steps to run each circuit for with --verify_circuits before
+// giving up on it:
+const VERIFY_MAX_TIME_STEPS: u32 = 10_000;
+
 //===========================================================================//
 
 fn main() {
-    match run_game(&parse_flags()) {
+    let flags = parse_flags();
+    if let Some(ref export_dir) = flags.export_solutions {
+        if let Err(error) = export_solutions(&flags, export_dir) {
+            eprintln!("ERROR: {}", error);
+            std::process::exit(1);
+        }
+        return;
+    }
+    if let Some(ref profile_name) = flags.verify_circuits {
+        if let Err(error) = verify_circuits(&flags, profile_name) {
+            eprintln!("ERROR: {}", error);
+            std::process::exit(1);
+        }
+        return;
+    }
+    match run_game(&flags) {
         Ok(()) => {}
         Err(error) => {
             eprintln!("ERROR: {}", error);
@@ -92,11 +116,16 @@ fn main() {
 
 #[derive(Debug)]
 struct StartupFlags {
-    antialiasing: Option<bool>,
+    antialiasing: Option<u8>,
+    debug_console: bool,
+    export_solutions: Option<PathBuf>,
     fullscreen: Option<bool>,
+    gl_info: bool,
+    no_audio: bool,
     resolution: Option<RectSize<i32>>,
     save_dir: Option<PathBuf>,
     score_server_addr: Option<String>,
+    verify_circuits: Option<String>,
 }
 
 fn parse_flags() -> StartupFlags {
@@ -105,13 +134,37 @@ fn parse_flags() -> StartupFlags {
     opts.optflagopt(
         "",
         "antialiasing",
-        "override antialiasing setting",
-        "BOOL",
+        "override antialiasing level (0, 2, 4, or 8)",
+        "LEVEL",
+    );
+    opts.optflag(
+        "",
+        "debug_console",
+        "enable the debug console (toggle with backquote key)",
+    );
+    opts.optopt(
+        "",
+        "export_solutions",
+        "write solution files for all passing circuits in the current \
+         profile to a directory and exit",
+        "DIR",
     );
     opts.optflagopt("", "fullscreen", "override fullscreen setting", "BOOL");
+    opts.optflag(
+        "",
+        "gl_info",
+        "print OpenGL/SDL capabilities once the window has been created",
+    );
+    opts.optflag("", "no_audio", "run without any audio output");
     opts.optopt("", "resolution", "override window/screen resolution", "WxH");
     opts.optopt("", "save_dir", "override save dir path", "PATH");
     opts.optopt("", "score_server", "override score server address", "ADDR");
+    opts.optflagopt(
+        "",
+        "verify_circuits",
+        "check all saved circuits in a profile (default current) and exit",
+        "PROFILE",
+    );
 
     let args: Vec<String> = std::env::args().collect();
     let matches = opts.parse(&args[1..]).unwrap_or_else(|failure| {
@@ -124,12 +177,23 @@ fn parse_flags() -> StartupFlags {
         std::process::exit(0);
     }
 
-    let antialiasing = matches
-        .opt_default("antialiasing", "true")
-        .and_then(|value| value.parse().ok());
+    let antialiasing =
+        matches.opt_default("antialiasing", "4").and_then(|value| match value
+            .as_str()
+        {
+            "true" => Some(4),
+            "false" => Some(0),
+            _ => value.parse().ok(),
+        });
+    let debug_console =
+        cfg!(debug_assertions) || matches.opt_present("debug_console");
+    let export_solutions =
+        matches.opt_str("export_solutions").map(PathBuf::from);
     let fullscreen = matches
         .opt_default("fullscreen", "true")
         .and_then(|value| value.parse().ok());
+    let gl_info = matches.opt_present("gl_info");
+    let no_audio = matches.opt_present("no_audio");
     let resolution = matches.opt_str("resolution").and_then(|value| {
         let pieces: Vec<&str> = value.split('x').collect();
         if pieces.len() != 2 {
@@ -144,12 +208,18 @@ fn parse_flags() -> StartupFlags {
     });
     let save_dir = matches.opt_str("save_dir").map(PathBuf::from);
     let score_server_addr = matches.opt_str("score_server");
+    let verify_circuits = matches.opt_default("verify_circuits", "");
     StartupFlags {
         antialiasing,
+        debug_console,
+        export_solutions,
         fullscreen,
+        gl_info,
+        no_audio,
         resolution,
         save_dir,
         score_server_addr,
+        verify_circuits,
     }
 }
 
@@ -158,26 +228,178 @@ fn parse_flags() -> StartupFlags {
 fn run_game(flags: &StartupFlags) -> Result<(), String> {
     let mut save_dir = SaveDir::create_or_load(&flags.save_dir)?;
     let scores_dir = save_dir.create_or_load_global_scores()?;
+    let texture_pack_dir = save_dir.texture_pack_dir();
     let mut state = GameState::new(save_dir)?;
+    let server_addr = flags
+        .score_server_addr
+        .as_ref()
+        .map(String::as_str)
+        .unwrap_or(DEFAULT_SCORE_SERVER_ADDR);
     let mut gui_context = GuiContext::init(
         state.prefs().sound_volume_percent(),
         state.prefs().music_volume_percent(),
-        flags
-            .score_server_addr
-            .as_ref()
-            .map(String::as_str)
-            .unwrap_or(DEFAULT_SCORE_SERVER_ADDR),
+        server_addr,
         scores_dir,
+        texture_pack_dir,
+        flags.debug_console,
+        flags.no_audio,
     )?;
+    if state.prefs().check_for_updates() {
+        gui_context.start_update_check(server_addr);
+    }
     let mut window_options =
         Some(initial_window_options(flags, state.prefs())?);
+    let mut print_gl_info = flags.gl_info;
     while let Some(options) = window_options {
-        window_options = boot_window(&mut state, &mut gui_context, options)?;
+        window_options =
+            boot_window(&mut state, &mut gui_context, options, print_gl_info)?;
+        print_gl_info = false;
     }
     state.save()?;
     Ok(())
 }
 
+/// Runs every saved circuit in the given profile (or the current profile, if
+/// `profile_name` is empty) and prints a report of which ones fail.  Circuits
+/// for Command puzzles need player input to succeed, so they are reported as
+/// skipped rather than run.  This never modifies any saved circuits.
+fn verify_circuits(
+    flags: &StartupFlags,
+    profile_name: &str,
+) -> Result<(), String> {
+    let save_dir = SaveDir::create_or_load(&flags.save_dir)?;
+    let profile = if profile_name.is_empty() {
+        save_dir
+            .load_current_profile_if_any()?
+            .ok_or_else(|| "No current profile".to_string())?
+    } else {
+        save_dir.load_profile(profile_name)?
+    };
+    let solved = profile.solved_puzzles();
+    let mut num_circuits = 0;
+    let mut num_failures = 0;
+    let mut num_skipped = 0;
+    for puzzle in Puzzle::all() {
+        // Sandbox circuits never finish, so there's nothing to verify.
+        if puzzle.kind() == PuzzleKind::Sandbox {
+            continue;
+        }
+        for circuit_name in profile.circuit_names(puzzle) {
+            if puzzle.kind() == PuzzleKind::Command {
+                num_skipped += 1;
+                println!(
+                    "skip: {:?} {:?} (needs input)",
+                    puzzle, circuit_name
+                );
+                continue;
+            }
+            num_circuits += 1;
+            let result = profile
+                .load_circuit(puzzle, circuit_name)
+                .map_err(|error| vec![error.to_string()])
+                .and_then(|data| {
+                    verify_circuit(
+                        puzzle,
+                        &solved,
+                        &data,
+                        VERIFY_MAX_TIME_STEPS,
+                    )
+                });
+            match result {
+                Ok(score) => {
+                    println!(
+                        "ok:   {:?} {:?} (score {})",
+                        puzzle, circuit_name, score
+                    );
+                }
+                Err(errors) => {
+                    num_failures += 1;
+                    println!("FAIL: {:?} {:?}", puzzle, circuit_name);
+                    for error in errors {
+                        println!("        {}", error);
+                    }
+                }
+            }
+        }
+    }
+    println!(
+        "{} of {} circuit(s) in profile {:?} failed ({} skipped).",
+        num_failures,
+        num_circuits,
+        profile.name(),
+        num_skipped
+    );
+    Ok(())
+}
+
+/// Runs every saved circuit in the current profile, and writes a solution
+/// file into `export_dir` for each one that solves its puzzle.  Circuits that
+/// fail (or that need player input to succeed) are skipped.
+fn export_solutions(
+    flags: &StartupFlags,
+    export_dir: &Path,
+) -> Result<(), String> {
+    let save_dir = SaveDir::create_or_load(&flags.save_dir)?;
+    let install_id = save_dir.prefs().install_id();
+    let profile = save_dir
+        .load_current_profile_if_any()?
+        .ok_or_else(|| "No current profile".to_string())?;
+    fs::create_dir_all(export_dir).map_err(|err| {
+        format!("Could not create directory {:?}: {}", export_dir, err)
+    })?;
+    let solved = profile.solved_puzzles();
+    let mut num_exported = 0;
+    for puzzle in Puzzle::all() {
+        if puzzle.kind() == PuzzleKind::Sandbox {
+            continue;
+        }
+        for circuit_name in profile.circuit_names(puzzle) {
+            let result = profile
+                .load_circuit(puzzle, circuit_name)
+                .map_err(|error| vec![error.to_string()])
+                .and_then(|data| {
+                    solve_circuit(
+                        puzzle,
+                        &solved,
+                        &data,
+                        VERIFY_MAX_TIME_STEPS,
+                        install_id,
+                    )
+                })
+                .and_then(|solution| {
+                    let errors = verify_solution(&solution);
+                    if errors.is_empty() {
+                        Ok(solution)
+                    } else {
+                        Err(errors)
+                    }
+                });
+            let solution = match result {
+                Ok(solution) => solution,
+                Err(errors) => {
+                    println!("skip: {:?} {:?}", puzzle, circuit_name);
+                    for error in errors {
+                        println!("        {}", error);
+                    }
+                    continue;
+                }
+            };
+            let mut file_name = OsString::from(format!("{:?}_", puzzle));
+            file_name.push(encode_name(circuit_name));
+            file_name.push(".toml");
+            let path = export_dir.join(file_name);
+            let string = solution.serialize_to_string()?;
+            fs::write(&path, string).map_err(|err| {
+                format!("Could not write solution to {:?}: {}", path, err)
+            })?;
+            println!("ok:   {:?} {:?} -> {:?}", puzzle, circuit_name, path);
+            num_exported += 1;
+        }
+    }
+    println!("Exported {} solution(s) to {:?}.", num_exported, export_dir);
+    Ok(())
+}
+
 fn initial_window_options(
     flags: &StartupFlags,
     prefs: &Prefs,
@@ -195,10 +417,16 @@ fn boot_window(
     state: &mut GameState,
     gui_context: &mut GuiContext,
     window_options: WindowOptions,
+    print_gl_info: bool,
 ) -> Result<Option<WindowOptions>, String> {
-    let mut window = Window::create(gui_context, window_options)?;
+    let mut window =
+        Window::create(gui_context, window_options, state.screenshots_dir())?;
+    if print_gl_info {
+        print!("{}", window.gl_info());
+    }
+    let mut preloader = AssetPreloader::new();
     loop {
-        match mode::run_mode(state, &mut window) {
+        match mode::run_mode(state, &mut window, &mut preloader) {
             ModeChange::Next => continue,
             ModeChange::RebootWindow(new_options) => {
                 return Ok(Some(new_options))
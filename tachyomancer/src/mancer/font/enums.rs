@@ -49,6 +49,20 @@ const SEGMENT7_PNG_DATA: &[u8] =
 const SEGMENT7_METRICS: (u32, u32, u32, u32) =
     include!(concat!(env!("OUT_DIR"), "/font/segment7_64_metrics.rs"));
 
+// The glyph drawn in place of a character that the font textures don't
+// include:
+const FALLBACK_GLYPH: u8 = b'?';
+
+// Ranges of combining diacritical marks, which are not drawn and take up no
+// width:
+const COMBINING_MARK_RANGES: &[(char, char)] = &[
+    ('\u{0300}', '\u{036F}'),
+    ('\u{1AB0}', '\u{1AFF}'),
+    ('\u{1DC0}', '\u{1DFF}'),
+    ('\u{20D0}', '\u{20FF}'),
+    ('\u{FE20}', '\u{FE2F}'),
+];
+
 //===========================================================================//
 
 #[derive(Clone, Copy)]
@@ -89,7 +103,7 @@ impl Font {
         height: f32,
         text: &str,
     ) -> f32 {
-        ratio * height * (text.chars().count() as f32)
+        ratio * height * (text_to_glyphs(text).count() as f32)
     }
 
     pub(super) fn png_name_and_data(self) -> (&'static str, &'static [u8]) {
@@ -123,9 +137,43 @@ impl Font {
 
 //===========================================================================//
 
+/// Returns the font texture glyph index to use for drawing the given
+/// character, or `None` if the character should not be drawn at all (e.g.
+/// because it is a combining mark).  Characters outside the range covered
+/// by the font textures are drawn as a fallback glyph.
+pub fn char_to_glyph(chr: char) -> Option<u8> {
+    if is_combining_mark(chr) {
+        None
+    } else if (chr as u32) < 256 {
+        Some(chr as u8)
+    } else {
+        Some(FALLBACK_GLYPH)
+    }
+}
+
+/// Returns the sequence of font texture glyph indices to use for drawing the
+/// given text.
+pub fn text_to_glyphs(text: &str) -> impl Iterator<Item = u8> + '_ {
+    text.chars().filter_map(char_to_glyph)
+}
+
+/// Returns true if any character in the text can't be drawn faithfully by
+/// the fonts (and will instead be dropped or drawn as a fallback glyph).
+pub fn has_unsupported_glyphs(text: &str) -> bool {
+    text.chars().any(|chr| (chr as u32) >= 256)
+}
+
+fn is_combining_mark(chr: char) -> bool {
+    COMBINING_MARK_RANGES
+        .iter()
+        .any(|&(start, end)| start <= chr && chr <= end)
+}
+
+//===========================================================================//
+
 #[cfg(test)]
 mod tests {
-    use super::Font;
+    use super::{has_unsupported_glyphs, text_to_glyphs, Font};
     use std::str::FromStr;
 
     #[test]
@@ -134,6 +182,29 @@ mod tests {
             assert_eq!(Font::from_str(&format!("{:?}", font)), Ok(font));
         }
     }
+
+    #[test]
+    fn glyphs_for_latin1_text() {
+        let glyphs: Vec<u8> = text_to_glyphs("Caf\u{e9}!").collect();
+        assert_eq!(glyphs, vec![b'C', b'a', b'f', 0xe9, b'!']);
+        assert!(!has_unsupported_glyphs("Caf\u{e9}!"));
+        let width = Font::str_width_for_ratio(0.5, 10.0, "Caf\u{e9}!");
+        assert_eq!(width, 25.0);
+    }
+
+    #[test]
+    fn glyphs_for_unsupported_text() {
+        // A character outside the Latin-1 range should be drawn as a
+        // fallback glyph, rather than truncated to some other character:
+        let glyphs: Vec<u8> = text_to_glyphs("\u{141}\u{3b1}").collect();
+        assert_eq!(glyphs, vec![b'?', b'?']);
+        assert!(has_unsupported_glyphs("\u{3b1}"));
+        // Combining marks should take up no width:
+        let text = "e\u{301}";
+        assert_eq!(text_to_glyphs(text).collect::<Vec<u8>>(), vec![b'e']);
+        assert_eq!(Font::str_width_for_ratio(0.5, 10.0, text), 5.0);
+        assert!(has_unsupported_glyphs(text));
+    }
 }
 
 //===========================================================================//
@@ -19,7 +19,9 @@
 
 mod enums;
 
-pub use self::enums::{Align, Font};
+pub use self::enums::{
+    char_to_glyph, has_unsupported_glyphs, text_to_glyphs, Align, Font,
+};
 use crate::mancer::gl::{
     Primitive, Shader, ShaderProgram, ShaderSampler, ShaderType,
     ShaderUniform, Texture2D, VertexArray, VertexBuffer,
@@ -138,7 +140,7 @@ impl FontData {
         slant: f32,
         text: &str,
     ) {
-        let chars: Vec<u8> = text.chars().map(|chr| chr as u8).collect();
+        let chars: Vec<u8> = text_to_glyphs(text).collect();
         self.draw_chars(matrix, height, align, start, color, slant, &chars);
     }
 
@@ -19,7 +19,7 @@
 
 use super::texture::{Texture2D, Texture2DMultisample};
 use gl;
-use gl::types::{GLsizei, GLuint};
+use gl::types::{GLenum, GLsizei, GLuint};
 use std::marker::PhantomData;
 use std::os::raw::c_void;
 use std::ptr;
@@ -40,14 +40,24 @@ assert_not_impl_any!(FrameBuffer: Send, Sync);
 
 impl FrameBuffer {
     pub fn new(width: usize, height: usize) -> FrameBuffer {
+        FrameBuffer::with_format(width, height, (gl::RGB, gl::RGB8))
+    }
+
+    /// Creates a framebuffer with an alpha channel, initially cleared to be
+    /// fully transparent.
+    pub fn with_alpha(width: usize, height: usize) -> FrameBuffer {
+        FrameBuffer::with_format(width, height, (gl::RGBA, gl::RGBA8))
+    }
+
+    fn with_format(
+        width: usize,
+        height: usize,
+        format: (GLenum, GLenum),
+    ) -> FrameBuffer {
         let texture_width = width.next_power_of_two();
         let texture_height = height.next_power_of_two();
-        let color_texture = Texture2D::new(
-            texture_width,
-            texture_height,
-            ptr::null(),
-            (gl::RGB, gl::RGB8),
-        );
+        let color_texture =
+            Texture2D::new(texture_width, texture_height, ptr::null(), format);
         let mut id: GLuint = 0;
         unsafe {
             gl::GenFramebuffers(1, &mut id);
@@ -102,6 +112,38 @@ impl Drop for FrameBuffer {
 
 //===========================================================================//
 
+/// Reads the RGB contents of the window's back buffer, with rows ordered from
+/// top to bottom.  This should be called after drawing a frame but before
+/// swapping buffers.
+pub fn read_window_rgb_data(size: RectSize<usize>) -> Vec<u8> {
+    let row_bytes = 3 * size.width;
+    let num_bytes = row_bytes * size.height;
+    let mut data = Vec::<u8>::with_capacity(num_bytes);
+    unsafe {
+        gl::PixelStorei(gl::PACK_ALIGNMENT, 1);
+        gl::ReadPixels(
+            0,
+            0,
+            size.width as GLsizei,
+            size.height as GLsizei,
+            gl::RGB,
+            gl::UNSIGNED_BYTE,
+            data.as_mut_ptr() as *mut c_void,
+        );
+        data.set_len(num_bytes);
+        gl::PixelStorei(gl::PACK_ALIGNMENT, 4);
+        debug_assert_eq!(gl::GetError(), gl::NO_ERROR);
+    }
+    // OpenGL returns rows from bottom to top, so flip them.
+    let mut flipped = Vec::with_capacity(num_bytes);
+    for row in data.chunks(row_bytes).rev() {
+        flipped.extend_from_slice(row);
+    }
+    flipped
+}
+
+//===========================================================================//
+
 pub struct FrameBufferBinding<'a> {
     window_size: RectSize<i32>,
     fbo: &'a FrameBuffer,
@@ -109,7 +151,18 @@ pub struct FrameBufferBinding<'a> {
 
 impl<'a> FrameBufferBinding<'a> {
     pub fn read_rgb_data(&self) -> Vec<u8> {
-        let num_bytes = 3 * self.fbo.size.width * self.fbo.size.height;
+        self.read_data(gl::RGB, 3)
+    }
+
+    /// Reads the RGBA contents of the framebuffer.  Note that the color
+    /// channels will be premultiplied by alpha.
+    pub fn read_rgba_data(&self) -> Vec<u8> {
+        self.read_data(gl::RGBA, 4)
+    }
+
+    fn read_data(&self, format: GLenum, bytes_per_pixel: usize) -> Vec<u8> {
+        let num_bytes =
+            bytes_per_pixel * self.fbo.size.width * self.fbo.size.height;
         let mut data = Vec::with_capacity(num_bytes);
         unsafe {
             gl::ReadPixels(
@@ -117,7 +170,7 @@ impl<'a> FrameBufferBinding<'a> {
                 0,
                 self.fbo.size.width as GLsizei,
                 self.fbo.size.height as GLsizei,
-                gl::RGB,
+                format,
                 gl::UNSIGNED_BYTE,
                 data.as_mut_ptr() as *mut c_void,
             );
@@ -0,0 +1,50 @@
+// +--------------------------------------------------------------------------+
+// | Copyright 2018 Matthew D. Steele <mdsteele@alum.mit.edu>                 |
+// |                                                                          |
+// | This file is part of Tachyomancer.                                       |
+// |                                                                          |
+// | Tachyomancer is free software: you can redistribute it and/or modify it  |
+// | under the terms of the GNU General Public License as published by the    |
+// | Free Software Foundation, either version 3 of the License, or (at your   |
+// | option) any later version.                                               |
+// |                                                                          |
+// | Tachyomancer is distributed in the hope that it will be useful, but      |
+// | WITHOUT ANY WARRANTY; without even the implied warranty of               |
+// | MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU        |
+// | General Public License for details.                                      |
+// |                                                                          |
+// | You should have received a copy of the GNU General Public License along  |
+// | with Tachyomancer.  If not, see <http://www.gnu.org/licenses/>.          |
+// +--------------------------------------------------------------------------+
+
+use gl;
+use gl::types::{GLenum, GLint};
+use std::ffi::CStr;
+use std::os::raw::c_char;
+
+//===========================================================================//
+
+/// Returns the value of a GL string parameter (e.g. `gl::VERSION`) for the
+/// current context, or a placeholder if the driver doesn't report one.
+pub fn gl_string(name: GLenum) -> String {
+    unsafe {
+        let ptr = gl::GetString(name);
+        if ptr.is_null() {
+            "(unknown)".to_string()
+        } else {
+            CStr::from_ptr(ptr as *const c_char).to_string_lossy().into_owned()
+        }
+    }
+}
+
+/// Returns the value of a GL integer parameter (e.g. `gl::MAX_SAMPLES`) for
+/// the current context.
+pub fn gl_integer(name: GLenum) -> i32 {
+    let mut value: GLint = 0;
+    unsafe {
+        gl::GetIntegerv(name, &mut value);
+    }
+    value
+}
+
+//===========================================================================//
@@ -21,6 +21,7 @@ mod depth;
 mod frame;
 mod heightmap;
 mod index;
+mod info;
 mod model;
 mod program;
 mod sampler;
@@ -32,10 +33,12 @@ mod vertex;
 
 pub use self::depth::Depth;
 pub use self::frame::{
-    FrameBuffer, FrameBufferBinding, FrameBufferMultisample,
+    read_window_rgb_data, FrameBuffer, FrameBufferBinding,
+    FrameBufferMultisample,
 };
 pub use self::heightmap::HeightmapModel;
 pub use self::index::IndexBuffer;
+pub use self::info::{gl_integer, gl_string};
 pub use self::model::{Model, ModelBuilder, ModelBuilderContext};
 pub use self::program::ShaderProgram;
 pub use self::sampler::ShaderSampler;
@@ -43,6 +46,8 @@ pub use self::shader::{Shader, ShaderType};
 pub use self::stencil::Stencil;
 pub use self::texture::{Texture1D, Texture2D, Texture2DMultisample};
 pub use self::uniform::ShaderUniform;
-pub use self::vertex::{Primitive, VertexArray, VertexBuffer};
+pub use self::vertex::{
+    take_draw_call_count, Primitive, VertexArray, VertexBuffer,
+};
 
 //===========================================================================//
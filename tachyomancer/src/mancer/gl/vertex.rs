@@ -24,6 +24,20 @@ use num_integer::Integer;
 use std::marker::PhantomData;
 use std::mem;
 use std::os::raw::c_void;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+//===========================================================================//
+
+// The number of draw calls made since the last call to
+// `take_draw_call_count`.  We only ever make GL calls from the main thread,
+// so relaxed ordering is sufficient.
+static DRAW_CALL_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+/// Returns the number of GL draw calls made since the last time this function
+/// was called, and resets the count to zero.
+pub fn take_draw_call_count() -> usize {
+    DRAW_CALL_COUNT.swap(0, Ordering::Relaxed)
+}
 
 //===========================================================================//
 
@@ -77,6 +91,7 @@ impl VertexArray {
     }
 
     pub fn draw(&self, primitive: Primitive, first: usize, count: usize) {
+        DRAW_CALL_COUNT.fetch_add(1, Ordering::Relaxed);
         unsafe {
             gl::DrawArrays(
                 primitive.to_gl_enum(),
@@ -93,6 +108,7 @@ impl VertexArray {
         indices: &IndexBuffer<A>,
     ) {
         indices.bind();
+        DRAW_CALL_COUNT.fetch_add(1, Ordering::Relaxed);
         unsafe {
             gl::DrawElements(
                 primitive.to_gl_enum(),
@@ -27,7 +27,9 @@ use std::collections::VecDeque;
 use std::io::Cursor;
 use std::mem;
 use std::ops::DerefMut;
+use std::sync::mpsc::{self, Receiver};
 use std::sync::{Arc, Mutex};
+use std::thread;
 
 //===========================================================================//
 
@@ -36,6 +38,17 @@ const DESIRED_BUFFER_SIZE: u16 = 1024; // num samples
 const DESIRED_NUM_CHANNELS: u8 = 1; // mono
 
 const MUSIC_FADE_OUT_SECONDS: f32 = 0.75;
+const MUSIC_CROSSFADE_SECONDS: f32 = 1.5;
+
+// While ducked, music plays at this fraction of its normal volume:
+const MUSIC_DUCK_VOLUME: f32 = 0.4;
+// How long it takes for music to fully duck or unduck:
+const MUSIC_DUCK_RAMP_SECONDS: f32 = 0.3;
+// How long music stays ducked after the most recent request to duck it:
+const MUSIC_DUCK_HOLD_SECONDS: f32 = 0.25;
+
+// How much of the start of a track to decode when preloading it:
+const MUSIC_PRELOAD_SECONDS: f32 = 3.0;
 
 //===========================================================================//
 
@@ -53,7 +66,7 @@ pub enum Sound {
 
 //===========================================================================//
 
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, EnumString, Eq, PartialEq)]
 pub enum Music {
     Aduro,
     AfterlifeCity,
@@ -69,6 +82,18 @@ pub enum Music {
     TheHyperboreanMenace,
 }
 
+/// Whether a music playlist should repeat after its last track finishes.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MusicRepeat {
+    /// Loop the playlist forever, crossfading from the end of each track
+    /// into the start of the next.
+    Loop,
+    /// Play each track once (with no gaps between them), then fall silent.
+    Once,
+}
+
+//===========================================================================//
+
 impl Music {
     fn ogg_data(&self) -> &'static [u8] {
         match *self {
@@ -95,8 +120,10 @@ impl Music {
 pub struct AudioQueue {
     sounds: Vec<Sound>,
     sound_volume: Option<f32>, // 0.0 to 1.0
-    music: Option<Vec<Music>>,
+    music: Option<(Vec<Music>, MusicRepeat)>,
     music_volume: Option<f32>, // 0.0 to 1.0
+    duck_music: bool,
+    preloads: Vec<PreloadedMusic>,
 }
 
 impl AudioQueue {
@@ -106,6 +133,8 @@ impl AudioQueue {
             sound_volume: None,
             music: None,
             music_volume: None,
+            duck_music: false,
+            preloads: Vec::new(),
         }
     }
 
@@ -113,8 +142,8 @@ impl AudioQueue {
         self.sounds.push(sound);
     }
 
-    pub fn play_music(&mut self, music: Vec<Music>) {
-        self.music = Some(music);
+    pub fn play_music(&mut self, music: Vec<Music>, repeat: MusicRepeat) {
+        self.music = Some((music, repeat));
     }
 
     pub fn set_sound_volume_percent(&mut self, percent: i32) {
@@ -125,6 +154,30 @@ impl AudioQueue {
         self.music_volume = Some(0.01 * (percent.max(0).min(100) as f32));
     }
 
+    /// Temporarily lowers the music volume.  The music will stay ducked as
+    /// long as this keeps getting called (e.g. on every clock tick), and
+    /// will return to normal volume shortly after it stops being called.
+    pub fn duck_music(&mut self) {
+        self.duck_music = true;
+    }
+
+    /// Starts decoding the beginning of each of the given tracks on a
+    /// background thread, so that a later call to `play_music` can start
+    /// them without stalling the audio thread.  If a track isn't finished
+    /// preloading by the time it's played, it will just be loaded normally.
+    pub fn preload_music(&mut self, music: &[Music]) {
+        for &music in music.iter() {
+            if self.preloads.iter().any(|preload| preload.music == music) {
+                continue;
+            }
+            let (sender, receiver) = mpsc::channel();
+            thread::spawn(move || {
+                let _ = sender.send(MusicStream::preload(music));
+            });
+            self.preloads.push(PreloadedMusic { music, receiver });
+        }
+    }
+
     pub(super) fn merge(&mut self, other: AudioQueue) {
         self.sounds.extend(other.sounds);
         self.sound_volume = other.sound_volume.or(self.sound_volume);
@@ -132,11 +185,23 @@ impl AudioQueue {
             self.music = other.music;
         }
         self.music_volume = other.music_volume.or(self.music_volume);
+        self.duck_music |= other.duck_music;
+        for preload in other.preloads {
+            self.preloads.retain(|old| old.music != preload.music);
+            self.preloads.push(preload);
+        }
     }
 }
 
 //===========================================================================//
 
+struct PreloadedMusic {
+    music: Music,
+    receiver: Receiver<Result<MusicStream, String>>,
+}
+
+//===========================================================================//
+
 struct AudioData {
     sound_data: Vec<Vec<f32>>,
 }
@@ -166,6 +231,7 @@ struct MusicStream {
     music: Music,
     samples: Vec<f32>,
     reader: OggStreamReader<Cursor<&'static [u8]>>,
+    exhausted: bool,
 }
 
 impl MusicStream {
@@ -191,8 +257,12 @@ impl MusicStream {
                         DESIRED_NUM_CHANNELS
                     ));
                 }
-                let stream =
-                    MusicStream { music, samples: Vec::new(), reader };
+                let stream = MusicStream {
+                    music,
+                    samples: Vec::new(),
+                    reader,
+                    exhausted: false,
+                };
                 return Ok(stream);
             }
             Err(error) => {
@@ -204,8 +274,28 @@ impl MusicStream {
         }
     }
 
-    pub fn read(&mut self, out: &mut [f32]) -> Result<usize, String> {
-        while self.samples.is_empty() {
+    fn preload(music: Music) -> Result<MusicStream, String> {
+        let mut stream = MusicStream::new(music)?;
+        let len =
+            ((DESIRED_AUDIO_RATE as f32) * MUSIC_PRELOAD_SECONDS) as usize;
+        stream.buffer(len)?;
+        Ok(stream)
+    }
+
+    /// Returns true if the whole track has been decoded, in which case
+    /// `num_buffered()` is the number of samples left in the track.
+    pub fn is_exhausted(&self) -> bool {
+        self.exhausted
+    }
+
+    pub fn num_buffered(&self) -> usize {
+        self.samples.len()
+    }
+
+    /// Decodes samples until at least `min_len` samples are buffered, or
+    /// until the end of the track is reached.
+    pub fn buffer(&mut self, min_len: usize) -> Result<(), String> {
+        while !self.exhausted && self.samples.len() < min_len {
             match self
                 .reader
                 .read_dec_packet_generic::<InterleavedSamples<f32>>()
@@ -215,9 +305,9 @@ impl MusicStream {
                         interleaved.channel_count,
                         DESIRED_NUM_CHANNELS as usize
                     );
-                    self.samples = interleaved.samples;
+                    self.samples.extend(interleaved.samples);
                 }
-                Ok(None) => return Ok(0),
+                Ok(None) => self.exhausted = true,
                 Err(error) => {
                     return Err(format!(
                         "Failed to decode {:?} samples: {:?}",
@@ -226,6 +316,11 @@ impl MusicStream {
                 }
             }
         }
+        Ok(())
+    }
+
+    pub fn read(&mut self, out: &mut [f32]) -> Result<usize, String> {
+        self.buffer(out.len())?;
         let len = self.samples.len().min(out.len());
         for index in 0..len {
             out[index] = self.samples[index];
@@ -245,7 +340,15 @@ pub struct AudioMixer {
     current_music: Option<MusicStream>,
     music_fade: Option<f32>,
     next_music: VecDeque<Music>,
-    music_volume: f32, // 0.0 to 1.0
+    music_repeat: MusicRepeat,
+    // The next track to play, if we're crossfading into it, along with the
+    // total length of the crossfade (in samples):
+    incoming_music: Option<(MusicStream, usize)>,
+    crossfade_buffer: Vec<f32>,
+    music_volume: f32,      // 0.0 to 1.0
+    music_duck: f32,        // MUSIC_DUCK_VOLUME to 1.0
+    music_duck_hold: usize, // num samples
+    preloaded_music: Vec<PreloadedMusic>,
 }
 
 impl AudioMixer {
@@ -261,7 +364,13 @@ impl AudioMixer {
             current_music: None,
             music_fade: None,
             next_music: VecDeque::new(),
+            music_repeat: MusicRepeat::Loop,
+            incoming_music: None,
+            crossfade_buffer: Vec::new(),
             music_volume: 0.0,
+            music_duck: 1.0,
+            music_duck_hold: 0,
+            preloaded_music: Vec::new(),
         };
         mixer.drain_queue();
         mixer
@@ -304,12 +413,15 @@ impl AudioMixer {
     }
 
     fn start_next_music(&mut self) {
+        self.incoming_music = None;
         if let Some(music) = self.next_music.pop_front() {
-            match MusicStream::new(music) {
+            match self.open_music(music) {
                 Ok(stream) => {
                     self.current_music = Some(stream);
                     self.music_fade = None;
-                    self.next_music.push_back(music);
+                    if self.music_repeat == MusicRepeat::Loop {
+                        self.next_music.push_back(music);
+                    }
                 }
                 Err(error) => {
                     debug_warn!("Failed to start music: {}", error);
@@ -324,6 +436,105 @@ impl AudioMixer {
         }
     }
 
+    /// Returns a stream for the given track, using the preloaded stream if
+    /// there is one ready, or else loading the track synchronously.
+    fn open_music(&mut self, music: Music) -> Result<MusicStream, String> {
+        if let Some(index) = self
+            .preloaded_music
+            .iter()
+            .position(|preload| preload.music == music)
+        {
+            let preload = self.preloaded_music.remove(index);
+            if let Ok(result) = preload.receiver.try_recv() {
+                return result;
+            }
+            debug_log!("{:?} isn't done preloading; loading it now", music);
+        }
+        MusicStream::new(music)
+    }
+
+    fn start_crossfade(&mut self, crossfade_len: usize) {
+        debug_assert!(self.incoming_music.is_none());
+        if let Some(&music) = self.next_music.front() {
+            match self.open_music(music) {
+                Ok(stream) => {
+                    self.incoming_music = Some((stream, crossfade_len));
+                    self.next_music.rotate_left(1);
+                }
+                Err(error) => {
+                    // Give up on crossfading; `start_next_music` will deal
+                    // with the error once the current track ends.
+                    debug_warn!("Failed to start music: {}", error);
+                }
+            }
+        }
+    }
+
+    /// Reads music samples into `out`, moving on to the next track (or
+    /// stopping the music) if the current track has ended.  Returns the
+    /// number of samples read, which will be zero upon a change of track.
+    fn read_music(&mut self, out: &mut [f32]) -> Result<usize, String> {
+        let max_crossfade_len =
+            ((DESIRED_AUDIO_RATE as f32) * MUSIC_CROSSFADE_SECONDS) as usize;
+        let remaining = match self.current_music {
+            Some(ref mut stream) => {
+                stream.buffer(out.len() + max_crossfade_len)?;
+                if stream.is_exhausted() {
+                    Some(stream.num_buffered())
+                } else {
+                    None
+                }
+            }
+            None => return Ok(0),
+        };
+        if let Some(remaining) = remaining {
+            if remaining == 0 {
+                if let Some((stream, _)) = self.incoming_music.take() {
+                    self.current_music = Some(stream);
+                } else {
+                    self.start_next_music();
+                }
+                return Ok(0);
+            }
+            if self.music_repeat == MusicRepeat::Loop
+                && self.music_fade.is_none()
+                && self.incoming_music.is_none()
+            {
+                self.start_crossfade(remaining);
+            }
+        }
+        let num_samples = match self.current_music {
+            Some(ref mut stream) => stream.read(out)?,
+            None => return Ok(0),
+        };
+        if let Some((ref mut incoming, crossfade_len)) = self.incoming_music {
+            debug_assert!(remaining.is_some());
+            let remaining = remaining.unwrap_or(0);
+            self.crossfade_buffer.clear();
+            self.crossfade_buffer.resize(num_samples, 0.0);
+            incoming.read(&mut self.crossfade_buffer)?;
+            for (index, sample) in out[..num_samples].iter_mut().enumerate() {
+                let weight =
+                    ((remaining - index) as f32) / (crossfade_len as f32);
+                *sample = *sample * weight
+                    + self.crossfade_buffer[index] * (1.0 - weight);
+            }
+        }
+        Ok(num_samples)
+    }
+
+    fn update_music_duck(&mut self, num_samples: usize) {
+        let step = (1.0 - MUSIC_DUCK_VOLUME) * (num_samples as f32)
+            / ((DESIRED_AUDIO_RATE as f32) * MUSIC_DUCK_RAMP_SECONDS);
+        if self.music_duck_hold > 0 {
+            self.music_duck_hold =
+                self.music_duck_hold.saturating_sub(num_samples);
+            self.music_duck = (self.music_duck - step).max(MUSIC_DUCK_VOLUME);
+        } else {
+            self.music_duck = (self.music_duck + step).min(1.0);
+        }
+    }
+
     fn drain_queue(&mut self) {
         let mut audio_queue = mem::replace(
             self.audio_queue.lock().unwrap().deref_mut(),
@@ -338,13 +549,28 @@ impl AudioMixer {
         if let Some(volume) = audio_queue.music_volume.take() {
             self.music_volume = volume;
         }
-        if let Some(new_music) = audio_queue.music.take() {
+        if audio_queue.duck_music {
+            self.music_duck_hold = ((DESIRED_AUDIO_RATE as f32)
+                * MUSIC_DUCK_HOLD_SECONDS)
+                as usize;
+        }
+        for preload in audio_queue.preloads.drain(..) {
+            self.preloaded_music.retain(|old| old.music != preload.music);
+            self.preloaded_music.push(preload);
+        }
+        if let Some((new_music, repeat)) = audio_queue.music.take() {
             self.next_music = new_music.into();
+            self.music_repeat = repeat;
+            self.incoming_music = None;
             if let Some(ref music_stream) = self.current_music {
                 if self.next_music.front() == Some(&music_stream.music) {
                     // TODO: fade back in if fading out
                     self.music_fade = None;
-                    self.next_music.rotate_left(1);
+                    if repeat == MusicRepeat::Loop {
+                        self.next_music.rotate_left(1);
+                    } else {
+                        self.next_music.pop_front();
+                    }
                 } else if self.music_fade.is_none() {
                     self.music_fade = Some(1.0);
                 }
@@ -370,24 +596,23 @@ impl sdl2::audio::AudioCallback for AudioMixer {
         } else {
             let mut start: usize = 0;
             while start < out.len() {
-                if let Some(ref mut music_stream) = self.current_music {
-                    match music_stream.read(&mut out[start..]) {
-                        Ok(0) => self.start_next_music(),
-                        Ok(num_samples) => {
-                            start += num_samples;
-                        }
-                        Err(error) => {
-                            debug_warn!("Failed to stream music: {}", error);
-                            self.current_music = None;
-                            self.music_fade = None;
-                            self.next_music.clear();
-                            break;
-                        }
-                    }
-                } else {
+                if self.current_music.is_none() {
                     debug_assert!(self.next_music.is_empty());
                     break;
                 }
+                match self.read_music(&mut out[start..]) {
+                    Ok(num_samples) => {
+                        start += num_samples;
+                    }
+                    Err(error) => {
+                        debug_warn!("Failed to stream music: {}", error);
+                        self.current_music = None;
+                        self.incoming_music = None;
+                        self.music_fade = None;
+                        self.next_music.clear();
+                        break;
+                    }
+                }
             }
             for sample in out[start..].iter_mut() {
                 *sample = 0.0;
@@ -406,7 +631,8 @@ impl sdl2::audio::AudioCallback for AudioMixer {
         } else {
             1.0
         };
-        let music_volume = fade * self.music_volume;
+        self.update_music_duck(out.len());
+        let music_volume = fade * self.music_duck * self.music_volume;
         for sample in out.iter_mut() {
             *sample *= music_volume;
         }
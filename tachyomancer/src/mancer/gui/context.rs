@@ -20,11 +20,13 @@
 use super::audio::{AudioMixer, AudioQueue};
 use super::clipboard::Clipboard;
 use super::cursor::Cursors;
-use super::debug::StdinReader;
+use super::debug::{DebugConsole, StdinReader};
 use super::score::ScoreClient;
+use super::update::UpdateChecker;
 use crate::mancer::save::GlobalScoresDir;
 use sdl2;
 use std::collections::HashSet;
+use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
 use tachy::geom::RectSize;
 
@@ -35,12 +37,13 @@ pub struct GuiContext {
     pub(super) video_subsystem: sdl2::VideoSubsystem,
     pub(super) clipboard: Clipboard,
     pub(super) event_pump: sdl2::EventPump,
-    _audio_subsystem: sdl2::AudioSubsystem,
-    _audio_device: sdl2::audio::AudioDevice<AudioMixer>,
-    pub(super) audio_queue: Arc<Mutex<AudioQueue>>,
+    pub(super) audio_output: Option<AudioOutput>,
     pub(super) cursors: Cursors,
     pub(super) score_client: ScoreClient,
+    pub(super) update_checker: UpdateChecker,
     pub(super) stdin_reader: StdinReader,
+    pub(super) debug_console: Option<DebugConsole>,
+    pub(super) texture_pack_dir: PathBuf,
 }
 
 impl GuiContext {
@@ -49,6 +52,9 @@ impl GuiContext {
         init_music_volume_percent: i32,
         server_addr: &str,
         scores_dir: GlobalScoresDir,
+        texture_pack_dir: PathBuf,
+        debug_console: bool,
+        no_audio: bool,
     ) -> Result<GuiContext, String> {
         let sdl_context = sdl2::init()?;
         if cfg!(any(target_os = "ios", target_os = "macos")) {
@@ -59,29 +65,47 @@ impl GuiContext {
         let event_pump = sdl_context.event_pump()?;
         let cursors = Cursors::new()?;
 
-        let audio_subsystem = sdl_context.audio()?;
-        let mut audio_queue = AudioQueue::new();
-        audio_queue.set_sound_volume_percent(init_sound_volume_percent);
-        audio_queue.set_music_volume_percent(init_music_volume_percent);
-        let audio_queue = Arc::new(Mutex::new(audio_queue));
-        let audio_device =
-            AudioMixer::audio_device(&audio_subsystem, audio_queue.clone())?;
-        audio_device.resume();
+        let audio_output = if no_audio {
+            None
+        } else {
+            match AudioOutput::init(
+                &sdl_context,
+                init_sound_volume_percent,
+                init_music_volume_percent,
+            ) {
+                Ok(output) => Some(output),
+                Err(err) => {
+                    debug_warn!("Continuing without audio: {}", err);
+                    None
+                }
+            }
+        };
 
         Ok(GuiContext {
             sdl_context,
             video_subsystem,
             clipboard,
             event_pump,
-            _audio_subsystem: audio_subsystem,
-            _audio_device: audio_device,
-            audio_queue,
+            audio_output,
             cursors,
             score_client: ScoreClient::start(server_addr, scores_dir),
+            update_checker: UpdateChecker::new(),
             stdin_reader: StdinReader::start(),
+            debug_console: if debug_console {
+                Some(DebugConsole::new())
+            } else {
+                None
+            },
+            texture_pack_dir,
         })
     }
 
+    /// Starts checking (in the background) whether a newer version of the
+    /// game is available from the server.
+    pub fn start_update_check(&self, server_addr: &str) {
+        self.update_checker.start(server_addr);
+    }
+
     pub fn get_native_resolution(&self) -> Result<RectSize<i32>, String> {
         let display_mode = self.video_subsystem.desktop_display_mode(0)?;
         Ok(RectSize::new(display_mode.w, display_mode.h))
@@ -106,3 +130,28 @@ impl GuiContext {
 }
 
 //===========================================================================//
+
+pub(super) struct AudioOutput {
+    _subsystem: sdl2::AudioSubsystem,
+    _device: sdl2::audio::AudioDevice<AudioMixer>,
+    pub(super) queue: Arc<Mutex<AudioQueue>>,
+}
+
+impl AudioOutput {
+    fn init(
+        sdl_context: &sdl2::Sdl,
+        init_sound_volume_percent: i32,
+        init_music_volume_percent: i32,
+    ) -> Result<AudioOutput, String> {
+        let subsystem = sdl_context.audio()?;
+        let mut queue = AudioQueue::new();
+        queue.set_sound_volume_percent(init_sound_volume_percent);
+        queue.set_music_volume_percent(init_music_volume_percent);
+        let queue = Arc::new(Mutex::new(queue));
+        let device = AudioMixer::audio_device(&subsystem, queue.clone())?;
+        device.resume();
+        Ok(AudioOutput { _subsystem: subsystem, _device: device, queue })
+    }
+}
+
+//===========================================================================//
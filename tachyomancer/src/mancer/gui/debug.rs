@@ -17,10 +17,23 @@
 // | with Tachyomancer.  If not, see <http://www.gnu.org/licenses/>.          |
 // +--------------------------------------------------------------------------+
 
+use super::event::{Event, Keycode};
+use super::resource::Resources;
+use crate::mancer::font::Align;
+use cgmath::Matrix4;
 use std::collections::VecDeque;
 use std::io;
 use std::sync::{Arc, Mutex};
 use std::thread;
+use tachy::geom::{Color4, Rect};
+
+//===========================================================================//
+
+const CONSOLE_HEIGHT: f32 = 30.0;
+const CONSOLE_FONT_SIZE: f32 = 20.0;
+const CONSOLE_MAX_LEN: usize = 200;
+const CONSOLE_TOGGLE_KEY: Keycode = Keycode::Backquote;
+const CONSOLE_TOGGLE_TEXT: &str = "`";
 
 //===========================================================================//
 
@@ -60,3 +73,136 @@ impl StdinReader {
 }
 
 //===========================================================================//
+
+/// An overlay that lets the user type `KEY=VALUE` lines, which are turned
+/// into `Event::Debug` events just like lines read from stdin.
+pub struct DebugConsole {
+    is_open: bool,
+    line: String,
+    skip_toggle_text: bool,
+}
+
+impl DebugConsole {
+    pub(super) fn new() -> DebugConsole {
+        DebugConsole {
+            is_open: false,
+            line: String::new(),
+            skip_toggle_text: false,
+        }
+    }
+
+    /// Returns the event that should be passed on to the views, or `None` if
+    /// the console consumed the event.
+    pub(super) fn filter_event(&mut self, event: Event) -> Option<Event> {
+        match event {
+            Event::KeyDown(ref key) if key.code == CONSOLE_TOGGLE_KEY => {
+                self.is_open = !self.is_open;
+                self.line.clear();
+                self.skip_toggle_text = true;
+                return None;
+            }
+            Event::TextInput(ref text)
+                if self.skip_toggle_text && text == CONSOLE_TOGGLE_TEXT =>
+            {
+                self.skip_toggle_text = false;
+                return None;
+            }
+            _ => {}
+        }
+        self.skip_toggle_text = false;
+        if !self.is_open {
+            return Some(event);
+        }
+        match event {
+            Event::KeyDown(key) => match key.code {
+                Keycode::Return | Keycode::KpEnter => {
+                    let line = std::mem::take(&mut self.line);
+                    if !line.trim().is_empty() {
+                        debug_log!("Debug console: {}", line);
+                        return Some(Event::new_debug(&line));
+                    }
+                }
+                Keycode::Backspace => {
+                    self.line.pop();
+                }
+                Keycode::Escape => {
+                    self.is_open = false;
+                    self.line.clear();
+                }
+                _ => {}
+            },
+            Event::TextInput(text) => {
+                for chr in text.chars() {
+                    if self.line.len() < CONSOLE_MAX_LEN
+                        && (' '..='~').contains(&chr)
+                    {
+                        self.line.push(chr);
+                    }
+                }
+            }
+            other => return Some(other),
+        }
+        None
+    }
+
+    pub(super) fn draw(&self, resources: &Resources, matrix: &Matrix4<f32>) {
+        if !self.is_open {
+            return;
+        }
+        let width = resources.window_size().width as f32;
+        let rect = Rect::new(0.0, 0.0, width, CONSOLE_HEIGHT);
+        let color = Color4::new(0.0, 0.0, 0.0, 0.8);
+        resources.shaders().solid().tint_rect(matrix, color, rect);
+        resources.fonts().roman().draw(
+            matrix,
+            CONSOLE_FONT_SIZE,
+            Align::MidLeft,
+            (5.0, 0.5 * CONSOLE_HEIGHT),
+            &format!("> {}_", self.line),
+        );
+    }
+}
+
+//===========================================================================//
+
+#[cfg(test)]
+mod tests {
+    use super::DebugConsole;
+    use crate::mancer::gui::event::{Event, KeyEventData, Keycode};
+    use cgmath::Point2;
+
+    fn key_down(code: Keycode) -> Event {
+        Event::KeyDown(KeyEventData {
+            code,
+            command: false,
+            shift: false,
+            mouse_pt: Point2::new(0, 0),
+        })
+    }
+
+    #[test]
+    fn console_produces_debug_events() {
+        let mut console = DebugConsole::new();
+        assert!(console.filter_event(Event::Redraw).is_some());
+        assert!(console.filter_event(key_down(Keycode::Backquote)).is_none());
+        assert!(console
+            .filter_event(Event::TextInput("`".to_string()))
+            .is_none());
+        let text = Event::TextInput("ResetScores = Tutorial".to_string());
+        assert!(console.filter_event(text).is_none());
+        assert!(console.filter_event(key_down(Keycode::A)).is_none());
+        assert!(console.filter_event(Event::Redraw).is_some());
+        match console.filter_event(key_down(Keycode::Return)) {
+            Some(Event::Debug(key, value)) => {
+                assert_eq!(key, "ResetScores");
+                assert_eq!(value, "Tutorial");
+            }
+            _ => panic!("wrong event type"),
+        }
+        assert!(console.filter_event(key_down(Keycode::Return)).is_none());
+        assert!(console.filter_event(key_down(Keycode::Escape)).is_none());
+        assert!(console.filter_event(key_down(Keycode::A)).is_some());
+    }
+}
+
+//===========================================================================//
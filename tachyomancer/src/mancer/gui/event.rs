@@ -165,6 +165,25 @@ impl Event {
         }
     }
 
+    /// Returns true if this event was caused directly by the player (as
+    /// opposed to e.g. a clock tick or window focus change).
+    pub fn is_player_input(&self) -> bool {
+        match self {
+            Event::KeyDown(_)
+            | Event::MouseDown(_)
+            | Event::MouseMove(_)
+            | Event::MouseUp(_)
+            | Event::Multitouch(_)
+            | Event::Scroll(_)
+            | Event::TextInput(_) => true,
+            Event::ClockTick(_)
+            | Event::Debug(_, _)
+            | Event::Quit
+            | Event::Redraw
+            | Event::Unfocus => false,
+        }
+    }
+
     pub fn relative_to(&self, origin: Point2<i32>) -> Event {
         match self {
             Event::MouseDown(mouse) => {
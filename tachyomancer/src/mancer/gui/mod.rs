@@ -25,10 +25,13 @@ mod debug;
 mod event;
 mod resource;
 mod score;
+mod screenshot;
+mod stats;
 mod ui;
+mod update;
 mod window;
 
-pub use self::audio::{AudioQueue, Music, Sound};
+pub use self::audio::{AudioQueue, Music, MusicRepeat, Sound};
 pub use self::clipboard::Clipboard;
 pub use self::context::GuiContext;
 pub use self::cursor::{Cursor, Cursors, NextCursor};
@@ -37,6 +40,7 @@ pub use self::event::{
     MultitouchEventData, ScrollEventData,
 };
 pub use self::resource::Resources;
+pub use self::screenshot::{write_rgb_png, write_rgba_png};
 pub use self::ui::{Keyboard, Ui};
 pub use self::window::{Window, WindowOptions};
 
@@ -20,6 +20,7 @@
 use crate::mancer::font::Fonts;
 use crate::mancer::shader::Shaders;
 use crate::mancer::texture::Textures;
+use std::path::Path;
 use std::sync::{Arc, Mutex};
 use std::time::Instant;
 use tachy::geom::RectSize;
@@ -31,6 +32,7 @@ pub struct Resources {
     window_size: RectSize<i32>,
     fonts: Fonts,
     global_scores: Arc<Mutex<ScoreCurveMap>>,
+    newer_version: Arc<Mutex<Option<String>>>,
     shaders: Shaders,
     textures: Textures,
 }
@@ -39,16 +41,25 @@ impl Resources {
     pub(super) fn new(
         window_size: RectSize<i32>,
         global_scores: Arc<Mutex<ScoreCurveMap>>,
+        newer_version: Arc<Mutex<Option<String>>>,
+        texture_pack_dir: &Path,
     ) -> Result<Resources, String> {
         let start_time = Instant::now();
         let fonts = Fonts::new()?;
         let shaders = Shaders::new(window_size)?;
-        let textures = Textures::new()?;
+        let textures = Textures::new(texture_pack_dir)?;
         debug_log!(
             "Generated resources in {} seconds",
             Instant::now().duration_since(start_time).as_secs_f64()
         );
-        Ok(Resources { window_size, fonts, global_scores, shaders, textures })
+        Ok(Resources {
+            window_size,
+            fonts,
+            global_scores,
+            newer_version,
+            shaders,
+            textures,
+        })
     }
 
     pub fn window_size(&self) -> RectSize<i32> {
@@ -63,6 +74,12 @@ impl Resources {
         self.global_scores.lock().unwrap().get(puzzle).clone()
     }
 
+    /// Returns the latest version of the game, if the update check has found
+    /// one newer than the running version.
+    pub fn newer_version(&self) -> Option<String> {
+        self.newer_version.lock().unwrap().clone()
+    }
+
     pub fn shaders(&self) -> &Shaders {
         &self.shaders
     }
@@ -0,0 +1,88 @@
+// +--------------------------------------------------------------------------+
+// | Copyright 2018 Matthew D. Steele <mdsteele@alum.mit.edu>                 |
+// |                                                                          |
+// | This file is part of Tachyomancer.                                       |
+// |                                                                          |
+// | Tachyomancer is free software: you can redistribute it and/or modify it  |
+// | under the terms of the GNU General Public License as published by the    |
+// | Free Software Foundation, either version 3 of the License, or (at your   |
+// | option) any later version.                                               |
+// |                                                                          |
+// | Tachyomancer is distributed in the hope that it will be useful, but      |
+// | WITHOUT ANY WARRANTY; without even the implied warranty of               |
+// | MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU        |
+// | General Public License for details.                                      |
+// |                                                                          |
+// | You should have received a copy of the GNU General Public License along  |
+// | with Tachyomancer.  If not, see <http://www.gnu.org/licenses/>.          |
+// +--------------------------------------------------------------------------+
+
+use png::{self, HasParameters};
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tachy::geom::RectSize;
+
+//===========================================================================//
+
+/// Encodes the given RGB image data (with rows ordered from top to bottom) as
+/// a PNG file at the given path.
+pub fn write_rgb_png(
+    path: &Path,
+    size: RectSize<usize>,
+    rgb: &[u8],
+) -> Result<(), String> {
+    let png_file = File::create(path).map_err(|err| err.to_string())?;
+    let mut encoder =
+        png::Encoder::new(png_file, size.width as u32, size.height as u32);
+    encoder.set(png::ColorType::RGB).set(png::BitDepth::Eight);
+    let mut writer = encoder.write_header().map_err(|err| err.to_string())?;
+    writer.write_image_data(rgb).map_err(|err| err.to_string())?;
+    Ok(())
+}
+
+/// Encodes the given RGBA image data (with rows ordered from top to bottom
+/// and color channels not premultiplied by alpha) as a PNG file at the given
+/// path.
+pub fn write_rgba_png(
+    path: &Path,
+    size: RectSize<usize>,
+    rgba: &[u8],
+) -> Result<(), String> {
+    let png_file = File::create(path).map_err(|err| err.to_string())?;
+    let mut encoder =
+        png::Encoder::new(png_file, size.width as u32, size.height as u32);
+    encoder.set(png::ColorType::RGBA).set(png::BitDepth::Eight);
+    let mut writer = encoder.write_header().map_err(|err| err.to_string())?;
+    writer.write_image_data(rgba).map_err(|err| err.to_string())?;
+    Ok(())
+}
+
+/// Saves a screenshot into the given directory (creating it if necessary),
+/// using a timestamped file name, and returns the path of the written file.
+pub(super) fn save_screenshot(
+    dir: &Path,
+    size: RectSize<usize>,
+    rgb: &[u8],
+) -> Result<PathBuf, String> {
+    fs::create_dir_all(dir).map_err(|err| {
+        format!("Could not create screenshots directory {:?}: {}", dir, err)
+    })?;
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0);
+    let name = format!("screenshot_{}", timestamp);
+    let mut path = dir.join(format!("{}.png", name));
+    let mut counter: u64 = 0;
+    while path.exists() {
+        counter += 1;
+        path = dir.join(format!("{}_{}.png", name, counter));
+    }
+    write_rgb_png(&path, size, rgb).map_err(|err| {
+        format!("Could not write screenshot to {:?}: {}", path, err)
+    })?;
+    Ok(path)
+}
+
+//===========================================================================//
@@ -0,0 +1,136 @@
+// +--------------------------------------------------------------------------+
+// | Copyright 2018 Matthew D. Steele <mdsteele@alum.mit.edu>                 |
+// |                                                                          |
+// | This file is part of Tachyomancer.                                       |
+// |                                                                          |
+// | Tachyomancer is free software: you can redistribute it and/or modify it  |
+// | under the terms of the GNU General Public License as published by the    |
+// | Free Software Foundation, either version 3 of the License, or (at your   |
+// | option) any later version.                                               |
+// |                                                                          |
+// | Tachyomancer is distributed in the hope that it will be useful, but      |
+// | WITHOUT ANY WARRANTY; without even the implied warranty of               |
+// | MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU        |
+// | General Public License for details.                                      |
+// |                                                                          |
+// | You should have received a copy of the GNU General Public License along  |
+// | with Tachyomancer.  If not, see <http://www.gnu.org/licenses/>.          |
+// +--------------------------------------------------------------------------+
+
+use super::resource::Resources;
+use crate::mancer::font::Align;
+use cgmath::Matrix4;
+use std::time::Instant;
+use tachy::geom::{Color3, Color4, Rect};
+
+//===========================================================================//
+
+const OVERLAY_FONT_SIZE: f32 = 20.0;
+const OVERLAY_LINE_HEIGHT: f32 = 22.0;
+const OVERLAY_MARGIN: f32 = 5.0;
+const OVERLAY_WIDTH: f32 = 230.0;
+
+// How much weight to give the latest frame when updating the running average
+// frame time:
+const AVERAGE_SMOOTHING: f64 = 0.05;
+
+//===========================================================================//
+
+/// A debugging overlay that shows frame rate, frame time, and the number of
+/// GL draw calls per frame.  It is hidden until toggled on with the
+/// `ShowFps` debug command.
+pub struct FrameStatsOverlay {
+    visible: bool,
+    last_frame: Option<Instant>,
+    last_frame_seconds: f64,
+    average_frame_seconds: f64,
+    draw_calls: usize,
+}
+
+impl FrameStatsOverlay {
+    pub fn new() -> FrameStatsOverlay {
+        FrameStatsOverlay {
+            visible: false,
+            last_frame: None,
+            last_frame_seconds: 0.0,
+            average_frame_seconds: 0.0,
+            draw_calls: 0,
+        }
+    }
+
+    pub fn toggle_visible(&mut self) {
+        self.visible = !self.visible;
+        self.last_frame = None;
+        self.average_frame_seconds = 0.0;
+    }
+
+    /// Records that a frame has just been drawn, using the given number of
+    /// draw calls.
+    pub fn on_frame(&mut self, draw_calls: usize) {
+        self.draw_calls = draw_calls;
+        let now = Instant::now();
+        if let Some(last_frame) = self.last_frame {
+            let elapsed = now.duration_since(last_frame);
+            let seconds = (elapsed.as_secs() as f64)
+                + 1e-9 * (elapsed.subsec_nanos() as f64);
+            self.last_frame_seconds = seconds;
+            self.average_frame_seconds = if self.average_frame_seconds > 0.0 {
+                self.average_frame_seconds
+                    + AVERAGE_SMOOTHING
+                        * (seconds - self.average_frame_seconds)
+            } else {
+                seconds
+            };
+        }
+        self.last_frame = Some(now);
+    }
+
+    pub fn draw(&self, resources: &Resources, matrix: &Matrix4<f32>) {
+        if !self.visible {
+            return;
+        }
+        let fps = if self.last_frame_seconds > 0.0 {
+            1.0 / self.last_frame_seconds
+        } else {
+            0.0
+        };
+        let lines = [
+            format!("FPS: {:.1}", fps),
+            format!(
+                "Avg frame: {:.2} ms",
+                1000.0 * self.average_frame_seconds
+            ),
+            format!("Draw calls: {}", self.draw_calls),
+        ];
+        let rect = Rect::new(
+            OVERLAY_MARGIN,
+            OVERLAY_MARGIN,
+            OVERLAY_WIDTH,
+            2.0 * OVERLAY_MARGIN + OVERLAY_LINE_HEIGHT * (lines.len() as f32),
+        );
+        resources.shaders().solid().tint_rect(
+            matrix,
+            Color3::BLACK.with_alpha(0.6),
+            rect,
+        );
+        let font = resources.fonts().roman();
+        for (index, line) in lines.iter().enumerate() {
+            font.draw_style(
+                matrix,
+                OVERLAY_FONT_SIZE,
+                Align::TopLeft,
+                (
+                    rect.x + OVERLAY_MARGIN,
+                    rect.y
+                        + OVERLAY_MARGIN
+                        + OVERLAY_LINE_HEIGHT * (index as f32),
+                ),
+                &Color4::WHITE,
+                0.0,
+                line,
+            );
+        }
+    }
+}
+
+//===========================================================================//
@@ -0,0 +1,144 @@
+// +--------------------------------------------------------------------------+
+// | Copyright 2018 Matthew D. Steele <mdsteele@alum.mit.edu>                 |
+// |                                                                          |
+// | This file is part of Tachyomancer.                                       |
+// |                                                                          |
+// | Tachyomancer is free software: you can redistribute it and/or modify it  |
+// | under the terms of the GNU General Public License as published by the    |
+// | Free Software Foundation, either version 3 of the License, or (at your   |
+// | option) any later version.                                               |
+// |                                                                          |
+// | Tachyomancer is distributed in the hope that it will be useful, but      |
+// | WITHOUT ANY WARRANTY; without even the implied warranty of               |
+// | MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU        |
+// | General Public License for details.                                      |
+// |                                                                          |
+// | You should have received a copy of the GNU General Public License along  |
+// | with Tachyomancer.  If not, see <http://www.gnu.org/licenses/>.          |
+// +--------------------------------------------------------------------------+
+
+use std::io::Read;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use ureq;
+
+//===========================================================================//
+
+/// The version of the game that is currently running.
+pub const CURRENT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+const CONNECT_TIMEOUT_MS: u64 = 5000;
+const READ_TIMEOUT_MS: u64 = 1000;
+const USER_AGENT: &str = concat!("tachyomancer/", env!("CARGO_PKG_VERSION"));
+
+// The version endpoint's response should be short; anything longer than this
+// is ignored:
+const MAX_VERSION_LEN: usize = 32;
+
+//===========================================================================//
+
+/// Asks the server (on a background thread) whether a newer version of the
+/// game is available.  Any failure is logged and otherwise ignored, so this
+/// never blocks startup or bothers the player when offline.
+pub struct UpdateChecker {
+    newer_version: Arc<Mutex<Option<String>>>,
+}
+
+impl UpdateChecker {
+    pub(super) fn new() -> UpdateChecker {
+        UpdateChecker { newer_version: Arc::new(Mutex::new(None)) }
+    }
+
+    pub(super) fn start(&self, server_addr: &str) {
+        let newer_version = self.newer_version.clone();
+        let server_addr_string = server_addr.to_string();
+        thread::spawn(move || {
+            match fetch_latest_version(&server_addr_string) {
+                Ok(latest) => {
+                    if is_newer_version(&latest, CURRENT_VERSION) {
+                        debug_log!("Newer version available: {}", latest);
+                        *newer_version.lock().unwrap() = Some(latest);
+                    }
+                }
+                Err(err) => {
+                    debug_log!("Failed to check for updates: {}", err);
+                }
+            }
+        });
+    }
+
+    pub fn newer_version(&self) -> &Arc<Mutex<Option<String>>> {
+        &self.newer_version
+    }
+}
+
+//===========================================================================//
+
+fn fetch_latest_version(server_addr: &str) -> Result<String, String> {
+    let response = ureq::get(&format!("{}/latest_version", server_addr))
+        .query("current", CURRENT_VERSION)
+        .set("User-Agent", USER_AGENT)
+        .timeout_connect(CONNECT_TIMEOUT_MS)
+        .timeout_read(READ_TIMEOUT_MS)
+        .call();
+    if let Some(err) = response.synthetic_error() {
+        return Err(format!("Network error: {}", err));
+    }
+    if response.status() != 200 {
+        return Err(format!(
+            "Got HTTP {}: {}",
+            response.status(),
+            response.status_text()
+        ));
+    }
+    let mut payload = String::new();
+    response
+        .into_reader()
+        .take(MAX_VERSION_LEN as u64 + 1)
+        .read_to_string(&mut payload)
+        .map_err(|err| format!("Failed to read response body: {}", err))?;
+    let version = payload.trim();
+    if version.is_empty() || version.len() > MAX_VERSION_LEN {
+        return Err(format!("Invalid version string: {:?}", version));
+    }
+    Ok(version.to_string())
+}
+
+/// Returns true if `latest` is a dotted version number (e.g. `"1.2.3"`) that
+/// is strictly greater than `current`.  Returns false if either can't be
+/// parsed.
+fn is_newer_version(latest: &str, current: &str) -> bool {
+    match (parse_version(latest), parse_version(current)) {
+        (Some(latest), Some(current)) => latest > current,
+        _ => false,
+    }
+}
+
+fn parse_version(version: &str) -> Option<Vec<u32>> {
+    version.split('.').map(|piece| piece.parse::<u32>().ok()).collect()
+}
+
+//===========================================================================//
+
+#[cfg(test)]
+mod tests {
+    use super::{is_newer_version, CURRENT_VERSION};
+
+    #[test]
+    fn newer_version_comparison() {
+        assert!(is_newer_version("0.2.0", "0.1.9"));
+        assert!(is_newer_version("0.10.0", "0.9.0"));
+        assert!(is_newer_version("1.0.0.1", "1.0.0"));
+        assert!(!is_newer_version("0.1.0", "0.1.0"));
+        assert!(!is_newer_version("0.1.0", "0.2.0"));
+        assert!(!is_newer_version("banana", "0.1.0"));
+        assert!(!is_newer_version("", CURRENT_VERSION));
+    }
+
+    #[test]
+    fn current_version_parses() {
+        assert!(super::parse_version(CURRENT_VERSION).is_some());
+    }
+}
+
+//===========================================================================//
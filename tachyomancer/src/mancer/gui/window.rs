@@ -17,17 +17,24 @@
 // | with Tachyomancer.  If not, see <http://www.gnu.org/licenses/>.          |
 // +--------------------------------------------------------------------------+
 
-use super::audio::AudioQueue;
+use super::audio::{AudioQueue, Sound};
 use super::context::GuiContext;
 use super::cursor::NextCursor;
-use super::event::Event;
+use super::event::{Event, Keycode};
 use super::resource::Resources;
+use super::screenshot::save_screenshot;
+use super::stats::FrameStatsOverlay;
 use super::ui::Ui;
 use crate::mancer::font::Align;
+use crate::mancer::gl::{
+    gl_integer, gl_string, read_window_rgb_data, take_draw_call_count,
+};
 use gl;
 use sdl2;
+use std::collections::BTreeSet;
 use std::mem;
 use std::os::raw::c_void;
+use std::path::PathBuf;
 use std::time::Instant;
 use tachy::geom::{AsFloat, Color4, RectSize};
 use tachy::save::SolutionData;
@@ -38,11 +45,22 @@ const WINDOW_MIN_WIDTH: i32 = 1024;
 const WINDOW_MIN_HEIGHT: i32 = 640;
 const WINDOW_TITLE: &str = "Tachyomancer";
 
+// Pressing this key in any mode saves a screenshot of the whole window:
+const SCREENSHOT_KEYCODE: Keycode = Keycode::F12;
+
+//===========================================================================//
+
+/// The multisample antialiasing levels (samples per pixel) that we offer, in
+/// ascending order; zero means that antialiasing is disabled.
+pub const ANTIALIASING_LEVELS: &[u8] = &[0, 2, 4, 8];
+
 //===========================================================================//
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct WindowOptions {
-    pub antialiasing: bool,
+    /// The number of multisample antialiasing samples per pixel (one of
+    /// `ANTIALIASING_LEVELS`), or zero to disable antialiasing.
+    pub antialiasing: u8,
     pub fullscreen: bool,
     pub resolution: Option<RectSize<i32>>,
 }
@@ -53,12 +71,16 @@ pub struct Window<'a> {
     _gl_context: sdl2::video::GLContext,
     resources: Resources,
     possible_resolutions: Vec<RectSize<i32>>,
+    supported_antialiasing_levels: Vec<u8>,
     options: WindowOptions,
     audio: AudioQueue,
     next_cursor: NextCursor,
     last_clock_tick: Instant,
     last_event_was_clock_tick: bool,
     redraw_requested: bool,
+    screenshots_dir: PathBuf,
+    screenshot_requested: bool,
+    frame_stats: FrameStatsOverlay,
     debug_counter: i32,
 }
 
@@ -66,6 +88,7 @@ impl<'a> Window<'a> {
     pub fn create(
         gui_context: &'a mut GuiContext,
         options: WindowOptions,
+        screenshots_dir: PathBuf,
     ) -> Result<Window<'a>, String> {
         debug_log!("Creating window: {:?}", options);
         {
@@ -83,46 +106,36 @@ impl<'a> Window<'a> {
 
             // Make sure we have a stencil buffer (1 bit is all we need).
             gl_attr.set_stencil_size(1);
-
-            // Optionally enable multisample antialiasing.
-            if options.antialiasing {
-                gl_attr.set_multisample_buffers(1);
-                gl_attr.set_multisample_samples(4);
-            }
         }
 
-        let native_resolution = gui_context.get_native_resolution()?;
-        let resolution = options.resolution.unwrap_or(native_resolution);
-        let sdl_window = {
-            let width = resolution
-                .width
-                .max(WINDOW_MIN_WIDTH)
-                .min(native_resolution.width);
-            let height = resolution
-                .height
-                .max(WINDOW_MIN_HEIGHT)
-                .min(native_resolution.height);
-            let mut builder = gui_context.video_subsystem.window(
-                WINDOW_TITLE,
-                width as u32,
-                height as u32,
-            );
-            builder.opengl();
-            if options.fullscreen {
-                if options.resolution.is_none() {
-                    builder.fullscreen_desktop();
-                } else {
-                    builder.fullscreen();
-                }
-            } else {
-                builder.position_centered();
-            };
-            builder
-                .build()
-                .map_err(|err| format!("Could not create window: {}", err))?
+        // Try to create the window with the requested antialiasing level.  If
+        // that fails, fall back to successively lower levels until we find
+        // one that the system supports.
+        let mut options = options;
+        let mut levels: Vec<u8> = ANTIALIASING_LEVELS
+            .iter()
+            .copied()
+            .filter(|&level| level < options.antialiasing)
+            .collect();
+        let (sdl_window, gl_context) = loop {
+            match Window::create_sdl_window(gui_context, &options) {
+                Ok(result) => break result,
+                Err(err) => match levels.pop() {
+                    Some(level) => {
+                        debug_warn!(
+                            "Could not create window with {}x \
+                             antialiasing ({}); falling back to {}x",
+                            options.antialiasing,
+                            err,
+                            level
+                        );
+                        options.antialiasing = level;
+                    }
+                    None => return Err(err),
+                },
+            }
         };
 
-        let gl_context = sdl_window.gl_create_context()?;
         // According to https://wiki.libsdl.org/SDL_GL_GetProcAddress, to
         // support Windows, we should wait until after we've created the GL
         // context before calling SDL_GL_GetProcAddress.
@@ -145,10 +158,27 @@ impl<'a> Window<'a> {
             debug_assert_eq!(gl::GetError(), gl::NO_ERROR);
         }
 
+        let max_samples = gl_integer(gl::MAX_SAMPLES).max(0) as u32;
+        let supported_antialiasing_levels: Vec<u8> = ANTIALIASING_LEVELS
+            .iter()
+            .copied()
+            .filter(|&level| (level as u32) <= max_samples)
+            .chain(Some(options.antialiasing))
+            .collect::<BTreeSet<u8>>()
+            .into_iter()
+            .collect();
+        debug_log!(
+            "Effective antialiasing level is {}x (supported levels: {:?})",
+            options.antialiasing,
+            supported_antialiasing_levels
+        );
+
         let (width, height) = sdl_window.size();
         let resources = Resources::new(
             RectSize::new(width as i32, height as i32),
             gui_context.score_client.global_scores().clone(),
+            gui_context.update_checker.newer_version().clone(),
+            &gui_context.texture_pack_dir,
         )?;
 
         let mut possible_resolutions =
@@ -163,16 +193,70 @@ impl<'a> Window<'a> {
             _gl_context: gl_context,
             resources,
             possible_resolutions,
+            supported_antialiasing_levels,
             options,
             audio: AudioQueue::new(),
             next_cursor: NextCursor::new(),
             last_clock_tick: Instant::now(),
             last_event_was_clock_tick: false,
             redraw_requested: true,
+            screenshots_dir,
+            screenshot_requested: false,
+            frame_stats: FrameStatsOverlay::new(),
             debug_counter: 0,
         })
     }
 
+    fn create_sdl_window(
+        gui_context: &GuiContext,
+        options: &WindowOptions,
+    ) -> Result<(sdl2::video::Window, sdl2::video::GLContext), String> {
+        {
+            let gl_attr = gui_context.video_subsystem.gl_attr();
+            if options.antialiasing > 0 {
+                gl_attr.set_multisample_buffers(1);
+                gl_attr.set_multisample_samples(options.antialiasing);
+            } else {
+                gl_attr.set_multisample_buffers(0);
+                gl_attr.set_multisample_samples(0);
+            }
+        }
+
+        let native_resolution = gui_context.get_native_resolution()?;
+        let resolution = options.resolution.unwrap_or(native_resolution);
+        let sdl_window = {
+            let width = resolution
+                .width
+                .max(WINDOW_MIN_WIDTH)
+                .min(native_resolution.width);
+            let height = resolution
+                .height
+                .max(WINDOW_MIN_HEIGHT)
+                .min(native_resolution.height);
+            let mut builder = gui_context.video_subsystem.window(
+                WINDOW_TITLE,
+                width as u32,
+                height as u32,
+            );
+            builder.opengl();
+            if options.fullscreen {
+                if options.resolution.is_none() {
+                    builder.fullscreen_desktop();
+                } else {
+                    builder.fullscreen();
+                }
+            } else {
+                builder.position_centered();
+            };
+            builder
+                .build()
+                .map_err(|err| format!("Could not create window: {}", err))?
+        };
+
+        let gl_context = sdl_window.gl_create_context()?;
+        Ok((sdl_window, gl_context))
+    }
+
     pub fn size(&self) -> RectSize<i32> {
         let (width, height) = self.sdl_window.size();
         RectSize::new(width as i32, height as i32)
@@ -182,10 +266,44 @@ impl<'a> Window<'a> {
         &self.possible_resolutions
     }
 
+    /// Returns the antialiasing levels (in ascending order) that the GL
+    /// context supports, always including zero (no antialiasing) and the
+    /// currently-active level.
+    pub fn supported_antialiasing_levels(&self) -> &[u8] {
+        &self.supported_antialiasing_levels
+    }
+
     pub fn options(&self) -> &WindowOptions {
         &self.options
     }
 
+    /// Returns a human-readable report of the OpenGL and SDL capabilities
+    /// of this window's graphics context, for diagnosing rendering problems.
+    pub fn gl_info(&self) -> String {
+        let video = &self.gui_context.video_subsystem;
+        let sdl_version = sdl2::version::version();
+        let lines = vec![
+            ("GL version", gl_string(gl::VERSION)),
+            ("GL vendor", gl_string(gl::VENDOR)),
+            ("GL renderer", gl_string(gl::RENDERER)),
+            ("GLSL version", gl_string(gl::SHADING_LANGUAGE_VERSION)),
+            ("Max texture size", gl_integer(gl::MAX_TEXTURE_SIZE).to_string()),
+            ("Max MSAA samples", gl_integer(gl::MAX_SAMPLES).to_string()),
+            (
+                "Supported MSAA levels",
+                format!("{:?}", self.supported_antialiasing_levels),
+            ),
+            ("Active MSAA level", self.options.antialiasing.to_string()),
+            ("SDL version", sdl_version.to_string()),
+            ("SDL video driver", video.current_video_driver().to_string()),
+        ];
+        let mut info = String::new();
+        for (label, value) in lines {
+            info.push_str(&format!("{}: {}\n", label, value));
+        }
+        info
+    }
+
     pub fn resources(&self) -> &Resources {
         &self.resources
     }
@@ -205,6 +323,38 @@ impl<'a> Window<'a> {
     }
 
     pub fn next_event(&mut self) -> Event {
+        let event = self.next_console_filtered_event();
+        if let Event::Debug(ref key, _) = event {
+            if key == "ShowFps" {
+                self.frame_stats.toggle_visible();
+                self.redraw_requested = true;
+            } else if key == "GlInfo" {
+                eprint!("{}", self.gl_info());
+            }
+        }
+        event
+    }
+
+    fn next_console_filtered_event(&mut self) -> Event {
+        loop {
+            let event = self.next_unfiltered_event();
+            let console = match self.gui_context.debug_console {
+                Some(ref mut console) => console,
+                None => return event,
+            };
+            match console.filter_event(event) {
+                Some(event) => {
+                    if let Event::Debug(_, _) = event {
+                        self.redraw_requested = true;
+                    }
+                    return event;
+                }
+                None => self.redraw_requested = true,
+            }
+        }
+    }
+
+    fn next_unfiltered_event(&mut self) -> Event {
         let mut should_block = false;
         if self.last_event_was_clock_tick {
             self.last_event_was_clock_tick = false;
@@ -237,9 +387,16 @@ impl<'a> Window<'a> {
                     return Event::new_clock_tick(elapsed);
                 }
                 Some(sdl_event) => {
-                    if let Some(event) = Event::from_sdl_event(sdl_event, pump)
-                    {
-                        return event;
+                    match Event::from_sdl_event(sdl_event, pump) {
+                        Some(Event::KeyDown(ref key))
+                            if key.code == SCREENSHOT_KEYCODE =>
+                        {
+                            // Take the screenshot after the next redraw.
+                            self.screenshot_requested = true;
+                            self.redraw_requested = true;
+                        }
+                        Some(event) => return event,
+                        None => {}
                     }
                 }
             }
@@ -247,8 +404,11 @@ impl<'a> Window<'a> {
     }
 
     pub fn pump_audio(&mut self) {
-        let mut queue = self.gui_context.audio_queue.lock().unwrap();
-        queue.merge(mem::replace(&mut self.audio, AudioQueue::new()));
+        let audio = mem::replace(&mut self.audio, AudioQueue::new());
+        // If there's no audio output, just discard the queued audio.
+        if let Some(ref output) = self.gui_context.audio_output {
+            output.queue.lock().unwrap().merge(audio);
+        }
     }
 
     pub fn pump_cursor(&mut self) {
@@ -257,6 +417,17 @@ impl<'a> Window<'a> {
     }
 
     pub fn pump_video(&mut self) {
+        let draw_calls = take_draw_call_count();
+        if self.screenshot_requested {
+            self.screenshot_requested = false;
+            self.take_screenshot();
+        }
+        if let Some(ref console) = self.gui_context.debug_console {
+            let size = self.size().as_f32();
+            let matrix =
+                cgmath::ortho(0.0, size.width, size.height, 0.0, -1.0, 1.0);
+            console.draw(&self.resources, &matrix);
+        }
         if cfg!(debug_assertions) {
             let size = self.size().as_f32();
             let matrix =
@@ -273,9 +444,27 @@ impl<'a> Window<'a> {
             );
             self.debug_counter = (self.debug_counter + 1) % 1000;
         }
+        self.frame_stats.on_frame(draw_calls);
+        let size = self.size().as_f32();
+        let matrix =
+            cgmath::ortho(0.0, size.width, size.height, 0.0, -1.0, 1.0);
+        self.frame_stats.draw(&self.resources, &matrix);
         self.sdl_window.gl_swap_window();
     }
 
+    fn take_screenshot(&mut self) {
+        let size = self.size();
+        let size = RectSize::new(size.width as usize, size.height as usize);
+        let rgb = read_window_rgb_data(size);
+        match save_screenshot(&self.screenshots_dir, size, &rgb) {
+            Ok(path) => {
+                debug_log!("Saved screenshot to {:?}", path);
+                self.audio.play_sound(Sound::Beep);
+            }
+            Err(err) => debug_warn!("Failed to save screenshot: {}", err),
+        }
+    }
+
     pub fn submit_solution(&mut self, solution: SolutionData) {
         self.gui_context.score_client.submit_solution(solution);
     }
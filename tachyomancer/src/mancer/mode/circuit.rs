@@ -18,28 +18,36 @@
 // +--------------------------------------------------------------------------+
 
 use super::shared::ModeChange;
-use crate::mancer::gui::{Event, Music, Window};
+use crate::mancer::gui::{
+    write_rgb_png, write_rgba_png, Event, Music, MusicRepeat, Window,
+};
 use crate::mancer::state::GameState;
-use crate::mancer::view::{export_circuit_image, CircuitAction, CircuitView};
+use crate::mancer::view::{
+    export_circuit_image, AssetPreloader, CircuitAction, CircuitView,
+};
 use directories::UserDirs;
-use png::{self, HasParameters};
-use std::fs::File;
 use std::path::PathBuf;
 use tachy::geom::RectSize;
 use tachy::save::{Puzzle, SolutionData};
 
 //===========================================================================//
 
-pub fn run(state: &mut GameState, window: &mut Window) -> ModeChange {
+pub fn run(
+    state: &mut GameState,
+    window: &mut Window,
+    preloader: &mut AssetPreloader,
+) -> ModeChange {
     debug_assert!(state.profile().is_some());
     debug_assert!(state.edit_grid().is_some());
-    window
-        .ui()
-        .audio()
-        .play_music(music_for_puzzle(state.edit_grid().unwrap().puzzle()));
+    preloader.preload_chapter(state.latest_chapter(), &mut window.ui());
+    window.ui().audio().play_music(
+        music_for_puzzle(state.edit_grid().unwrap().puzzle()),
+        MusicRepeat::Loop,
+    );
     let mut view = {
         let grid = state.edit_grid().unwrap();
-        CircuitView::new(window, grid, state.prefs())
+        let solved = state.is_puzzle_solved(grid.puzzle());
+        CircuitView::new(window, grid, solved, state.prefs())
     };
     loop {
         match window.next_event() {
@@ -50,6 +58,10 @@ pub fn run(state: &mut GameState, window: &mut Window) -> ModeChange {
                 window.pump_video();
             }
             event => {
+                if let Event::ClockTick(ref tick) = event {
+                    let puzzle = state.edit_grid().unwrap().puzzle();
+                    state.add_puzzle_seconds_spent(puzzle, tick.elapsed);
+                }
                 match view.on_event(
                     &event,
                     &mut window.ui(),
@@ -68,11 +80,72 @@ pub fn run(state: &mut GameState, window: &mut Window) -> ModeChange {
                     Some(CircuitAction::BackToMenuWithoutSaving) => {
                         return back_to_menu(state);
                     }
-                    Some(CircuitAction::ExportImage(score)) => {
-                        export_image(window, &mut view, state, score);
+                    Some(CircuitAction::ExportImage(score, transparent)) => {
+                        export_image(
+                            window,
+                            &mut view,
+                            state,
+                            score,
+                            transparent,
+                        );
+                    }
+                    Some(CircuitAction::ExportRecording(recording)) => {
+                        match state.write_input_recording(&recording) {
+                            Ok(path) => {
+                                view.show_export_recording_success(
+                                    &mut window.ui(),
+                                    state.prefs(),
+                                    &path.to_string_lossy(),
+                                );
+                            }
+                            Err(error) => {
+                                view.show_export_recording_error(
+                                    &mut window.ui(),
+                                    state.prefs(),
+                                    &error,
+                                );
+                            }
+                        }
+                    }
+                    Some(CircuitAction::VerifyReference) => {
+                        let result = state.verify_against_sandbox_reference();
+                        view.show_reference_results(
+                            &mut window.ui(),
+                            state.prefs(),
+                            result,
+                        );
+                    }
+                    Some(CircuitAction::ChooseSnapshotSlot) => {
+                        let slot_names = state.snapshot_slot_names();
+                        view.show_snapshot_slots_dialog(
+                            &mut window.ui(),
+                            state.prefs(),
+                            slot_names,
+                        );
+                    }
+                    Some(CircuitAction::SaveSnapshot(slot, name)) => {
+                        if let Err(err) = state.save_snapshot(slot, &name) {
+                            view.show_snapshot_error(
+                                &mut window.ui(),
+                                state.prefs(),
+                                &err,
+                            );
+                        }
+                    }
+                    Some(CircuitAction::LoadSnapshot(slot)) => {
+                        match state.load_snapshot_into_edit_grid(slot) {
+                            Ok(()) => window.ui().request_redraw(),
+                            Err(err) => {
+                                view.show_snapshot_error(
+                                    &mut window.ui(),
+                                    state.prefs(),
+                                    &err,
+                                );
+                            }
+                        }
                     }
                     Some(CircuitAction::Victory(solution)) => {
-                        record_score(window, &mut view, state, solution);
+                        record_score(window, &mut view, state, *solution);
                     }
                     None => {}
                 }
@@ -136,12 +209,20 @@ fn export_image(
     view: &mut CircuitView,
     state: &GameState,
     score: u32,
+    transparent: bool,
 ) {
     debug_assert!(state.edit_grid().is_some());
     let grid = state.edit_grid().unwrap();
     let name = format!("{} {}", grid.puzzle().title(), state.circuit_name());
-    let (size, rgb) = export_circuit_image(window.resources(), grid, score);
-    match save_png(&name, size, &rgb) {
+    let labeled = state.prefs().label_exported_images();
+    let (size, data) = export_circuit_image(
+        window.resources(),
+        grid,
+        score,
+        labeled,
+        transparent,
+    );
+    match save_png(&name, size, &data, transparent) {
         Ok(path) => {
             view.show_export_image_success(
                 &mut window.ui(),
@@ -163,10 +244,13 @@ fn is_alphanum_or_period(ch: char) -> bool {
     ch == '.' || ch.is_ascii_alphanumeric()
 }
 
+/// Saves image data (RGBA if `alpha` is true, otherwise RGB) as a PNG file in
+/// the player's downloads directory, and returns the path of the new file.
 fn save_png(
     name: &str,
     size: RectSize<usize>,
-    rgb: &[u8],
+    data: &[u8],
+    alpha: bool,
 ) -> Result<PathBuf, String> {
     let user_dirs = UserDirs::new()
         .ok_or_else(|| "No valid home directory found.".to_string())?;
@@ -180,12 +264,11 @@ fn save_png(
         counter += 1;
         png_path = downloads_dir.join(format!("{}_{}.png", name, counter));
     }
-    let png_file = File::create(&png_path).map_err(|err| err.to_string())?;
-    let mut encoder =
-        png::Encoder::new(png_file, size.width as u32, size.height as u32);
-    encoder.set(png::ColorType::RGB).set(png::BitDepth::Eight);
-    let mut writer = encoder.write_header().map_err(|err| err.to_string())?;
-    writer.write_image_data(rgb).map_err(|err| err.to_string())?;
+    if alpha {
+        write_rgba_png(&png_path, size, data)?;
+    } else {
+        write_rgb_png(&png_path, size, data)?;
+    }
     Ok(png_path)
 }
 
@@ -198,6 +281,8 @@ fn record_score(
     let puzzle = solution.puzzle;
     let area = solution.circuit.size.area();
     let score = solution.score;
+    let stats = state.edit_grid().unwrap().stats();
+    debug_assert_eq!(stats.area, area);
     window.submit_solution(solution);
     match state.record_puzzle_score(puzzle, area, score) {
         Ok(()) => {
@@ -205,7 +290,7 @@ fn record_score(
                 &mut window.ui(),
                 state.prefs(),
                 puzzle,
-                area,
+                stats,
                 score,
                 state.local_scores(puzzle),
             );
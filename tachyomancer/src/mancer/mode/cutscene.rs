@@ -20,11 +20,18 @@
 use super::shared::ModeChange;
 use crate::mancer::gui::{Event, Window};
 use crate::mancer::state::GameState;
-use crate::mancer::view::{CutsceneAction, CutsceneView};
+use crate::mancer::view::{AssetPreloader, CutsceneAction, CutsceneView};
 
 //===========================================================================//
 
-pub fn run(state: &mut GameState, window: &mut Window) -> ModeChange {
+pub fn run(
+    state: &mut GameState,
+    window: &mut Window,
+    preloader: &mut AssetPreloader,
+) -> ModeChange {
+    // Once the cutscene is over, we'll usually return to the menu, so start
+    // loading the menu assets now.
+    preloader.preload_chapter(state.latest_chapter(), &mut window.ui());
     window.set_cursor_visible(false);
     let mode_change = run_internal(state, window);
     window.set_cursor_visible(true);
@@ -18,15 +18,26 @@
 // +--------------------------------------------------------------------------+
 
 use super::shared::ModeChange;
-use crate::mancer::gui::{Event, Window};
+use crate::mancer::gui::{Event, Window, WindowOptions};
 use crate::mancer::state::GameState;
-use crate::mancer::view::{MenuAction, MenuView};
+use crate::mancer::view::{AssetPreloader, MenuAction, MenuView};
 
 //===========================================================================//
 
-pub fn run(state: &mut GameState, window: &mut Window) -> ModeChange {
+pub fn run(
+    state: &mut GameState,
+    window: &mut Window,
+    preloader: &mut AssetPreloader,
+) -> ModeChange {
     debug_assert!(state.profile().is_some());
-    let mut view = MenuView::new(window, state);
+    let mut view = MenuView::new(window, state, preloader);
+    // Only one warning dialog can be shown at a time; any other pending
+    // warning will be shown the next time we return to the menu.
+    if let Some(path) = state.take_missing_location_warning() {
+        view.show_missing_location_warning(&mut window.ui(), state, &path);
+    } else if let Some(path) = state.take_read_only_warning() {
+        view.show_read_only_warning(&mut window.ui(), state, &path);
+    }
     loop {
         match window.next_event() {
             Event::Quit => return ModeChange::Quit,
@@ -78,8 +89,8 @@ pub fn run(state: &mut GameState, window: &mut Window) -> ModeChange {
                         }
                         view.update_puzzle_list(&mut ui, state);
                     }
-                    Some(MenuAction::CopyCircuit) => {
-                        match state.copy_current_circuit() {
+                    Some(MenuAction::CopyCircuit(kind)) => {
+                        match state.copy_current_circuit(kind) {
                             Ok(()) => {
                                 view.update_circuit_list(
                                     &mut window.ui(),
@@ -127,6 +138,39 @@ pub fn run(state: &mut GameState, window: &mut Window) -> ModeChange {
                             }
                         }
                     }
+                    Some(MenuAction::ImportCircuit(code)) => {
+                        match state.import_shared_circuit(&code) {
+                            Ok(notes) if !notes.is_empty() => {
+                                view.show_imported_circuit_notes(
+                                    &mut window.ui(),
+                                    state,
+                                    &notes,
+                                );
+                            }
+                            Ok(_) => match state.load_and_set_edit_grid() {
+                                Ok(()) => return ModeChange::Next,
+                                Err(err) => {
+                                    view.show_error(
+                                        &mut window.ui(),
+                                        state,
+                                        "load circuit",
+                                        &err,
+                                    );
+                                }
+                            },
+                            Err(err) => {
+                                view.show_error(
+                                    &mut window.ui(),
+                                    state,
+                                    "import circuit",
+                                    &err,
+                                );
+                            }
+                        }
+                        let mut ui = window.ui();
+                        view.update_puzzle_list(&mut ui, state);
+                        view.update_circuit_list(&mut ui, state);
+                    }
                     Some(MenuAction::RenameCircuit(name)) => {
                         match state.rename_current_circuit(&name) {
                             Ok(()) => {
@@ -190,6 +234,29 @@ pub fn run(state: &mut GameState, window: &mut Window) -> ModeChange {
                             }
                         }
                     }
+                    Some(MenuAction::ResetAllSettings) => {
+                        debug_log!("Resetting all settings to defaults");
+                        state.prefs_mut().reset_all();
+                        let prefs = state.prefs();
+                        let mut ui = window.ui();
+                        ui.audio().set_sound_volume_percent(
+                            prefs.sound_volume_percent(),
+                        );
+                        ui.audio().set_music_volume_percent(
+                            prefs.music_volume_percent(),
+                        );
+                        let options = WindowOptions {
+                            antialiasing: prefs.antialiasing(),
+                            fullscreen: prefs.fullscreen(),
+                            resolution: prefs.resolution(),
+                        };
+                        if &options != window.options() {
+                            return ModeChange::RebootWindow(options);
+                        }
+                        // Rebuild the menu view so that all of the settings
+                        // widgets reflect the reset values.
+                        return ModeChange::Next;
+                    }
                     Some(MenuAction::QuitGame) => return ModeChange::Quit,
                     None => {}
                 }
@@ -20,19 +20,24 @@
 use super::shared::ModeChange;
 use crate::mancer::gui::Window;
 use crate::mancer::state::GameState;
+use crate::mancer::view::AssetPreloader;
 
 //===========================================================================//
 
-pub fn run_mode(state: &mut GameState, window: &mut Window) -> ModeChange {
+pub fn run_mode(
+    state: &mut GameState,
+    window: &mut Window,
+    preloader: &mut AssetPreloader,
+) -> ModeChange {
     window.ui().request_redraw();
     if state.cutscene().is_some() {
-        super::cutscene::run(state, window)
+        super::cutscene::run(state, window, preloader)
     } else if state.profile().is_none() {
         super::begin::run(state, window)
     } else if state.edit_grid().is_some() {
-        super::circuit::run(state, window)
+        super::circuit::run(state, window, preloader)
     } else {
-        super::menu::run(state, window)
+        super::menu::run(state, window, preloader)
     }
 }
 
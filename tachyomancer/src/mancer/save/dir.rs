@@ -18,13 +18,22 @@
 // +--------------------------------------------------------------------------+
 
 use super::encode::{decode_name, encode_name};
+use super::error::SaveError;
 use super::prefs::Prefs;
 use super::profile::{is_valid_profile_name, Profile};
+use super::report::{
+    IssueReport, INPUT_RECORDING_FILE_STEM, ISSUE_REPORT_FILE_NAME,
+};
 use super::score::GlobalScoresDir;
-use directories::ProjectDirs;
+use directories::{BaseDirs, ProjectDirs};
 use std::collections::{btree_set, BTreeSet};
+use std::env;
 use std::fs;
-use std::path::PathBuf;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tachy::save::SolutionData;
 use unicase::UniCase;
 
 //===========================================================================//
@@ -33,32 +42,124 @@ use unicase::UniCase;
 // ensure that it cannot conflict with any encoded profile name.
 const GLOBAL_SCORES_DIR_NAME: &str = "global.scores";
 
+// Note: like GLOBAL_SCORES_DIR_NAME, this dir name needs to have a period to
+// ensure that it cannot conflict with any encoded profile name.
+const TEXTURE_PACK_DIR_NAME: &str = "texture.pack";
+
+// Note: like GLOBAL_SCORES_DIR_NAME, this dir name needs to have a period to
+// ensure that it cannot conflict with any encoded profile name.
+const SCREENSHOTS_DIR_NAME: &str = "screen.shots";
+
 const PREFS_FILE_NAME: &str = "prefs.toml";
 
+const SANDBOX_REFERENCE_FILE_NAME: &str = "sandbox_reference.toml";
+
+// Note: like GLOBAL_SCORES_DIR_NAME, this file name needs to have a period to
+// ensure that it cannot conflict with any encoded profile name.
+const WRITE_TEST_FILE_NAME: &str = "write.test";
+
+// The name of the file (within the default save data directory) that records
+// where the player has chosen to move their save data to, if anywhere.  Like
+// GLOBAL_SCORES_DIR_NAME, this needs to have a period.
+const LOCATION_FILE_NAME: &str = "save.location";
+
+// The name prefix of the directory (within the OS temp dir) that we fall back
+// to if the real save data directory is not writable.  Each run creates its
+// own uniquely-named directory, so that concurrent instances (or other users)
+// can't interfere with each other, and removes it again when the SaveDir is
+// dropped on exit.
+const FALLBACK_DIR_PREFIX: &str = "tachyomancer-fallback-save";
+
+// How many differently-named fallback directories to try creating before
+// giving up:
+const MAX_FALLBACK_DIR_ATTEMPTS: u32 = 16;
+
 //===========================================================================//
 
 pub struct SaveDir {
     base_path: PathBuf,
+    location_file_path: Option<PathBuf>,
+    missing_location_path: Option<PathBuf>,
+    read_only_path: Option<PathBuf>,
+    fallback_path: Option<PathBuf>,
     prefs: Prefs,
     profile_names: BTreeSet<UniCase<String>>,
 }
 
 impl SaveDir {
-    pub fn create_or_load(path: &Option<PathBuf>) -> Result<SaveDir, String> {
-        // Get or create save dir.
-        let base_path: PathBuf = match path {
-            Some(p) => p.clone(),
-            None => get_default_save_dir_path().map_err(|err| {
-                format!("Could not find save data directory: {}", err)
-            })?,
-        };
+    pub fn create_or_load(
+        path: &Option<PathBuf>,
+    ) -> Result<SaveDir, SaveError> {
+        // Get or create save dir.  If the player has moved their save data
+        // elsewhere (see relocate), the default save dir records where.  If
+        // that location has gone missing (e.g. because it's on a drive that
+        // isn't mounted), fall back to the default save dir, and remember to
+        // warn the player (see missing_location_path).
+        let mut base_path: PathBuf;
+        let location_file_path: Option<PathBuf>;
+        let mut missing_location_path = None;
+        match path {
+            Some(p) => {
+                base_path = p.clone();
+                location_file_path = None;
+            }
+            None => {
+                let default_path =
+                    get_default_save_dir_path().ok_or_else(|| {
+                        SaveError::NotFound(
+                            "Could not find save data directory: \
+                             No valid home directory found."
+                                .to_string(),
+                        )
+                    })?;
+                let location_path = default_path.join(LOCATION_FILE_NAME);
+                base_path = match read_location_file(&location_path) {
+                    Some(path) if path.is_dir() => path,
+                    Some(path) => {
+                        debug_warn!("Save data location {:?} not found", path);
+                        missing_location_path = Some(path);
+                        default_path
+                    }
+                    None => default_path,
+                };
+                location_file_path = Some(location_path);
+            }
+        }
         debug_log!("Using save data directory: {:?}", base_path);
         if !base_path.exists() {
             fs::create_dir_all(&base_path).map_err(|err| {
-                format!("Could not create save data directory: {}", err)
+                SaveError::Io(
+                    "Could not create save data directory".to_string(),
+                    err,
+                )
             })?;
         }
 
+        // If the save data directory isn't writable (e.g. because it's on
+        // read-only media), copy its contents to a temporary directory and
+        // use that instead, so that the game remains playable; the player is
+        // warned that their progress won't be kept (see read_only_path).
+        let mut read_only_path = None;
+        let mut fallback_path = None;
+        if let Err(err) = test_writable(&base_path) {
+            debug_warn!("Save data directory is not writable: {}", err);
+            let temp_path = create_fallback_dir()?;
+            debug_log!("Falling back to {:?}", temp_path);
+            let copied = copy_dir_contents(
+                &base_path,
+                &temp_path,
+                &[LOCATION_FILE_NAME, SCREENSHOTS_DIR_NAME],
+            )
+            .and_then(|()| test_writable(&temp_path));
+            if let Err(err) = copied {
+                let _ = fs::remove_dir_all(&temp_path);
+                return Err(err);
+            }
+            fallback_path = Some(temp_path.clone());
+            read_only_path = Some(base_path);
+            base_path = temp_path;
+        }
+
         // Load prefs.
         let prefs_path = base_path.join(PREFS_FILE_NAME);
         let mut prefs = Prefs::create_or_load(&prefs_path)?;
@@ -66,20 +167,25 @@ impl SaveDir {
         // Load list of profiles.
         let mut profile_names = BTreeSet::<UniCase<String>>::new();
         let entries = base_path.read_dir().map_err(|err| {
-            format!("Could not read contents of save data directory: {}", err)
+            SaveError::Io(
+                "Could not read contents of save data directory".to_string(),
+                err,
+            )
         })?;
         for entry_result in entries {
             let entry = entry_result.map_err(|err| {
-                format!(
-                    "Error while reading contents of save data \
-                     directory: {}",
-                    err
+                SaveError::Io(
+                    "Error while reading contents of save data directory"
+                        .to_string(),
+                    err,
                 )
             })?;
             let entry_path = entry.path();
             if !entry_path.is_dir()
                 || entry_path.file_name()
                     == Some(GLOBAL_SCORES_DIR_NAME.as_ref())
+                || entry_path.file_name()
+                    == Some(TEXTURE_PACK_DIR_NAME.as_ref())
             {
                 continue;
             }
@@ -112,7 +218,108 @@ impl SaveDir {
             prefs.save()?;
         }
 
-        Ok(SaveDir { base_path, prefs, profile_names })
+        Ok(SaveDir {
+            base_path,
+            location_file_path,
+            missing_location_path,
+            read_only_path,
+            fallback_path,
+            prefs,
+            profile_names,
+        })
+    }
+
+    /// Returns the path of the directory where save data is stored.
+    pub fn path(&self) -> &Path {
+        &self.base_path
+    }
+
+    /// Moves the save data to a new directory, which must not already
+    /// contain anything.  All save data is first copied to the new location,
+    /// and only once that has succeeded does this switch over to using it
+    /// (now and on future launches).  The data in the old location is left
+    /// in place.
+    pub fn relocate(&mut self, new_path: &Path) -> Result<(), SaveError> {
+        let location_file_path =
+            self.location_file_path.clone().ok_or_else(|| {
+                SaveError::Invalid(
+                    "The save data location was set by the --save_dir flag"
+                        .to_string(),
+                )
+            })?;
+        if !new_path.is_absolute() {
+            return Err(SaveError::Invalid(format!(
+                "Not an absolute path: {:?}",
+                new_path
+            )));
+        }
+        if new_path == self.base_path {
+            return Ok(());
+        }
+        if new_path.starts_with(&self.base_path) {
+            return Err(SaveError::Invalid(format!(
+                "Cannot move save data into a subdirectory of {:?}",
+                self.base_path
+            )));
+        }
+        if new_path.exists() && !is_empty_dir(new_path)? {
+            return Err(SaveError::Invalid(format!(
+                "Directory {:?} is not empty",
+                new_path
+            )));
+        }
+
+        // Copy everything over.
+        self.prefs.save()?;
+        debug_log!(
+            "Copying save data from {:?} to {:?}",
+            self.base_path,
+            new_path
+        );
+        copy_dir_contents(&self.base_path, new_path, &[LOCATION_FILE_NAME])?;
+        test_writable(new_path)?;
+        let prefs = Prefs::create_or_load(&new_path.join(PREFS_FILE_NAME))?;
+
+        // Switch over to the new location.
+        if Some(new_path) == location_file_path.parent() {
+            if location_file_path.exists() {
+                fs::remove_file(&location_file_path).map_err(|err| {
+                    SaveError::Io(
+                        format!("Could not remove {:?}", location_file_path),
+                        err,
+                    )
+                })?;
+            }
+        } else {
+            fs::write(
+                &location_file_path,
+                new_path.to_string_lossy().as_ref(),
+            )
+            .map_err(|err| {
+                SaveError::Io(
+                    format!("Could not write {:?}", location_file_path),
+                    err,
+                )
+            })?;
+        }
+        debug_log!("Now using save data directory: {:?}", new_path);
+        self.base_path = new_path.to_path_buf();
+        self.prefs = prefs;
+        Ok(())
+    }
+
+    /// If the save data was moved to a directory that could not be found on
+    /// startup, returns that directory's path.  In that case, the default
+    /// save data directory is being used instead.
+    pub fn missing_location_path(&self) -> Option<&Path> {
+        self.missing_location_path.as_deref()
+    }
+
+    /// If the requested save data directory turned out not to be writable,
+    /// returns its path.  In that case, save data is being written to a
+    /// temporary directory instead, and will not persist.
+    pub fn read_only_path(&self) -> Option<&Path> {
+        self.read_only_path.as_deref()
     }
 
     pub fn prefs(&self) -> &Prefs {
@@ -123,7 +330,7 @@ impl SaveDir {
         &mut self.prefs
     }
 
-    pub fn save(&mut self) -> Result<(), String> {
+    pub fn save(&mut self) -> Result<(), SaveError> {
         self.prefs.save()
     }
 
@@ -160,7 +367,7 @@ impl SaveDir {
 
     pub fn load_current_profile_if_any(
         &self,
-    ) -> Result<Option<Profile>, String> {
+    ) -> Result<Option<Profile>, SaveError> {
         if let Some(name) = self.prefs.current_profile() {
             let path = self.base_path.join(encode_name(name));
             let profile = Profile::create_or_load(name.to_string(), &path)?;
@@ -170,12 +377,18 @@ impl SaveDir {
         }
     }
 
-    pub fn load_profile(&self, name: &str) -> Result<Profile, String> {
+    pub fn load_profile(&self, name: &str) -> Result<Profile, SaveError> {
         if !is_valid_profile_name(name) {
-            return Err(format!("Invalid profile name: {:?}", name));
+            return Err(SaveError::Invalid(format!(
+                "Invalid profile name: {:?}",
+                name
+            )));
         }
         if !self.has_profile(name) {
-            return Err(format!("No such profile: {:?}", name));
+            return Err(SaveError::NotFound(format!(
+                "No such profile: {:?}",
+                name
+            )));
         }
         let name = self.canonicalize_profile_name(name);
         let path = self.base_path.join(encode_name(&name));
@@ -186,9 +399,12 @@ impl SaveDir {
     pub fn create_or_load_and_set_profile(
         &mut self,
         name: &str,
-    ) -> Result<Profile, String> {
+    ) -> Result<Profile, SaveError> {
         if !is_valid_profile_name(name) {
-            return Err(format!("Invalid profile name: {:?}", name));
+            return Err(SaveError::Invalid(format!(
+                "Invalid profile name: {:?}",
+                name
+            )));
         }
         let name = self.canonicalize_profile_name(name);
         let is_current_profile = self.current_profile_is(&name);
@@ -202,12 +418,18 @@ impl SaveDir {
         return Ok(profile);
     }
 
-    pub fn delete_profile(&mut self, name: &str) -> Result<(), String> {
+    pub fn delete_profile(&mut self, name: &str) -> Result<(), SaveError> {
         if !is_valid_profile_name(name) {
-            return Err(format!("Invalid profile name: {:?}", name));
+            return Err(SaveError::Invalid(format!(
+                "Invalid profile name: {:?}",
+                name
+            )));
         }
         if !self.has_profile(name) {
-            return Err(format!("No such profile: {:?}", name));
+            return Err(SaveError::NotFound(format!(
+                "No such profile: {:?}",
+                name
+            )));
         }
         let name = self.canonicalize_profile_name(name);
         let is_current_profile = self.current_profile_is(&name);
@@ -220,22 +442,110 @@ impl SaveDir {
         let path = self.base_path.join(encode_name(&name));
         debug_log!("Deleting profile {:?} from {:?}", name, path);
         fs::remove_dir_all(&path).map_err(|err| {
-            format!(
-                "Could not delete profile {:?} data from {:?}: {}",
-                name, path, err
+            SaveError::Io(
+                format!(
+                    "Could not delete profile {:?} data from {:?}",
+                    name, path
+                ),
+                err,
             )
         })?;
         return Ok(());
     }
 
+    /// Writes an issue report into the save dir, redacting the save dir and
+    /// home dir paths, and returns the path of the written file.
+    pub fn write_issue_report(
+        &self,
+        report: &IssueReport,
+    ) -> Result<PathBuf, SaveError> {
+        let home_dir =
+            BaseDirs::new().map(|dirs| dirs.home_dir().to_path_buf());
+        let mut redactions: Vec<(&Path, &str)> =
+            vec![(&self.base_path, "<SAVE_DIR>")];
+        if let Some(ref home_dir) = home_dir {
+            redactions.push((home_dir, "<HOME>"));
+        }
+        let contents = report.format(&redactions)?;
+        let path = self.base_path.join(ISSUE_REPORT_FILE_NAME);
+        debug_log!("Writing issue report to {:?}", path);
+        fs::write(&path, contents).map_err(|err| {
+            SaveError::Io(
+                format!("Could not write issue report to {:?}", path),
+                err,
+            )
+        })?;
+        Ok(path)
+    }
+
+    /// Writes a recording of an evaluation run (the circuit along with any
+    /// inputs given to it) into a new file in the save dir, so that it can be
+    /// attached to a bug report, and returns the path of the written file.
+    pub fn write_input_recording(
+        &self,
+        recording: &SolutionData,
+    ) -> Result<PathBuf, SaveError> {
+        let contents = recording.serialize_to_string()?;
+        let mut path =
+            self.base_path.join(format!("{}.toml", INPUT_RECORDING_FILE_STEM));
+        let mut counter: u64 = 0;
+        while path.exists() {
+            counter += 1;
+            path = self.base_path.join(format!(
+                "{}_{}.toml",
+                INPUT_RECORDING_FILE_STEM, counter
+            ));
+        }
+        debug_log!("Writing input recording to {:?}", path);
+        fs::write(&path, contents).map_err(|err| {
+            SaveError::Io(
+                format!("Could not write input recording to {:?}", path),
+                err,
+            )
+        })?;
+        Ok(path)
+    }
+
+    /// Returns the path of the file from which the reference table for
+    /// checking sandbox circuits is loaded.  This file is optional, and may
+    /// not exist.
+    pub fn sandbox_reference_path(&self) -> PathBuf {
+        self.base_path.join(SANDBOX_REFERENCE_FILE_NAME)
+    }
+
+    /// Returns the path of the directory from which replacement textures are
+    /// loaded.  This directory is optional, and may not exist.
+    pub fn texture_pack_dir(&self) -> PathBuf {
+        self.base_path.join(TEXTURE_PACK_DIR_NAME)
+    }
+
+    /// Returns the path of the directory into which screenshots are saved.
+    /// This directory is created on demand, and may not exist yet.
+    pub fn screenshots_dir(&self) -> PathBuf {
+        self.base_path.join(SCREENSHOTS_DIR_NAME)
+    }
+
     pub fn create_or_load_global_scores(
         &mut self,
-    ) -> Result<GlobalScoresDir, String> {
+    ) -> Result<GlobalScoresDir, SaveError> {
         let path = self.base_path.join(GLOBAL_SCORES_DIR_NAME);
         GlobalScoresDir::create_or_load(&path)
     }
 }
 
+impl Drop for SaveDir {
+    fn drop(&mut self) {
+        // The player was warned that nothing written to the temporary
+        // fallback directory would be kept, so don't leave it lying around.
+        if let Some(ref path) = self.fallback_path {
+            debug_log!("Removing fallback save directory {:?}", path);
+            if let Err(err) = fs::remove_dir_all(path) {
+                debug_warn!("Could not remove {:?}: {}", path, err);
+            }
+        }
+    }
+}
+
 //===========================================================================//
 
 pub struct ProfileNamesIter<'a> {
@@ -252,10 +562,134 @@ impl<'a> Iterator for ProfileNamesIter<'a> {
 
 //===========================================================================//
 
-fn get_default_save_dir_path() -> Result<PathBuf, String> {
-    let project_dirs = ProjectDirs::from("games", "mdsteele", "Tachyomancer")
-        .ok_or_else(|| "No valid home directory found.".to_string())?;
-    Ok(project_dirs.data_dir().to_path_buf())
+fn get_default_save_dir_path() -> Option<PathBuf> {
+    let project_dirs = ProjectDirs::from("games", "mdsteele", "Tachyomancer")?;
+    Some(project_dirs.data_dir().to_path_buf())
+}
+
+/// Reads the save data location recorded by `SaveDir::relocate`, if any.
+/// Returns `None` if there is no location recorded (or it can't be read).
+/// Note that the recorded directory might not exist.
+fn read_location_file(path: &Path) -> Option<PathBuf> {
+    if !path.exists() {
+        return None;
+    }
+    match fs::read_to_string(path) {
+        Ok(string) => Some(PathBuf::from(string.trim())),
+        Err(err) => {
+            debug_warn!("Could not read {:?}: {}", path, err);
+            None
+        }
+    }
+}
+
+/// Returns true if the given directory contains nothing (or nothing but a
+/// save location file).
+fn is_empty_dir(dir: &Path) -> Result<bool, SaveError> {
+    let entries = dir.read_dir().map_err(|err| {
+        SaveError::Io(format!("Could not read contents of {:?}", dir), err)
+    })?;
+    for entry_result in entries {
+        let entry = entry_result.map_err(|err| {
+            SaveError::Io(
+                format!("Error while reading contents of {:?}", dir),
+                err,
+            )
+        })?;
+        if entry.file_name() != LOCATION_FILE_NAME {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+/// Checks that files can be written to the given directory, by writing (and
+/// then removing) a small test file.
+fn test_writable(dir: &Path) -> Result<(), SaveError> {
+    let path = dir.join(WRITE_TEST_FILE_NAME);
+    fs::write(&path, b"test").map_err(|err| {
+        SaveError::Io(format!("Could not write to {:?}", dir), err)
+    })?;
+    fs::remove_file(&path).map_err(|err| {
+        SaveError::Io(format!("Could not remove {:?}", path), err)
+    })
+}
+
+/// Creates a new, empty, uniquely-named directory within the OS temp dir to
+/// hold a copy of read-only save data.  Fails rather than reusing any existing
+/// file or directory (including a symlink) at the chosen path.
+fn create_fallback_dir() -> Result<PathBuf, SaveError> {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.subsec_nanos())
+        .unwrap_or(0);
+    let mut builder = fs::DirBuilder::new();
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::DirBuilderExt;
+        builder.mode(0o700);
+    }
+    let mut attempt = 0;
+    loop {
+        let name = format!(
+            "{}-{}-{:08x}",
+            FALLBACK_DIR_PREFIX,
+            process::id(),
+            nanos.wrapping_add(attempt)
+        );
+        let path = env::temp_dir().join(name);
+        match builder.create(&path) {
+            Ok(()) => return Ok(path),
+            Err(ref err)
+                if err.kind() == io::ErrorKind::AlreadyExists
+                    && attempt + 1 < MAX_FALLBACK_DIR_ATTEMPTS =>
+            {
+                attempt += 1;
+            }
+            Err(err) => {
+                return Err(SaveError::Io(
+                    format!("Could not create {:?}", path),
+                    err,
+                ));
+            }
+        }
+    }
+}
+
+/// Recursively copies the contents of `from` into `to` (creating `to` if
+/// necessary), skipping any top-level entries with the given names.
+fn copy_dir_contents(
+    from: &Path,
+    to: &Path,
+    skip: &[&str],
+) -> Result<(), SaveError> {
+    fs::create_dir_all(to).map_err(|err| {
+        SaveError::Io(format!("Could not create {:?}", to), err)
+    })?;
+    let entries = from.read_dir().map_err(|err| {
+        SaveError::Io(format!("Could not read contents of {:?}", from), err)
+    })?;
+    for entry_result in entries {
+        let entry = entry_result.map_err(|err| {
+            SaveError::Io(
+                format!("Error while reading contents of {:?}", from),
+                err,
+            )
+        })?;
+        if skip.iter().any(|&name| entry.file_name() == name) {
+            continue;
+        }
+        let entry_path = entry.path();
+        let dest_path = to.join(entry.file_name());
+        if entry_path.is_dir() {
+            copy_dir_contents(&entry_path, &dest_path, &[])?;
+        } else {
+            fs::copy(&entry_path, &dest_path).map_err(|err| {
+                SaveError::Io(format!("Could not copy {:?}", entry_path), err)
+            })?;
+        }
+    }
+    Ok(())
 }
 
 //===========================================================================//
@@ -0,0 +1,130 @@
+// +--------------------------------------------------------------------------+
+// | Copyright 2018 Matthew D. Steele <mdsteele@alum.mit.edu>                 |
+// |                                                                          |
+// | This file is part of Tachyomancer.                                       |
+// |                                                                          |
+// | Tachyomancer is free software: you can redistribute it and/or modify it  |
+// | under the terms of the GNU General Public License as published by the    |
+// | Free Software Foundation, either version 3 of the License, or (at your   |
+// | option) any later version.                                               |
+// |                                                                          |
+// | Tachyomancer is distributed in the hope that it will be useful, but      |
+// | WITHOUT ANY WARRANTY; without even the implied warranty of               |
+// | MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU        |
+// | General Public License for details.                                      |
+// |                                                                          |
+// | You should have received a copy of the GNU General Public License along  |
+// | with Tachyomancer.  If not, see <http://www.gnu.org/licenses/>.          |
+// +--------------------------------------------------------------------------+
+
+use std::error;
+use std::fmt;
+use std::io;
+
+//===========================================================================//
+
+/// An error that occurred while reading or writing save data.
+#[derive(Debug)]
+pub enum SaveError {
+    /// A filesystem operation failed.  The string describes what was being
+    /// attempted when the error occurred.
+    Io(String, io::Error),
+    /// Some save data could not be serialized or parsed.
+    Format(String),
+    /// The requested profile, circuit, or snapshot does not exist.
+    NotFound(String),
+    /// A name or other argument was invalid, or would clobber existing data.
+    Invalid(String),
+    /// An error reported by the `tachy` data layer (e.g. while loading or
+    /// saving a circuit file).
+    Other(String),
+}
+
+impl SaveError {
+    /// Returns true if this error was caused by something (a file, directory,
+    /// profile, circuit, etc.) not existing.
+    pub fn is_not_found(&self) -> bool {
+        match self {
+            SaveError::Io(_, err) => err.kind() == io::ErrorKind::NotFound,
+            SaveError::NotFound(_) => true,
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for SaveError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SaveError::Io(context, err) => {
+                write!(formatter, "{}: {}", context, err)
+            }
+            SaveError::Format(message)
+            | SaveError::NotFound(message)
+            | SaveError::Invalid(message)
+            | SaveError::Other(message) => formatter.write_str(message),
+        }
+    }
+}
+
+impl error::Error for SaveError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            SaveError::Io(_, err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<String> for SaveError {
+    fn from(message: String) -> SaveError {
+        SaveError::Other(message)
+    }
+}
+
+impl From<SaveError> for String {
+    fn from(error: SaveError) -> String {
+        error.to_string()
+    }
+}
+
+//===========================================================================//
+
+#[cfg(test)]
+mod tests {
+    use super::SaveError;
+    use std::io;
+
+    #[test]
+    fn display_preserves_messages() {
+        let err = SaveError::Io(
+            "Could not write prefs file".to_string(),
+            io::Error::new(io::ErrorKind::PermissionDenied, "denied"),
+        );
+        assert_eq!(err.to_string(), "Could not write prefs file: denied");
+        let err = SaveError::NotFound("No such circuit: \"Foo\"".to_string());
+        assert_eq!(err.to_string(), "No such circuit: \"Foo\"");
+        let string: String = SaveError::Other("oops".to_string()).into();
+        assert_eq!(string, "oops");
+    }
+
+    #[test]
+    fn not_found_errors() {
+        let err = SaveError::Io(
+            "Could not read file".to_string(),
+            io::Error::new(io::ErrorKind::NotFound, "missing"),
+        );
+        assert!(err.is_not_found());
+        let err = SaveError::Io(
+            "Could not read file".to_string(),
+            io::Error::new(io::ErrorKind::PermissionDenied, "denied"),
+        );
+        assert!(!err.is_not_found());
+        assert!(
+            SaveError::NotFound("No such profile".to_string()).is_not_found()
+        );
+        assert!(!SaveError::Format("bad toml".to_string()).is_not_found());
+        assert!(!SaveError::Invalid("bad name".to_string()).is_not_found());
+    }
+}
+
+//===========================================================================//
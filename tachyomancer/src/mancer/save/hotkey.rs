@@ -40,6 +40,7 @@ use tachy::save::HotkeyCode;
     PartialOrd,
 )]
 pub enum Hotkey {
+    EvalExportRecording,
     EvalFastForward,
     EvalReset,
     EvalRunPause,
@@ -48,15 +49,23 @@ pub enum Hotkey {
     EvalStepTime,
     FlipHorz,
     FlipVert,
+    PinTooltip,
+    PlaceChip,
     RotateCcw,
     RotateCw,
+    SandboxFreezeInputs,
+    SandboxSnapshots,
+    SandboxVerifyReference,
     ScrollDown,
     ScrollLeft,
     ScrollRight,
     ScrollUp,
+    ToggleSpecTray,
+    ToggleVerifyTray,
     ZoomDefault,
     ZoomIn,
     ZoomOut,
+    ZoomToFit,
 }
 
 #[cfg_attr(rustfmt, rustfmt_skip)]
@@ -68,12 +77,14 @@ pub const HOTKEY_CATEGORIES: &[(&str, &[Hotkey])] = &[
         Hotkey::EvalStepTime,
         Hotkey::EvalStepCycle,
         Hotkey::EvalStepSubcycle,
+        Hotkey::EvalExportRecording,
     ]),
     ("Selection", &[
         Hotkey::RotateCw,
         Hotkey::RotateCcw,
         Hotkey::FlipHorz,
         Hotkey::FlipVert,
+        Hotkey::PlaceChip,
     ]),
     ("Camera", &[
         Hotkey::ScrollUp,
@@ -83,6 +94,17 @@ pub const HOTKEY_CATEGORIES: &[(&str, &[Hotkey])] = &[
         Hotkey::ZoomIn,
         Hotkey::ZoomOut,
         Hotkey::ZoomDefault,
+        Hotkey::ZoomToFit,
+    ]),
+    ("Interface", &[
+        Hotkey::ToggleSpecTray,
+        Hotkey::ToggleVerifyTray,
+        Hotkey::PinTooltip,
+    ]),
+    ("Sandbox", &[
+        Hotkey::SandboxSnapshots,
+        Hotkey::SandboxFreezeInputs,
+        Hotkey::SandboxVerifyReference,
     ]),
 ];
 
@@ -94,6 +116,7 @@ impl Hotkey {
 
     pub fn name(self) -> &'static str {
         match self {
+            Hotkey::EvalExportRecording => "Export input recording",
             Hotkey::EvalFastForward => "Fast-forward evaluation",
             Hotkey::EvalReset => "Reset evaluation",
             Hotkey::EvalRunPause => "Run/pause evaluation",
@@ -102,20 +125,29 @@ impl Hotkey {
             Hotkey::EvalStepTime => "Advance by one time step",
             Hotkey::FlipHorz => "Flip horzizontally",
             Hotkey::FlipVert => "Flip vertically",
+            Hotkey::PinTooltip => "Pin/unpin tooltip",
+            Hotkey::PlaceChip => "Place chips by name",
             Hotkey::RotateCcw => "Rotate counterclockwise",
             Hotkey::RotateCw => "Rotate clockwise",
+            Hotkey::SandboxFreezeInputs => "Freeze/unfreeze inputs",
+            Hotkey::SandboxSnapshots => "Save/load snapshots",
+            Hotkey::SandboxVerifyReference => "Check against reference",
             Hotkey::ScrollDown => "Scroll down",
             Hotkey::ScrollLeft => "Scroll left",
             Hotkey::ScrollRight => "Scroll right",
             Hotkey::ScrollUp => "Scroll up",
+            Hotkey::ToggleSpecTray => "Show/hide specification",
+            Hotkey::ToggleVerifyTray => "Show/hide verification",
             Hotkey::ZoomDefault => "Zoom to actual size",
             Hotkey::ZoomIn => "Zoom in",
             Hotkey::ZoomOut => "Zoom out",
+            Hotkey::ZoomToFit => "Zoom to fit circuit",
         }
     }
 
     pub fn default_keycode(self) -> HotkeyCode {
         match self {
+            Hotkey::EvalExportRecording => HotkeyCode::Backslash,
             Hotkey::EvalFastForward => HotkeyCode::G,
             Hotkey::EvalReset => HotkeyCode::T,
             Hotkey::EvalRunPause => HotkeyCode::R,
@@ -124,36 +156,53 @@ impl Hotkey {
             Hotkey::EvalStepTime => HotkeyCode::F,
             Hotkey::FlipHorz => HotkeyCode::A,
             Hotkey::FlipVert => HotkeyCode::W,
+            Hotkey::PinTooltip => HotkeyCode::P,
+            Hotkey::PlaceChip => HotkeyCode::C,
             Hotkey::RotateCcw => HotkeyCode::Q,
             Hotkey::RotateCw => HotkeyCode::E,
+            Hotkey::SandboxFreezeInputs => HotkeyCode::I,
+            Hotkey::SandboxSnapshots => HotkeyCode::B,
+            Hotkey::SandboxVerifyReference => HotkeyCode::K,
             Hotkey::ScrollDown => HotkeyCode::Down,
             Hotkey::ScrollLeft => HotkeyCode::Left,
             Hotkey::ScrollRight => HotkeyCode::Right,
             Hotkey::ScrollUp => HotkeyCode::Up,
+            Hotkey::ToggleSpecTray => HotkeyCode::LeftBracket,
+            Hotkey::ToggleVerifyTray => HotkeyCode::RightBracket,
             Hotkey::ZoomDefault => HotkeyCode::Num0,
             Hotkey::ZoomIn => HotkeyCode::Equals,
             Hotkey::ZoomOut => HotkeyCode::Minus,
+            Hotkey::ZoomToFit => HotkeyCode::Z,
         }
     }
 
     pub fn default_for_keycode(code: HotkeyCode) -> Option<Hotkey> {
         match code {
             HotkeyCode::A => Some(Hotkey::FlipHorz),
+            HotkeyCode::B => Some(Hotkey::SandboxSnapshots),
+            HotkeyCode::C => Some(Hotkey::PlaceChip),
             HotkeyCode::D => Some(Hotkey::EvalStepCycle),
             HotkeyCode::E => Some(Hotkey::RotateCw),
             HotkeyCode::F => Some(Hotkey::EvalStepTime),
             HotkeyCode::G => Some(Hotkey::EvalFastForward),
+            HotkeyCode::I => Some(Hotkey::SandboxFreezeInputs),
+            HotkeyCode::K => Some(Hotkey::SandboxVerifyReference),
+            HotkeyCode::P => Some(Hotkey::PinTooltip),
             HotkeyCode::Q => Some(Hotkey::RotateCcw),
             HotkeyCode::R => Some(Hotkey::EvalRunPause),
             HotkeyCode::S => Some(Hotkey::EvalStepSubcycle),
             HotkeyCode::T => Some(Hotkey::EvalReset),
             HotkeyCode::W => Some(Hotkey::FlipVert),
+            HotkeyCode::Z => Some(Hotkey::ZoomToFit),
             HotkeyCode::Num0 => Some(Hotkey::ZoomDefault),
+            HotkeyCode::Backslash => Some(Hotkey::EvalExportRecording),
             HotkeyCode::Down => Some(Hotkey::ScrollDown),
             HotkeyCode::Equals => Some(Hotkey::ZoomIn),
             HotkeyCode::Left => Some(Hotkey::ScrollLeft),
+            HotkeyCode::LeftBracket => Some(Hotkey::ToggleSpecTray),
             HotkeyCode::Minus => Some(Hotkey::ZoomOut),
             HotkeyCode::Right => Some(Hotkey::ScrollRight),
+            HotkeyCode::RightBracket => Some(Hotkey::ToggleVerifyTray),
             HotkeyCode::Up => Some(Hotkey::ScrollUp),
             _ => None,
         }
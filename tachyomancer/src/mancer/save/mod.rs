@@ -19,19 +19,35 @@
 
 mod dir;
 mod encode;
+mod error;
 mod hotkey;
 mod menu;
 mod prefs;
 mod profile;
 mod progress;
+mod pronouns;
+mod report;
 mod score;
+mod strings;
 
 pub use self::dir::{ProfileNamesIter, SaveDir};
+pub use self::encode::encode_name;
+pub use self::error::SaveError;
 pub use self::hotkey::{Hotkey, HotkeyCodeExt, HotkeyIter, HOTKEY_CATEGORIES};
 pub use self::menu::MenuSection;
-pub use self::prefs::Prefs;
+pub use self::prefs::{
+    PrefKey, PrefValue, Prefs, MAX_DOUBLE_CLICK_MS, MAX_SIMULATION_SPEED,
+    MAX_SNAPSHOT_SLOTS, MIN_DOUBLE_CLICK_MS, MIN_SIMULATION_SPEED,
+    MIN_SNAPSHOT_SLOTS,
+};
 pub use self::profile::{Profile, PROFILE_NAME_MAX_CHARS};
-pub use self::progress::{CircuitNamesIter, CIRCUIT_NAME_MAX_CHARS};
+pub use self::progress::{
+    is_valid_circuit_rename, unique_circuit_name, CircuitCopyKind,
+    CircuitNamesIter, CIRCUIT_NAME_MAX_CHARS, SNAPSHOT_NAME_MAX_CHARS,
+};
+pub use self::pronouns::Pronouns;
+pub use self::report::IssueReport;
 pub use self::score::GlobalScoresDir;
+pub use self::strings::StringKey;
 
 //===========================================================================//
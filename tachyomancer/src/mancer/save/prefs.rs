@@ -17,19 +17,94 @@
 // | with Tachyomancer.  If not, see <http://www.gnu.org/licenses/>.          |
 // +--------------------------------------------------------------------------+
 
+use super::error::SaveError;
 use super::hotkey::{Hotkey, HotkeyCodes};
+use super::pronouns::Pronouns;
+use super::strings::StringTable;
 use rand::{self, Rng};
 use std::fs;
 use std::io;
 use std::path::{Path, PathBuf};
+use strum::IntoEnumIterator;
 use tachy::geom::RectSize;
 use tachy::save::HotkeyCode;
 use toml;
 
 //===========================================================================//
 
+// Default values for all settings.  These should only be used via
+// Prefs::default_value and the Prefs getters.
+const DEFAULT_ANTIALIASING: u8 = 0;
+const DEFAULT_FULLSCREEN: bool = true;
+const DEFAULT_RESOLUTION: Option<RectSize<i32>> = None;
 const DEFAULT_SOUND_VOLUME_PERCENT: i32 = 80;
 const DEFAULT_MUSIC_VOLUME_PERCENT: i32 = 80;
+const DEFAULT_DUCK_MUSIC: bool = false;
+const DEFAULT_SIMULATION_SPEED: f64 = 1.0;
+const DEFAULT_REDUCE_MOTION: bool = false;
+const DEFAULT_ALLOW_SKIP_CUTSCENES: bool = true;
+const DEFAULT_HIGHLIGHT_UNCONNECTED_PORTS: bool = true;
+const DEFAULT_SNAPSHOT_SLOTS: usize = 3;
+const DEFAULT_CHECK_FOR_UPDATES: bool = false;
+const DEFAULT_DOUBLE_CLICK_MS: u32 = 400;
+const DEFAULT_JUMP_TO_FAILURES: bool = true;
+const DEFAULT_EMPHASIZE_WIRE_SIZES: bool = false;
+const DEFAULT_IDLE_PAUSE_SECONDS: u32 = 300;
+const DEFAULT_LABEL_EXPORTED_IMAGES: bool = true;
+const DEFAULT_AUTO_ROUTE_WIRES: bool = false;
+
+pub const MIN_SIMULATION_SPEED: f64 = 0.25;
+pub const MAX_SIMULATION_SPEED: f64 = 16.0;
+
+// Bounds on the time window, in milliseconds, within which two clicks on the
+// same chip count as a double-click:
+pub const MIN_DOUBLE_CLICK_MS: u32 = 200;
+pub const MAX_DOUBLE_CLICK_MS: u32 = 1000;
+
+// Antialiasing level to use for prefs files written before antialiasing was
+// configurable by level, when it was stored as a simple on/off flag:
+const LEGACY_ANTIALIASING_LEVEL: u8 = 4;
+
+// Bounds on the number of snapshot slots per sandbox puzzle:
+pub const MIN_SNAPSHOT_SLOTS: usize = 1;
+pub const MAX_SNAPSHOT_SLOTS: usize = 5;
+
+// Optional file of UI string translations, stored next to the prefs file:
+const STRINGS_FILE_NAME: &str = "strings.toml";
+
+//===========================================================================//
+
+/// Identifies a single player-adjustable setting.
+#[derive(Clone, Copy, Debug, EnumIter, Eq, PartialEq)]
+pub enum PrefKey {
+    Antialiasing,
+    Fullscreen,
+    Resolution,
+    SoundVolume,
+    MusicVolume,
+    DuckMusic,
+    SimulationSpeed,
+    ReduceMotion,
+    AllowSkipCutscenes,
+    HighlightUnconnectedPorts,
+    SnapshotSlots,
+    CheckForUpdates,
+    DoubleClickMs,
+    JumpToFailures,
+    EmphasizeWireSizes,
+    IdlePauseSeconds,
+    LabelExportedImages,
+    AutoRouteWires,
+}
+
+/// The value of a single setting.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PrefValue {
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Resolution(Option<RectSize<i32>>),
+}
 
 //===========================================================================//
 
@@ -37,11 +112,26 @@ const DEFAULT_MUSIC_VOLUME_PERCENT: i32 = 80;
 struct PrefsData {
     id: Option<u64>,
     current_profile: Option<String>,
+    pronouns: Option<Pronouns>,
     antialiasing: Option<bool>,
+    antialiasing_level: Option<u8>,
     fullscreen: Option<bool>,
     resolution: Option<(i32, i32)>,
     sound_volume: Option<i32>,
     music_volume: Option<i32>,
+    duck_music: Option<bool>,
+    simulation_speed: Option<f64>,
+    reduce_motion: Option<bool>,
+    allow_skip_cutscenes: Option<bool>,
+    highlight_unconnected_ports: Option<bool>,
+    snapshot_slots: Option<usize>,
+    check_for_updates: Option<bool>,
+    double_click_ms: Option<u32>,
+    jump_to_failures: Option<bool>,
+    emphasize_wire_sizes: Option<bool>,
+    idle_pause_seconds: Option<u32>,
+    label_exported_images: Option<bool>,
+    auto_route_wires: Option<bool>,
     hotkeys: Option<HotkeyCodes>,
 }
 
@@ -55,14 +145,35 @@ impl PrefsData {
 
 //===========================================================================//
 
+fn load_strings(path: &Path) -> StringTable {
+    if !path.exists() {
+        return StringTable::english();
+    }
+    let result = fs::read_to_string(path)
+        .map_err(|err| {
+            SaveError::Io("Could not read strings file".to_string(), err)
+        })
+        .and_then(|data| StringTable::from_toml(&data));
+    match result {
+        Ok(strings) => strings,
+        Err(err) => {
+            debug_warn!("{}", err);
+            StringTable::english()
+        }
+    }
+}
+
+//===========================================================================//
+
 pub struct Prefs {
     path: PathBuf,
     data: PrefsData,
+    strings: StringTable,
     needs_save: bool,
 }
 
 impl Prefs {
-    pub fn create_or_load(path: &Path) -> Result<Prefs, String> {
+    pub fn create_or_load(path: &Path) -> Result<Prefs, SaveError> {
         let mut needs_save = false;
         let mut data = if path.exists() {
             match PrefsData::try_load(path) {
@@ -84,34 +195,233 @@ impl Prefs {
                 needs_save = true;
             }
         }
-        let mut prefs = Prefs { path: path.to_path_buf(), data, needs_save };
+        let strings = load_strings(&path.with_file_name(STRINGS_FILE_NAME));
+        let mut prefs =
+            Prefs { path: path.to_path_buf(), data, strings, needs_save };
         prefs.save()?;
         Ok(prefs)
     }
 
-    pub fn save(&mut self) -> Result<(), String> {
+    pub fn save(&mut self) -> Result<(), SaveError> {
         if !self.needs_save {
             return Ok(());
         }
         debug_log!("Saving prefs to {:?}", self.path);
-        let data = toml::to_vec(&self.data)
-            .map_err(|err| format!("Could not serialize prefs: {}", err))?;
-        fs::write(&self.path, data)
-            .map_err(|err| format!("Could not write prefs file: {}", err))?;
+        let data = toml::to_vec(&self.data).map_err(|err| {
+            SaveError::Format(format!("Could not serialize prefs: {}", err))
+        })?;
+        fs::write(&self.path, data).map_err(|err| {
+            SaveError::Io("Could not write prefs file".to_string(), err)
+        })?;
         self.needs_save = false;
         return Ok(());
     }
 
+    /// Serializes these prefs for inclusion in an issue report, leaving out
+    /// the install ID.
+    pub fn serialize_for_report(&self) -> Result<String, SaveError> {
+        let mut value = toml::Value::try_from(&self.data).map_err(|err| {
+            SaveError::Format(format!("Could not serialize prefs: {}", err))
+        })?;
+        if let Some(table) = value.as_table_mut() {
+            table.remove("id");
+        }
+        toml::to_string(&value).map_err(|err| {
+            SaveError::Format(format!("Could not serialize prefs: {}", err))
+        })
+    }
+
     pub fn install_id(&self) -> Option<u64> {
         self.data.id
     }
 
-    pub fn antialiasing(&self) -> bool {
-        self.data.antialiasing.unwrap_or(false)
+    pub fn strings(&self) -> &StringTable {
+        &self.strings
+    }
+
+    /// Returns the value that the given setting has if the player hasn't
+    /// changed it.
+    pub fn default_value(key: PrefKey) -> PrefValue {
+        match key {
+            PrefKey::Antialiasing => {
+                PrefValue::Int(DEFAULT_ANTIALIASING as i64)
+            }
+            PrefKey::Fullscreen => PrefValue::Bool(DEFAULT_FULLSCREEN),
+            PrefKey::Resolution => PrefValue::Resolution(DEFAULT_RESOLUTION),
+            PrefKey::SoundVolume => {
+                PrefValue::Int(DEFAULT_SOUND_VOLUME_PERCENT as i64)
+            }
+            PrefKey::MusicVolume => {
+                PrefValue::Int(DEFAULT_MUSIC_VOLUME_PERCENT as i64)
+            }
+            PrefKey::DuckMusic => PrefValue::Bool(DEFAULT_DUCK_MUSIC),
+            PrefKey::SimulationSpeed => {
+                PrefValue::Float(DEFAULT_SIMULATION_SPEED)
+            }
+            PrefKey::ReduceMotion => PrefValue::Bool(DEFAULT_REDUCE_MOTION),
+            PrefKey::AllowSkipCutscenes => {
+                PrefValue::Bool(DEFAULT_ALLOW_SKIP_CUTSCENES)
+            }
+            PrefKey::HighlightUnconnectedPorts => {
+                PrefValue::Bool(DEFAULT_HIGHLIGHT_UNCONNECTED_PORTS)
+            }
+            PrefKey::SnapshotSlots => {
+                PrefValue::Int(DEFAULT_SNAPSHOT_SLOTS as i64)
+            }
+            PrefKey::CheckForUpdates => {
+                PrefValue::Bool(DEFAULT_CHECK_FOR_UPDATES)
+            }
+            PrefKey::DoubleClickMs => {
+                PrefValue::Int(DEFAULT_DOUBLE_CLICK_MS as i64)
+            }
+            PrefKey::JumpToFailures => {
+                PrefValue::Bool(DEFAULT_JUMP_TO_FAILURES)
+            }
+            PrefKey::EmphasizeWireSizes => {
+                PrefValue::Bool(DEFAULT_EMPHASIZE_WIRE_SIZES)
+            }
+            PrefKey::IdlePauseSeconds => {
+                PrefValue::Int(DEFAULT_IDLE_PAUSE_SECONDS as i64)
+            }
+            PrefKey::LabelExportedImages => {
+                PrefValue::Bool(DEFAULT_LABEL_EXPORTED_IMAGES)
+            }
+            PrefKey::AutoRouteWires => {
+                PrefValue::Bool(DEFAULT_AUTO_ROUTE_WIRES)
+            }
+        }
+    }
+
+    /// Returns the current value of the given setting.
+    pub fn value(&self, key: PrefKey) -> PrefValue {
+        match key {
+            PrefKey::Antialiasing => {
+                PrefValue::Int(self.antialiasing() as i64)
+            }
+            PrefKey::Fullscreen => PrefValue::Bool(self.fullscreen()),
+            PrefKey::Resolution => PrefValue::Resolution(self.resolution()),
+            PrefKey::SoundVolume => {
+                PrefValue::Int(self.sound_volume_percent() as i64)
+            }
+            PrefKey::MusicVolume => {
+                PrefValue::Int(self.music_volume_percent() as i64)
+            }
+            PrefKey::DuckMusic => PrefValue::Bool(self.duck_music()),
+            PrefKey::SimulationSpeed => {
+                PrefValue::Float(self.simulation_speed())
+            }
+            PrefKey::ReduceMotion => PrefValue::Bool(self.reduce_motion()),
+            PrefKey::AllowSkipCutscenes => {
+                PrefValue::Bool(self.allow_skip_cutscenes())
+            }
+            PrefKey::HighlightUnconnectedPorts => {
+                PrefValue::Bool(self.highlight_unconnected_ports())
+            }
+            PrefKey::SnapshotSlots => {
+                PrefValue::Int(self.snapshot_slots() as i64)
+            }
+            PrefKey::CheckForUpdates => {
+                PrefValue::Bool(self.check_for_updates())
+            }
+            PrefKey::DoubleClickMs => {
+                PrefValue::Int(self.double_click_ms() as i64)
+            }
+            PrefKey::JumpToFailures => {
+                PrefValue::Bool(self.jump_to_failures())
+            }
+            PrefKey::EmphasizeWireSizes => {
+                PrefValue::Bool(self.emphasize_wire_sizes())
+            }
+            PrefKey::IdlePauseSeconds => {
+                PrefValue::Int(self.idle_pause_seconds() as i64)
+            }
+            PrefKey::LabelExportedImages => {
+                PrefValue::Bool(self.label_exported_images())
+            }
+            PrefKey::AutoRouteWires => {
+                PrefValue::Bool(self.auto_route_wires())
+            }
+        }
+    }
+
+    /// Returns true if the given setting currently has its default value.
+    pub fn is_default(&self, key: PrefKey) -> bool {
+        self.value(key) == Prefs::default_value(key)
     }
 
-    pub fn set_antialiasing(&mut self, antialiasing: bool) {
-        self.data.antialiasing = Some(antialiasing);
+    /// Returns true if all settings (including hotkeys) currently have their
+    /// default values.
+    pub fn are_all_defaults(&self) -> bool {
+        PrefKey::iter().all(|key| self.is_default(key))
+            && self.hotkeys_are_defaults()
+    }
+
+    /// Resets the given setting to its default value.
+    pub fn reset(&mut self, key: PrefKey) {
+        let data = &mut self.data;
+        let was_set = match key {
+            PrefKey::Antialiasing => {
+                let was_set = data.antialiasing.take().is_some();
+                data.antialiasing_level.take().is_some() || was_set
+            }
+            PrefKey::Fullscreen => data.fullscreen.take().is_some(),
+            PrefKey::Resolution => data.resolution.take().is_some(),
+            PrefKey::SoundVolume => data.sound_volume.take().is_some(),
+            PrefKey::MusicVolume => data.music_volume.take().is_some(),
+            PrefKey::DuckMusic => data.duck_music.take().is_some(),
+            PrefKey::SimulationSpeed => data.simulation_speed.take().is_some(),
+            PrefKey::ReduceMotion => data.reduce_motion.take().is_some(),
+            PrefKey::AllowSkipCutscenes => {
+                data.allow_skip_cutscenes.take().is_some()
+            }
+            PrefKey::HighlightUnconnectedPorts => {
+                data.highlight_unconnected_ports.take().is_some()
+            }
+            PrefKey::SnapshotSlots => data.snapshot_slots.take().is_some(),
+            PrefKey::CheckForUpdates => {
+                data.check_for_updates.take().is_some()
+            }
+            PrefKey::DoubleClickMs => data.double_click_ms.take().is_some(),
+            PrefKey::JumpToFailures => data.jump_to_failures.take().is_some(),
+            PrefKey::EmphasizeWireSizes => {
+                data.emphasize_wire_sizes.take().is_some()
+            }
+            PrefKey::IdlePauseSeconds => {
+                data.idle_pause_seconds.take().is_some()
+            }
+            PrefKey::LabelExportedImages => {
+                data.label_exported_images.take().is_some()
+            }
+            PrefKey::AutoRouteWires => data.auto_route_wires.take().is_some(),
+        };
+        if was_set {
+            self.needs_save = true;
+        }
+    }
+
+    /// Resets all settings (including hotkeys) to their default values.  The
+    /// current profile and player pronouns are not affected.
+    pub fn reset_all(&mut self) {
+        for key in PrefKey::iter() {
+            self.reset(key);
+        }
+        self.set_hotkeys_to_defaults();
+    }
+
+    /// Returns the number of multisample antialiasing samples per pixel, or
+    /// zero if antialiasing is disabled.
+    pub fn antialiasing(&self) -> u8 {
+        match (self.data.antialiasing_level, self.data.antialiasing) {
+            (Some(level), _) => level,
+            (None, Some(true)) => LEGACY_ANTIALIASING_LEVEL,
+            (None, Some(false)) => 0,
+            (None, None) => DEFAULT_ANTIALIASING,
+        }
+    }
+
+    pub fn set_antialiasing(&mut self, level: u8) {
+        self.data.antialiasing = None;
+        self.data.antialiasing_level = Some(level);
         self.needs_save = true;
     }
 
@@ -124,8 +434,17 @@ impl Prefs {
         self.needs_save = true;
     }
 
+    pub fn pronouns(&self) -> Pronouns {
+        self.data.pronouns.unwrap_or_default()
+    }
+
+    pub fn set_pronouns(&mut self, pronouns: Pronouns) {
+        self.data.pronouns = Some(pronouns);
+        self.needs_save = true;
+    }
+
     pub fn fullscreen(&self) -> bool {
-        self.data.fullscreen.unwrap_or(true)
+        self.data.fullscreen.unwrap_or(DEFAULT_FULLSCREEN)
     }
 
     pub fn set_fullscreen(&mut self, fullscreen: bool) {
@@ -134,7 +453,10 @@ impl Prefs {
     }
 
     pub fn resolution(&self) -> Option<RectSize<i32>> {
-        self.data.resolution.map(|(w, h)| RectSize::new(w, h))
+        match self.data.resolution {
+            Some((w, h)) => Some(RectSize::new(w, h)),
+            None => DEFAULT_RESOLUTION,
+        }
     }
 
     pub fn set_resolution(&mut self, res: Option<RectSize<i32>>) {
@@ -168,6 +490,163 @@ impl Prefs {
         self.needs_save = true;
     }
 
+    /// Returns true if music should be temporarily lowered while
+    /// conversation text is being typed out.
+    pub fn duck_music(&self) -> bool {
+        self.data.duck_music.unwrap_or(DEFAULT_DUCK_MUSIC)
+    }
+
+    pub fn set_duck_music(&mut self, duck_music: bool) {
+        self.data.duck_music = Some(duck_music);
+        self.needs_save = true;
+    }
+
+    /// Returns the multiplier applied to the rate at which circuit
+    /// evaluation proceeds while running (1.0 is normal speed).
+    pub fn simulation_speed(&self) -> f64 {
+        self.data
+            .simulation_speed
+            .unwrap_or(DEFAULT_SIMULATION_SPEED)
+            .clamp(MIN_SIMULATION_SPEED, MAX_SIMULATION_SPEED)
+    }
+
+    pub fn set_simulation_speed(&mut self, speed: f64) {
+        self.data.simulation_speed =
+            Some(speed.clamp(MIN_SIMULATION_SPEED, MAX_SIMULATION_SPEED));
+        self.needs_save = true;
+    }
+
+    pub fn reduce_motion(&self) -> bool {
+        self.data.reduce_motion.unwrap_or(DEFAULT_REDUCE_MOTION)
+    }
+
+    pub fn set_reduce_motion(&mut self, reduce_motion: bool) {
+        self.data.reduce_motion = Some(reduce_motion);
+        self.needs_save = true;
+    }
+
+    /// Returns true if the player should be allowed to skip cutscenes.
+    pub fn allow_skip_cutscenes(&self) -> bool {
+        self.data.allow_skip_cutscenes.unwrap_or(DEFAULT_ALLOW_SKIP_CUTSCENES)
+    }
+
+    pub fn set_allow_skip_cutscenes(&mut self, allow: bool) {
+        self.data.allow_skip_cutscenes = Some(allow);
+        self.needs_save = true;
+    }
+
+    /// Returns true if the circuit editor should mark chip ports that aren't
+    /// wired to anything.
+    pub fn highlight_unconnected_ports(&self) -> bool {
+        self.data
+            .highlight_unconnected_ports
+            .unwrap_or(DEFAULT_HIGHLIGHT_UNCONNECTED_PORTS)
+    }
+
+    pub fn set_highlight_unconnected_ports(&mut self, highlight: bool) {
+        self.data.highlight_unconnected_ports = Some(highlight);
+        self.needs_save = true;
+    }
+
+    /// Returns true if the game should ask the server at startup whether a
+    /// newer version is available.  This is off unless the player opts in,
+    /// so that the game makes no network requests by default.
+    pub fn check_for_updates(&self) -> bool {
+        self.data.check_for_updates.unwrap_or(DEFAULT_CHECK_FOR_UPDATES)
+    }
+
+    pub fn set_check_for_updates(&mut self, check: bool) {
+        self.data.check_for_updates = Some(check);
+        self.needs_save = true;
+    }
+
+    /// Returns the maximum time, in milliseconds, between two clicks on the
+    /// same chip for them to count as a double-click.
+    pub fn double_click_ms(&self) -> u32 {
+        self.data
+            .double_click_ms
+            .unwrap_or(DEFAULT_DOUBLE_CLICK_MS)
+            .clamp(MIN_DOUBLE_CLICK_MS, MAX_DOUBLE_CLICK_MS)
+    }
+
+    pub fn set_double_click_ms(&mut self, millis: u32) {
+        self.data.double_click_ms =
+            Some(millis.clamp(MIN_DOUBLE_CLICK_MS, MAX_DOUBLE_CLICK_MS));
+        self.needs_save = true;
+    }
+
+    /// Returns true if the camera should automatically move to show the
+    /// location of a failure when circuit evaluation fails.
+    pub fn jump_to_failures(&self) -> bool {
+        self.data.jump_to_failures.unwrap_or(DEFAULT_JUMP_TO_FAILURES)
+    }
+
+    pub fn set_jump_to_failures(&mut self, jump: bool) {
+        self.data.jump_to_failures = Some(jump);
+        self.needs_save = true;
+    }
+
+    /// Returns true if wires of different sizes should be drawn with more
+    /// exaggerated differences in thickness.
+    pub fn emphasize_wire_sizes(&self) -> bool {
+        self.data.emphasize_wire_sizes.unwrap_or(DEFAULT_EMPHASIZE_WIRE_SIZES)
+    }
+
+    pub fn set_emphasize_wire_sizes(&mut self, emphasize: bool) {
+        self.data.emphasize_wire_sizes = Some(emphasize);
+        self.needs_save = true;
+    }
+
+    /// Returns how many seconds a circuit evaluation may run without any
+    /// player input before it is automatically paused, or zero if running
+    /// evaluations should never be paused for idleness.
+    pub fn idle_pause_seconds(&self) -> u32 {
+        self.data.idle_pause_seconds.unwrap_or(DEFAULT_IDLE_PAUSE_SECONDS)
+    }
+
+    pub fn set_idle_pause_seconds(&mut self, seconds: u32) {
+        self.data.idle_pause_seconds = Some(seconds);
+        self.needs_save = true;
+    }
+
+    /// Returns true if exported circuit images should be stamped with the
+    /// puzzle title and the circuit's score and area.
+    pub fn label_exported_images(&self) -> bool {
+        self.data
+            .label_exported_images
+            .unwrap_or(DEFAULT_LABEL_EXPORTED_IMAGES)
+    }
+
+    pub fn set_label_exported_images(&mut self, label: bool) {
+        self.data.label_exported_images = Some(label);
+        self.needs_save = true;
+    }
+
+    /// Returns true if dragging a wire from one port to another should
+    /// automatically route the wire around chips and existing wires.
+    pub fn auto_route_wires(&self) -> bool {
+        self.data.auto_route_wires.unwrap_or(DEFAULT_AUTO_ROUTE_WIRES)
+    }
+
+    pub fn set_auto_route_wires(&mut self, auto_route: bool) {
+        self.data.auto_route_wires = Some(auto_route);
+        self.needs_save = true;
+    }
+
+    /// Returns the number of snapshot slots available in each sandbox puzzle.
+    pub fn snapshot_slots(&self) -> usize {
+        self.data
+            .snapshot_slots
+            .unwrap_or(DEFAULT_SNAPSHOT_SLOTS)
+            .clamp(MIN_SNAPSHOT_SLOTS, MAX_SNAPSHOT_SLOTS)
+    }
+
+    pub fn set_snapshot_slots(&mut self, slots: usize) {
+        self.data.snapshot_slots =
+            Some(slots.clamp(MIN_SNAPSHOT_SLOTS, MAX_SNAPSHOT_SLOTS));
+        self.needs_save = true;
+    }
+
     pub fn hotkey_for_code(&self, keycode: HotkeyCode) -> Option<Hotkey> {
         if let Some(ref hotkeys) = self.data.hotkeys {
             hotkeys.hotkey(keycode)
@@ -211,9 +690,79 @@ impl Prefs {
         Prefs {
             path: std::env::temp_dir(),
             data: PrefsData::default(),
+            strings: StringTable::english(),
             needs_save: false,
         }
     }
 }
 
 //===========================================================================//
+
+#[cfg(test)]
+mod tests {
+    use super::{PrefKey, PrefValue, Prefs};
+    use strum::IntoEnumIterator;
+    use tachy::geom::RectSize;
+
+    #[test]
+    fn new_prefs_have_default_values() {
+        let prefs = Prefs::for_testing();
+        for key in PrefKey::iter() {
+            assert_eq!(prefs.value(key), Prefs::default_value(key));
+            assert!(prefs.is_default(key));
+        }
+    }
+
+    #[test]
+    fn reset_single_pref() {
+        let mut prefs = Prefs::for_testing();
+        prefs.set_fullscreen(false);
+        prefs.set_music_volume_percent(17);
+        assert!(!prefs.is_default(PrefKey::Fullscreen));
+        prefs.reset(PrefKey::Fullscreen);
+        assert!(prefs.is_default(PrefKey::Fullscreen));
+        assert_eq!(prefs.value(PrefKey::MusicVolume), PrefValue::Int(17));
+    }
+
+    #[test]
+    fn reset_all_prefs() {
+        let mut prefs = Prefs::for_testing();
+        prefs.set_current_profile(Some("Jane".to_string()));
+        prefs.set_resolution(Some(RectSize::new(1024, 768)));
+        prefs.set_simulation_speed(4.0);
+        prefs.set_emphasize_wire_sizes(true);
+        assert!(!prefs.are_all_defaults());
+        prefs.reset_all();
+        for key in PrefKey::iter() {
+            assert!(prefs.is_default(key), "{:?} was not reset", key);
+        }
+        assert!(prefs.are_all_defaults());
+        assert_eq!(prefs.current_profile(), Some("Jane"));
+    }
+
+    #[test]
+    fn update_check_is_opt_in() {
+        let mut prefs = Prefs::for_testing();
+        assert!(!prefs.check_for_updates());
+        prefs.set_check_for_updates(true);
+        assert!(prefs.check_for_updates());
+        prefs.reset(PrefKey::CheckForUpdates);
+        assert!(!prefs.check_for_updates());
+    }
+
+    #[test]
+    fn legacy_antialiasing_flag() {
+        let mut prefs = Prefs::for_testing();
+        prefs.data = toml::from_str("antialiasing = true\n").unwrap();
+        assert_eq!(prefs.antialiasing(), 4);
+        prefs.data = toml::from_str("antialiasing = false\n").unwrap();
+        assert_eq!(prefs.antialiasing(), 0);
+        prefs.set_antialiasing(8);
+        assert_eq!(prefs.antialiasing(), 8);
+        assert_eq!(prefs.data.antialiasing, None);
+        prefs.reset(PrefKey::Antialiasing);
+        assert!(prefs.is_default(PrefKey::Antialiasing));
+    }
+}
+
+//===========================================================================//
@@ -17,8 +17,9 @@
 // | with Tachyomancer.  If not, see <http://www.gnu.org/licenses/>.          |
 // +--------------------------------------------------------------------------+
 
+use super::error::SaveError;
 use super::progress::{
-    is_valid_circuit_name, CircuitNamesIter, PuzzleProgress,
+    is_valid_circuit_name, CircuitCopyKind, CircuitNamesIter, PuzzleProgress,
 };
 use std::collections::{HashMap, HashSet};
 use std::fs;
@@ -73,15 +74,17 @@ impl Profile {
     pub fn create_or_load(
         name: String,
         base_path: &Path,
-    ) -> Result<Profile, String> {
+    ) -> Result<Profile, SaveError> {
         // Create directory if needed:
         if !base_path.exists() {
             debug_log!("Creating profile {:?} at {:?}", name, base_path);
             fs::create_dir_all(&base_path).map_err(|err| {
-                format!(
-                    "Could not create profile {:?} \
-                     directory at {:?}: {}",
-                    name, base_path, err
+                SaveError::Io(
+                    format!(
+                        "Could not create profile {:?} directory at {:?}",
+                        name, base_path
+                    ),
+                    err,
                 )
             })?;
         } else {
@@ -146,7 +149,7 @@ impl Profile {
         Ok(profile)
     }
 
-    pub fn save(&mut self) -> Result<(), String> {
+    pub fn save(&mut self) -> Result<(), SaveError> {
         if self.needs_save {
             let data_path = self.base_path.join(DATA_FILE_NAME);
             debug_log!(
@@ -155,16 +158,18 @@ impl Profile {
                 data_path
             );
             let data = toml::to_vec(&self.data).map_err(|err| {
-                format!(
+                SaveError::Format(format!(
                     "Could not serialize profile {:?} data: {}",
                     self.name, err
-                )
+                ))
             })?;
             fs::write(&data_path, data).map_err(|err| {
-                format!(
-                    "Could not write profile {:?} \
-                     data file to {:?}: {}",
-                    self.name, data_path, err
+                SaveError::Io(
+                    format!(
+                        "Could not write profile {:?} data file to {:?}",
+                        self.name, data_path
+                    ),
+                    err,
                 )
             })?;
             self.needs_save = false;
@@ -312,7 +317,7 @@ impl Profile {
         self.puzzles.contains_key(&puzzle)
     }
 
-    pub fn unlock_puzzle(&mut self, puzzle: Puzzle) -> Result<(), String> {
+    pub fn unlock_puzzle(&mut self, puzzle: Puzzle) -> Result<(), SaveError> {
         if !self.puzzles.contains_key(&puzzle) {
             debug_log!("Unlocking puzzle {:?}", puzzle);
             let puzzle_path = self.base_path.join(format!("{:?}", puzzle));
@@ -362,7 +367,7 @@ impl Profile {
         puzzle: Puzzle,
         area: i32,
         score: u32,
-    ) -> Result<(), String> {
+    ) -> Result<(), SaveError> {
         if !self.puzzles.contains_key(&puzzle) {
             let puzzle_path = self.base_path.join(format!("{:?}", puzzle));
             let progress = PuzzleProgress::create_or_load(&puzzle_path)?;
@@ -373,6 +378,16 @@ impl Profile {
         progress.save()
     }
 
+    pub fn puzzle_seconds_spent(&self, puzzle: Puzzle) -> f64 {
+        self.puzzles.get(&puzzle).map_or(0.0, PuzzleProgress::seconds_spent)
+    }
+
+    pub fn add_puzzle_seconds_spent(&mut self, puzzle: Puzzle, seconds: f64) {
+        if let Some(progress) = self.puzzles.get_mut(&puzzle) {
+            progress.add_seconds_spent(seconds);
+        }
+    }
+
     pub fn last_circuit_name_for_current_puzzle(&self) -> Option<String> {
         self.circuit_names(self.current_puzzle())
             .next_back()
@@ -423,11 +438,14 @@ impl Profile {
         &self,
         puzzle: Puzzle,
         circuit_name: &str,
-    ) -> Result<CircuitData, String> {
+    ) -> Result<CircuitData, SaveError> {
         if let Some(progress) = self.puzzles.get(&puzzle) {
             progress.load_circuit(circuit_name)
         } else {
-            Err(format!("No such circuit: {:?}", circuit_name))
+            Err(SaveError::NotFound(format!(
+                "No such circuit: {:?}",
+                circuit_name
+            )))
         }
     }
 
@@ -436,7 +454,7 @@ impl Profile {
         puzzle: Puzzle,
         circuit_name: &str,
         circuit_data: &CircuitData,
-    ) -> Result<(), String> {
+    ) -> Result<(), SaveError> {
         if !self.puzzles.contains_key(&puzzle) {
             let puzzle_path = self.base_path.join(format!("{:?}", puzzle));
             let progress = PuzzleProgress::create_or_load(&puzzle_path)?;
@@ -446,16 +464,63 @@ impl Profile {
         progress.save_circuit(circuit_name, circuit_data)
     }
 
+    /// Returns the name of the snapshot in the given slot for the given
+    /// puzzle, or `None` if that slot is empty.
+    pub fn snapshot_name(
+        &self,
+        puzzle: Puzzle,
+        slot: usize,
+    ) -> Option<String> {
+        self.puzzles
+            .get(&puzzle)
+            .and_then(|progress| progress.snapshot_name(slot))
+    }
+
+    pub fn load_snapshot(
+        &self,
+        puzzle: Puzzle,
+        slot: usize,
+    ) -> Result<CircuitData, SaveError> {
+        if let Some(progress) = self.puzzles.get(&puzzle) {
+            progress.load_snapshot(slot)
+        } else {
+            Err(SaveError::NotFound(format!(
+                "Snapshot slot {} is empty",
+                slot + 1
+            )))
+        }
+    }
+
+    pub fn save_snapshot(
+        &mut self,
+        puzzle: Puzzle,
+        slot: usize,
+        name: &str,
+        circuit_data: &CircuitData,
+    ) -> Result<(), SaveError> {
+        if !self.puzzles.contains_key(&puzzle) {
+            let puzzle_path = self.base_path.join(format!("{:?}", puzzle));
+            let progress = PuzzleProgress::create_or_load(&puzzle_path)?;
+            self.puzzles.insert(puzzle, progress);
+        }
+        let progress = self.puzzles.get_mut(&puzzle).unwrap();
+        progress.save_snapshot(slot, name, circuit_data)
+    }
+
     pub fn copy_circuit(
         &mut self,
         puzzle: Puzzle,
         old_name: &str,
         new_name: &str,
-    ) -> Result<(), String> {
+        kind: CircuitCopyKind,
+    ) -> Result<(), SaveError> {
         if let Some(progress) = self.puzzles.get_mut(&puzzle) {
-            progress.copy_circuit(old_name, new_name)
+            progress.copy_circuit(old_name, new_name, kind)
         } else {
-            Err(format!("No such circuit: {:?}", old_name))
+            Err(SaveError::NotFound(format!(
+                "No such circuit: {:?}",
+                old_name
+            )))
         }
     }
 
@@ -463,11 +528,14 @@ impl Profile {
         &mut self,
         puzzle: Puzzle,
         circuit_name: &str,
-    ) -> Result<(), String> {
+    ) -> Result<(), SaveError> {
         if let Some(progress) = self.puzzles.get_mut(&puzzle) {
             progress.delete_circuit(circuit_name)
         } else {
-            Err(format!("No such circuit: {:?}", circuit_name))
+            Err(SaveError::NotFound(format!(
+                "No such circuit: {:?}",
+                circuit_name
+            )))
         }
     }
 
@@ -476,11 +544,14 @@ impl Profile {
         puzzle: Puzzle,
         old_name: &str,
         new_name: &str,
-    ) -> Result<(), String> {
+    ) -> Result<(), SaveError> {
         if let Some(progress) = self.puzzles.get_mut(&puzzle) {
             progress.rename_circuit(old_name, new_name)
         } else {
-            Err(format!("No such circuit: {:?}", old_name))
+            Err(SaveError::NotFound(format!(
+                "No such circuit: {:?}",
+                old_name
+            )))
         }
     }
 }
@@ -18,6 +18,7 @@
 // +--------------------------------------------------------------------------+
 
 use super::encode::{decode_name, encode_name};
+use super::error::SaveError;
 use std::collections::{btree_set, BTreeSet};
 use std::fs;
 use std::io;
@@ -30,22 +31,101 @@ use unicase::UniCase;
 /// Maximum permitted number of characters in a circuit name.
 pub const CIRCUIT_NAME_MAX_CHARS: usize = 20;
 
+/// Maximum permitted number of characters in a sandbox snapshot name.
+pub const SNAPSHOT_NAME_MAX_CHARS: usize = CIRCUIT_NAME_MAX_CHARS;
+
 // Note: this file name needs to have a period (or other special character) in
 // the non-extension part to ensure that it cannot conflict with any encoded
 // circuit name.
 const DATA_FILE_NAME: &str = "puzzle.progress.toml";
 
+// Sandbox snapshots are stored in a subdirectory of the puzzle directory, so
+// that they don't get mistaken for circuits.  As with DATA_FILE_NAME, the
+// period ensures that this cannot conflict with any encoded circuit name.
+const SNAPSHOTS_DIR_NAME: &str = "snapshots.dir";
+
+//===========================================================================//
+
+/// How much of a circuit to keep when making a copy of it.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CircuitCopyKind {
+    /// Copy the circuit exactly, including all of its wires.
+    Full,
+    /// Copy only the chip layout, leaving the copy without any wires.
+    ChipsOnly,
+}
+
 //===========================================================================//
 
 pub fn is_valid_circuit_name(name: &str) -> bool {
     !name.is_empty() && name.chars().count() <= CIRCUIT_NAME_MAX_CHARS
 }
 
+/// Returns true if a circuit currently named `old_name` can be renamed to
+/// `new_name` (after trimming whitespace).  The `is_taken` function should
+/// return true for names already in use by other circuits for the same
+/// puzzle; since circuit names are case-insensitive, a circuit may always be
+/// renamed to a different capitalization of its current name.
+pub fn is_valid_circuit_rename<F>(
+    old_name: &str,
+    new_name: &str,
+    is_taken: F,
+) -> bool
+where
+    F: Fn(&str) -> bool,
+{
+    let new_name = new_name.trim();
+    if !is_valid_circuit_name(new_name) {
+        return false;
+    }
+    unicase::eq(new_name, old_name) || !is_taken(new_name)
+}
+
+/// Chooses a name for a copy of the circuit named `name`, of the form
+/// `"Name (2)"`, `"Name (3)"`, etc., such that `is_taken` returns false for
+/// it.  If `name` already ends with such a number, it is replaced rather than
+/// appended to.  The base name is truncated as necessary to keep the result
+/// within `CIRCUIT_NAME_MAX_CHARS`.
+pub fn unique_circuit_name<F>(name: &str, is_taken: F) -> String
+where
+    F: Fn(&str) -> bool,
+{
+    let base = strip_copy_number(name.trim());
+    let mut num: u64 = 2;
+    loop {
+        let suffix = format!(" ({})", num);
+        let max_base_chars =
+            CIRCUIT_NAME_MAX_CHARS.saturating_sub(suffix.chars().count());
+        let truncated: String = base.chars().take(max_base_chars).collect();
+        let candidate = format!("{}{}", truncated.trim_end(), suffix);
+        if !is_taken(&candidate) {
+            return candidate;
+        }
+        num += 1;
+    }
+}
+
+/// Removes a trailing copy number like `" (2)"` from a circuit name, if there
+/// is one.
+fn strip_copy_number(name: &str) -> &str {
+    if name.ends_with(')') {
+        if let Some(index) = name.rfind(" (") {
+            let digits = &name[(index + 2)..(name.len() - 1)];
+            if !digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit())
+            {
+                return &name[..index];
+            }
+        }
+    }
+    name
+}
+
 //===========================================================================//
 
 #[derive(Default, Deserialize, Serialize)]
 pub struct PuzzleProgressData {
     graph: Option<ScoreCurve>,
+    seconds_spent: Option<f64>,
 }
 
 impl PuzzleProgressData {
@@ -55,9 +135,12 @@ impl PuzzleProgressData {
         })
     }
 
-    fn serialize_toml(&self) -> Result<Vec<u8>, String> {
+    fn serialize_toml(&self) -> Result<Vec<u8>, SaveError> {
         toml::to_vec(self).map_err(|err| {
-            format!("Could not serialize puzzle progress data: {}", err)
+            SaveError::Format(format!(
+                "Could not serialize puzzle progress data: {}",
+                err
+            ))
         })
     }
 }
@@ -72,14 +155,19 @@ pub struct PuzzleProgress {
 }
 
 impl PuzzleProgress {
-    pub fn create_or_load(base_path: &Path) -> Result<PuzzleProgress, String> {
+    pub fn create_or_load(
+        base_path: &Path,
+    ) -> Result<PuzzleProgress, SaveError> {
         // Create directory if needed:
         if !base_path.exists() {
             debug_log!("Creating puzzle directory at {:?}", base_path);
             fs::create_dir_all(&base_path).map_err(|err| {
-                format!(
-                    "Could not create puzzle directory at {:?}: {}",
-                    base_path, err
+                SaveError::Io(
+                    format!(
+                        "Could not create puzzle directory at {:?}",
+                        base_path
+                    ),
+                    err,
                 )
             })?;
         }
@@ -108,17 +196,23 @@ impl PuzzleProgress {
         // Get circuit names:
         let mut circuit_names = BTreeSet::<UniCase<String>>::new();
         let entries = base_path.read_dir().map_err(|err| {
-            format!(
-                "Could not read contents of puzzle directory {:?}: {}",
-                base_path, err
+            SaveError::Io(
+                format!(
+                    "Could not read contents of puzzle directory {:?}",
+                    base_path
+                ),
+                err,
             )
         })?;
         for entry_result in entries {
             let entry = entry_result.map_err(|err| {
-                format!(
-                    "Error while reading contents of \
-                     puzzle directory {:?}: {}",
-                    base_path, err
+                SaveError::Io(
+                    format!(
+                        "Error while reading contents of \
+                         puzzle directory {:?}",
+                        base_path
+                    ),
+                    err,
                 )
             })?;
             let entry_path = entry.path();
@@ -144,16 +238,19 @@ impl PuzzleProgress {
         Ok(progress)
     }
 
-    pub fn save(&mut self) -> Result<(), String> {
+    pub fn save(&mut self) -> Result<(), SaveError> {
         if self.needs_save {
             let data_path = self.base_path.join(DATA_FILE_NAME);
             debug_log!("Saving puzzle progress to {:?}", data_path);
             let data_toml = self.data.serialize_toml()?;
             fs::write(&data_path, data_toml).map_err(|err| {
-                format!(
-                    "Could not write puzzle progress \
-                     data file to {:?}: {}",
-                    data_path, err
+                SaveError::Io(
+                    format!(
+                        "Could not write puzzle progress \
+                         data file to {:?}",
+                        data_path
+                    ),
+                    err,
                 )
             })?;
             self.needs_save = false;
@@ -186,6 +283,17 @@ impl PuzzleProgress {
         self.needs_save = true;
     }
 
+    /// Returns the total time, in seconds, that the player has spent with
+    /// this puzzle open in the circuit editor.  This is tracked locally only.
+    pub fn seconds_spent(&self) -> f64 {
+        self.data.seconds_spent.unwrap_or(0.0)
+    }
+
+    pub fn add_seconds_spent(&mut self, seconds: f64) {
+        self.data.seconds_spent = Some(self.seconds_spent() + seconds);
+        self.needs_save = true;
+    }
+
     pub fn circuit_names(&self) -> CircuitNamesIter {
         CircuitNamesIter::new(&self.circuit_names)
     }
@@ -204,12 +312,15 @@ impl PuzzleProgress {
     pub fn load_circuit(
         &self,
         circuit_name: &str,
-    ) -> Result<CircuitData, String> {
+    ) -> Result<CircuitData, SaveError> {
         let circuit_name_uni = UniCase::new(circuit_name.to_string());
         let circuit_path = match self.circuit_names.get(&circuit_name_uni) {
             Some(name) => self.circuit_path(&name),
             None => {
-                return Err(format!("No such circuit: {:?}", circuit_name))
+                return Err(SaveError::NotFound(format!(
+                    "No such circuit: {:?}",
+                    circuit_name
+                )));
             }
         };
         debug_log!(
@@ -217,16 +328,19 @@ impl PuzzleProgress {
             circuit_name,
             circuit_path
         );
-        CircuitData::load(&circuit_path)
+        Ok(CircuitData::load(&circuit_path)?)
     }
 
     pub fn save_circuit(
         &mut self,
         circuit_name: &str,
         circuit_data: &CircuitData,
-    ) -> Result<(), String> {
+    ) -> Result<(), SaveError> {
         if !is_valid_circuit_name(circuit_name) {
-            return Err(format!("Invalid circuit name: {:?}", circuit_name));
+            return Err(SaveError::Invalid(format!(
+                "Invalid circuit name: {:?}",
+                circuit_name
+            )));
         }
         let circuit_name_uni = UniCase::new(circuit_name.to_string());
         let circuit_path = match self.circuit_names.get(&circuit_name_uni) {
@@ -243,30 +357,61 @@ impl PuzzleProgress {
         &mut self,
         old_name: &str,
         new_name: &str,
-    ) -> Result<(), String> {
+        kind: CircuitCopyKind,
+    ) -> Result<(), SaveError> {
         let old_name_uni = UniCase::new(old_name.to_string());
         let old_path = match self.circuit_names.get(&old_name_uni) {
             Some(name) => self.circuit_path(&name),
-            None => return Err(format!("No such circuit: {:?}", old_name)),
+            None => {
+                return Err(SaveError::NotFound(format!(
+                    "No such circuit: {:?}",
+                    old_name
+                )))
+            }
         };
         if !is_valid_circuit_name(new_name) {
-            return Err(format!("Invalid circuit name: {:?}", new_name));
+            return Err(SaveError::Invalid(format!(
+                "Invalid circuit name: {:?}",
+                new_name
+            )));
         }
         let new_name_uni = UniCase::new(new_name.to_string());
         if self.circuit_names.contains(&new_name_uni) {
-            return Err(format!("Circuit already exists: {:?}", new_name));
+            return Err(SaveError::Invalid(format!(
+                "Circuit already exists: {:?}",
+                new_name
+            )));
         }
         let new_path = self.circuit_path(new_name);
         if new_path.exists() {
-            return Err(format!("Path already exists: {:?}", new_path));
+            return Err(SaveError::Invalid(format!(
+                "Path already exists: {:?}",
+                new_path
+            )));
+        }
+        debug_log!(
+            "Copying circuit ({:?}) from {:?} to {:?}",
+            kind,
+            old_path,
+            new_path
+        );
+        match kind {
+            CircuitCopyKind::Full => {
+                fs::copy(&old_path, &new_path).map_err(|err| {
+                    SaveError::Io(
+                        format!(
+                            "Could not copy circuit file {:?} to {:?}",
+                            old_path, new_path
+                        ),
+                        err,
+                    )
+                })?;
+            }
+            CircuitCopyKind::ChipsOnly => {
+                let data = CircuitData::load(&old_path)?;
+                data.without_wires().save(&new_path)?;
+            }
         }
-        debug_log!("Copying circuit from {:?} to {:?}", old_path, new_path);
-        fs::copy(&old_path, &new_path).map_err(|err| {
-            format!(
-                "Could not copy circuit file {:?} to {:?}: {}",
-                old_path, new_path, err
-            )
-        })?;
         self.circuit_names.insert(new_name_uni);
         Ok(())
     }
@@ -274,12 +419,15 @@ impl PuzzleProgress {
     pub fn delete_circuit(
         &mut self,
         circuit_name: &str,
-    ) -> Result<(), String> {
+    ) -> Result<(), SaveError> {
         let circuit_name_uni = UniCase::new(circuit_name.to_string());
         let circuit_path = match self.circuit_names.get(&circuit_name_uni) {
             Some(name) => self.circuit_path(&name),
             None => {
-                return Err(format!("No such circuit: {:?}", circuit_name))
+                return Err(SaveError::NotFound(format!(
+                    "No such circuit: {:?}",
+                    circuit_name
+                )));
             }
         };
         debug_log!(
@@ -288,9 +436,9 @@ impl PuzzleProgress {
             circuit_path
         );
         fs::remove_file(&circuit_path).map_err(|err| {
-            format!(
-                "Could not delete circuit file {:?}: {}",
-                circuit_path, err
+            SaveError::Io(
+                format!("Could not delete circuit file {:?}", circuit_path),
+                err,
             )
         })?;
         self.circuit_names.remove(&circuit_name_uni);
@@ -301,23 +449,34 @@ impl PuzzleProgress {
         &mut self,
         old_name: &str,
         new_name: &str,
-    ) -> Result<(), String> {
+    ) -> Result<(), SaveError> {
         let old_name_uni = UniCase::new(old_name.to_string());
         let old_path = match self.circuit_names.get(&old_name_uni) {
             Some(name) => self.circuit_path(&name),
-            None => return Err(format!("No such circuit: {:?}", old_name)),
+            None => {
+                return Err(SaveError::NotFound(format!(
+                    "No such circuit: {:?}",
+                    old_name
+                )))
+            }
         };
         if new_name == old_name {
             return Ok(());
         }
         if !is_valid_circuit_name(new_name) {
-            return Err(format!("Invalid circuit name: {:?}", new_name));
+            return Err(SaveError::Invalid(format!(
+                "Invalid circuit name: {:?}",
+                new_name
+            )));
         }
         let new_name_uni = UniCase::new(new_name.to_string());
         let new_path = self.circuit_path(&new_name);
         if new_name_uni != old_name_uni {
             if self.circuit_names.contains(&new_name_uni) {
-                return Err(format!("Circuit already exists: {:?}", new_name));
+                return Err(SaveError::Invalid(format!(
+                    "Circuit already exists: {:?}",
+                    new_name
+                )));
             }
             // We already know there's not another circuit with this name, so
             // there shouldn't be a file at the new path, but in case there is
@@ -326,14 +485,20 @@ impl PuzzleProgress {
             // by case, because otherwise a case-insensitive filesystem would
             // report that the new path already exists.
             if new_path.exists() {
-                return Err(format!("Path already exists: {:?}", new_path));
+                return Err(SaveError::Invalid(format!(
+                    "Path already exists: {:?}",
+                    new_path
+                )));
             }
         }
         debug_log!("Moving circuit from {:?} to {:?}", old_path, new_path);
         fs::rename(&old_path, &new_path).map_err(|err| {
-            format!(
-                "Could not move circuit file {:?} to {:?}: {}",
-                old_path, new_path, err
+            SaveError::Io(
+                format!(
+                    "Could not move circuit file {:?} to {:?}",
+                    old_path, new_path
+                ),
+                err,
             )
         })?;
         self.circuit_names.remove(&old_name_uni);
@@ -344,6 +509,76 @@ impl PuzzleProgress {
     fn circuit_path(&self, circuit_name: &str) -> PathBuf {
         self.base_path.join(encode_name(circuit_name)).with_extension("toml")
     }
+
+    pub fn has_snapshot(&self, slot: usize) -> bool {
+        self.snapshot_path(slot).is_file()
+    }
+
+    /// Returns the name that the given snapshot slot's snapshot was saved
+    /// under, or `None` if the slot is empty.  Snapshots saved without a
+    /// name have an empty name.
+    pub fn snapshot_name(&self, slot: usize) -> Option<String> {
+        if !self.has_snapshot(slot) {
+            return None;
+        }
+        let name_path = self.snapshot_path(slot).with_extension("name");
+        let name = fs::read_to_string(&name_path).unwrap_or_default();
+        Some(name.trim().chars().take(SNAPSHOT_NAME_MAX_CHARS).collect())
+    }
+
+    pub fn load_snapshot(
+        &self,
+        slot: usize,
+    ) -> Result<CircuitData, SaveError> {
+        let snapshot_path = self.snapshot_path(slot);
+        if !snapshot_path.is_file() {
+            return Err(SaveError::NotFound(format!(
+                "Snapshot slot {} is empty",
+                slot + 1
+            )));
+        }
+        debug_log!("Loading snapshot {} from {:?}", slot, snapshot_path);
+        Ok(CircuitData::load(&snapshot_path)?)
+    }
+
+    pub fn save_snapshot(
+        &mut self,
+        slot: usize,
+        name: &str,
+        circuit_data: &CircuitData,
+    ) -> Result<(), SaveError> {
+        let snapshots_dir = self.base_path.join(SNAPSHOTS_DIR_NAME);
+        if !snapshots_dir.exists() {
+            debug_log!("Creating snapshots directory at {:?}", snapshots_dir);
+            fs::create_dir_all(&snapshots_dir).map_err(|err| {
+                SaveError::Io(
+                    format!(
+                        "Could not create snapshots directory at {:?}",
+                        snapshots_dir
+                    ),
+                    err,
+                )
+            })?;
+        }
+        let snapshot_path = self.snapshot_path(slot);
+        debug_log!("Saving snapshot {} to {:?}", slot, snapshot_path);
+        circuit_data.save(&snapshot_path)?;
+        let name_path = snapshot_path.with_extension("name");
+        let name: String =
+            name.trim().chars().take(SNAPSHOT_NAME_MAX_CHARS).collect();
+        fs::write(&name_path, name).map_err(|err| {
+            SaveError::Io(
+                format!("Could not write snapshot name to {:?}", name_path),
+                err,
+            )
+        })
+    }
+
+    fn snapshot_path(&self, slot: usize) -> PathBuf {
+        self.base_path
+            .join(SNAPSHOTS_DIR_NAME)
+            .join(format!("slot{}.toml", slot + 1))
+    }
 }
 
 //===========================================================================//
@@ -385,3 +620,92 @@ impl<'a> DoubleEndedIterator for CircuitNamesIter<'a> {
 }
 
 //===========================================================================//
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        is_valid_circuit_rename, unique_circuit_name, CIRCUIT_NAME_MAX_CHARS,
+    };
+
+    fn taken<'a>(names: &'a [&'a str]) -> impl Fn(&str) -> bool + 'a {
+        move |name| names.iter().any(|other| unicase::eq(*other, name))
+    }
+
+    #[test]
+    fn unique_name_without_collision() {
+        assert_eq!(unique_circuit_name("Foo", taken(&["Foo"])), "Foo (2)");
+        assert_eq!(unique_circuit_name("Foo", taken(&[])), "Foo (2)");
+    }
+
+    #[test]
+    fn unique_name_skips_collisions() {
+        let names = ["Foo", "Foo (2)", "foo (3)"];
+        assert_eq!(unique_circuit_name("Foo", taken(&names)), "Foo (4)");
+    }
+
+    #[test]
+    fn unique_name_replaces_existing_copy_number() {
+        let names = ["Foo", "Foo (2)"];
+        assert_eq!(unique_circuit_name("Foo (2)", taken(&names)), "Foo (3)");
+        assert_eq!(
+            unique_circuit_name("Foo (bar)", taken(&names)),
+            "Foo (bar) (2)"
+        );
+        assert_eq!(unique_circuit_name("Foo ()", taken(&names)), "Foo () (2)");
+    }
+
+    #[test]
+    fn unique_name_truncates_long_names() {
+        let name = "ABCDEFGHIJKLMNOPQRST";
+        assert_eq!(name.chars().count(), CIRCUIT_NAME_MAX_CHARS);
+        let copy = unique_circuit_name(name, taken(&[name]));
+        assert_eq!(copy, "ABCDEFGHIJKLMNOP (2)");
+        assert_eq!(copy.chars().count(), CIRCUIT_NAME_MAX_CHARS);
+
+        let names = [name, "ABCDEFGHIJKLMNOP (2)", "ABCDEFGHIJKLMNOP (3)"];
+        let names: Vec<String> = names
+            .iter()
+            .map(|name| name.to_string())
+            .chain((4..10).map(|n| format!("ABCDEFGHIJKLMNOP ({})", n)))
+            .collect();
+        let names: Vec<&str> = names.iter().map(String::as_str).collect();
+        let copy = unique_circuit_name(name, taken(&names));
+        assert_eq!(copy, "ABCDEFGHIJKLMNO (10)");
+        assert_eq!(copy.chars().count(), CIRCUIT_NAME_MAX_CHARS);
+    }
+
+    #[test]
+    fn unique_name_truncates_by_chars_not_bytes() {
+        let name = "\u{e9}\u{e9}\u{e9}\u{e9}\u{e9}\u{e9}\u{e9}\u{e9}\u{e9}\u{e9}\
+                    \u{e9}\u{e9}\u{e9}\u{e9}\u{e9}\u{e9}\u{e9}\u{e9}\u{e9}\u{e9}";
+        let copy = unique_circuit_name(name, taken(&[]));
+        assert_eq!(copy.chars().count(), CIRCUIT_NAME_MAX_CHARS);
+        assert!(copy.ends_with("\u{e9} (2)"));
+    }
+
+    #[test]
+    fn unique_name_trims_whitespace_before_suffix() {
+        let name = "ABCDEFGHIJKLMNO QRST";
+        assert_eq!(
+            unique_circuit_name(name, taken(&[])),
+            "ABCDEFGHIJKLMNO (2)"
+        );
+    }
+
+    #[test]
+    fn valid_renames() {
+        let names = ["Foo", "Bar"];
+        assert!(is_valid_circuit_rename("Foo", "Baz", taken(&names)));
+        assert!(is_valid_circuit_rename("Foo", "  Baz ", taken(&names)));
+        assert!(is_valid_circuit_rename("Foo", "FOO", taken(&names)));
+        assert!(!is_valid_circuit_rename("Foo", "bar", taken(&names)));
+        assert!(!is_valid_circuit_rename("Foo", "   ", taken(&names)));
+        assert!(!is_valid_circuit_rename(
+            "Foo",
+            "ABCDEFGHIJKLMNOPQRSTU",
+            taken(&names)
+        ));
+    }
+}
+
+//===========================================================================//
@@ -0,0 +1,62 @@
+// +--------------------------------------------------------------------------+
+// | Copyright 2018 Matthew D. Steele <mdsteele@alum.mit.edu>                 |
+// |                                                                          |
+// | This file is part of Tachyomancer.                                       |
+// |                                                                          |
+// | Tachyomancer is free software: you can redistribute it and/or modify it  |
+// | under the terms of the GNU General Public License as published by the    |
+// | Free Software Foundation, either version 3 of the License, or (at your   |
+// | option) any later version.                                               |
+// |                                                                          |
+// | Tachyomancer is distributed in the hope that it will be useful, but      |
+// | WITHOUT ANY WARRANTY; without even the implied warranty of               |
+// | MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU        |
+// | General Public License for details.                                      |
+// |                                                                          |
+// | You should have received a copy of the GNU General Public License along  |
+// | with Tachyomancer.  If not, see <http://www.gnu.org/licenses/>.          |
+// +--------------------------------------------------------------------------+
+
+//===========================================================================//
+
+/// The pronouns that conversation text uses to refer to the player.
+#[derive(
+    Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize,
+)]
+pub enum Pronouns {
+    She,
+    He,
+    #[default]
+    They,
+}
+
+impl Pronouns {
+    /// Returns the subject pronoun (e.g. "they").
+    pub fn subject(self) -> &'static str {
+        match self {
+            Pronouns::She => "she",
+            Pronouns::He => "he",
+            Pronouns::They => "they",
+        }
+    }
+
+    /// Returns the object pronoun (e.g. "them").
+    pub fn object(self) -> &'static str {
+        match self {
+            Pronouns::She => "her",
+            Pronouns::He => "him",
+            Pronouns::They => "them",
+        }
+    }
+
+    /// Returns the possessive determiner (e.g. "their").
+    pub fn possessive(self) -> &'static str {
+        match self {
+            Pronouns::She => "her",
+            Pronouns::He => "his",
+            Pronouns::They => "their",
+        }
+    }
+}
+
+//===========================================================================//
@@ -0,0 +1,140 @@
+// +--------------------------------------------------------------------------+
+// | Copyright 2018 Matthew D. Steele <mdsteele@alum.mit.edu>                 |
+// |                                                                          |
+// | This file is part of Tachyomancer.                                       |
+// |                                                                          |
+// | Tachyomancer is free software: you can redistribute it and/or modify it  |
+// | under the terms of the GNU General Public License as published by the    |
+// | Free Software Foundation, either version 3 of the License, or (at your   |
+// | option) any later version.                                               |
+// |                                                                          |
+// | Tachyomancer is distributed in the hope that it will be useful, but      |
+// | WITHOUT ANY WARRANTY; without even the implied warranty of               |
+// | MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU        |
+// | General Public License for details.                                      |
+// |                                                                          |
+// | You should have received a copy of the GNU General Public License along  |
+// | with Tachyomancer.  If not, see <http://www.gnu.org/licenses/>.          |
+// +--------------------------------------------------------------------------+
+
+use std::path::Path;
+use tachy::save::{CircuitData, Puzzle};
+
+//===========================================================================//
+
+pub const ISSUE_REPORT_FILE_NAME: &str = "issue_report.txt";
+
+// Input recordings are written to e.g. "input_recording.toml", or to
+// "input_recording_1.toml" (and so on) if that file already exists:
+pub const INPUT_RECORDING_FILE_STEM: &str = "input_recording";
+
+//===========================================================================//
+
+/// The information bundled into an issue report file.
+pub struct IssueReport<'a> {
+    pub prefs_toml: String,
+    pub circuit: Option<(Puzzle, &'a str, CircuitData)>,
+    pub log_lines: Vec<String>,
+}
+
+impl<'a> IssueReport<'a> {
+    /// Formats the report as text, replacing any occurrences of the given
+    /// paths with the corresponding placeholder strings.
+    pub fn format(
+        &self,
+        redactions: &[(&Path, &str)],
+    ) -> Result<String, String> {
+        let mut report = format!(
+            "Tachyomancer issue report\n\
+             Please attach this file to your bug report at\n\
+             https://github.com/mdsteele/tachyomancer/issues\n\n\
+             VERSION={}, OS={}, ARCH={}\n",
+            env!("CARGO_PKG_VERSION"),
+            std::env::consts::OS,
+            std::env::consts::ARCH
+        );
+        report.push_str("\n=== Prefs ===\n");
+        report.push_str(&self.prefs_toml);
+        report.push_str("\n=== Circuit ===\n");
+        match self.circuit {
+            Some((puzzle, name, ref data)) => {
+                report.push_str(&format!(
+                    "puzzle = {:?}\nname = {:?}\n\n",
+                    puzzle, name
+                ));
+                report.push_str(&data.serialize_to_string()?);
+            }
+            None => report.push_str("(no active circuit)\n"),
+        }
+        report.push_str("\n=== Recent log ===\n");
+        for line in self.log_lines.iter() {
+            report.push_str(line);
+            report.push('\n');
+        }
+        Ok(redact_paths(report, redactions))
+    }
+}
+
+/// Replaces each of the given paths in `text` with its placeholder.  Longer
+/// paths are replaced first, so that a path nested inside another redacted
+/// path gets its own placeholder.
+fn redact_paths(mut text: String, redactions: &[(&Path, &str)]) -> String {
+    let mut redactions: Vec<(String, &str)> = redactions
+        .iter()
+        .map(|&(path, placeholder)| {
+            (path.to_string_lossy().into_owned(), placeholder)
+        })
+        .filter(|(path, _)| !path.is_empty())
+        .collect();
+    redactions.sort_by_key(|(path, _)| std::cmp::Reverse(path.len()));
+    for (path, placeholder) in redactions {
+        text = text.replace(&path, placeholder);
+    }
+    text
+}
+
+//===========================================================================//
+
+#[cfg(test)]
+mod tests {
+    use super::{redact_paths, IssueReport};
+    use std::path::Path;
+    use tachy::save::{CircuitData, Puzzle};
+
+    #[test]
+    fn redact_nested_paths() {
+        let text = "Saving to /home/alice/.local/tachy/prefs.toml \
+                    from /home/alice/bin"
+            .to_string();
+        let redactions = [
+            (Path::new("/home/alice"), "<HOME>"),
+            (Path::new("/home/alice/.local/tachy"), "<SAVE_DIR>"),
+        ];
+        assert_eq!(
+            redact_paths(text, &redactions),
+            "Saving to <SAVE_DIR>/prefs.toml from <HOME>/bin"
+        );
+    }
+
+    #[test]
+    fn format_report() {
+        let report = IssueReport {
+            prefs_toml: "fullscreen = true\n".to_string(),
+            circuit: Some((
+                Puzzle::TutorialOr,
+                "Version 1",
+                CircuitData::new(4, 3),
+            )),
+            log_lines: vec!["Saving to \"/saves/Alice\"".to_string()],
+        };
+        let text =
+            report.format(&[(Path::new("/saves"), "<SAVE_DIR>")]).unwrap();
+        assert!(text.contains("fullscreen = true\n"));
+        assert!(text.contains("puzzle = TutorialOr\n"));
+        assert!(text.contains("size = [4, 3]\n"));
+        assert!(text.contains("Saving to \"<SAVE_DIR>/Alice\"\n"));
+        assert!(!text.contains("/saves"));
+    }
+}
+
+//===========================================================================//
@@ -17,6 +17,7 @@
 // | with Tachyomancer.  If not, see <http://www.gnu.org/licenses/>.          |
 // +--------------------------------------------------------------------------+
 
+use super::error::SaveError;
 use std::fs;
 use std::path::{Path, PathBuf};
 use tachy::save::ScoreCurveMap;
@@ -34,14 +35,17 @@ pub struct GlobalScoresDir {
 impl GlobalScoresDir {
     pub(super) fn create_or_load(
         base_path: &Path,
-    ) -> Result<GlobalScoresDir, String> {
+    ) -> Result<GlobalScoresDir, SaveError> {
         // Create directory if needed:
         if !base_path.exists() {
             debug_log!("Creating global scores directory at {:?}", base_path);
             fs::create_dir_all(&base_path).map_err(|err| {
-                format!(
-                    "Could not create global scores directory at {:?}: {}",
-                    base_path, err
+                SaveError::Io(
+                    format!(
+                        "Could not create global scores directory at {:?}",
+                        base_path
+                    ),
+                    err,
                 )
             })?;
         } else {
@@ -51,16 +55,20 @@ impl GlobalScoresDir {
         Ok(GlobalScoresDir { base_path: base_path.to_path_buf() })
     }
 
-    pub fn load_global_score_cache(&self) -> Result<ScoreCurveMap, String> {
+    pub fn load_global_score_cache(&self) -> Result<ScoreCurveMap, SaveError> {
         let path = self.base_path.join(CACHE_FILE_NAME);
         if path.exists() {
             let serialized = fs::read_to_string(&path).map_err(|err| {
-                format!(
-                    "Could not read global scores cache from {:?}: {}",
-                    path, err
+                SaveError::Io(
+                    format!(
+                        "Could not read global scores cache from {:?}",
+                        path
+                    ),
+                    err,
                 )
             })?;
             ScoreCurveMap::deserialize_from_string(&serialized)
+                .map_err(SaveError::Format)
         } else {
             Ok(ScoreCurveMap::new())
         }
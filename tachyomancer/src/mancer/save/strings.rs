@@ -0,0 +1,153 @@
+// +--------------------------------------------------------------------------+
+// | Copyright 2018 Matthew D. Steele <mdsteele@alum.mit.edu>                 |
+// |                                                                          |
+// | This file is part of Tachyomancer.                                       |
+// |                                                                          |
+// | Tachyomancer is free software: you can redistribute it and/or modify it  |
+// | under the terms of the GNU General Public License as published by the    |
+// | Free Software Foundation, either version 3 of the License, or (at your   |
+// | option) any later version.                                               |
+// |                                                                          |
+// | Tachyomancer is distributed in the hope that it will be useful, but      |
+// | WITHOUT ANY WARRANTY; without even the implied warranty of               |
+// | MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU        |
+// | General Public License for details.                                      |
+// |                                                                          |
+// | You should have received a copy of the GNU General Public License along  |
+// | with Tachyomancer.  If not, see <http://www.gnu.org/licenses/>.          |
+// +--------------------------------------------------------------------------+
+
+use super::error::SaveError;
+use std::collections::HashMap;
+use std::str::FromStr;
+use toml;
+
+//===========================================================================//
+
+/// Identifies a piece of user-facing UI text that can be localized.
+#[derive(Clone, Copy, Debug, EnumIter, EnumString, Eq, Hash, PartialEq)]
+pub enum StringKey {
+    ButtonCancel,
+    ButtonChipsOnly,
+    ButtonDelete,
+    ButtonFullCopy,
+    ButtonOk,
+    ButtonReset,
+    MenuMessages,
+    MenuNavigation,
+    MenuSettings,
+    MenuTasks,
+}
+
+impl StringKey {
+    /// Returns the default (English) text for this key.
+    pub fn english(self) -> &'static str {
+        match self {
+            StringKey::ButtonCancel => "Cancel",
+            StringKey::ButtonChipsOnly => "Chips Only",
+            StringKey::ButtonDelete => "Delete",
+            StringKey::ButtonFullCopy => "Full Copy",
+            StringKey::ButtonOk => "OK",
+            StringKey::ButtonReset => "Reset",
+            StringKey::MenuMessages => "Messages",
+            StringKey::MenuNavigation => "Navigation",
+            StringKey::MenuSettings => "Settings",
+            StringKey::MenuTasks => "Tasks",
+        }
+    }
+}
+
+//===========================================================================//
+
+/// A table of UI strings.  Any key without a translation falls back to its
+/// English text.
+pub struct StringTable {
+    translations: HashMap<StringKey, String>,
+}
+
+impl StringTable {
+    pub fn english() -> StringTable {
+        StringTable { translations: HashMap::new() }
+    }
+
+    /// Parses a TOML table mapping `StringKey` names to translated text.
+    /// Each translation must use the same `Paragraph` escape sequences as
+    /// the English text that it replaces.
+    pub fn from_toml(toml_str: &str) -> Result<StringTable, SaveError> {
+        let entries: HashMap<String, String> = toml::from_str(toml_str)
+            .map_err(|err| {
+                SaveError::Format(format!("Could not parse strings: {}", err))
+            })?;
+        let mut translations = HashMap::with_capacity(entries.len());
+        for (name, text) in entries {
+            let key = StringKey::from_str(&name).map_err(|_| {
+                SaveError::Format(format!("Unknown string key: {:?}", name))
+            })?;
+            if escape_chars(&text) != escape_chars(key.english()) {
+                return Err(SaveError::Format(format!(
+                    "Translation for {:?} has mismatched escapes: {:?}",
+                    key, text
+                )));
+            }
+            translations.insert(key, text);
+        }
+        Ok(StringTable { translations })
+    }
+
+    pub fn get(&self, key: StringKey) -> &str {
+        match self.translations.get(&key) {
+            Some(text) => text.as_str(),
+            None => key.english(),
+        }
+    }
+}
+
+/// Returns the (sorted) characters following each `$` escape in the string.
+fn escape_chars(text: &str) -> Vec<char> {
+    let mut escapes = Vec::new();
+    let mut chars = text.chars();
+    while let Some(chr) = chars.next() {
+        if chr == '$' {
+            if let Some(next) = chars.next() {
+                escapes.push(next);
+            }
+        }
+    }
+    escapes.sort();
+    escapes
+}
+
+//===========================================================================//
+
+#[cfg(test)]
+mod tests {
+    use super::{StringKey, StringTable};
+    use strum::IntoEnumIterator;
+
+    #[test]
+    fn english_table_uses_english_text() {
+        let table = StringTable::english();
+        for key in StringKey::iter() {
+            assert_eq!(table.get(key), key.english());
+        }
+    }
+
+    #[test]
+    fn translations_fall_back_to_english() {
+        let table =
+            StringTable::from_toml("ButtonCancel = \"Annuler\"\n").unwrap();
+        assert_eq!(table.get(StringKey::ButtonCancel), "Annuler");
+        assert_eq!(table.get(StringKey::ButtonOk), "OK");
+    }
+
+    #[test]
+    fn translations_must_keep_escapes() {
+        assert!(StringTable::from_toml("ButtonOk = \"$*OK$*\"\n").is_err());
+        assert!(
+            StringTable::from_toml("ButtonOk = \"D'accord $$\"\n").is_err()
+        );
+        assert!(StringTable::from_toml("NoSuchKey = \"Foo\"\n").is_err());
+    }
+}
+
+//===========================================================================//
@@ -103,6 +103,25 @@ impl FrameBufferShader {
     pub fn read_rgb_data(&self, fbo: &FrameBufferMultisample) -> Vec<u8> {
         let size = fbo.size();
         let mut target = FrameBuffer::new(size.width, size.height);
+        self.read_data(fbo, &mut target, false)
+    }
+
+    /// Like `read_rgb_data`, but preserves the alpha channel of the
+    /// multisample framebuffer.  The returned color channels are
+    /// premultiplied by alpha.
+    pub fn read_rgba_data(&self, fbo: &FrameBufferMultisample) -> Vec<u8> {
+        let size = fbo.size();
+        let mut target = FrameBuffer::with_alpha(size.width, size.height);
+        self.read_data(fbo, &mut target, true)
+    }
+
+    fn read_data(
+        &self,
+        fbo: &FrameBufferMultisample,
+        target: &mut FrameBuffer,
+        alpha: bool,
+    ) -> Vec<u8> {
+        let size = fbo.size();
         let binding = target.bind(self.window_size);
         let matrix = cgmath::ortho(
             0.0,
@@ -113,7 +132,11 @@ impl FrameBufferShader {
             1.0,
         );
         self.draw(&matrix, fbo, Point2::new(0.0, 0.0), false);
-        let data = binding.read_rgb_data();
+        let data = if alpha {
+            binding.read_rgba_data()
+        } else {
+            binding.read_rgb_data()
+        };
         binding.unbind();
         data
     }
@@ -43,7 +43,7 @@ pub use self::scene::SceneShader;
 pub use self::shadow::ShadowShader;
 pub use self::solid::SolidShader;
 pub use self::ui::UiShader;
-pub use self::wire::WireShader;
+pub use self::wire::{WireShader, EMPHASIZED_SIZE_INDEX_OFFSET};
 use tachy::geom::RectSize;
 
 //===========================================================================//
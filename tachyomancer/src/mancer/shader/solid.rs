@@ -37,6 +37,8 @@ pub struct SolidShader {
     mvp: ShaderUniform<Matrix4<f32>>,
     rect_varray: VertexArray,
     _rect_vbuffer: VertexBuffer<u8>,
+    triangle_varray: VertexArray,
+    _triangle_vbuffer: VertexBuffer<u8>,
 }
 
 impl SolidShader {
@@ -54,6 +56,10 @@ impl SolidShader {
             VertexBuffer::new(&[0, 0, 0, 1, 0, 0, 0, 1, 0, 1, 1, 0]);
         rect_varray.bind();
         rect_vbuffer.attribf(0, 3, 0, 0);
+        let triangle_varray = VertexArray::new(1);
+        let triangle_vbuffer = VertexBuffer::new(&[0, 0, 0, 2, 1, 0, 0, 2, 0]);
+        triangle_varray.bind();
+        triangle_vbuffer.attribf(0, 3, 0, 0);
 
         Ok(SolidShader {
             program,
@@ -61,6 +67,8 @@ impl SolidShader {
             mvp,
             rect_varray,
             _rect_vbuffer: rect_vbuffer,
+            triangle_varray,
+            _triangle_vbuffer: triangle_vbuffer,
         })
     }
 
@@ -88,6 +96,25 @@ impl SolidShader {
         self.rect_varray.bind();
         self.rect_varray.draw(Primitive::TriangleStrip, 0, 4);
     }
+
+    /// Draws a triangle filling the given rect, with its base along the
+    /// rect's left edge and pointing towards the middle of its right edge.
+    /// Use a negative rect width to point the triangle the other way.
+    pub fn tint_triangle(
+        &self,
+        matrix: &Matrix4<f32>,
+        color: Color4,
+        rect: Rect<f32>,
+    ) {
+        self.program.bind();
+        self.color.set(&color);
+        let mvp = matrix
+            * Matrix4::trans2(rect.x, rect.y)
+            * Matrix4::scale2(0.5 * rect.width, 0.5 * rect.height);
+        self.mvp.set(&mvp);
+        self.triangle_varray.bind();
+        self.triangle_varray.draw(Primitive::Triangles, 0, 3);
+    }
 }
 
 //===========================================================================//
@@ -46,11 +46,29 @@ const SIZES: &[(f32, f32, f32, f32)] = &[
     (18./32., 88./128., 106./128., 124./128.), // 16-bit
 ];
 
+// Like SIZES, but with the difference in thickness between wire sizes
+// exaggerated, for players who want wider buses to stand out more.  Size
+// indices after the end of SIZES refer to these.
+#[cfg_attr(rustfmt, rustfmt_skip)]
+const EMPHASIZED_SIZES: &[(f32, f32, f32, f32)] = &[
+    ( 3./32.,  0./128.,   5./128.,  10./128.), // 0-bit
+    ( 4./32., 10./128.,  16./128.,  22./128.), // 1-bit
+    ( 7./32., 22./128.,  30./128.,  38./128.), // 2-bit
+    (11./32., 38./128.,  49./128.,  60./128.), // 4-bit
+    (15./32., 60./128.,  74./128.,  88./128.), // 8-bit
+    (18./32., 88./128., 106./128., 124./128.), // 16-bit
+];
+
+/// The size index of the first emphasized wire width (see
+/// `EMPHASIZED_SIZES`).
+pub const EMPHASIZED_SIZE_INDEX_OFFSET: usize = SIZES.len();
+
 #[cfg_attr(rustfmt, rustfmt_skip)]
 fn generate_wire_vertex_buffer() -> VertexBuffer<f32> {
-    let data_len = 3 * VERTICES_PER_WIRE_SIZE * SIZES.len();
+    let data_len =
+        3 * VERTICES_PER_WIRE_SIZE * (SIZES.len() + EMPHASIZED_SIZES.len());
     let mut data = Vec::with_capacity(data_len);
-    for &(st, outer, inner, outer2) in SIZES {
+    for &(st, outer, inner, outer2) in SIZES.iter().chain(EMPHASIZED_SIZES) {
         // Stub (east):
         data.extend_from_slice(&[
             28./32., 0.0,       inner,
@@ -44,6 +44,7 @@ pub enum Portrait {
 pub enum ConversationBubble {
     YouSpeech(String),
     YouChoice(String, Vec<(String, String)>),
+    YouChoiceMade(Vec<(String, String)>, String),
     NpcSpeech(Portrait, String, bool),
     Cutscene(Cutscene),
     Puzzles(Vec<Puzzle>),
@@ -217,8 +218,9 @@ impl<'a, 'b> ChoiceBuilder<'a, 'b> {
         if choice.is_some()
             || self.conversation.progress > self.conversation.bubbles.len()
         {
-            let (value, label) = self.get_choice(choice);
-            let bubble = ConversationBubble::YouSpeech(label);
+            let value = self.get_choice(choice);
+            let bubble =
+                ConversationBubble::YouChoiceMade(self.choices, value.clone());
             self.conversation.bubbles.push(bubble);
             Ok(value)
         } else {
@@ -228,15 +230,15 @@ impl<'a, 'b> ChoiceBuilder<'a, 'b> {
         }
     }
 
-    fn get_choice(&self, opt_choice: Option<&str>) -> (String, String) {
+    fn get_choice(&self, opt_choice: Option<&str>) -> String {
         if let Some(choice) = opt_choice {
-            for &(ref value, ref label) in self.choices.iter() {
+            for &(ref value, _) in self.choices.iter() {
                 if value == choice {
-                    return (value.clone(), label.clone());
+                    return value.clone();
                 }
             }
         }
-        return self.choices[0].clone();
+        return self.choices[0].0.clone();
     }
 }
 
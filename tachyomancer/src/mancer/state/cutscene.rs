@@ -105,6 +105,9 @@ impl CutsceneScript {
         self.node.unpause()
     }
 
+    /// Jumps straight to the end of the cutscene, applying any lasting
+    /// effects (such as background color changes) that haven't happened yet,
+    /// without playing sounds or showing talk bubbles.
     pub fn skip<T: Theater>(&mut self, theater: &mut T) {
         self.node.skip(theater);
     }
@@ -324,3 +327,88 @@ impl SceneNode {
 }
 
 //===========================================================================//
+
+#[cfg(test)]
+mod tests {
+    use super::{sn, CutsceneScript, Theater};
+    use crate::mancer::gui::Sound;
+    use crate::mancer::state::Portrait;
+    use std::collections::BTreeSet;
+    use tachy::geom::Color3;
+
+    #[derive(Default)]
+    struct TestTheater {
+        talks: BTreeSet<i32>,
+        next_tag: i32,
+        num_sounds: usize,
+        background: Option<(f32, f32, f32)>,
+    }
+
+    impl Theater for TestTheater {
+        fn add_talk(&mut self, _: Portrait, _: (i32, i32), _: &str) -> i32 {
+            let tag = self.next_tag;
+            self.next_tag += 1;
+            self.talks.insert(tag);
+            tag
+        }
+
+        fn talk_is_done(&self, _tag: i32) -> bool {
+            true
+        }
+
+        fn remove_talk(&mut self, tag: i32) {
+            self.talks.remove(&tag);
+        }
+
+        fn play_sound(&mut self, _sound: Sound) {
+            self.num_sounds += 1;
+        }
+
+        fn set_background_color(&mut self, color: Color3) {
+            self.background = Some((color.r, color.g, color.b));
+        }
+    }
+
+    fn test_script() -> CutsceneScript {
+        CutsceneScript::new(sn::seq(vec![
+            sn::background(0.1, 0.1, 0.1),
+            sn::wait(1.0),
+            sn::par(vec![
+                sn::talk(Portrait::Esra, (0, 0), "Hello"),
+                sn::sound(Sound::Beep),
+            ]),
+            sn::background(0.5, 0.5, 0.5),
+            sn::talk(Portrait::Lisa, (0, 0), "Goodbye"),
+            sn::background(0.9, 0.9, 0.9),
+            sn::wait(1.0),
+        ]))
+    }
+
+    #[test]
+    fn skip_jumps_to_final_state() {
+        let mut theater = TestTheater::default();
+        let mut script = test_script();
+        assert!(!script.tick(1.5, &mut theater));
+        assert_eq!(theater.talks.len(), 1);
+        assert_eq!(theater.num_sounds, 1);
+        script.skip(&mut theater);
+        assert!(theater.talks.is_empty());
+        assert_eq!(theater.num_sounds, 1);
+        assert_eq!(theater.background, Some((0.9, 0.9, 0.9)));
+        assert!(script.tick(0.0, &mut theater));
+    }
+
+    #[test]
+    fn skip_before_starting() {
+        let mut theater = TestTheater::default();
+        let mut script = test_script();
+        script.skip(&mut theater);
+        assert!(theater.talks.is_empty());
+        assert_eq!(theater.next_tag, 0);
+        assert_eq!(theater.num_sounds, 0);
+        assert_eq!(theater.background, Some((0.9, 0.9, 0.9)));
+        assert!(script.tick(0.0, &mut theater));
+    }
+}
+
+//===========================================================================//
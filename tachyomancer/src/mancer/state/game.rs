@@ -19,12 +19,21 @@
 
 use super::cutscene::CutsceneScript;
 use crate::mancer::save::{
-    MenuSection, Prefs, Profile, ProfileNamesIter, SaveDir,
+    is_valid_circuit_rename, unique_circuit_name, CircuitCopyKind,
+    IssueReport, MenuSection, Prefs, Profile, ProfileNamesIter, SaveDir,
+    SaveError,
 };
+use std::path::{Path, PathBuf};
 use std::time::Duration;
-use tachy::save::{Chapter, Conversation, Puzzle, ScoreCurve};
-use tachy::state::EditGrid;
-use unicase;
+use tachy::save::{
+    decode_share_code, encode_share_code, Chapter, CircuitNotes, Conversation,
+    Puzzle, PuzzleKind, PuzzleOptimality, ReferenceSpec, ScoreCurve,
+    SolutionData,
+};
+use tachy::state::{
+    check_shared_circuit, verify_against_reference, EditGrid, PuzzleExt,
+    ReferenceMismatch,
+};
 
 //===========================================================================//
 
@@ -35,10 +44,13 @@ const AUTOSAVE_DURATION: Duration = Duration::from_secs(60);
 pub struct GameState {
     savedir: SaveDir,
     menu_section: MenuSection,
+    puzzle_filter: Option<PuzzleKind>,
     profile: Option<Profile>,
     circuit_name: String,
     edit_grid: Option<EditGrid>,
     cutscene: Option<CutsceneScript>,
+    missing_location_warning_pending: bool,
+    read_only_warning_pending: bool,
 }
 
 impl GameState {
@@ -52,16 +64,46 @@ impl GameState {
                 circuit_name = name;
             }
         }
+        let missing_location_warning_pending =
+            savedir.missing_location_path().is_some();
+        let read_only_warning_pending = savedir.read_only_path().is_some();
         Ok(GameState {
             savedir,
             menu_section,
+            puzzle_filter: None,
             profile: opt_profile,
             circuit_name,
             edit_grid: None,
             cutscene: None,
+            missing_location_warning_pending,
+            read_only_warning_pending,
         })
     }
 
+    /// If the save data directory recorded from a previous move couldn't be
+    /// found and the player hasn't been warned about that yet, returns its
+    /// path (and marks the warning as shown).
+    pub fn take_missing_location_warning(&mut self) -> Option<PathBuf> {
+        if self.missing_location_warning_pending {
+            self.missing_location_warning_pending = false;
+            self.savedir.missing_location_path().map(Path::to_path_buf)
+        } else {
+            None
+        }
+    }
+
+    /// If the save data directory turned out to be read-only and the player
+    /// hasn't been warned about that yet, returns its path (and marks the
+    /// warning as shown).
+    pub fn take_read_only_warning(&mut self) -> Option<PathBuf> {
+        if self.read_only_warning_pending {
+            self.read_only_warning_pending = false;
+            self.savedir.read_only_path().map(Path::to_path_buf)
+        } else {
+            None
+        }
+    }
+
     pub fn save(&mut self) -> Result<(), String> {
         if let Some(ref mut profile) = self.profile {
             if let Some(ref mut grid) = self.edit_grid {
@@ -82,6 +124,56 @@ impl GameState {
         Ok(())
     }
 
+    /// Writes an issue report bundling the prefs, the current circuit (if
+    /// any), and recent log lines, and returns the path of the report file.
+    pub fn write_issue_report(&self) -> Result<PathBuf, String> {
+        let mut circuit = None;
+        if let Some(ref profile) = self.profile {
+            let puzzle = profile.current_puzzle();
+            let name = self.circuit_name.as_str();
+            if let Some(ref grid) = self.edit_grid {
+                circuit = Some((puzzle, name, grid.to_circuit_data()));
+            } else if !name.is_empty() {
+                match profile.load_circuit(puzzle, name) {
+                    Ok(data) => circuit = Some((puzzle, name, data)),
+                    Err(err) => {
+                        debug_warn!(
+                            "Could not load circuit for report: {}",
+                            err
+                        )
+                    }
+                }
+            }
+        }
+        let report = IssueReport {
+            prefs_toml: self.savedir.prefs().serialize_for_report()?,
+            circuit,
+            log_lines: tachy::log::recent_lines(),
+        };
+        self.savedir.write_issue_report(&report).map_err(String::from)
+    }
+
+    pub fn write_input_recording(
+        &self,
+        recording: &SolutionData,
+    ) -> Result<PathBuf, String> {
+        self.savedir.write_input_recording(recording).map_err(String::from)
+    }
+
+    /// Checks the current sandbox circuit against the reference table in
+    /// the save dir, returning any mismatches found.
+    pub fn verify_against_sandbox_reference(
+        &self,
+    ) -> Result<Vec<ReferenceMismatch>, Vec<String>> {
+        let grid = match self.edit_grid {
+            Some(ref grid) => grid,
+            None => return Err(vec!["No circuit loaded".to_string()]),
+        };
+        let spec = ReferenceSpec::load(self.savedir.sandbox_reference_path())
+            .map_err(|err| vec![err])?;
+        verify_against_reference(grid.puzzle(), &grid.to_circuit_data(), &spec)
+    }
+
     pub fn maybe_autosave_circuit(&mut self) {
         if let Some(ref mut grid) = self.edit_grid {
             if grid.has_been_modified_for_at_least(AUTOSAVE_DURATION)
@@ -107,6 +199,24 @@ impl GameState {
         }
     }
 
+    /// Returns the path of the directory where save data is stored.
+    pub fn save_dir_path(&self) -> &Path {
+        self.savedir.path()
+    }
+
+    /// Saves everything, then moves all save data to the given (empty or
+    /// nonexistent) directory and switches to using it.
+    pub fn move_save_dir(&mut self, new_path: &Path) -> Result<(), String> {
+        self.save()?;
+        self.savedir.relocate(new_path)?;
+        self.profile = self.savedir.load_current_profile_if_any()?;
+        Ok(())
+    }
+
+    pub fn screenshots_dir(&self) -> PathBuf {
+        self.savedir.screenshots_dir()
+    }
+
     pub fn prefs(&self) -> &Prefs {
         self.savedir.prefs()
     }
@@ -153,7 +263,7 @@ impl GameState {
         self.profile.as_ref()
     }
 
-    pub fn load_profile(&self, name: &str) -> Result<Profile, String> {
+    pub fn load_profile(&self, name: &str) -> Result<Profile, SaveError> {
         self.savedir.load_profile(name)
     }
 
@@ -201,6 +311,16 @@ impl GameState {
         self.menu_section = section;
     }
 
+    /// Returns the kind of puzzle that the puzzles list is currently
+    /// restricted to, if any.
+    pub fn puzzle_filter(&self) -> Option<PuzzleKind> {
+        self.puzzle_filter
+    }
+
+    pub fn set_puzzle_filter(&mut self, filter: Option<PuzzleKind>) {
+        self.puzzle_filter = filter;
+    }
+
     pub fn current_conversation(&self) -> Conversation {
         if let Some(ref profile) = self.profile {
             profile.current_conversation()
@@ -236,6 +356,15 @@ impl GameState {
         }
     }
 
+    /// Returns the earliest conversation that is unlocked but not yet
+    /// complete, if any.
+    pub fn first_incomplete_conversation(&self) -> Option<Conversation> {
+        Conversation::all().find(|&conv| {
+            self.is_conversation_unlocked(conv)
+                && !self.is_conversation_complete(conv)
+        })
+    }
+
     pub fn is_conversation_complete(&self, conv: Conversation) -> bool {
         self.profile
             .as_ref()
@@ -291,6 +420,11 @@ impl GameState {
     }
 
     pub fn set_current_puzzle(&mut self, puzzle: Puzzle) {
+        if let Some(kind) = self.puzzle_filter {
+            if puzzle.kind() != kind {
+                self.puzzle_filter = None;
+            }
+        }
         if let Some(ref mut profile) = self.profile {
             if profile.current_puzzle() != puzzle {
                 profile.set_current_puzzle(puzzle);
@@ -317,7 +451,9 @@ impl GameState {
 
     pub fn unlock_puzzle(&mut self, puzzle: Puzzle) -> Result<(), String> {
         match self.profile.as_mut() {
-            Some(profile) => profile.unlock_puzzle(puzzle),
+            Some(profile) => {
+                profile.unlock_puzzle(puzzle).map_err(String::from)
+            }
             None => Err("No profile loaded".to_string()),
         }
     }
@@ -328,6 +464,47 @@ impl GameState {
             .map_or(false, |profile| profile.is_puzzle_solved(puzzle))
     }
 
+    pub fn puzzle_seconds_spent(&self, puzzle: Puzzle) -> f64 {
+        self.profile
+            .as_ref()
+            .map_or(0.0, |profile| profile.puzzle_seconds_spent(puzzle))
+    }
+
+    /// Adds to the (purely local) record of how much time the player has
+    /// spent working on the given puzzle.
+    pub fn add_puzzle_seconds_spent(&mut self, puzzle: Puzzle, seconds: f64) {
+        if let Some(ref mut profile) = self.profile {
+            profile.add_puzzle_seconds_spent(puzzle, seconds);
+        }
+    }
+
+    /// Returns the earliest puzzle that is unlocked but not yet solved, if
+    /// any.
+    pub fn first_unsolved_puzzle(&self) -> Option<Puzzle> {
+        Puzzle::all().find(|&puzzle| {
+            self.is_puzzle_unlocked(puzzle) && !self.is_puzzle_solved(puzzle)
+        })
+    }
+
+    /// Compares the player's scores for the puzzle against the given global
+    /// scores.  If there are no global scores to compare against, a solved
+    /// puzzle will never be considered optimal.
+    pub fn puzzle_optimality(
+        &self,
+        puzzle: Puzzle,
+        global_scores: &ScoreCurve,
+    ) -> PuzzleOptimality {
+        if !self.is_puzzle_solved(puzzle) {
+            PuzzleOptimality::Unsolved
+        } else if !global_scores.is_empty()
+            && self.local_scores(puzzle).reaches_frontier(global_scores)
+        {
+            PuzzleOptimality::Optimal
+        } else {
+            PuzzleOptimality::Solved
+        }
+    }
+
     pub fn local_scores(&self, puzzle: Puzzle) -> &ScoreCurve {
         if let Some(ref profile) = self.profile {
             profile.local_scores(puzzle)
@@ -355,14 +532,36 @@ impl GameState {
                 area,
                 score
             );
+            let was_solved = profile.is_puzzle_solved(puzzle);
             profile.record_puzzle_score(puzzle, area, score)?;
             debug_assert!(profile.is_puzzle_solved(puzzle));
+            if !was_solved {
+                self.queue_first_solve_conversation(puzzle);
+            }
             Ok(())
         } else {
             Err("No profile loaded".to_string())
         }
     }
 
+    /// If solving the given puzzle for the first time advances a story
+    /// conversation, makes that the current conversation and switches the
+    /// menu to the messages section, so that the player sees it when they
+    /// return to the menu.
+    fn queue_first_solve_conversation(&mut self, puzzle: Puzzle) {
+        if let Some(conv) = puzzle.first_solve_conversation() {
+            if self.is_conversation_unlocked(conv) {
+                debug_log!(
+                    "Queuing {:?} after first solve of {:?}",
+                    conv,
+                    puzzle
+                );
+                self.set_current_conversation(conv);
+                self.set_menu_section(MenuSection::Messages);
+            }
+        }
+    }
+
     pub fn circuit_name(&self) -> &str {
         &self.circuit_name
     }
@@ -380,22 +579,26 @@ impl GameState {
     }
 
     pub fn is_valid_circuit_rename(&self, name: &str) -> bool {
-        let name = name.trim();
-        if name.is_empty() {
-            return false;
-        }
-        if unicase::eq(name, &self.circuit_name) {
-            return true;
-        }
-        return !self.has_circuit_name(name);
+        is_valid_circuit_rename(&self.circuit_name, name, |name| {
+            self.has_circuit_name(name)
+        })
     }
 
-    pub fn copy_current_circuit(&mut self) -> Result<(), String> {
+    pub fn copy_current_circuit(
+        &mut self,
+        kind: CircuitCopyKind,
+    ) -> Result<(), String> {
         if let Some(ref mut profile) = self.profile {
-            let prefix = format!("{}.", self.circuit_name);
-            let new_name = profile.choose_new_circuit_name(&prefix);
             let puzzle = profile.current_puzzle();
-            profile.copy_circuit(puzzle, &self.circuit_name, &new_name)?;
+            let new_name = unique_circuit_name(&self.circuit_name, |name| {
+                profile.has_circuit_name(puzzle, name)
+            });
+            profile.copy_circuit(
+                puzzle,
+                &self.circuit_name,
+                &new_name,
+                kind,
+            )?;
             self.circuit_name = new_name;
             Ok(())
         } else {
@@ -446,15 +649,128 @@ impl GameState {
         }
     }
 
+    /// Returns a share code for the current circuit, which another player
+    /// can paste to import the circuit.
+    pub fn current_circuit_share_code(&self) -> Result<String, String> {
+        if let Some(ref profile) = self.profile {
+            let puzzle = profile.current_puzzle();
+            let data = profile.load_circuit(puzzle, &self.circuit_name)?;
+            encode_share_code(puzzle, &data)
+        } else {
+            Err("No profile loaded".to_string())
+        }
+    }
+
+    /// Decodes a circuit share code and saves the circuit under a new name
+    /// for the puzzle it is for (which must be unlocked), making that the
+    /// current puzzle and circuit.  Returns the circuit's notes (if any), so
+    /// that they can be shown to the player.
+    pub fn import_shared_circuit(
+        &mut self,
+        code: &str,
+    ) -> Result<CircuitNotes, String> {
+        let (puzzle, mut data) = decode_share_code(code)?;
+        data.notes = data.notes.truncated();
+        if !self.is_puzzle_unlocked(puzzle) {
+            return Err(format!(
+                "This circuit is for a puzzle that you haven't unlocked yet \
+                 ({}).",
+                puzzle.title()
+            ));
+        }
+        // Validate the circuit before switching puzzles, so that a rejected
+        // import leaves the player where they were.
+        match self.profile {
+            Some(ref profile) => {
+                check_shared_circuit(puzzle, &profile.solved_puzzles(), &data)?
+            }
+            None => return Err("No profile loaded".to_string()),
+        }
+        self.set_current_puzzle(puzzle);
+        if let Some(ref mut profile) = self.profile {
+            let new_name = profile.choose_new_circuit_name("Imported ");
+            debug_log!("Importing shared circuit as {:?}", new_name);
+            profile.save_circuit(puzzle, &new_name, &data)?;
+            self.circuit_name = new_name;
+            Ok(data.notes)
+        } else {
+            Err("No profile loaded".to_string())
+        }
+    }
+
+    /// Returns the name of the snapshot held in each of the current puzzle's
+    /// snapshot slots, or `None` for slots that are empty.
+    pub fn snapshot_slot_names(&self) -> Vec<Option<String>> {
+        let num_slots = self.prefs().snapshot_slots();
+        if let Some(ref profile) = self.profile {
+            let puzzle = profile.current_puzzle();
+            (0..num_slots)
+                .map(|slot| profile.snapshot_name(puzzle, slot))
+                .collect()
+        } else {
+            vec![None; num_slots]
+        }
+    }
+
+    pub fn save_snapshot(
+        &mut self,
+        slot: usize,
+        name: &str,
+    ) -> Result<(), String> {
+        if let Some(ref mut profile) = self.profile {
+            if let Some(ref grid) = self.edit_grid {
+                let puzzle = profile.current_puzzle();
+                let circuit_data = grid.to_circuit_data();
+                profile
+                    .save_snapshot(puzzle, slot, name, &circuit_data)
+                    .map_err(String::from)
+            } else {
+                Err("No circuit loaded".to_string())
+            }
+        } else {
+            Err("No profile loaded".to_string())
+        }
+    }
+
+    /// Replaces the contents of the current edit grid with the circuit
+    /// stored in the given snapshot slot.  This is a single undoable change
+    /// to the grid, so the player can undo it to get back what they had, and
+    /// it marks the grid as modified, so that the result will get saved
+    /// under the current circuit name.
+    pub fn load_snapshot_into_edit_grid(
+        &mut self,
+        slot: usize,
+    ) -> Result<(), String> {
+        if let Some(ref profile) = self.profile {
+            let puzzle = profile.current_puzzle();
+            let solved = profile.solved_puzzles();
+            let data = profile.load_snapshot(puzzle, slot)?;
+            let snapshot = EditGrid::from_circuit_data(puzzle, &solved, &data);
+            let grid = self
+                .edit_grid
+                .as_mut()
+                .ok_or_else(|| "No circuit loaded".to_string())?;
+            if grid.try_replace_with(&snapshot) {
+                Ok(())
+            } else {
+                Err("The snapshot could not be loaded into the current \
+                     circuit."
+                    .to_string())
+            }
+        } else {
+            Err("No profile loaded".to_string())
+        }
+    }
+
     pub fn edit_grid(&self) -> Option<&EditGrid> {
         self.edit_grid.as_ref()
     }
 
     pub fn edit_grid_mut_and_prefs(
         &mut self,
-    ) -> Option<(&mut EditGrid, &Prefs)> {
+    ) -> Option<(&mut EditGrid, &mut Prefs)> {
         if let Some(ref mut grid) = self.edit_grid {
-            Some((grid, self.savedir.prefs()))
+            Some((grid, self.savedir.prefs_mut()))
         } else {
             None
         }
@@ -18,6 +18,12 @@
 // +--------------------------------------------------------------------------+
 
 use crate::mancer::gl::{Texture1D, Texture2D};
+use jpeg_decoder;
+use png;
+use std::error;
+use std::fmt;
+use std::fs;
+use std::path::Path;
 
 //===========================================================================//
 
@@ -68,266 +74,43 @@ const STARFIELD_JPEG_DATA: &[u8] = include_bytes!("scene/starfield.jpeg");
 const VALLEY_HEIGHTMAP_PNG_DATA: &[u8] =
     include_bytes!("scene/valley_heightmap.png");
 
-#[cfg_attr(rustfmt, rustfmt_skip)]
-const WIRE_TEXTURE1D_DATA: &[u8; 1024] = &[
-    // 0-bit (6 + 4 pixels):
-    0, 0, 255, 32,
-    0, 0, 255, 64,
-    0, 0, 255, 96,
-    0, 0, 255, 128,
-    0, 0, 255, 160,
-    0, 0, 255, 192,
-    192, 255, 0, 0,
-    128, 255, 0, 0,
-    0, 255, 0, 0,
-    0, 255, 0, 0,
-    0, 255, 0, 0,
-    0, 255, 0, 0,
-    128, 255, 0, 0,
-    192, 255, 0, 0,
-    0, 0, 255, 192,
-    0, 0, 255, 160,
-    0, 0, 255, 128,
-    0, 0, 255, 96,
-    0, 0, 255, 64,
-    0, 0, 255, 32,
-    // 1-bit (6 + 6 pixels):
-    0, 0, 255, 32,
-    0, 0, 255, 64,
-    0, 0, 255, 96,
-    0, 0, 255, 128,
-    0, 0, 255, 160,
-    0, 0, 255, 192,
-    128, 255, 0, 0,
-    128, 255, 0, 0,
-    0, 255, 0, 0,
-    0, 255, 0, 0,
-    255, 255, 0, 0,
-    255, 255, 0, 0,
-    255, 255, 0, 0,
-    255, 255, 0, 0,
-    0, 255, 0, 0,
-    0, 255, 0, 0,
-    128, 255, 0, 0,
-    128, 255, 0, 0,
-    0, 0, 255, 192,
-    0, 0, 255, 160,
-    0, 0, 255, 128,
-    0, 0, 255, 96,
-    0, 0, 255, 64,
-    0, 0, 255, 32,
-    // 2-bit (6 + 10 pixels):
-    0, 0, 255, 32,
-    0, 0, 255, 64,
-    0, 0, 255, 96,
-    0, 0, 255, 128,
-    0, 0, 255, 160,
-    0, 0, 255, 192,
-    128, 255, 0, 0,
-    128, 255, 0, 0,
-    0, 255, 0, 0,
-    0, 255, 0, 0,
-    192, 255, 0, 0,
-    255, 255, 0, 0,
-    255, 255, 0, 0,
-    192, 255, 0, 0,
-    0, 255, 0, 0,
-    0, 255, 0, 0,
-    0, 255, 0, 0,
-    0, 255, 0, 0,
-    192, 255, 0, 0,
-    255, 255, 0, 0,
-    255, 255, 0, 0,
-    192, 255, 0, 0,
-    0, 255, 0, 0,
-    0, 255, 0, 0,
-    128, 255, 0, 0,
-    128, 255, 0, 0,
-    0, 0, 255, 192,
-    0, 0, 255, 160,
-    0, 0, 255, 128,
-    0, 0, 255, 96,
-    0, 0, 255, 64,
-    0, 0, 255, 32,
-    // 4-bit (6 + 16 pixels):
-    0, 0, 255, 32,
-    0, 0, 255, 64,
-    0, 0, 255, 96,
-    0, 0, 255, 128,
-    0, 0, 255, 160,
-    0, 0, 255, 192,
-    128, 255, 0, 0,
-    0, 255, 0, 0,
-    0, 255, 0, 0,
-    0, 255, 0, 0,
-    255, 255, 0, 0,
-    255, 255, 0, 0,
-    255, 255, 0, 0,
-    0, 255, 0, 0,
-    0, 255, 0, 0,
-    0, 255, 0, 0,
-    0, 255, 0, 0,
-    255, 255, 0, 0,
-    255, 255, 0, 0,
-    255, 255, 0, 0,
-    0, 255, 0, 0,
-    0, 255, 0, 0,
-    0, 255, 0, 0,
-    0, 255, 0, 0,
-    255, 255, 0, 0,
-    255, 255, 0, 0,
-    255, 255, 0, 0,
-    0, 255, 0, 0,
-    0, 255, 0, 0,
-    0, 255, 0, 0,
-    0, 255, 0, 0,
-    255, 255, 0, 0,
-    255, 255, 0, 0,
-    255, 255, 0, 0,
-    0, 255, 0, 0,
-    0, 255, 0, 0,
-    0, 255, 0, 0,
-    128, 255, 0, 0,
-    0, 0, 255, 192,
-    0, 0, 255, 160,
-    0, 0, 255, 128,
-    0, 0, 255, 96,
-    0, 0, 255, 64,
-    0, 0, 255, 32,
-    // 8-bit (6 + 22 pixels):
-    0, 0, 255, 32,
-    0, 0, 255, 64,
-    0, 0, 255, 96,
-    0, 0, 255, 128,
-    0, 0, 255, 160,
-    0, 0, 255, 192,
-    0, 255, 0, 0,
-    0, 255, 0, 0,
-    0, 255, 0, 0,
-    255, 255, 0, 0,
-    255, 255, 0, 0,
-    255, 255, 0, 0,
-    0, 255, 0, 0,
-    0, 255, 0, 0,
-    255, 255, 0, 0,
-    255, 255, 0, 0,
-    255, 255, 0, 0,
-    0, 255, 0, 0,
-    0, 255, 0, 0,
-    255, 255, 0, 0,
-    255, 255, 0, 0,
-    255, 255, 0, 0,
-    0, 255, 0, 0,
-    0, 255, 0, 0,
-    255, 255, 0, 0,
-    255, 255, 0, 0,
-    255, 255, 0, 0,
-    0, 255, 0, 0,
-    0, 255, 0, 0,
-    255, 255, 0, 0,
-    255, 255, 0, 0,
-    255, 255, 0, 0,
-    0, 255, 0, 0,
-    0, 255, 0, 0,
-    255, 255, 0, 0,
-    255, 255, 0, 0,
-    255, 255, 0, 0,
-    0, 255, 0, 0,
-    0, 255, 0, 0,
-    255, 255, 0, 0,
-    255, 255, 0, 0,
-    255, 255, 0, 0,
-    0, 255, 0, 0,
-    0, 255, 0, 0,
-    255, 255, 0, 0,
-    255, 255, 0, 0,
-    255, 255, 0, 0,
-    0, 255, 0, 0,
-    0, 255, 0, 0,
-    0, 255, 0, 0,
-    0, 0, 255, 192,
-    0, 0, 255, 160,
-    0, 0, 255, 128,
-    0, 0, 255, 96,
-    0, 0, 255, 64,
-    0, 0, 255, 32,
-    // 16-bit (6 + 30 pixels):
-    0, 0, 255, 32,
-    0, 0, 255, 64,
-    0, 0, 255, 96,
-    0, 0, 255, 128,
-    0, 0, 255, 160,
-    0, 0, 255, 192,
-    128, 255, 0, 0,
-    128, 255, 0, 0,
-    0, 255, 0, 0,
-    0, 255, 0, 0,
-    128, 255, 0, 0,
-    144, 255, 0, 0,
-    160, 255, 0, 0,
-    176, 255, 0, 0,
-    192, 255, 0, 0,
-    192, 255, 0, 0,
-    176, 255, 0, 0,
-    160, 255, 0, 0,
-    144, 255, 0, 0,
-    128, 255, 0, 0,
-    0, 255, 0, 0,
-    0, 255, 0, 0,
-    0, 255, 0, 0,
-    0, 255, 0, 0,
-    128, 255, 0, 0,
-    144, 255, 0, 0,
-    160, 255, 0, 0,
-    176, 255, 0, 0,
-    192, 255, 0, 0,
-    192, 255, 0, 0,
-    176, 255, 0, 0,
-    160, 255, 0, 0,
-    144, 255, 0, 0,
-    128, 255, 0, 0,
-    0, 255, 0, 0,
-    0, 255, 0, 0,
-    0, 255, 0, 0,
-    0, 255, 0, 0,
-    128, 255, 0, 0,
-    144, 255, 0, 0,
-    160, 255, 0, 0,
-    176, 255, 0, 0,
-    192, 255, 0, 0,
-    192, 255, 0, 0,
-    176, 255, 0, 0,
-    160, 255, 0, 0,
-    144, 255, 0, 0,
-    128, 255, 0, 0,
-    0, 255, 0, 0,
-    0, 255, 0, 0,
-    0, 255, 0, 0,
-    0, 255, 0, 0,
-    128, 255, 0, 0,
-    144, 255, 0, 0,
-    160, 255, 0, 0,
-    176, 255, 0, 0,
-    192, 255, 0, 0,
-    192, 255, 0, 0,
-    176, 255, 0, 0,
-    160, 255, 0, 0,
-    144, 255, 0, 0,
-    128, 255, 0, 0,
-    0, 255, 0, 0,
-    0, 255, 0, 0,
-    128, 255, 0, 0,
-    128, 255, 0, 0,
-    0, 0, 255, 192,
-    0, 0, 255, 160,
-    0, 0, 255, 128,
-    0, 0, 255, 96,
-    0, 0, 255, 64,
-    0, 0, 255, 32,
-    // Padding:
-    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-];
+// The wire bit widths that get a section in the wire texture, in order (see
+// SIZES in shader/wire.rs for the corresponding texture coordinates).
+const WIRE_TEXTURE_BIT_WIDTHS: &[u32] = &[0, 1, 2, 4, 8, 16];
+
+// The number of pixels on each side of a wire texture section used for the
+// wire's edge gradient:
+const WIRE_TEXTURE_EDGE_PIXELS: usize = 6;
+
+//===========================================================================//
+
+/// An error that occurred while creating textures.
+#[derive(Debug)]
+pub enum TextureError {
+    /// The named built-in image could not be decoded or uploaded.
+    Embedded(&'static str, String),
+    /// The named procedurally-generated texture could not be created.
+    Generated(&'static str, String),
+}
+
+impl fmt::Display for TextureError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TextureError::Embedded(_, message)
+            | TextureError::Generated(_, message) => {
+                formatter.write_str(message)
+            }
+        }
+    }
+}
+
+impl error::Error for TextureError {}
+
+impl From<TextureError> for String {
+    fn from(error: TextureError) -> String {
+        error.to_string()
+    }
+}
 
 //===========================================================================//
 
@@ -352,43 +135,57 @@ pub struct Textures {
 }
 
 impl Textures {
-    pub fn new() -> Result<Textures, String> {
+    /// Creates all textures.  Scene textures can be overridden by image files
+    /// in `texture_pack_dir` (e.g. `starfield.jpeg`); any replacement that is
+    /// missing, invalid, or a different size than the embedded default falls
+    /// back to the embedded default.
+    pub fn new(texture_pack_dir: &Path) -> Result<Textures, TextureError> {
         let brushed_metal =
-            Texture2D::from_jpeg("brushed_metal", BRUSHED_METAL_JPEG_DATA)?;
+            embedded_jpeg("brushed_metal", BRUSHED_METAL_JPEG_DATA)?;
         let chip_icons =
-            Texture2D::from_png("texture/chip_icons", CHIP_ICONS_PNG_DATA)?;
-        let diagram_background = Texture2D::from_png(
-            "diagram/background",
-            DIAGRAM_BACKGROUND_PNG_DATA,
-        )?;
+            embedded_png("texture/chip_icons", CHIP_ICONS_PNG_DATA)?;
+        let diagram_background =
+            embedded_png("diagram/background", DIAGRAM_BACKGROUND_PNG_DATA)?;
         let diagram_lander =
-            Texture2D::from_png("diagram/lander", DIAGRAM_LANDER_PNG_DATA)?;
+            embedded_png("diagram/lander", DIAGRAM_LANDER_PNG_DATA)?;
         let diagram_patch =
-            Texture2D::from_png("diagram/patch", DIAGRAM_PATCH_PNG_DATA)?;
+            embedded_png("diagram/patch", DIAGRAM_PATCH_PNG_DATA)?;
         let diagram_reactor =
-            Texture2D::from_png("diagram/reactor", DIAGRAM_REACTOR_PNG_DATA)?;
+            embedded_png("diagram/reactor", DIAGRAM_REACTOR_PNG_DATA)?;
         let diagram_shields =
-            Texture2D::from_png("diagram/shields", DIAGRAM_SHIELDS_PNG_DATA)?;
+            embedded_png("diagram/shields", DIAGRAM_SHIELDS_PNG_DATA)?;
         let diagram_storage =
-            Texture2D::from_png("diagram/storage", DIAGRAM_STORAGE_PNG_DATA)?;
+            embedded_png("diagram/storage", DIAGRAM_STORAGE_PNG_DATA)?;
         let diagram_turret =
-            Texture2D::from_png("diagram/turret", DIAGRAM_TURRET_PNG_DATA)?;
+            embedded_png("diagram/turret", DIAGRAM_TURRET_PNG_DATA)?;
         let list_icons =
-            Texture2D::from_png("texture/list_icons", LIST_ICONS_PNG_DATA)?;
-        let portraits =
-            Texture2D::from_png("texture/portraits", PORTRAITS_PNG_DATA)?;
-        let red_desert =
-            Texture2D::from_jpeg("red_desert", RED_DESERT_JPEG_DATA)?;
-        let red_planet =
-            Texture2D::from_jpeg("red_planet", RED_PLANET_JPEG_DATA)?;
-        let starfield =
-            Texture2D::from_jpeg("starfield", STARFIELD_JPEG_DATA)?;
-        let valley_heightmap = Texture2D::from_png(
+            embedded_png("texture/list_icons", LIST_ICONS_PNG_DATA)?;
+        let portraits = embedded_png("texture/portraits", PORTRAITS_PNG_DATA)?;
+        let red_desert = load_scene_jpeg(
+            texture_pack_dir,
+            "red_desert",
+            RED_DESERT_JPEG_DATA,
+        )?;
+        let red_planet = load_scene_jpeg(
+            texture_pack_dir,
+            "red_planet",
+            RED_PLANET_JPEG_DATA,
+        )?;
+        let starfield = load_scene_jpeg(
+            texture_pack_dir,
+            "starfield",
+            STARFIELD_JPEG_DATA,
+        )?;
+        let valley_heightmap = load_scene_png(
+            texture_pack_dir,
             "valley_heightmap",
             VALLEY_HEIGHTMAP_PNG_DATA,
         )?;
-        let white = Texture2D::new_rgba(1, 1, &[255, 255, 255, 255])?;
-        let wire = Texture1D::new_rgba(WIRE_TEXTURE1D_DATA)?;
+        let white = Texture2D::new_rgba(1, 1, &[255, 255, 255, 255])
+            .map_err(|err| TextureError::Generated("white", err))?;
+        let wire =
+            Texture1D::new_rgba(&wire_texture1d_data(WIRE_TEXTURE_BIT_WIDTHS))
+                .map_err(|err| TextureError::Generated("wire", err))?;
         Ok(Textures {
             brushed_metal,
             chip_icons,
@@ -480,3 +277,486 @@ impl Textures {
 }
 
 //===========================================================================//
+
+fn load_scene_jpeg(
+    texture_pack_dir: &Path,
+    name: &'static str,
+    default_data: &[u8],
+) -> Result<Texture2D, TextureError> {
+    let path = texture_pack_dir.join(format!("{}.jpeg", name));
+    if let Some(data) = read_replacement(&path) {
+        match check_replacement_size(name, jpeg_size, &data, default_data)
+            .and_then(|()| Texture2D::from_jpeg(name, &data))
+        {
+            Ok(texture) => return Ok(texture),
+            Err(err) => {
+                debug_warn!("Ignoring invalid replacement {:?}: {}", path, err)
+            }
+        }
+    }
+    embedded_jpeg(name, default_data)
+}
+
+fn load_scene_png(
+    texture_pack_dir: &Path,
+    name: &'static str,
+    default_data: &[u8],
+) -> Result<Texture2D, TextureError> {
+    let path = texture_pack_dir.join(format!("{}.png", name));
+    if let Some(data) = read_replacement(&path) {
+        match check_replacement_size(name, png_size, &data, default_data)
+            .and_then(|()| Texture2D::from_png(name, &data))
+        {
+            Ok(texture) => return Ok(texture),
+            Err(err) => {
+                debug_warn!("Ignoring invalid replacement {:?}: {}", path, err)
+            }
+        }
+    }
+    embedded_png(name, default_data)
+}
+
+fn embedded_jpeg(
+    name: &'static str,
+    data: &[u8],
+) -> Result<Texture2D, TextureError> {
+    Texture2D::from_jpeg(name, data)
+        .map_err(|err| TextureError::Embedded(name, err))
+}
+
+fn embedded_png(
+    name: &'static str,
+    data: &[u8],
+) -> Result<Texture2D, TextureError> {
+    Texture2D::from_png(name, data)
+        .map_err(|err| TextureError::Embedded(name, err))
+}
+
+/// Returns an error if the replacement image data has different dimensions
+/// than the built-in image it replaces (the scenes that use these textures
+/// assume the built-in sizes).
+fn check_replacement_size<F>(
+    name: &str,
+    image_size: F,
+    data: &[u8],
+    default_data: &[u8],
+) -> Result<(), String>
+where
+    F: Fn(&[u8]) -> Result<(u32, u32), String>,
+{
+    let (width, height) = image_size(data)?;
+    let (default_width, default_height) = image_size(default_data)?;
+    if (width, height) != (default_width, default_height) {
+        return Err(format!(
+            "Replacement for {} has size of {}x{}, but must be {}x{}",
+            name, width, height, default_width, default_height
+        ));
+    }
+    Ok(())
+}
+
+fn jpeg_size(data: &[u8]) -> Result<(u32, u32), String> {
+    let mut decoder = jpeg_decoder::Decoder::new(data);
+    decoder
+        .read_info()
+        .map_err(|err| format!("Failed to read JPEG header: {}", err))?;
+    let info = decoder.info().unwrap();
+    Ok((info.width.into(), info.height.into()))
+}
+
+fn png_size(data: &[u8]) -> Result<(u32, u32), String> {
+    let (info, _) = png::Decoder::new(data)
+        .read_info()
+        .map_err(|err| format!("Failed to read PNG header: {}", err))?;
+    Ok((info.width, info.height))
+}
+
+fn read_replacement(path: &Path) -> Option<Vec<u8>> {
+    if !path.is_file() {
+        return None;
+    }
+    match fs::read(path) {
+        Ok(data) => {
+            debug_log!("Loading replacement texture from {:?}", path);
+            Some(data)
+        }
+        Err(err) => {
+            debug_warn!("Could not read replacement {:?}: {}", path, err);
+            None
+        }
+    }
+}
+
+/// Returns the red-channel values for the first half of the stripe pattern
+/// drawn down the middle of a wire with the given bit width (the second half
+/// is the mirror image of the first).
+fn wire_stripe_half_pattern(num_bits: u32) -> &'static [u8] {
+    match num_bits {
+        0 => &[192, 128, 0, 0],
+        1 => &[128, 128, 0, 0, 255, 255],
+        2 => &[128, 128, 0, 0, 192, 255, 255, 192, 0, 0],
+        4 => &[128, 0, 0, 0, 255, 255, 255, 0, 0, 0, 0, 255, 255, 255, 0, 0],
+        8 => &[
+            0, 0, 0, 255, 255, 255, 0, 0, 255, 255, 255, 0, 0, 255, 255, 255,
+            0, 0, 255, 255, 255, 0,
+        ],
+        16 => &[
+            128, 128, 0, 0, 128, 144, 160, 176, 192, 192, 176, 160, 144, 128,
+            0, 0, 0, 0, 128, 144, 160, 176, 192, 192, 176, 160, 144, 128, 0,
+            0,
+        ],
+        _ => panic!("No wire texture pattern for {}-bit wires", num_bits),
+    }
+}
+
+/// Generates RGBA data for the 1D wire texture, with one section for each of
+/// the given bit widths, padded with transparent pixels out to a power-of-two
+/// width.  Each section is symmetric about its center, and consists of an
+/// edge gradient (in the blue and alpha channels) on either side of the
+/// stripe pattern for that bit width (in the red and green channels).
+fn wire_texture1d_data(bit_widths: &[u32]) -> Vec<u8> {
+    let mut data = Vec::<u8>::new();
+    for &num_bits in bit_widths.iter() {
+        let mut half = Vec::<[u8; 4]>::new();
+        for index in 0..WIRE_TEXTURE_EDGE_PIXELS {
+            half.push([0, 0, 255, 32 * (index as u8 + 1)]);
+        }
+        for &red in wire_stripe_half_pattern(num_bits).iter() {
+            half.push([red, 255, 0, 0]);
+        }
+        for pixel in half.iter().chain(half.iter().rev()) {
+            data.extend_from_slice(pixel);
+        }
+    }
+    let num_pixels = (data.len() / 4).next_power_of_two();
+    data.resize(num_pixels * 4, 0);
+    data
+}
+
+//===========================================================================//
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        check_replacement_size, jpeg_size, png_size, wire_texture1d_data,
+        STARFIELD_JPEG_DATA, VALLEY_HEIGHTMAP_PNG_DATA,
+        WIRE_TEXTURE_BIT_WIDTHS,
+    };
+    use png::{self, HasParameters};
+
+    fn grayscale_png(width: u32, height: u32) -> Vec<u8> {
+        let mut data = Vec::<u8>::new();
+        {
+            let mut encoder = png::Encoder::new(&mut data, width, height);
+            encoder.set(png::ColorType::Grayscale).set(png::BitDepth::Eight);
+            let mut writer = encoder.write_header().unwrap();
+            let pixels = vec![0u8; (width * height) as usize];
+            writer.write_image_data(&pixels).unwrap();
+        }
+        data
+    }
+
+    #[test]
+    fn replacement_with_same_size_is_accepted() {
+        assert!(check_replacement_size(
+            "starfield",
+            jpeg_size,
+            STARFIELD_JPEG_DATA,
+            STARFIELD_JPEG_DATA
+        )
+        .is_ok());
+        let (width, height) = png_size(VALLEY_HEIGHTMAP_PNG_DATA).unwrap();
+        let replacement = grayscale_png(width, height);
+        assert!(check_replacement_size(
+            "valley_heightmap",
+            png_size,
+            &replacement,
+            VALLEY_HEIGHTMAP_PNG_DATA
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn replacement_with_different_size_is_rejected() {
+        let (width, height) = png_size(VALLEY_HEIGHTMAP_PNG_DATA).unwrap();
+        let replacement = grayscale_png(width / 2, height);
+        let result = check_replacement_size(
+            "valley_heightmap",
+            png_size,
+            &replacement,
+            VALLEY_HEIGHTMAP_PNG_DATA,
+        );
+        assert!(result.is_err());
+        let message = result.unwrap_err();
+        assert!(message.contains(&format!("{}x{}", width / 2, height)));
+    }
+
+    #[test]
+    fn wire_texture1d_data_matches_original() {
+        let data = wire_texture1d_data(WIRE_TEXTURE_BIT_WIDTHS);
+        assert_eq!(data.as_slice(), &WIRE_TEXTURE1D_DATA[..]);
+    }
+
+    #[cfg_attr(rustfmt, rustfmt_skip)]
+    const WIRE_TEXTURE1D_DATA: &[u8; 1024] = &[
+        // 0-bit (6 + 4 pixels):
+        0, 0, 255, 32,
+        0, 0, 255, 64,
+        0, 0, 255, 96,
+        0, 0, 255, 128,
+        0, 0, 255, 160,
+        0, 0, 255, 192,
+        192, 255, 0, 0,
+        128, 255, 0, 0,
+        0, 255, 0, 0,
+        0, 255, 0, 0,
+        0, 255, 0, 0,
+        0, 255, 0, 0,
+        128, 255, 0, 0,
+        192, 255, 0, 0,
+        0, 0, 255, 192,
+        0, 0, 255, 160,
+        0, 0, 255, 128,
+        0, 0, 255, 96,
+        0, 0, 255, 64,
+        0, 0, 255, 32,
+        // 1-bit (6 + 6 pixels):
+        0, 0, 255, 32,
+        0, 0, 255, 64,
+        0, 0, 255, 96,
+        0, 0, 255, 128,
+        0, 0, 255, 160,
+        0, 0, 255, 192,
+        128, 255, 0, 0,
+        128, 255, 0, 0,
+        0, 255, 0, 0,
+        0, 255, 0, 0,
+        255, 255, 0, 0,
+        255, 255, 0, 0,
+        255, 255, 0, 0,
+        255, 255, 0, 0,
+        0, 255, 0, 0,
+        0, 255, 0, 0,
+        128, 255, 0, 0,
+        128, 255, 0, 0,
+        0, 0, 255, 192,
+        0, 0, 255, 160,
+        0, 0, 255, 128,
+        0, 0, 255, 96,
+        0, 0, 255, 64,
+        0, 0, 255, 32,
+        // 2-bit (6 + 10 pixels):
+        0, 0, 255, 32,
+        0, 0, 255, 64,
+        0, 0, 255, 96,
+        0, 0, 255, 128,
+        0, 0, 255, 160,
+        0, 0, 255, 192,
+        128, 255, 0, 0,
+        128, 255, 0, 0,
+        0, 255, 0, 0,
+        0, 255, 0, 0,
+        192, 255, 0, 0,
+        255, 255, 0, 0,
+        255, 255, 0, 0,
+        192, 255, 0, 0,
+        0, 255, 0, 0,
+        0, 255, 0, 0,
+        0, 255, 0, 0,
+        0, 255, 0, 0,
+        192, 255, 0, 0,
+        255, 255, 0, 0,
+        255, 255, 0, 0,
+        192, 255, 0, 0,
+        0, 255, 0, 0,
+        0, 255, 0, 0,
+        128, 255, 0, 0,
+        128, 255, 0, 0,
+        0, 0, 255, 192,
+        0, 0, 255, 160,
+        0, 0, 255, 128,
+        0, 0, 255, 96,
+        0, 0, 255, 64,
+        0, 0, 255, 32,
+        // 4-bit (6 + 16 pixels):
+        0, 0, 255, 32,
+        0, 0, 255, 64,
+        0, 0, 255, 96,
+        0, 0, 255, 128,
+        0, 0, 255, 160,
+        0, 0, 255, 192,
+        128, 255, 0, 0,
+        0, 255, 0, 0,
+        0, 255, 0, 0,
+        0, 255, 0, 0,
+        255, 255, 0, 0,
+        255, 255, 0, 0,
+        255, 255, 0, 0,
+        0, 255, 0, 0,
+        0, 255, 0, 0,
+        0, 255, 0, 0,
+        0, 255, 0, 0,
+        255, 255, 0, 0,
+        255, 255, 0, 0,
+        255, 255, 0, 0,
+        0, 255, 0, 0,
+        0, 255, 0, 0,
+        0, 255, 0, 0,
+        0, 255, 0, 0,
+        255, 255, 0, 0,
+        255, 255, 0, 0,
+        255, 255, 0, 0,
+        0, 255, 0, 0,
+        0, 255, 0, 0,
+        0, 255, 0, 0,
+        0, 255, 0, 0,
+        255, 255, 0, 0,
+        255, 255, 0, 0,
+        255, 255, 0, 0,
+        0, 255, 0, 0,
+        0, 255, 0, 0,
+        0, 255, 0, 0,
+        128, 255, 0, 0,
+        0, 0, 255, 192,
+        0, 0, 255, 160,
+        0, 0, 255, 128,
+        0, 0, 255, 96,
+        0, 0, 255, 64,
+        0, 0, 255, 32,
+        // 8-bit (6 + 22 pixels):
+        0, 0, 255, 32,
+        0, 0, 255, 64,
+        0, 0, 255, 96,
+        0, 0, 255, 128,
+        0, 0, 255, 160,
+        0, 0, 255, 192,
+        0, 255, 0, 0,
+        0, 255, 0, 0,
+        0, 255, 0, 0,
+        255, 255, 0, 0,
+        255, 255, 0, 0,
+        255, 255, 0, 0,
+        0, 255, 0, 0,
+        0, 255, 0, 0,
+        255, 255, 0, 0,
+        255, 255, 0, 0,
+        255, 255, 0, 0,
+        0, 255, 0, 0,
+        0, 255, 0, 0,
+        255, 255, 0, 0,
+        255, 255, 0, 0,
+        255, 255, 0, 0,
+        0, 255, 0, 0,
+        0, 255, 0, 0,
+        255, 255, 0, 0,
+        255, 255, 0, 0,
+        255, 255, 0, 0,
+        0, 255, 0, 0,
+        0, 255, 0, 0,
+        255, 255, 0, 0,
+        255, 255, 0, 0,
+        255, 255, 0, 0,
+        0, 255, 0, 0,
+        0, 255, 0, 0,
+        255, 255, 0, 0,
+        255, 255, 0, 0,
+        255, 255, 0, 0,
+        0, 255, 0, 0,
+        0, 255, 0, 0,
+        255, 255, 0, 0,
+        255, 255, 0, 0,
+        255, 255, 0, 0,
+        0, 255, 0, 0,
+        0, 255, 0, 0,
+        255, 255, 0, 0,
+        255, 255, 0, 0,
+        255, 255, 0, 0,
+        0, 255, 0, 0,
+        0, 255, 0, 0,
+        0, 255, 0, 0,
+        0, 0, 255, 192,
+        0, 0, 255, 160,
+        0, 0, 255, 128,
+        0, 0, 255, 96,
+        0, 0, 255, 64,
+        0, 0, 255, 32,
+        // 16-bit (6 + 30 pixels):
+        0, 0, 255, 32,
+        0, 0, 255, 64,
+        0, 0, 255, 96,
+        0, 0, 255, 128,
+        0, 0, 255, 160,
+        0, 0, 255, 192,
+        128, 255, 0, 0,
+        128, 255, 0, 0,
+        0, 255, 0, 0,
+        0, 255, 0, 0,
+        128, 255, 0, 0,
+        144, 255, 0, 0,
+        160, 255, 0, 0,
+        176, 255, 0, 0,
+        192, 255, 0, 0,
+        192, 255, 0, 0,
+        176, 255, 0, 0,
+        160, 255, 0, 0,
+        144, 255, 0, 0,
+        128, 255, 0, 0,
+        0, 255, 0, 0,
+        0, 255, 0, 0,
+        0, 255, 0, 0,
+        0, 255, 0, 0,
+        128, 255, 0, 0,
+        144, 255, 0, 0,
+        160, 255, 0, 0,
+        176, 255, 0, 0,
+        192, 255, 0, 0,
+        192, 255, 0, 0,
+        176, 255, 0, 0,
+        160, 255, 0, 0,
+        144, 255, 0, 0,
+        128, 255, 0, 0,
+        0, 255, 0, 0,
+        0, 255, 0, 0,
+        0, 255, 0, 0,
+        0, 255, 0, 0,
+        128, 255, 0, 0,
+        144, 255, 0, 0,
+        160, 255, 0, 0,
+        176, 255, 0, 0,
+        192, 255, 0, 0,
+        192, 255, 0, 0,
+        176, 255, 0, 0,
+        160, 255, 0, 0,
+        144, 255, 0, 0,
+        128, 255, 0, 0,
+        0, 255, 0, 0,
+        0, 255, 0, 0,
+        0, 255, 0, 0,
+        0, 255, 0, 0,
+        128, 255, 0, 0,
+        144, 255, 0, 0,
+        160, 255, 0, 0,
+        176, 255, 0, 0,
+        192, 255, 0, 0,
+        192, 255, 0, 0,
+        176, 255, 0, 0,
+        160, 255, 0, 0,
+        144, 255, 0, 0,
+        128, 255, 0, 0,
+        0, 255, 0, 0,
+        0, 255, 0, 0,
+        128, 255, 0, 0,
+        128, 255, 0, 0,
+        0, 0, 255, 192,
+        0, 0, 255, 160,
+        0, 0, 255, 128,
+        0, 0, 255, 96,
+        0, 0, 255, 64,
+        0, 0, 255, 32,
+        // Padding:
+        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+    ];
+}
+
+//===========================================================================//
@@ -21,23 +21,55 @@ mod odyssey;
 mod planetfall;
 mod shared;
 
-pub use self::odyssey::OdysseyBackgroundView;
-pub use self::planetfall::PlanetfallBackgroundView;
+pub use self::odyssey::{OdysseyBackgroundView, OdysseyGeometry};
+pub use self::planetfall::{PlanetfallBackgroundView, PlanetfallGeometry};
 pub use self::shared::BackgroundView;
 use tachy::geom::RectSize;
 use tachy::save::Chapter;
 
 //===========================================================================//
 
+/// The model geometry for a chapter background.  Generating this is the slow
+/// part of creating a background, but unlike creating the view itself (which
+/// uploads the models to the GPU), it can be done off of the main thread.
+pub enum BackgroundGeometry {
+    Odyssey(OdysseyGeometry),
+    Planetfall(PlanetfallGeometry),
+}
+
+impl BackgroundGeometry {
+    pub fn for_chapter(chapter: Chapter) -> BackgroundGeometry {
+        match chapter {
+            Chapter::Odyssey => {
+                BackgroundGeometry::Odyssey(OdysseyGeometry::generate())
+            }
+            // TODO: other chapter backgrounds
+            _ => {
+                BackgroundGeometry::Planetfall(PlanetfallGeometry::generate())
+            }
+        }
+    }
+
+    pub fn into_view(
+        self,
+        screen_size: RectSize<f32>,
+    ) -> Box<dyn BackgroundView> {
+        match self {
+            BackgroundGeometry::Odyssey(geometry) => {
+                Box::new(OdysseyBackgroundView::new(geometry, screen_size))
+            }
+            BackgroundGeometry::Planetfall(geometry) => {
+                Box::new(PlanetfallBackgroundView::new(geometry, screen_size))
+            }
+        }
+    }
+}
+
 pub fn background_for_chapter(
     chapter: Chapter,
     screen_size: RectSize<f32>,
 ) -> Box<dyn BackgroundView> {
-    match chapter {
-        Chapter::Odyssey => Box::new(OdysseyBackgroundView::new(screen_size)),
-        // TODO: other chapter backgrounds
-        _ => Box::new(PlanetfallBackgroundView::new(screen_size)),
-    }
+    BackgroundGeometry::for_chapter(chapter).into_view(screen_size)
 }
 
 //===========================================================================//
@@ -32,19 +32,16 @@ const PLANET_TILT_Z: Deg<f32> = Deg(10.0);
 
 //===========================================================================//
 
-pub struct OdysseyBackgroundView {
-    p_matrix: Matrix4<f32>,
-    planet_model: Model,
-    planet_rotation: i32,
-    ship_model: Model,
-    starfield_model: Model,
+/// The model geometry for an `OdysseyBackgroundView`, which can be generated
+/// on any thread.
+pub struct OdysseyGeometry {
+    planet: ModelBuilder,
+    ship: ModelBuilder,
+    starfield: ModelBuilder,
 }
 
-impl OdysseyBackgroundView {
-    pub fn new(screen_size: RectSize<f32>) -> OdysseyBackgroundView {
-        let aspect = screen_size.width / screen_size.height;
-        let p_matrix = cgmath::perspective(Deg(45.0), aspect, 0.1, 1000.0);
-
+impl OdysseyGeometry {
+    pub fn generate() -> OdysseyGeometry {
         let mut planet = ModelBuilder::new();
         planet
             .with_transform(Matrix4::from_scale(PLANET_RADIUS))
@@ -58,12 +55,33 @@ impl OdysseyBackgroundView {
             Color3::WHITE,
         );
 
+        OdysseyGeometry { planet, ship: make_ship_model(), starfield }
+    }
+}
+
+//===========================================================================//
+
+pub struct OdysseyBackgroundView {
+    p_matrix: Matrix4<f32>,
+    planet_model: Model,
+    planet_rotation: i32,
+    ship_model: Model,
+    starfield_model: Model,
+}
+
+impl OdysseyBackgroundView {
+    pub fn new(
+        geometry: OdysseyGeometry,
+        screen_size: RectSize<f32>,
+    ) -> OdysseyBackgroundView {
+        let aspect = screen_size.width / screen_size.height;
+        let p_matrix = cgmath::perspective(Deg(45.0), aspect, 0.1, 1000.0);
         OdysseyBackgroundView {
             p_matrix,
-            planet_model: planet.build(),
+            planet_model: geometry.planet.build(),
             planet_rotation: get_planet_rotation(),
-            ship_model: make_ship_model(),
-            starfield_model: starfield.build(),
+            ship_model: geometry.ship.build(),
+            starfield_model: geometry.starfield.build(),
         }
     }
 }
@@ -144,7 +162,7 @@ fn get_planet_rotation() -> i32 {
 
 //===========================================================================//
 
-fn make_ship_model() -> Model {
+fn make_ship_model() -> ModelBuilder {
     let mut ship = ModelBuilder::new();
 
     // Chassis:
@@ -182,7 +200,7 @@ fn make_ship_model() -> Model {
             * Matrix4::from_angle_x(Deg(90.0)),
     ));
 
-    ship.build()
+    ship
 }
 
 fn make_fuel_tank(ctx: &mut ModelBuilderContext) {
@@ -27,18 +27,15 @@ use tachy::geom::{Color3, Rect, RectSize};
 
 //===========================================================================//
 
-pub struct PlanetfallBackgroundView {
-    p_matrix: Matrix4<f32>,
-    habitat_model: Model,
-    terrain_model: HeightmapModel,
-    sky_model: Model,
+/// The model geometry for a `PlanetfallBackgroundView`, which can be
+/// generated on any thread.
+pub struct PlanetfallGeometry {
+    habitat: ModelBuilder,
+    sky: ModelBuilder,
 }
 
-impl PlanetfallBackgroundView {
-    pub fn new(screen_size: RectSize<f32>) -> PlanetfallBackgroundView {
-        let aspect = screen_size.width / screen_size.height;
-        let p_matrix = cgmath::perspective(Deg(45.0), aspect, 0.1, 1000.0);
-
+impl PlanetfallGeometry {
+    pub fn generate() -> PlanetfallGeometry {
         let mut habitat = ModelBuilder::new();
         make_habitat(&mut habitat.context());
 
@@ -53,11 +50,31 @@ impl PlanetfallBackgroundView {
             );
         }
 
+        PlanetfallGeometry { habitat, sky }
+    }
+}
+
+//===========================================================================//
+
+pub struct PlanetfallBackgroundView {
+    p_matrix: Matrix4<f32>,
+    habitat_model: Model,
+    terrain_model: HeightmapModel,
+    sky_model: Model,
+}
+
+impl PlanetfallBackgroundView {
+    pub fn new(
+        geometry: PlanetfallGeometry,
+        screen_size: RectSize<f32>,
+    ) -> PlanetfallBackgroundView {
+        let aspect = screen_size.width / screen_size.height;
+        let p_matrix = cgmath::perspective(Deg(45.0), aspect, 0.1, 1000.0);
         PlanetfallBackgroundView {
             p_matrix,
-            habitat_model: habitat.build(),
+            habitat_model: geometry.habitat.build(),
             terrain_model: HeightmapModel::new(128),
-            sky_model: sky.build(),
+            sky_model: geometry.sky.build(),
         }
     }
 }
@@ -22,7 +22,7 @@ use super::dialog::ButtonDialogBox;
 use super::paragraph::Paragraph;
 use crate::mancer::font::Align;
 use crate::mancer::gui::{Event, Keycode, Resources, Sound, Ui};
-use crate::mancer::save::PROFILE_NAME_MAX_CHARS;
+use crate::mancer::save::{StringKey, PROFILE_NAME_MAX_CHARS};
 use crate::mancer::state::GameState;
 use cgmath::{self, Deg, Matrix4, Rad, Vector2};
 use tachy::geom::{AsFloat, AsInt, Color3, Color4, MatrixExt, Rect, RectSize};
@@ -225,7 +225,8 @@ impl BeginView {
             unable,
             Paragraph::escape(error)
         );
-        let buttons = &[("OK", (), Some(Keycode::Return))];
+        let ok = state.prefs().strings().get(StringKey::ButtonOk);
+        let buttons = &[(ok, (), Some(Keycode::Return))];
         let dialog =
             ButtonDialogBox::new(size, state.prefs(), &format, buttons);
         self.error_dialog = Some(dialog);
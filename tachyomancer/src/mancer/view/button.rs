@@ -17,7 +17,7 @@
 // | with Tachyomancer.  If not, see <http://www.gnu.org/licenses/>.          |
 // +--------------------------------------------------------------------------+
 
-use crate::mancer::font::{Align, Font};
+use crate::mancer::font::{has_unsupported_glyphs, Align, Font};
 use crate::mancer::gui::{
     ClockEventData, Cursor, Event, Keycode, Resources, Sound, Ui,
 };
@@ -80,6 +80,10 @@ impl Checkbox {
         }
     }
 
+    pub fn rect(&self) -> Rect<i32> {
+        self.rect
+    }
+
     pub fn draw(
         &self,
         resources: &Resources,
@@ -415,6 +419,10 @@ impl<T: Clone + PartialEq> RadioCheckbox<T> {
         RadioCheckbox { inner: Checkbox::new(top_left, label), value }
     }
 
+    pub fn rect(&self) -> Rect<i32> {
+        self.inner.rect()
+    }
+
     pub fn draw(
         &self,
         resources: &Resources,
@@ -619,7 +627,7 @@ impl Scrollbar {
 
 //===========================================================================//
 
-const SLIDER_MAX: i32 = 100;
+const SLIDER_PERCENT_MAX: i32 = 100;
 
 pub enum SliderAction {
     Update(i32),
@@ -629,23 +637,71 @@ pub enum SliderAction {
 pub struct Slider {
     rect: Rect<i32>,
     value: i32,
+    max_value: i32,
     label: String,
+    value_label: fn(i32) -> String,
     drag: Option<(i32, i32)>,
     hover_pulse: HoverPulse,
 }
 
 impl Slider {
+    /// Creates a slider that ranges from 0% to 100%.
     pub fn new(rect: Rect<i32>, value: i32, label: String) -> Slider {
+        Slider::with_range(
+            rect,
+            value,
+            SLIDER_PERCENT_MAX,
+            label,
+            percent_label,
+        )
+    }
+
+    /// Creates a slider that ranges from 0 to `max_value` (inclusive), using
+    /// the given function to format the value label.
+    pub fn with_range(
+        rect: Rect<i32>,
+        value: i32,
+        max_value: i32,
+        label: String,
+        value_label: fn(i32) -> String,
+    ) -> Slider {
         debug_assert!(rect.width > rect.height);
+        debug_assert!(max_value > 0);
         Slider {
             rect,
-            value: value.max(0).min(SLIDER_MAX),
+            value: value.max(0).min(max_value),
+            max_value,
             label,
+            value_label,
             drag: None,
             hover_pulse: HoverPulse::new(),
         }
     }
 
+    pub fn rect(&self) -> Rect<i32> {
+        self.rect
+    }
+
+    /// Moves the slider to the given value (e.g. because the underlying
+    /// setting was changed by something other than this slider).
+    pub fn set_value(&mut self, value: i32) {
+        self.value = value.max(0).min(self.max_value);
+        self.drag = None;
+    }
+
+    /// Changes the slider's maximum value, and moves the slider to the given
+    /// value.
+    pub fn set_range(&mut self, value: i32, max_value: i32) {
+        debug_assert!(max_value > 0);
+        self.max_value = max_value;
+        self.set_value(value);
+    }
+
+    /// Returns true if the slider handle is currently being dragged.
+    pub fn is_dragging(&self) -> bool {
+        self.drag.is_some()
+    }
+
     pub fn draw(&self, resources: &Resources, matrix: &Matrix4<f32>) {
         let rect = self.rect.as_f32();
         let ui = resources.shaders().ui();
@@ -686,7 +742,7 @@ impl Slider {
             SLIDER_FONT_SIZE,
             Align::MidLeft,
             ((self.rect.right() + SLIDER_TEXT_MARGIN) as f32, mid_y),
-            &format!("{:3}%", self.value),
+            &(self.value_label)(self.value),
         );
     }
 
@@ -713,12 +769,12 @@ impl Slider {
                     let delta = mouse.pt.x - start;
                     let range = self.rect.width - self.rect.height;
                     let value = div_round(
-                        range * self.value + delta * SLIDER_MAX,
+                        range * self.value + delta * self.max_value,
                         range,
                     );
-                    let value = value.max(0).min(SLIDER_MAX);
+                    let value = value.max(0).min(self.max_value);
                     if value != self.value {
-                        self.value = value.max(0).min(SLIDER_MAX);
+                        self.value = value;
                         let new_left = self.handle_left();
                         let new_start = start + new_left - old_left;
                         let new_delta = mouse.pt.x - new_start;
@@ -756,7 +812,7 @@ impl Slider {
         self.rect.x
             + div_round(
                 (self.rect.width - self.rect.height) * self.value,
-                SLIDER_MAX,
+                self.max_value,
             )
     }
 
@@ -771,6 +827,10 @@ impl Slider {
     }
 }
 
+fn percent_label(value: i32) -> String {
+    format!("{:3}%", value)
+}
+
 //===========================================================================//
 
 pub struct TextBox {
@@ -926,6 +986,11 @@ impl TextBox {
                 }
             }
             Event::TextInput(text) => {
+                if has_unsupported_glyphs(text) {
+                    // Let the player know that some of the typed characters
+                    // can't be displayed (and so will be dropped).
+                    ui.audio().play_sound(Sound::Beep);
+                }
                 for chr in text.chars() {
                     if self.string.chars().count() >= self.max_len {
                         break;
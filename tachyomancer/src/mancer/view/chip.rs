@@ -31,6 +31,11 @@ use tachy::state::{ChipExt, EditGrid, Interface, PortSpec};
 
 const INTERFACE_LABEL_COLOR: Color4 = Color4::new(0.75, 0.75, 0.75, 1.0);
 
+// The font size and distance from the interface body (in grid cell units) of
+// the interface label drawn outside the board edge:
+const INTERFACE_EDGE_LABEL_FONT_SIZE: f32 = 0.3;
+const INTERFACE_EDGE_LABEL_MARGIN: f32 = 0.1;
+
 //===========================================================================//
 
 /// The margin around chip rects, in grid cell units.
@@ -104,6 +109,42 @@ impl ChipModel {
         }
     }
 
+    /// Draws the interface's short label just outside of it, on the far side
+    /// from the board.
+    pub fn draw_interface_label(
+        resources: &Resources,
+        grid_matrix: &Matrix4<f32>,
+        coords: Coords,
+        interface: &Interface,
+    ) {
+        let rect = Rect::with_size(coords, interface.size()).as_f32();
+        let center_x = rect.x + 0.5 * rect.width;
+        let center_y = rect.y + 0.5 * rect.height;
+        let margin = INTERFACE_EDGE_LABEL_MARGIN;
+        let (x, y, align) = match interface.side() {
+            Direction::East => {
+                (rect.right() + margin, center_y, Align::MidLeft)
+            }
+            Direction::South => {
+                (center_x, rect.bottom() + margin, Align::TopCenter)
+            }
+            Direction::West => (rect.x - margin, center_y, Align::MidRight),
+            Direction::North => {
+                (center_x, rect.y - margin, Align::BottomCenter)
+            }
+        };
+        let matrix = grid_matrix * Matrix4::trans2(x, y);
+        resources.fonts().roman().draw_style(
+            &matrix,
+            INTERFACE_EDGE_LABEL_FONT_SIZE,
+            align,
+            (0.0, 0.0),
+            &INTERFACE_LABEL_COLOR,
+            0.0,
+            interface.label(),
+        );
+    }
+
     pub fn draw_chip(
         resources: &Resources,
         grid_matrix: &Matrix4<f32>,
@@ -20,7 +20,9 @@
 use crate::mancer::gui::{ClockEventData, Ui};
 use crate::mancer::save::{Hotkey, HotkeyCodeExt, Prefs};
 use cgmath::{self, vec2, Matrix4, Point2, Vector2};
-use tachy::geom::{AsFloat, AsInt, Coords, CoordsRect, MatrixExt, RectSize};
+use tachy::geom::{
+    AsFloat, AsInt, Coords, CoordsRect, MatrixExt, Rect, RectSize,
+};
 
 //===========================================================================//
 
@@ -107,6 +109,21 @@ impl EditGridCamera {
         }
     }
 
+    /// Zooms (as far as the zoom limits allow) so that the given rectangle,
+    /// in grid coordinates, fits within the view, and sets the camera goal to
+    /// the center of the rectangle.
+    pub fn fit_to(&mut self, grid_rect: Rect<f32>, ui: &mut Ui) {
+        let cell_size = GRID_CELL_SIZE as f32;
+        let zoom = (self.window_size.width / (grid_rect.width * cell_size))
+            .min(self.window_size.height / (grid_rect.height * cell_size));
+        self.zoom = zoom.clamp(ZOOM_MIN, ZOOM_MAX);
+        self.set_goal(Point2::new(
+            grid_rect.x + 0.5 * grid_rect.width,
+            grid_rect.y + 0.5 * grid_rect.height,
+        ));
+        ui.request_redraw();
+    }
+
     pub fn reset_zoom_to_default(&mut self, ui: &mut Ui) {
         if self.zoom != ZOOM_DEFAULT {
             self.zoom = ZOOM_DEFAULT;
@@ -180,10 +197,13 @@ impl EditGridCamera {
         bounds: CoordsRect,
         prefs: &Prefs,
     ) {
+        let snap = prefs.reduce_motion();
         if let Some(goal) = self.scroll_goal {
             if self.scroll != goal {
-                self.scroll.x = track_towards(self.scroll.x, goal.x, tick);
-                self.scroll.y = track_towards(self.scroll.y, goal.y, tick);
+                self.scroll.x =
+                    track_towards(self.scroll.x, goal.x, tick, snap);
+                self.scroll.y =
+                    track_towards(self.scroll.y, goal.y, tick, snap);
                 ui.request_redraw();
             }
             if self.scroll == goal {
@@ -215,19 +235,25 @@ impl EditGridCamera {
         let scroll_limit =
             (bounds * GRID_CELL_SIZE).expand2(expand.width, expand.height);
         if self.scroll.x < scroll_limit.x {
-            self.scroll.x = track_towards(self.scroll.x, scroll_limit.x, tick);
+            self.scroll.x =
+                track_towards(self.scroll.x, scroll_limit.x, tick, snap);
             ui.request_redraw();
         } else if self.scroll.x > scroll_limit.right() {
             self.scroll.x =
-                track_towards(self.scroll.x, scroll_limit.right(), tick);
+                track_towards(self.scroll.x, scroll_limit.right(), tick, snap);
             ui.request_redraw();
         }
         if self.scroll.y < scroll_limit.y {
-            self.scroll.y = track_towards(self.scroll.y, scroll_limit.y, tick);
+            self.scroll.y =
+                track_towards(self.scroll.y, scroll_limit.y, tick, snap);
             ui.request_redraw();
         } else if self.scroll.y > scroll_limit.bottom() {
-            self.scroll.y =
-                track_towards(self.scroll.y, scroll_limit.bottom(), tick);
+            self.scroll.y = track_towards(
+                self.scroll.y,
+                scroll_limit.bottom(),
+                tick,
+                snap,
+            );
             ui.request_redraw();
         }
     }
@@ -239,7 +265,17 @@ fn is_hotkey_held(hotkey: Hotkey, ui: &Ui, prefs: &Prefs) -> bool {
     ui.keyboard().is_held(prefs.hotkey_code(hotkey).to_keycode())
 }
 
-fn track_towards(current: i32, goal: i32, tick: &ClockEventData) -> i32 {
+/// Moves `current` partway towards `goal`, or all the way there if `snap` is
+/// true (i.e. if the player has asked for reduced motion).
+fn track_towards(
+    current: i32,
+    goal: i32,
+    tick: &ClockEventData,
+    snap: bool,
+) -> i32 {
+    if snap {
+        return goal;
+    }
     let tracking_base: f64 = 0.0001; // smaller = faster tracking
     let difference = (goal - current) as f64;
     let change = difference * (1.0 - tracking_base.powf(tick.elapsed));
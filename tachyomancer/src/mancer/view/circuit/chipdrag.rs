@@ -21,13 +21,14 @@ use crate::mancer::gui::{Sound, Ui};
 use cgmath::Point2;
 use std::collections::{HashMap, HashSet};
 use tachy::geom::{
-    AsFloat, AsInt, Coords, CoordsRect, Direction, Orientation,
+    AsFloat, AsInt, Coords, CoordsExt, CoordsRect, Direction, Orientation,
 };
 use tachy::save::{ChipType, WireShape};
 use tachy::state::{ChipExt, EditGrid, GridChange};
 
 //===========================================================================//
 
+#[derive(Clone)]
 pub struct ChipDrag {
     chip_type: ChipType,
     old_orient: Orientation,
@@ -125,9 +126,8 @@ impl ChipDrag {
         let mut old_wires = HashMap::new();
         let mut new_wires = HashMap::new();
         for coords in new_rect {
-            for dir in Direction::all() {
+            for (dir, coords2) in coords.neighbors() {
                 if let Some(shape) = grid.wire_shape_at(coords, dir) {
-                    let coords2 = coords + dir;
                     if new_rect.contains_point(coords2) {
                         old_wires.insert((coords, dir), shape);
                     } else if grid.wire_shape_at(coords2, -dir)
@@ -17,13 +17,15 @@
 // | with Tachyomancer.  If not, see <http://www.gnu.org/licenses/>.          |
 // +--------------------------------------------------------------------------+
 
-use super::super::button::HoverPulse;
+use super::super::button::{HoverPulse, Slider, SliderAction};
 use super::super::tooltip::TooltipSink;
 use super::tray::TraySlide;
 use super::tutorial::TutorialBubble;
 use crate::mancer::font::Align;
 use crate::mancer::gui::{Cursor, Event, Resources, Sound, Ui};
-use crate::mancer::save::{Hotkey, HotkeyCodeExt, Prefs};
+use crate::mancer::save::{
+    Hotkey, HotkeyCodeExt, Prefs, MAX_SIMULATION_SPEED, MIN_SIMULATION_SPEED,
+};
 use crate::mancer::shader::UiShader;
 use cgmath::{Deg, Matrix4, Point2};
 use tachy::geom::{AsFloat, Color3, Color4, MatrixExt, Rect, RectSize};
@@ -37,6 +39,13 @@ const BUTTON_WIDTH: i32 = 48;
 const BUTTON_HEIGHT: i32 = 32;
 const BUTTON_SPACING: i32 = 8;
 
+const SPEED_SLIDER_HEIGHT: i32 = 20;
+const SPEED_SLIDER_LABEL_WIDTH: i32 = 60;
+const SPEED_SLIDER_VALUE_WIDTH: i32 = 56;
+// Each step of the speed slider doubles the simulation speed, so this is
+// log2(MAX_SIMULATION_SPEED / MIN_SIMULATION_SPEED).
+const SPEED_SLIDER_MAX_STEP: i32 = 6;
+
 const TIMER_FONT_SIZE: f32 = 24.0;
 
 const TRAY_EXTRA_HIDDEN_HEIGHT: i32 = 20;
@@ -144,6 +153,8 @@ impl ControlsAction {
 pub struct ControlsTray {
     rect: Rect<i32>,
     buttons: Vec<ControlsButton>,
+    speed_slider: Slider,
+    timers_top: i32,
     show_cycle_count: bool,
     slide: TraySlide,
     tutorial_bubble: Option<TutorialBubble>,
@@ -153,6 +164,7 @@ impl ControlsTray {
     pub fn new(
         window_size: RectSize<i32>,
         current_puzzle: Puzzle,
+        prefs: &Prefs,
         tutorial_bubble: Option<TutorialBubble>,
     ) -> ControlsTray {
         let show_cycle_count = current_puzzle.allows_events();
@@ -208,7 +220,29 @@ impl ControlsTray {
             ControlsAction::StepSubcycle,
             Rect::new(button_left, button_top, BUTTON_WIDTH, BUTTON_HEIGHT),
         ));
-        let tray_top = button_top - TRAY_INNER_MARGIN;
+        let timers_top = button_top;
+        debug_assert_eq!(
+            speed_for_step(SPEED_SLIDER_MAX_STEP),
+            MAX_SIMULATION_SPEED
+        );
+        let slider_top = button_top - (SPEED_SLIDER_HEIGHT + BUTTON_SPACING);
+        let slider_left = TRAY_INNER_MARGIN + SPEED_SLIDER_LABEL_WIDTH;
+        let speed_slider = Slider::with_range(
+            Rect::new(
+                slider_left,
+                slider_top,
+                tray_width
+                    - (slider_left
+                        + SPEED_SLIDER_VALUE_WIDTH
+                        + TRAY_INNER_MARGIN),
+                SPEED_SLIDER_HEIGHT,
+            ),
+            step_for_speed(prefs.simulation_speed()),
+            SPEED_SLIDER_MAX_STEP,
+            "Speed".to_string(),
+            speed_step_label,
+        );
+        let tray_top = slider_top - TRAY_INNER_MARGIN;
         let rect = Rect::new(
             0,
             tray_top,
@@ -218,6 +252,8 @@ impl ControlsTray {
         ControlsTray {
             rect,
             buttons,
+            speed_slider,
+            timers_top,
             show_cycle_count,
             slide: TraySlide::new(rect.width),
             tutorial_bubble,
@@ -242,6 +278,7 @@ impl ControlsTray {
         for button in self.buttons.iter() {
             button.draw(resources, &matrix, status, grid);
         }
+        self.speed_slider.draw(resources, &matrix);
         if let Some(ref bubble) = self.tutorial_bubble {
             let left = self.rect.right() + 26;
             let top = self.rect.bottom()
@@ -295,8 +332,7 @@ impl ControlsTray {
             (0, 0, 0)
         };
         let timer_right = (self.rect.right() - TRAY_INNER_MARGIN) as f32;
-        let mut timer_mid =
-            self.rect.y + TRAY_INNER_MARGIN + BUTTON_HEIGHT / 2;
+        let mut timer_mid = self.timers_top + BUTTON_HEIGHT / 2;
         resources.fonts().roman().draw(
             matrix,
             TIMER_FONT_SIZE,
@@ -332,10 +368,18 @@ impl ControlsTray {
         grid: &EditGrid,
         is_interacting: bool,
         tooltip: &mut dyn TooltipSink<ControlsAction>,
-        prefs: &Prefs,
+        prefs: &mut Prefs,
     ) -> Option<Option<ControlsAction>> {
         let rel_event =
             event.relative_to(Point2::new(-self.slide.distance(), 0));
+        match self.speed_slider.on_event(&rel_event, ui) {
+            Some(SliderAction::Update(step)) => {
+                prefs.set_simulation_speed(speed_for_step(step));
+                return Some(None);
+            }
+            Some(SliderAction::Release) => return Some(None),
+            None => {}
+        }
         for button in self.buttons.iter_mut() {
             let opt_action = button.on_event(
                 &rel_event,
@@ -392,6 +436,18 @@ impl ControlsTray {
     }
 }
 
+fn speed_for_step(step: i32) -> f64 {
+    MIN_SIMULATION_SPEED * 2f64.powi(step)
+}
+
+fn step_for_speed(speed: f64) -> i32 {
+    (speed / MIN_SIMULATION_SPEED).log2().round() as i32
+}
+
+fn speed_step_label(step: i32) -> String {
+    format!("{}x", speed_for_step(step))
+}
+
 //===========================================================================//
 
 struct ControlsButton {
@@ -34,21 +34,51 @@ const EXPORT_HEIGHT: usize = 480;
 
 //===========================================================================//
 
+/// Renders the circuit to an image, returning its size and pixel data.  If
+/// `labeled` is true, the image is stamped with the puzzle title, score, and
+/// circuit area.  If `transparent` is true, the board background is omitted
+/// and the data is RGBA (not premultiplied); otherwise, the data is RGB.
 pub fn export_circuit_image(
     resources: &Resources,
     grid: &EditGrid,
     score: u32,
+    labeled: bool,
+    transparent: bool,
 ) -> (RectSize<usize>, Vec<u8>) {
+    // Newly-created framebuffers are cleared to transparent black.
     let fbo = FrameBufferMultisample::new(EXPORT_WIDTH, EXPORT_HEIGHT, true);
     fbo.bind();
-    draw_background(resources);
+    if !transparent {
+        draw_background(resources);
+    }
     draw_circuit(resources, grid);
-    draw_title(resources, grid.puzzle(), grid.bounds().area(), score);
+    if labeled {
+        draw_title(resources, grid.puzzle(), grid.bounds().area(), score);
+    }
     fbo.unbind(resources.window_size());
-    let data = resources.shaders().frame().read_rgb_data(&fbo);
+    let data = if transparent {
+        let mut data = resources.shaders().frame().read_rgba_data(&fbo);
+        unpremultiply_alpha(&mut data);
+        data
+    } else {
+        resources.shaders().frame().read_rgb_data(&fbo)
+    };
     (RectSize::new(EXPORT_WIDTH, EXPORT_HEIGHT), data)
 }
 
+/// Converts RGBA data from premultiplied alpha (which is what our blending
+/// mode produces) to straight alpha (which is what PNG files expect).
+fn unpremultiply_alpha(rgba: &mut [u8]) {
+    for pixel in rgba.chunks_mut(4) {
+        let alpha = pixel[3] as u32;
+        if alpha > 0 && alpha < 255 {
+            for channel in pixel[..3].iter_mut() {
+                *channel = ((*channel as u32) * 255 / alpha).min(255) as u8;
+            }
+        }
+    }
+}
+
 fn draw_background(resources: &Resources) {
     let texel_rect =
         Rect::new(0.0, 0.0, EXPORT_WIDTH as f32, EXPORT_HEIGHT as f32);
@@ -110,11 +140,11 @@ fn draw_circuit(resources: &Resources, grid: &EditGrid) {
         WireModel::draw_fragment(
             resources,
             &grid_matrix,
-            coords,
-            dir,
+            (coords, dir),
             shape,
             color,
             size,
+            false,
             &Color4::TRANSPARENT,
         );
     }
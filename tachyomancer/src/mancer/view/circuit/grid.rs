@@ -30,6 +30,7 @@ use super::select::{self, SelectingDrag, Selection, SelectionDrag};
 use super::tooltip::GridTooltipTag;
 use super::tutorial::TutorialBubble;
 use super::wiredrag::WireDrag;
+use crate::mancer::font::Align;
 use crate::mancer::gl::Depth;
 use crate::mancer::gui::{
     Cursor, Event, Keycode, MouseEventData, NextCursor, Resources, Sound, Ui,
@@ -43,13 +44,44 @@ use tachy::geom::{
     MatrixExt, Orientation, Rect, RectSize,
 };
 use tachy::save::{ChipType, HotkeyCode, WireSize};
-use tachy::state::{EditGrid, GridChange, WireColor, WireId};
+use tachy::state::{
+    ChipExt, EditGrid, GridChange, PortColor, PortFlow, WireColor, WireError,
+    WireId,
+};
 
 //===========================================================================//
 
 // How much to multiply/divide the zoom by when pressing a zoom hotkey:
 const ZOOM_PER_KEYDOWN: f32 = 1.415; // slightly more than sqrt(2)
 
+// How much empty space to leave around the circuit when zooming to fit it, in
+// grid cells:
+const ZOOM_TO_FIT_MARGIN: f32 = 1.0;
+
+// Where to draw the port flow arrows for a hovered chip, in grid cells
+// outward from the center of the port's cell:
+const PORT_ARROW_INNER: f32 = 0.55;
+const PORT_ARROW_LENGTH: f32 = 0.25;
+
+// How close to a chip port (or interface port) the mouse must be, in grid
+// cells, for hovering it to highlight the attached wire:
+const PORT_HOVER_DEPTH: f32 = 0.3;
+const PORT_HOVER_HALF_WIDTH: f32 = 0.25;
+
+// The side length of the marker drawn on unconnected ports (or on ports with
+// conflicting wire sizes), in grid cells:
+const UNCONNECTED_PORT_MARKER_SIZE: f32 = 0.12;
+
+// The font size of wire labels, and how far from the cell center (toward the
+// labeled fragment) to draw them, in grid cells:
+const WIRE_LABEL_FONT_SIZE: f32 = 0.3;
+const WIRE_LABEL_OFFSET: f32 = 0.25;
+const WIRE_LABEL_COLOR: Color4 = Color4::new(1.0, 1.0, 0.75, 1.0);
+
+// How long the highlight on event wires that just fired takes to fade out
+// after stepping a paused evaluation by one subcycle, in seconds:
+const FIRED_EVENT_FLASH_SECONDS: f64 = 0.8;
+
 //===========================================================================//
 
 pub enum EditGridAction {
@@ -58,6 +90,7 @@ pub enum EditGridAction {
     EditComment(Coords, String),
     EditConst(Coords, u8),
     EditVref(Coords, Fixed),
+    EditWireLabel((Coords, Direction), String),
 }
 
 //===========================================================================//
@@ -68,6 +101,11 @@ pub struct EditGridView {
     tutorial_bubbles: Vec<(Direction, TutorialBubble)>,
     hover_wire: Option<WireId>,
     manip_buttons: ManipulationButtons,
+    highlight_unconnected_ports: bool,
+    emphasize_wire_sizes: bool,
+    fired_events: HashSet<WireId>,
+    fired_events_flash: f64,
+    last_chip_click: Option<(Coords, f64)>,
 }
 
 impl EditGridView {
@@ -75,6 +113,8 @@ impl EditGridView {
         window_size: RectSize<i32>,
         init_circuit_bounds: CoordsRect,
         tutorial_bubbles: Vec<(Direction, TutorialBubble)>,
+        highlight_unconnected_ports: bool,
+        emphasize_wire_sizes: bool,
     ) -> EditGridView {
         EditGridView {
             camera: EditGridCamera::new(window_size, init_circuit_bounds),
@@ -82,6 +122,11 @@ impl EditGridView {
             tutorial_bubbles,
             hover_wire: None,
             manip_buttons: ManipulationButtons::new(),
+            highlight_unconnected_ports,
+            emphasize_wire_sizes,
+            fired_events: HashSet::new(),
+            fired_events_flash: 0.0,
+            last_chip_click: None,
         }
     }
 
@@ -214,6 +259,139 @@ impl EditGridView {
         }
     }
 
+    fn draw_unconnected_ports(
+        &self,
+        resources: &Resources,
+        grid_matrix: &Matrix4<f32>,
+        grid: &EditGrid,
+    ) {
+        let dragged_chip_coords = match self.interaction {
+            Interaction::DraggingChip(ref drag) => drag.old_coords(),
+            _ => None,
+        };
+        let dragged_chip_rect = dragged_chip_coords.and_then(|coords| {
+            grid.chip_at(coords).map(|(coords, ctype, orient)| {
+                CoordsRect::with_size(coords, orient * ctype.size())
+            })
+        });
+        let ports =
+            grid.unconnected_ports().iter().copied().filter(|&(coords, _)| {
+                match dragged_chip_rect {
+                    Some(rect) => !rect.contains_point(coords),
+                    None => true,
+                }
+            });
+        let color = Color3::RED3.with_alpha(0.6);
+        self.draw_port_markers(resources, grid_matrix, ports, color);
+    }
+
+    /// Marks the ports responsible for wire errors, such as the two ports of
+    /// a size conflict or the chip ports that a combinational loop passes
+    /// through.
+    fn draw_error_ports(
+        &self,
+        resources: &Resources,
+        grid_matrix: &Matrix4<f32>,
+        grid: &EditGrid,
+    ) {
+        let ports = grid.errors().iter().flat_map(WireError::port_locs);
+        let color = Color3::YELLOW3.with_alpha(0.8);
+        self.draw_port_markers(resources, grid_matrix, ports, color);
+    }
+
+    fn draw_wire_labels(
+        &self,
+        resources: &Resources,
+        grid_matrix: &Matrix4<f32>,
+        grid: &EditGrid,
+    ) {
+        let font = resources.fonts().roman();
+        for ((coords, dir), label) in grid.wire_labels() {
+            let center = coords.as_f32()
+                + vec2(0.5, 0.5)
+                + dir.delta().as_f32() * WIRE_LABEL_OFFSET;
+            font.draw_style(
+                grid_matrix,
+                WIRE_LABEL_FONT_SIZE,
+                Align::MidCenter,
+                (center.x, center.y),
+                &WIRE_LABEL_COLOR,
+                0.0,
+                label,
+            );
+        }
+    }
+
+    fn draw_port_markers<I>(
+        &self,
+        resources: &Resources,
+        grid_matrix: &Matrix4<f32>,
+        ports: I,
+        color: Color4,
+    ) where
+        I: IntoIterator<Item = (Coords, Direction)>,
+    {
+        let solid = resources.shaders().solid();
+        for (coords, dir) in ports {
+            let matrix = grid_matrix
+                * Matrix4::trans2(
+                    (coords.x as f32) + 0.5,
+                    (coords.y as f32) + 0.5,
+                )
+                * Matrix4::from_angle_z(dir.angle_from_east());
+            let rect = Rect::new(
+                PORT_ARROW_INNER,
+                -0.5 * UNCONNECTED_PORT_MARKER_SIZE,
+                UNCONNECTED_PORT_MARKER_SIZE,
+                UNCONNECTED_PORT_MARKER_SIZE,
+            );
+            solid.tint_rect(&matrix, color, rect);
+        }
+    }
+
+    fn draw_port_arrows(
+        &self,
+        resources: &Resources,
+        grid_matrix: &Matrix4<f32>,
+        grid: &EditGrid,
+        coords: Coords,
+    ) {
+        let (coords, ctype, orient) = match grid.chip_at(coords) {
+            Some(chip) => chip,
+            None => return,
+        };
+        let solid = resources.shaders().solid();
+        for port in ctype.ports(coords, orient) {
+            let matrix = grid_matrix
+                * Matrix4::trans2(
+                    (port.coords.x as f32) + 0.5,
+                    (port.coords.y as f32) + 0.5,
+                )
+                * Matrix4::from_angle_z(port.dir.angle_from_east());
+            let half_height = port_arrow_half_height(port.max_size);
+            let rect = match port.flow {
+                PortFlow::Source => Rect::new(
+                    PORT_ARROW_INNER,
+                    -half_height,
+                    PORT_ARROW_LENGTH,
+                    2.0 * half_height,
+                ),
+                PortFlow::Sink => Rect::new(
+                    PORT_ARROW_INNER + PORT_ARROW_LENGTH,
+                    -half_height,
+                    -PORT_ARROW_LENGTH,
+                    2.0 * half_height,
+                ),
+            };
+            let color = match port.color {
+                PortColor::Behavior => Color3::ORANGE4,
+                PortColor::Event => Color3::CYAN4,
+                PortColor::Analog => Color3::GREEN4,
+            };
+            solid.tint_triangle(&matrix, color.with_alpha(0.9), rect);
+        }
+    }
+
     fn draw_interfaces(
         &self,
         resources: &Resources,
@@ -232,6 +410,12 @@ impl EditGridView {
                 coords,
                 interface,
             );
+            ChipModel::draw_interface_label(
+                resources,
+                grid_matrix,
+                coords,
+                interface,
+            );
         }
     }
 
@@ -260,12 +444,20 @@ impl EditGridView {
             } else {
                 None
             };
+        let flash = if grid.eval().is_some() {
+            (self.fired_events_flash / FIRED_EVENT_FLASH_SECONDS) as f32
+        } else {
+            0.0
+        };
+        let fired_event_hilight = Color4::YELLOW5.mix(Color4::ORANGE4, flash);
         for (coords, dir, shape, size, color, has_error) in
             grid.wire_fragments()
         {
             let wire_id = grid.wire_id_at(coords, dir).unwrap();
             let hilight = if self.hover_wire == Some(wire_id) {
                 &Color4::CYAN5
+            } else if flash > 0.0 && self.fired_events.contains(&wire_id) {
+                &fired_event_hilight
             } else if subcycle_wires.contains(&wire_id) {
                 &Color4::YELLOW5
             } else {
@@ -279,21 +471,21 @@ impl EditGridView {
                 WireModel::draw_half_straight(
                     resources,
                     &grid_matrix,
-                    coords,
-                    dir,
+                    (coords, dir),
                     color,
                     size,
+                    self.emphasize_wire_sizes,
                     hilight,
                 );
             } else {
                 WireModel::draw_fragment(
                     resources,
                     &grid_matrix,
-                    coords,
-                    dir,
+                    (coords, dir),
                     shape,
                     color,
                     size,
+                    self.emphasize_wire_sizes,
                     hilight,
                 );
             }
@@ -331,7 +523,14 @@ impl EditGridView {
         }
     }
 
-    pub fn draw_board(&self, resources: &Resources, grid: &EditGrid) {
+    /// Draws the board.  If `hovered_chip` is the coordinates of a chip whose
+    /// tooltip is being shown, also draws that chip's port flow arrows.
+    pub fn draw_board(
+        &self,
+        resources: &Resources,
+        grid: &EditGrid,
+        hovered_chip: Option<Coords>,
+    ) {
         let grid_matrix = self.camera.grid_matrix();
         self.draw_background(resources);
         self.draw_bounds(resources, &grid_matrix, grid);
@@ -342,12 +541,25 @@ impl EditGridView {
         self.draw_interfaces(resources, &grid_matrix, grid);
         self.draw_wires(resources, &grid_matrix, grid);
         depth.disable();
+        self.draw_wire_labels(resources, &grid_matrix, grid);
+
+        if self.highlight_unconnected_ports && grid.eval().is_none() {
+            self.draw_unconnected_ports(resources, &grid_matrix, grid);
+        }
+        if grid.eval().is_none() {
+            self.draw_error_ports(resources, &grid_matrix, grid);
+        }
+        if let Some(coords) = hovered_chip {
+            self.draw_port_arrows(resources, &grid_matrix, grid, coords);
+        }
 
         self.draw_selection_box_if_any(resources);
     }
 
     pub fn draw_dragged(&self, resources: &Resources) {
-        if let Interaction::DraggingChip(ref drag) = self.interaction {
+        if let Interaction::DraggingChip(ref drag)
+        | Interaction::StampingChip(ref drag) = self.interaction
+        {
             let pt = drag.chip_topleft();
             let grid_matrix =
                 self.camera.grid_matrix() * Matrix4::trans2(pt.x, pt.y);
@@ -384,7 +596,7 @@ impl EditGridView {
             Interaction::DraggingWires(_) => {
                 next_cursor.request(Cursor::Wire);
             }
-            Interaction::SelectingRect(_) => {
+            Interaction::SelectingRect(_) | Interaction::StampingChip(_) => {
                 next_cursor.request(Cursor::Crosshair);
             }
             Interaction::Nothing | Interaction::RectSelected(_) => {}
@@ -459,6 +671,10 @@ impl EditGridView {
             self.camera.zoom_by(1.0 / ZOOM_PER_KEYDOWN, ui);
         } else if hotkey == Hotkey::ZoomDefault {
             self.camera.reset_zoom_to_default(ui);
+        } else if hotkey == Hotkey::ZoomToFit {
+            let rect =
+                occupied_bounds(grid).as_f32().expand(ZOOM_TO_FIT_MARGIN);
+            self.camera.fit_to(rect, ui);
         } else if let Some(action) = ManipulationAction::from_hotkey(hotkey) {
             self.apply_manipulation(action, ui, grid);
         }
@@ -471,7 +687,8 @@ impl EditGridView {
         grid: &mut EditGrid,
     ) {
         match self.interaction {
-            Interaction::DraggingChip(ref mut drag) => match action {
+            Interaction::DraggingChip(ref mut drag)
+            | Interaction::StampingChip(ref mut drag) => match action {
                 ManipulationAction::FlipHorz => drag.flip_horz(ui),
                 ManipulationAction::FlipVert => drag.flip_vert(ui),
                 ManipulationAction::RotateCcw => drag.rotate_ccw(ui),
@@ -484,19 +701,25 @@ impl EditGridView {
                 ManipulationAction::RotateCw => drag.rotate_cw(ui),
             },
             Interaction::RectSelected(rect) => {
-                match action {
+                let new_rect = match action {
                     ManipulationAction::FlipHorz => {
-                        select::flip_horz(grid, rect);
+                        select::flip_horz(grid, rect)
                     }
                     ManipulationAction::FlipVert => {
-                        select::flip_vert(grid, rect);
+                        select::flip_vert(grid, rect)
                     }
                     ManipulationAction::RotateCcw => {
-                        select::rotate_ccw(grid, rect);
+                        select::rotate_ccw(grid, rect)
                     }
                     ManipulationAction::RotateCw => {
-                        select::rotate_cw(grid, rect);
+                        select::rotate_cw(grid, rect)
                     }
+                };
+                match new_rect {
+                    Some(new_rect) => {
+                        self.interaction = Interaction::RectSelected(new_rect);
+                    }
+                    None => ui.audio().play_sound(Sound::Beep),
                 }
                 ui.request_redraw();
             }
@@ -524,6 +747,14 @@ impl EditGridView {
         match event {
             Event::ClockTick(tick) => {
                 self.camera.on_clock_tick(tick, ui, grid.bounds(), prefs);
+                if let Some((_, ref mut elapsed)) = self.last_chip_click {
+                    *elapsed += tick.elapsed;
+                }
+                if self.fired_events_flash > 0.0 {
+                    self.fired_events_flash =
+                        (self.fired_events_flash - tick.elapsed).max(0.0);
+                    ui.request_redraw();
+                }
             }
             Event::KeyDown(key) => {
                 if key.code == Keycode::Backspace
@@ -532,10 +763,11 @@ impl EditGridView {
                     match self.interaction {
                         Interaction::Nothing => {
                             if let Some(wire) = self.hover_wire {
-                                select::delete_wire(grid, wire);
-                                self.hover_wire = None;
-                                // TODO: play sound for delete
-                                ui.request_redraw();
+                                if select::delete_wire(grid, wire) {
+                                    self.hover_wire = None;
+                                    // TODO: play sound for delete
+                                    ui.request_redraw();
+                                }
                             }
                         }
                         Interaction::RectSelected(rect) => {
@@ -617,6 +849,13 @@ impl EditGridView {
             Event::MouseDown(mouse) if mouse.left => {
                 let grid_pt = self.camera.screen_pt_to_grid_pt(mouse.pt);
                 self.stop_hover(ui);
+                if let Interaction::StampingChip(ref mut drag) =
+                    self.interaction
+                {
+                    drag.move_to(grid_pt, ui);
+                    drag.clone().drop_onto_board(ui, grid);
+                    return None;
+                }
                 if grid.eval().is_some() {
                     match grid.chip_at(grid_pt.as_i32_floor()) {
                         Some((coords, ChipType::Break(_), _)) => {
@@ -699,6 +938,22 @@ impl EditGridView {
                 {
                     let chip_rect = chip_grid_rect(coords, ctype, orient);
                     if chip_rect.contains_point(grid_pt) {
+                        let double_click_seconds =
+                            (prefs.double_click_ms() as f64) / 1000.0;
+                        if let Some((last_coords, elapsed)) =
+                            self.last_chip_click.take()
+                        {
+                            if last_coords == coords
+                                && elapsed <= double_click_seconds
+                            {
+                                if let Some(action) =
+                                    edit_action_for_chip(coords, ctype)
+                                {
+                                    return Some(action);
+                                }
+                            }
+                        }
+                        self.last_chip_click = Some((coords, 0.0));
                         let change =
                             GridChange::RemoveChip(coords, ctype, orient);
                         if grid.try_mutate_provisionally(vec![change]) {
@@ -729,7 +984,7 @@ impl EditGridView {
                     .expand(CHIP_MARGIN)
                     .contains_point(grid_pt)
                 {
-                    let mut drag = WireDrag::new();
+                    let mut drag = WireDrag::new(prefs.auto_route_wires());
                     if drag.move_to(grid_pt, ui, grid) {
                         self.interaction = Interaction::DraggingWires(drag);
                     } else {
@@ -752,23 +1007,6 @@ impl EditGridView {
                             return None;
                         }
                     }
-                    Some((_, ChipType::Button(code), _)) => {
-                        return Some(EditGridAction::EditButton(coords, code));
-                    }
-                    Some((_, ChipType::Coerce(size), _)) => {
-                        return Some(EditGridAction::EditCoerce(coords, size));
-                    }
-                    Some((_, ChipType::Comment(bytes), _)) => {
-                        let string: String =
-                            bytes.iter().map(|&b| char::from(b)).collect();
-                        let string = string.trim().to_string();
-                        return Some(EditGridAction::EditComment(
-                            coords, string,
-                        ));
-                    }
-                    Some((_, ChipType::Const(value), _)) => {
-                        return Some(EditGridAction::EditConst(coords, value));
-                    }
                     Some((_, ChipType::Toggle(value), orient)) => {
                         if try_toggle_switch(coords, value, orient, grid) {
                             // TODO: Play sound for flipping toggle switch.
@@ -776,16 +1014,28 @@ impl EditGridView {
                             return None;
                         }
                     }
-                    Some((_, ChipType::Vref(value), _)) => {
-                        return Some(EditGridAction::EditVref(coords, value));
+                    Some((_, ctype, _)) => {
+                        if let Some(action) =
+                            edit_action_for_chip(coords, ctype)
+                        {
+                            return Some(action);
+                        }
                     }
-                    _ => {}
+                    None => {}
                 }
                 if WireDrag::try_toggle_cross(coords, grid) {
                     debug_assert!(grid.has_provisional_changes());
                     grid.commit_provisional_changes();
                     ui.audio().play_sound(Sound::DragWire);
                     ui.request_redraw();
+                } else if let Some(dir) = Direction::all()
+                    .find(|&dir| grid.wire_shape_at(coords, dir).is_some())
+                {
+                    let label = grid.wire_label_at(coords, dir).unwrap_or("");
+                    return Some(EditGridAction::EditWireLabel(
+                        (coords, dir),
+                        label.to_string(),
+                    ));
                 }
             }
             Event::MouseMove(mouse) => {
@@ -798,7 +1048,8 @@ impl EditGridView {
                     Interaction::DraggingBounds(ref mut drag) => {
                         drag.move_to(grid_pt, ui, grid);
                     }
-                    Interaction::DraggingChip(ref mut drag) => {
+                    Interaction::DraggingChip(ref mut drag)
+                    | Interaction::StampingChip(ref mut drag) => {
                         drag.move_to(grid_pt, ui);
                     }
                     Interaction::SelectingRect(ref mut drag) => {
@@ -845,6 +1096,9 @@ impl EditGridView {
                         Interaction::DraggingWires(drag) => {
                             drag.finish(ui, grid);
                         }
+                        Interaction::StampingChip(drag) => {
+                            self.interaction = Interaction::StampingChip(drag);
+                        }
                     }
                     let grid_pt = self.camera.screen_pt_to_grid_pt(mouse.pt);
                     let cursor = self.cursor_for_grid_pt(grid_pt, grid);
@@ -895,15 +1149,17 @@ impl EditGridView {
         }
         let grid_pt = self.camera.screen_pt_to_grid_pt(mouse.pt);
         if let Some(tag) = GridTooltipTag::for_grid_pt(grid, grid_pt) {
-            if let GridTooltipTag::Wire(wire) = tag {
-                if self.interaction.is_nothing()
-                    && self.hover_wire != Some(wire)
-                {
-                    self.hover_wire = Some(wire);
-                    ui.request_redraw();
+            let hover_wire = match tag {
+                GridTooltipTag::Wire(wire) => Some(wire),
+                GridTooltipTag::Chip(_, _) | GridTooltipTag::Interface(_) => {
+                    port_wire_at(grid, grid_pt)
                 }
-            } else if self.hover_wire.is_some() {
-                self.hover_wire = None;
+                GridTooltipTag::Manipulation(_) => None,
+            };
+            if (hover_wire.is_none() || self.interaction.is_nothing())
+                && self.hover_wire != hover_wire
+            {
+                self.hover_wire = hover_wire;
                 ui.request_redraw();
             }
             tooltip.hover_tag(mouse.pt, ui, tag);
@@ -936,6 +1192,19 @@ impl EditGridView {
         ui.audio().play_sound(Sound::GrabChip);
     }
 
+    /// Starts repeatedly placing chips of the given type: the chip follows
+    /// the mouse, and each click places a copy of it onto the board, until
+    /// the interaction is cancelled.
+    pub fn start_stamping_chip(&mut self, ui: &mut Ui, ctype: ChipType) {
+        let size = ctype.size();
+        let start = 0.5 * Point2::new(size.width, size.height).as_f32();
+        let mut drag =
+            ChipDrag::new(ctype, Orientation::default(), None, start);
+        drag.move_to(self.camera.center_grid_pt(), ui);
+        self.interaction = Interaction::StampingChip(drag);
+        ui.request_redraw();
+    }
+
     pub fn drop_into_parts_tray(&mut self, ui: &mut Ui, grid: &mut EditGrid) {
         match self.interaction.take() {
             Interaction::DraggingChip(drag) => {
@@ -979,7 +1248,9 @@ impl EditGridView {
                 ui.request_redraw();
                 false
             }
-            Interaction::DraggingBounds(_) | Interaction::SelectingRect(_) => {
+            Interaction::DraggingBounds(_)
+            | Interaction::SelectingRect(_)
+            | Interaction::StampingChip(_) => {
                 ui.request_redraw();
                 false
             }
@@ -994,6 +1265,117 @@ impl EditGridView {
     pub fn set_camera_goal(&mut self, grid_pt: Point2<f32>) {
         self.camera.set_goal(grid_pt);
     }
+
+    /// Briefly highlights the event wires that fired during the subcycle
+    /// that the grid's evaluation (if any) just finished.
+    pub fn flash_fired_event_wires(&mut self, grid: &EditGrid, ui: &mut Ui) {
+        self.fired_events.clear();
+        if let Some(eval) = grid.eval() {
+            if eval.subcycle() > 0 {
+                let group = grid.wire_index_group(eval.subcycle() - 1);
+                self.fired_events.extend(group.iter().copied().filter(
+                    |&wire_id| {
+                        grid.wire_color(wire_id) == WireColor::Event
+                            && eval.wire_event(wire_id).is_some()
+                    },
+                ));
+            }
+        }
+        self.fired_events_flash = if self.fired_events.is_empty() {
+            0.0
+        } else {
+            FIRED_EVENT_FLASH_SECONDS
+        };
+        ui.request_redraw();
+    }
+}
+
+/// Returns the action for opening the edit dialog of the given chip, if it's
+/// a kind of chip that has one.
+fn edit_action_for_chip(
+    coords: Coords,
+    ctype: ChipType,
+) -> Option<EditGridAction> {
+    match ctype {
+        ChipType::Button(code) => {
+            Some(EditGridAction::EditButton(coords, code))
+        }
+        ChipType::Coerce(size) => {
+            Some(EditGridAction::EditCoerce(coords, size))
+        }
+        ChipType::Comment(bytes) => {
+            let string: String =
+                bytes.iter().map(|&b| char::from(b)).collect();
+            let string = string.trim().to_string();
+            Some(EditGridAction::EditComment(coords, string))
+        }
+        ChipType::Const(value) => {
+            Some(EditGridAction::EditConst(coords, value))
+        }
+        ChipType::Vref(value) => Some(EditGridAction::EditVref(coords, value)),
+        _ => None,
+    }
+}
+
+/// Returns the wire attached to the port on the edge of the grid cell under
+/// `grid_pt`, if the point is close enough to that edge.  Wire fragments
+/// within a chip's (or interface's) cells only ever exist at its ports, so
+/// this doesn't need to check for the port itself.
+fn port_wire_at(grid: &EditGrid, grid_pt: Point2<f32>) -> Option<WireId> {
+    let coords: Coords = grid_pt.as_i32_floor();
+    let sub_pt = grid_pt - coords.as_f32();
+    for dir in Direction::all() {
+        let (along, across) = match dir {
+            Direction::East => (sub_pt.x, sub_pt.y),
+            Direction::South => (sub_pt.y, sub_pt.x),
+            Direction::West => (1.0 - sub_pt.x, sub_pt.y),
+            Direction::North => (1.0 - sub_pt.y, sub_pt.x),
+        };
+        if along >= 1.0 - PORT_HOVER_DEPTH
+            && (across - 0.5).abs() <= PORT_HOVER_HALF_WIDTH
+        {
+            if let Some(wire) = grid.wire_id_at(coords, dir) {
+                return Some(wire);
+            }
+        }
+    }
+    None
+}
+
+fn port_arrow_half_height(size: WireSize) -> f32 {
+    match size {
+        WireSize::Zero | WireSize::One => 0.08,
+        WireSize::Two => 0.11,
+        WireSize::Four => 0.14,
+        WireSize::Eight => 0.17,
+    }
+}
+
+//===========================================================================//
+
+/// Returns the smallest rectangle containing all chips and wires in the grid,
+/// or the grid's bounds if the grid is empty.
+fn occupied_bounds(grid: &EditGrid) -> CoordsRect {
+    let mut occupied: Option<CoordsRect> = None;
+    let chip_rects = grid.chips().map(|(coords, ctype, orient)| {
+        CoordsRect::with_size(coords, orient * ctype.size())
+    });
+    let wire_rects = grid.wire_fragments().map(|(coords, _, _, _, _, _)| {
+        CoordsRect::new(coords.x, coords.y, 1, 1)
+    });
+    for rect in chip_rects.chain(wire_rects) {
+        occupied = Some(match occupied {
+            Some(union) => {
+                let left = union.x.min(rect.x);
+                let top = union.y.min(rect.y);
+                let right = union.right().max(rect.right());
+                let bottom = union.bottom().max(rect.bottom());
+                CoordsRect::new(left, top, right - left, bottom - top)
+            }
+            None => rect,
+        });
+    }
+    occupied.unwrap_or_else(|| grid.bounds())
 }
 
 fn try_toggle_break(
@@ -1028,6 +1410,7 @@ enum Interaction {
     Nothing,
     DraggingBounds(BoundsDrag),
     DraggingChip(ChipDrag),
+    StampingChip(ChipDrag),
     SelectingRect(SelectingDrag),
     RectSelected(CoordsRect),
     DraggingSelection(SelectionDrag),
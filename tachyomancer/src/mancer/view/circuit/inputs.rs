@@ -0,0 +1,245 @@
+// +--------------------------------------------------------------------------+
+// | Copyright 2018 Matthew D. Steele <mdsteele@alum.mit.edu>                 |
+// |                                                                          |
+// | This file is part of Tachyomancer.                                       |
+// |                                                                          |
+// | Tachyomancer is free software: you can redistribute it and/or modify it  |
+// | under the terms of the GNU General Public License as published by the    |
+// | Free Software Foundation, either version 3 of the License, or (at your   |
+// | option) any later version.                                               |
+// |                                                                          |
+// | Tachyomancer is distributed in the hope that it will be useful, but      |
+// | WITHOUT ANY WARRANTY; without even the implied warranty of               |
+// | MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU        |
+// | General Public License for details.                                      |
+// |                                                                          |
+// | You should have received a copy of the GNU General Public License along  |
+// | with Tachyomancer.  If not, see <http://www.gnu.org/licenses/>.          |
+// +--------------------------------------------------------------------------+
+
+use super::super::button::TextButton;
+use super::super::tooltip::TooltipSink;
+use super::tray::TraySlide;
+use crate::mancer::font::Align;
+use crate::mancer::gui::{Cursor, Event, Resources, Ui};
+use crate::mancer::shader::UiShader;
+use cgmath::{vec2, Deg, Matrix4, Point2};
+use tachy::geom::{AsFloat, Color4, MatrixExt, Rect, RectSize};
+use tachy::save::{Puzzle, PuzzleKind};
+use tachy::state::{sandbox_input_names, EditGrid};
+
+//===========================================================================//
+
+const INPUT_BUTTON_HEIGHT: i32 = 30;
+const INPUT_BUTTON_WIDTH: i32 = 80;
+const INPUT_LABEL_FONT_SIZE: f32 = 20.0;
+const INPUT_LABEL_WIDTH: i32 = 40;
+const INPUT_ROW_SPACING: i32 = 8;
+const INPUT_VALUE_FONT_SIZE: f32 = 20.0;
+
+const TRAY_EXTRA_HIDDEN_HEIGHT: i32 = 20;
+const TRAY_FLIP_HORZ: bool = true;
+const TRAY_INNER_MARGIN: i32 = 20;
+const TRAY_TAB_FONT_SIZE: f32 = 16.0;
+const TRAY_TAB_HEIGHT: f32 = 60.0;
+const TRAY_TAB_TEXT: &str = "INPUTS";
+
+//===========================================================================//
+
+/// A tray (shown only for sandbox puzzles) that lets the player choose the
+/// values sent by the sandbox's input interface each time step.
+pub struct SandboxInputTray {
+    rect: Rect<i32>,
+    names: Vec<&'static str>,
+    buttons: Vec<TextButton<usize>>,
+    slide: TraySlide,
+}
+
+impl SandboxInputTray {
+    pub fn new(
+        window_size: RectSize<i32>,
+        current_puzzle: Puzzle,
+    ) -> SandboxInputTray {
+        let names = if current_puzzle.kind() == PuzzleKind::Sandbox {
+            sandbox_input_names()
+        } else {
+            Vec::new()
+        };
+        let rect = if names.is_empty() {
+            Rect::new(window_size.width, window_size.height, 0, 0)
+        } else {
+            let num_rows = names.len() as i32;
+            let size = RectSize::new(
+                INPUT_LABEL_WIDTH + INPUT_BUTTON_WIDTH,
+                num_rows * (INPUT_BUTTON_HEIGHT + INPUT_ROW_SPACING)
+                    - INPUT_ROW_SPACING,
+            )
+            .expand(TRAY_INNER_MARGIN);
+            Rect::new(
+                window_size.width - size.width,
+                window_size.height - size.height,
+                size.width,
+                size.height + TRAY_EXTRA_HIDDEN_HEIGHT,
+            )
+        };
+        let buttons = (0..names.len())
+            .map(|index| {
+                let top = rect.y
+                    + TRAY_INNER_MARGIN
+                    + (index as i32)
+                        * (INPUT_BUTTON_HEIGHT + INPUT_ROW_SPACING);
+                let button_rect = Rect::new(
+                    rect.x + TRAY_INNER_MARGIN + INPUT_LABEL_WIDTH,
+                    top,
+                    INPUT_BUTTON_WIDTH,
+                    INPUT_BUTTON_HEIGHT,
+                );
+                TextButton::new(button_rect, "", index)
+            })
+            .collect();
+        SandboxInputTray {
+            rect,
+            names,
+            buttons,
+            slide: TraySlide::new(rect.width),
+        }
+    }
+
+    pub fn toggle(&mut self) {
+        if !self.rect.is_empty() {
+            self.slide.toggle();
+        }
+    }
+
+    fn slid_rect(&self) -> Rect<i32> {
+        self.rect + vec2(self.slide.distance(), 0)
+    }
+
+    pub fn draw(
+        &self,
+        resources: &Resources,
+        matrix: &Matrix4<f32>,
+        grid: &EditGrid,
+    ) {
+        if self.rect.is_empty() {
+            return;
+        }
+        let matrix =
+            matrix * Matrix4::trans2(self.slide.distance() as f32, 0.0);
+        let ui = resources.shaders().ui();
+        let rect = self.rect.as_f32();
+        let tab_rect =
+            UiShader::tray_tab_rect(rect, TRAY_TAB_HEIGHT, TRAY_FLIP_HORZ);
+        ui.draw_tray(
+            &matrix,
+            &rect,
+            TRAY_TAB_HEIGHT,
+            TRAY_FLIP_HORZ,
+            &Color4::ORANGE2,
+            &Color4::CYAN2,
+            &Color4::PURPLE0_TRANSLUCENT,
+        );
+
+        let tab_matrix = matrix
+            * Matrix4::trans2(
+                tab_rect.x + 0.5 * tab_rect.width,
+                tab_rect.y + 0.5 * tab_rect.height,
+            )
+            * Matrix4::from_angle_z(Deg(90.0));
+        let font = resources.fonts().roman();
+        font.draw(
+            &tab_matrix,
+            TRAY_TAB_FONT_SIZE,
+            Align::MidCenter,
+            (0.0, -2.0),
+            TRAY_TAB_TEXT,
+        );
+
+        let bold = resources.fonts().bold();
+        for (index, button) in self.buttons.iter().enumerate() {
+            button.draw(resources, &matrix, true);
+            let button_rect = button.rect().as_f32();
+            let mid_y = button_rect.y + 0.5 * button_rect.height;
+            bold.draw(
+                &matrix,
+                INPUT_LABEL_FONT_SIZE,
+                Align::MidCenter,
+                (button_rect.x - 0.5 * INPUT_LABEL_WIDTH as f32, mid_y),
+                self.names[index],
+            );
+            font.draw(
+                &matrix,
+                INPUT_VALUE_FONT_SIZE,
+                Align::MidCenter,
+                (button_rect.x + 0.5 * button_rect.width, mid_y),
+                &grid.sandbox_input(index).to_string(),
+            );
+        }
+    }
+
+    /// Returns `Some(Some(index))` if the player clicked on the given input's
+    /// value to edit it, `Some(None)` if the event was otherwise absorbed by
+    /// the tray, or `None` if the event should be passed on.
+    pub fn on_event(
+        &mut self,
+        event: &Event,
+        ui: &mut Ui,
+        tooltip: &mut dyn TooltipSink<()>,
+    ) -> Option<Option<usize>> {
+        if self.rect.is_empty() {
+            return None;
+        }
+        let rel_event =
+            event.relative_to(Point2::new(-self.slide.distance(), 0));
+        for button in self.buttons.iter_mut() {
+            if let Some(index) = button.on_event(&rel_event, ui, true) {
+                return Some(Some(index));
+            }
+        }
+        match event {
+            Event::ClockTick(tick) => self.slide.on_clock_tick(tick, ui),
+            Event::MouseDown(mouse) => {
+                let rel_mouse_pt = mouse.pt - vec2(self.slide.distance(), 0);
+                let tab_rect = UiShader::tray_tab_rect(
+                    self.rect.as_f32(),
+                    TRAY_TAB_HEIGHT,
+                    TRAY_FLIP_HORZ,
+                );
+                if tab_rect.contains_point(rel_mouse_pt.as_f32()) {
+                    self.slide.toggle();
+                    return Some(None);
+                } else if self.rect.contains_point(rel_mouse_pt) {
+                    return Some(None);
+                }
+            }
+            Event::MouseMove(mouse) | Event::MouseUp(mouse) => {
+                let rel_mouse_pt = mouse.pt - vec2(self.slide.distance(), 0);
+                let tab_rect = UiShader::tray_tab_rect(
+                    self.rect.as_f32(),
+                    TRAY_TAB_HEIGHT,
+                    TRAY_FLIP_HORZ,
+                );
+                if self.rect.contains_point(rel_mouse_pt)
+                    || tab_rect.contains_point(rel_mouse_pt.as_f32())
+                {
+                    ui.cursor().request(Cursor::default());
+                    tooltip.hover_none(ui);
+                }
+            }
+            Event::Multitouch(touch)
+                if self.slid_rect().contains_point(touch.pt) =>
+            {
+                return Some(None);
+            }
+            Event::Scroll(scroll)
+                if self.slid_rect().contains_point(scroll.pt) =>
+            {
+                return Some(None);
+            }
+            _ => {}
+        }
+        None
+    }
+}
+
+//===========================================================================//
@@ -23,10 +23,12 @@ mod chipdrag;
 mod control;
 mod export;
 mod grid;
+mod inputs;
 mod manip;
 mod parts;
 mod select;
 mod specify;
+mod timeline;
 mod tooltip;
 mod tray;
 mod tutorial;
@@ -36,8 +38,10 @@ mod wiredrag;
 use self::control::{ControlsAction, ControlsStatus, ControlsTray};
 pub use self::export::export_circuit_image;
 use self::grid::{EditGridAction, EditGridView};
+use self::inputs::SandboxInputTray;
 use self::parts::{PartsAction, PartsTray};
-use self::specify::SpecificationTray;
+use self::specify::{SpecificationAction, SpecificationTray};
+use self::timeline::TimeScrubber;
 use self::tooltip::GridTooltipTag;
 use self::tutorial::TutorialBubble;
 use self::verify::VerificationTray;
@@ -47,17 +51,22 @@ use super::dialog::{
 };
 use super::paragraph::Paragraph;
 use super::tooltip::Tooltip;
+use crate::mancer::font::{text_to_glyphs, Align};
 use crate::mancer::gui::{Event, Keycode, Resources, Sound, Ui, Window};
-use crate::mancer::save::Prefs;
+use crate::mancer::save::{
+    Hotkey, HotkeyCodeExt, Prefs, StringKey, SNAPSHOT_NAME_MAX_CHARS,
+};
 use cgmath::{self, vec2, MetricSpace, Point2};
 use std::u8;
-use tachy::geom::{AsFloat, Coords, Direction, Fixed, RectSize};
+use tachy::geom::{AsFloat, Color4, Coords, Direction, Fixed, RectSize};
 use tachy::save::{
-    ChipType, HotkeyCode, Puzzle, ScoreCurve, SolutionData, WireSize,
-    MAX_COMMENT_CHARS,
+    ChipType, HotkeyCode, Puzzle, PuzzleKind, ScoreCurve, SolutionData,
+    WireSize, MAX_COMMENT_CHARS, MAX_WIRE_LABEL_CHARS,
 };
 use tachy::state::{
-    EditGrid, EvalResult, GridChange, PuzzleExt, TutorialBubblePosition,
+    CircuitStats, EditGrid, EvalResult, FabricationEval, FabricationMismatch,
+    GridChange, PuzzleExt, ReferenceMismatch, RejectedChip,
+    TutorialBubblePosition,
 };
 
 //===========================================================================//
@@ -65,13 +74,29 @@ use tachy::state::{
 const FAST_FORWARD_SPEEDUP: f64 = 5.0;
 const PARTS_CONTROLS_SPACING: i32 = 4;
 
+// Font size and spacing above the controls tray for the prompt shown when a
+// running evaluation has been paused because the player seems to be away:
+const IDLE_PROMPT_FONT_SIZE: f32 = 18.0;
+const IDLE_PROMPT_MARGIN: f32 = 8.0;
+
+// The most rejected chips to list individually when warning about a circuit
+// that couldn't be loaded completely:
+const MAX_REJECTED_CHIPS_LISTED: usize = 8;
+const MAX_REFERENCE_MISMATCHES_LISTED: usize = 8;
+const MAX_CHIP_NAME_CHARS: usize = 16;
+
 //===========================================================================//
 
 pub enum CircuitAction {
     BackToMenu,
     BackToMenuWithoutSaving,
-    ExportImage(u32),
-    Victory(SolutionData),
+    ExportImage(u32, bool),
+    ExportRecording(Box<SolutionData>),
+    VerifyReference,
+    ChooseSnapshotSlot,
+    SaveSnapshot(usize, String),
+    LoadSnapshot(usize),
+    Victory(Box<SolutionData>),
 }
 
 #[derive(Clone, Copy)]
@@ -86,10 +111,23 @@ enum FailedSaveDialogAction {
     ContinueEditing,
 }
 
+#[derive(Clone, Copy)]
+enum SnapshotSlotsDialogAction {
+    ChooseSlot(usize),
+    ContinueEditing,
+}
+
+#[derive(Clone, Copy)]
+enum SnapshotDialogAction {
+    Save(usize),
+    Load(usize),
+    ContinueEditing,
+}
+
 #[derive(Clone, Copy)]
 enum VictoryDialogAction {
     BackToMenu,
-    ExportImage(u32),
+    ExportImage(u32, bool),
     ContinueEditing,
 }
 
@@ -126,7 +164,11 @@ pub struct CircuitView {
     parts_tray: PartsTray,
     specification_tray: SpecificationTray,
     verification_tray: VerificationTray,
+    sandbox_input_tray: SandboxInputTray,
+    time_scrubber: TimeScrubber,
     seconds_since_time_step: f64,
+    seconds_since_input: f64,
+    idle_paused: bool,
     controls_status: ControlsStatus,
     tooltip: Tooltip<CircuitTooltipTag>,
     edit_button_dialog: Option<(HotkeyDialogBox, Coords)>,
@@ -134,8 +176,20 @@ pub struct CircuitView {
     edit_comment_dialog: Option<(TextDialogBox, Coords)>,
     edit_const_dialog: Option<(TextDialogBox, Coords)>,
     edit_vref_dialog: Option<(TextDialogBox, Coords)>,
+    edit_wire_label_dialog: Option<(TextDialogBox, (Coords, Direction))>,
+    edit_sandbox_input_dialog: Option<(TextDialogBox, usize)>,
+    snapshot_name_dialog: Option<(TextDialogBox, usize)>,
     export_image_dialog: Option<ButtonDialogBox<ExportImageDialogAction>>,
     failed_save_dialog: Option<ButtonDialogBox<FailedSaveDialogAction>>,
+    failure_dialog: Option<ButtonDialogBox<()>>,
+    loop_breaking_dialog: Option<ButtonDialogBox<Option<Vec<GridChange>>>>,
+    place_chip_dialog: Option<TextDialogBox>,
+    recording_dialog: Option<ButtonDialogBox<()>>,
+    reference_dialog: Option<ButtonDialogBox<()>>,
+    rejected_chips_dialog: Option<ButtonDialogBox<()>>,
+    snapshot_slots_dialog: Option<ButtonDialogBox<SnapshotSlotsDialogAction>>,
+    snapshot_dialog: Option<ButtonDialogBox<SnapshotDialogAction>>,
+    snapshot_names: Vec<Option<String>>,
     victory_dialog: Option<ScoreGraphDialogBox<VictoryDialogAction>>,
 }
 
@@ -143,6 +197,7 @@ impl CircuitView {
     pub fn new(
         window: &Window,
         grid: &EditGrid,
+        puzzle_solved: bool,
         prefs: &Prefs,
     ) -> CircuitView {
         let window_size = window.size();
@@ -167,7 +222,7 @@ impl CircuitView {
             .find(|&&(pos, _)| pos == TutorialBubblePosition::PartsTray)
             .map(|&(_, format)| TutorialBubble::new(prefs, format));
         let controls_tray =
-            ControlsTray::new(window_size, puzzle, controls_bubble);
+            ControlsTray::new(window_size, puzzle, prefs, controls_bubble);
         let parts_tray = PartsTray::new(
             window,
             grid.allowed_chips(),
@@ -181,16 +236,23 @@ impl CircuitView {
                 window_size,
                 grid.bounds(),
                 bounds_bubbles,
+                prefs.highlight_unconnected_ports(),
+                prefs.emphasize_wire_sizes(),
             ),
             controls_tray,
             parts_tray,
             specification_tray: SpecificationTray::new(
                 window_size,
                 puzzle,
+                puzzle_solved,
                 prefs,
             ),
             verification_tray: VerificationTray::new(window_size, puzzle),
+            sandbox_input_tray: SandboxInputTray::new(window_size, puzzle),
+            time_scrubber: TimeScrubber::new(window_size),
             seconds_since_time_step: 0.0,
+            seconds_since_input: 0.0,
+            idle_paused: false,
             controls_status: ControlsStatus::Stopped,
             tooltip: Tooltip::new(window_size),
             edit_button_dialog: None,
@@ -198,17 +260,40 @@ impl CircuitView {
             edit_comment_dialog: None,
             edit_const_dialog: None,
             edit_vref_dialog: None,
+            edit_wire_label_dialog: None,
+            edit_sandbox_input_dialog: None,
+            snapshot_name_dialog: None,
             export_image_dialog: None,
             failed_save_dialog: None,
+            failure_dialog: None,
+            loop_breaking_dialog: None,
+            place_chip_dialog: None,
+            recording_dialog: None,
+            reference_dialog: None,
+            rejected_chips_dialog: rejected_chips_dialog(
+                window_size,
+                prefs,
+                grid.rejected_chips(),
+            ),
+            snapshot_slots_dialog: None,
+            snapshot_dialog: None,
+            snapshot_names: Vec::new(),
             victory_dialog: None,
         }
     }
 
     pub fn draw(&self, resources: &Resources, grid: &EditGrid) {
-        self.edit_grid.draw_board(resources, grid);
+        let hovered_chip = match self.tooltip.active_tag() {
+            Some(CircuitTooltipTag::Grid(GridTooltipTag::Chip(coords, _))) => {
+                Some(*coords)
+            }
+            _ => None,
+        };
+        self.edit_grid.draw_board(resources, grid, hovered_chip);
         let projection =
             cgmath::ortho(0.0, self.width, self.height, 0.0, -100.0, 100.0);
         self.verification_tray.draw(resources, &projection, grid.eval());
+        self.sandbox_input_tray.draw(resources, &projection, grid);
         self.specification_tray.draw(resources, &projection);
         self.parts_tray.draw(resources, &projection, grid.eval().is_none());
         self.controls_tray.draw(
@@ -217,6 +302,21 @@ impl CircuitView {
             self.controls_status,
             grid,
         );
+        self.time_scrubber.draw(resources, &projection);
+        if self.idle_paused {
+            resources.fonts().roman().draw_style(
+                &projection,
+                IDLE_PROMPT_FONT_SIZE,
+                Align::BottomCenter,
+                (
+                    0.5 * self.width,
+                    (self.controls_tray.rect().y as f32) - IDLE_PROMPT_MARGIN,
+                ),
+                &Color4::CYAN3,
+                0.0,
+                "Paused while you were away.  Press Run to continue.",
+            );
+        }
         self.edit_grid.draw_dragged(resources);
         self.tooltip.draw(resources, &projection);
         if let Some((ref dialog, _)) = self.edit_button_dialog {
@@ -229,10 +329,34 @@ impl CircuitView {
             dialog.draw(resources, &projection, is_valid_const);
         } else if let Some((ref dialog, _)) = self.edit_vref_dialog {
             dialog.draw(resources, &projection, is_valid_voltage);
+        } else if let Some((ref dialog, _)) = self.edit_wire_label_dialog {
+            dialog.draw(resources, &projection, |_| true);
+        } else if let Some((ref dialog, _)) = self.edit_sandbox_input_dialog {
+            dialog.draw(resources, &projection, is_valid_const);
+        } else if let Some((ref dialog, _)) = self.snapshot_name_dialog {
+            dialog.draw(resources, &projection, is_valid_snapshot_name);
         } else if let Some(ref dialog) = self.export_image_dialog {
             dialog.draw(resources, &projection);
         } else if let Some(ref dialog) = self.failed_save_dialog {
             dialog.draw(resources, &projection);
+        } else if let Some(ref dialog) = self.failure_dialog {
+            dialog.draw(resources, &projection);
+        } else if let Some(ref dialog) = self.loop_breaking_dialog {
+            dialog.draw(resources, &projection);
+        } else if let Some(ref dialog) = self.place_chip_dialog {
+            dialog.draw(resources, &projection, |name| {
+                self.parts_tray.find_part(name).is_some()
+            });
+        } else if let Some(ref dialog) = self.recording_dialog {
+            dialog.draw(resources, &projection);
+        } else if let Some(ref dialog) = self.reference_dialog {
+            dialog.draw(resources, &projection);
+        } else if let Some(ref dialog) = self.rejected_chips_dialog {
+            dialog.draw(resources, &projection);
+        } else if let Some(ref dialog) = self.snapshot_slots_dialog {
+            dialog.draw(resources, &projection);
+        } else if let Some(ref dialog) = self.snapshot_dialog {
+            dialog.draw(resources, &projection);
         } else if let Some(ref dialog) = self.victory_dialog {
             dialog.draw(resources, &projection);
         }
@@ -242,7 +366,7 @@ impl CircuitView {
         &mut self,
         event: &Event,
         ui: &mut Ui,
-        (grid, prefs): (&mut EditGrid, &Prefs),
+        (grid, prefs): (&mut EditGrid, &mut Prefs),
     ) -> Option<CircuitAction> {
         debug_assert_eq!(
             self.controls_status == ControlsStatus::Stopped,
@@ -252,6 +376,14 @@ impl CircuitView {
         self.tooltip
             .on_event(event, ui, prefs, |tag| tag.tooltip_format(grid));
 
+        if event.is_player_input() {
+            self.seconds_since_input = 0.0;
+            if self.idle_paused {
+                self.idle_paused = false;
+                ui.request_redraw();
+            }
+        }
+
         if let Some((mut dialog, coords)) = self.edit_button_dialog.take() {
             match dialog.on_event(event, ui) {
                 Some(DialogAction::Value(opt_code)) => {
@@ -312,6 +444,35 @@ impl CircuitView {
             return None;
         }
 
+        if let Some((mut dialog, loc)) = self.edit_wire_label_dialog.take() {
+            match dialog.on_event(event, ui, |_| true) {
+                Some(DialogAction::Value(text)) => {
+                    change_wire_label(ui, grid, loc, &text);
+                }
+                Some(DialogAction::Cancel) => {}
+                None => self.edit_wire_label_dialog = Some((dialog, loc)),
+            }
+            return None;
+        }
+
+        if let Some((mut dialog, index)) =
+            self.edit_sandbox_input_dialog.take()
+        {
+            match dialog.on_event(event, ui, is_valid_const) {
+                Some(DialogAction::Value(text)) => {
+                    if let Ok(value) = text.parse::<u8>() {
+                        grid.set_sandbox_input(index, value as u32);
+                        ui.request_redraw();
+                    }
+                }
+                Some(DialogAction::Cancel) => {}
+                None => {
+                    self.edit_sandbox_input_dialog = Some((dialog, index));
+                }
+            }
+            return None;
+        }
+
         if let Some(mut dialog) = self.export_image_dialog.take() {
             match dialog.on_event(event, ui) {
                 Some(ExportImageDialogAction::BackToMenu) => {
@@ -334,13 +495,113 @@ impl CircuitView {
             return None;
         }
 
+        if let Some(mut dialog) = self.failure_dialog.take() {
+            if dialog.on_event(event, ui).is_none() {
+                self.failure_dialog = Some(dialog);
+            }
+            return None;
+        }
+
+        if let Some(mut dialog) = self.loop_breaking_dialog.take() {
+            match dialog.on_event(event, ui) {
+                Some(Some(changes)) => {
+                    if grid.try_mutate(changes) {
+                        ui.audio().play_sound(Sound::Beep);
+                        ui.request_redraw();
+                    } else {
+                        debug_warn!("loop-breaking Delay mutation failed");
+                    }
+                }
+                Some(None) => {}
+                None => self.loop_breaking_dialog = Some(dialog),
+            }
+            return None;
+        }
+
+        if let Some(mut dialog) = self.place_chip_dialog.take() {
+            let parts_tray = &self.parts_tray;
+            match dialog.on_event(event, ui, |name| {
+                parts_tray.find_part(name).is_some()
+            }) {
+                Some(DialogAction::Value(name)) => {
+                    if let Some(ctype) = self.parts_tray.find_part(&name) {
+                        self.edit_grid.start_stamping_chip(ui, ctype);
+                    }
+                }
+                Some(DialogAction::Cancel) => {}
+                None => self.place_chip_dialog = Some(dialog),
+            }
+            return None;
+        }
+
+        if let Some(mut dialog) = self.recording_dialog.take() {
+            if dialog.on_event(event, ui).is_none() {
+                self.recording_dialog = Some(dialog);
+            }
+            return None;
+        }
+
+        if let Some(mut dialog) = self.reference_dialog.take() {
+            if dialog.on_event(event, ui).is_none() {
+                self.reference_dialog = Some(dialog);
+            }
+            return None;
+        }
+
+        if let Some(mut dialog) = self.rejected_chips_dialog.take() {
+            if dialog.on_event(event, ui).is_none() {
+                self.rejected_chips_dialog = Some(dialog);
+            }
+            return None;
+        }
+
+        if let Some(mut dialog) = self.snapshot_slots_dialog.take() {
+            match dialog.on_event(event, ui) {
+                Some(SnapshotSlotsDialogAction::ChooseSlot(slot)) => {
+                    self.show_snapshot_dialog(ui, prefs, slot);
+                }
+                Some(SnapshotSlotsDialogAction::ContinueEditing) => {}
+                None => self.snapshot_slots_dialog = Some(dialog),
+            }
+            return None;
+        }
+
+        if let Some(mut dialog) = self.snapshot_dialog.take() {
+            match dialog.on_event(event, ui) {
+                Some(SnapshotDialogAction::Save(slot)) => {
+                    self.show_snapshot_name_dialog(ui, prefs, slot);
+                }
+                Some(SnapshotDialogAction::Load(slot)) => {
+                    return Some(CircuitAction::LoadSnapshot(slot));
+                }
+                Some(SnapshotDialogAction::ContinueEditing) => {}
+                None => self.snapshot_dialog = Some(dialog),
+            }
+            return None;
+        }
+
+        if let Some((mut dialog, slot)) = self.snapshot_name_dialog.take() {
+            match dialog.on_event(event, ui, is_valid_snapshot_name) {
+                Some(DialogAction::Value(name)) => {
+                    let name = name.trim().to_string();
+                    return Some(CircuitAction::SaveSnapshot(slot, name));
+                }
+                Some(DialogAction::Cancel) => {}
+                None => self.snapshot_name_dialog = Some((dialog, slot)),
+            }
+            return None;
+        }
+
         if let Some(mut dialog) = self.victory_dialog.take() {
             match dialog.on_event(event, ui) {
                 Some(VictoryDialogAction::BackToMenu) => {
                     return Some(CircuitAction::BackToMenu);
                 }
-                Some(VictoryDialogAction::ExportImage(score)) => {
-                    return Some(CircuitAction::ExportImage(score));
+                Some(VictoryDialogAction::ExportImage(score, transparent)) => {
+                    return Some(CircuitAction::ExportImage(
+                        score,
+                        transparent,
+                    ));
                 }
                 Some(VictoryDialogAction::ContinueEditing) => {}
                 None => self.victory_dialog = Some(dialog),
@@ -351,13 +612,30 @@ impl CircuitView {
         let mut action: Option<CircuitAction> = None;
         match event {
             Event::ClockTick(tick) => {
+                self.seconds_since_input += tick.elapsed;
+                let idle_pause_seconds = prefs.idle_pause_seconds();
+                if idle_pause_seconds > 0
+                    && self.seconds_since_input >= idle_pause_seconds as f64
+                    && (self.controls_status == ControlsStatus::FastForwarding
+                        || self.controls_status == ControlsStatus::Running)
+                {
+                    debug_log!(
+                        "No input for {} seconds; pausing evaluation",
+                        idle_pause_seconds
+                    );
+                    self.seconds_since_time_step = 0.0;
+                    self.controls_status = ControlsStatus::Paused;
+                    self.idle_paused = true;
+                    ui.request_redraw();
+                }
                 let mut result = EvalResult::Continue;
                 if let Some(eval) = grid.eval_mut() {
                     if self.controls_status == ControlsStatus::FastForwarding
                         || self.controls_status == ControlsStatus::Running
                     {
-                        let mut seconds_per_time_step =
-                            eval.seconds_per_time_step();
+                        let mut seconds_per_time_step = eval
+                            .seconds_per_time_step()
+                            / prefs.simulation_speed();
                         if self.controls_status
                             == ControlsStatus::FastForwarding
                         {
@@ -383,6 +661,83 @@ impl CircuitView {
                     } else {
                         return Some(CircuitAction::BackToMenu);
                     }
+                } else if key.code
+                    == prefs
+                        .hotkey_code(Hotkey::EvalExportRecording)
+                        .to_keycode()
+                    && !key.command
+                    && grid.eval().is_some()
+                {
+                    self.edit_grid.cancel_interaction(ui, grid);
+                    let recording = current_run_data(grid, prefs, 0);
+                    return Some(CircuitAction::ExportRecording(Box::new(
+                        recording,
+                    )));
+                } else if key.code
+                    == prefs.hotkey_code(Hotkey::PlaceChip).to_keycode()
+                    && !key.command
+                    && grid.eval().is_none()
+                {
+                    self.edit_grid.cancel_interaction(ui, grid);
+                    let size =
+                        RectSize::new(self.width as i32, self.height as i32);
+                    self.place_chip_dialog = Some(TextDialogBox::new(
+                        size,
+                        prefs,
+                        "Place which chip?  Click on the board to place \
+                         copies of it, and press Escape when done.",
+                        "",
+                        MAX_CHIP_NAME_CHARS,
+                    ));
+                    ui.request_redraw();
+                    return None;
+                } else if key.code
+                    == prefs.hotkey_code(Hotkey::SandboxSnapshots).to_keycode()
+                    && grid.puzzle().kind() == PuzzleKind::Sandbox
+                    && grid.eval().is_none()
+                {
+                    self.edit_grid.cancel_interaction(ui, grid);
+                    return Some(CircuitAction::ChooseSnapshotSlot);
+                } else if key.code
+                    == prefs
+                        .hotkey_code(Hotkey::SandboxVerifyReference)
+                        .to_keycode()
+                    && !key.command
+                    && grid.puzzle().kind() == PuzzleKind::Sandbox
+                    && grid.eval().is_none()
+                {
+                    self.edit_grid.cancel_interaction(ui, grid);
+                    return Some(CircuitAction::VerifyReference);
+                } else if key.code
+                    == prefs
+                        .hotkey_code(Hotkey::SandboxFreezeInputs)
+                        .to_keycode()
+                    && grid.puzzle().kind() == PuzzleKind::Sandbox
+                {
+                    if let Some(eval) = grid.eval_mut() {
+                        let frozen = !eval.inputs_frozen();
+                        debug_log!("Inputs frozen: {}", frozen);
+                        eval.set_inputs_frozen(frozen);
+                        ui.audio().play_sound(Sound::Beep);
+                        ui.request_redraw();
+                    }
+                } else if key.code
+                    == prefs.hotkey_code(Hotkey::ToggleSpecTray).to_keycode()
+                {
+                    // Don't slide trays around underneath an in-progress
+                    // drag; the drop target shouldn't move mid-gesture.
+                    if !self.edit_grid.is_dragging() {
+                        self.specification_tray.toggle();
+                    }
+                    return None;
+                } else if key.code
+                    == prefs.hotkey_code(Hotkey::ToggleVerifyTray).to_keycode()
+                {
+                    if !self.edit_grid.is_dragging() {
+                        self.verification_tray.toggle();
+                        self.sandbox_input_tray.toggle();
+                    }
+                    return None;
                 }
             }
             _ => {}
@@ -390,6 +745,18 @@ impl CircuitView {
 
         self.edit_grid.request_interaction_cursor(event, ui.cursor());
 
+        if let Some(opt_result) = self.time_scrubber.on_event(event, ui, grid)
+        {
+            if let Some(result) = opt_result {
+                self.edit_grid.cancel_interaction(ui, grid);
+                self.seconds_since_time_step = 0.0;
+                self.controls_status = ControlsStatus::Paused;
+                ui.request_redraw();
+                action = self.on_eval_result(result, ui, grid, prefs);
+            }
+            return action;
+        }
+
         if let Some(opt_action) = self.controls_tray.on_event(
             event,
             ui,
@@ -434,6 +801,7 @@ impl CircuitView {
                 }
                 Some(ControlsAction::GoToError) => {
                     self.move_camera_to_grid_error(ui, grid);
+                    self.offer_loop_breaking_delay(ui, grid, prefs);
                 }
                 Some(ControlsAction::Reset) => {
                     if grid.eval().is_some() {
@@ -519,6 +887,7 @@ impl CircuitView {
                         result = eval.step_subcycle();
                         ui.request_redraw();
                     }
+                    self.edit_grid.flash_fired_event_wires(grid, ui);
                     action = self.on_eval_result(result, ui, grid, prefs);
                 }
             }
@@ -544,12 +913,17 @@ impl CircuitView {
             return action;
         }
 
-        let stop = self.specification_tray.on_event(
+        if let Some(opt_action) = self.specification_tray.on_event(
             event,
             ui,
             &mut self.tooltip.sink(CircuitTooltipTag::Unused),
-        );
-        if stop {
+        ) {
+            match opt_action {
+                Some(SpecificationAction::CenterOnPort(iface, port)) => {
+                    self.move_camera_to_interface_port(ui, grid, iface, port);
+                }
+                None => {}
+            }
             return action;
         }
 
@@ -562,6 +936,27 @@ impl CircuitView {
             return action;
         }
 
+        if let Some(opt_index) = self.sandbox_input_tray.on_event(
+            event,
+            ui,
+            &mut self.tooltip.sink(CircuitTooltipTag::Unused),
+        ) {
+            if let Some(index) = opt_index {
+                let size =
+                    RectSize::new(self.width as i32, self.height as i32);
+                let dialog = TextDialogBox::new(
+                    size,
+                    prefs,
+                    "Choose new input value:",
+                    &grid.sandbox_input(index).to_string(),
+                    u8::MAX.to_string().len(),
+                );
+                self.edit_sandbox_input_dialog = Some((dialog, index));
+                ui.request_redraw();
+            }
+            return action;
+        }
+
         match self.edit_grid.on_event(
             event,
             ui,
@@ -632,6 +1027,19 @@ impl CircuitView {
                 self.edit_vref_dialog = Some((dialog, coords));
                 ui.request_redraw();
             }
+            Some(EditGridAction::EditWireLabel(loc, label)) => {
+                let size =
+                    RectSize::new(self.width as i32, self.height as i32);
+                let dialog = TextDialogBox::new(
+                    size,
+                    prefs,
+                    "Enter wire label:",
+                    &label,
+                    MAX_WIRE_LABEL_CHARS,
+                );
+                self.edit_wire_label_dialog = Some((dialog, loc));
+                ui.request_redraw();
+            }
             None => {}
         }
         return action;
@@ -660,28 +1068,30 @@ impl CircuitView {
                 None
             }
             EvalResult::Victory(score) => {
-                let bounds = grid.bounds();
-                let (time_steps, inputs) = {
-                    // TODO: It would be nice to not have this unwrap() here.
-                    let eval = grid.eval().unwrap();
-                    (eval.time_step(), eval.recorded_inputs(bounds.top_left()))
-                };
-                debug_log!("Victory: area={}, score={}", bounds.area(), score);
+                let area = grid.bounds().area();
+                debug_log!("Victory: area={}, score={}", area, score);
+                let solution = current_run_data(grid, prefs, score);
                 grid.stop_eval();
                 self.controls_status = ControlsStatus::Stopped;
                 ui.request_redraw();
-                Some(CircuitAction::Victory(SolutionData {
-                    install_id: prefs.install_id(),
-                    puzzle: grid.puzzle(),
-                    score,
-                    time_steps,
-                    circuit: grid.to_circuit_data(),
-                    inputs,
-                }))
+                Some(CircuitAction::Victory(Box::new(solution)))
             }
             EvalResult::Failure => {
                 debug_log!("Failure!");
-                // TODO: If fatal port error, move camera to show port.
+                if prefs.jump_to_failures() {
+                    let port = grid.eval().and_then(|eval| {
+                        eval.errors()
+                            .iter()
+                            .filter(|error| error.fatal)
+                            .find_map(|error| error.port)
+                    });
+                    if let Some((coords, dir)) = port {
+                        let goal = coords.as_f32()
+                            + vec2(0.5, 0.5)
+                            + dir.delta().as_f32() * 0.5;
+                        self.edit_grid.set_camera_goal(goal);
+                    }
+                }
                 if cfg!(debug_assertions) {
                     for error in grid.eval().unwrap().errors() {
                         debug_log!(
@@ -691,6 +1101,13 @@ impl CircuitView {
                         );
                     }
                 }
+                let mismatch = grid
+                    .eval()
+                    .and_then(|eval| eval.try_puzzle_eval::<FabricationEval>())
+                    .and_then(FabricationEval::first_mismatch);
+                if let Some(mismatch) = mismatch {
+                    self.show_fabrication_failure(prefs, mismatch);
+                }
                 self.controls_status = ControlsStatus::Finished;
                 ui.request_redraw();
                 None
@@ -724,6 +1141,53 @@ impl CircuitView {
         }
     }
 
+    /// If the grid has an event loop that a `Delay` chip could break, moves
+    /// the camera to where the chip would go and offers to insert it.
+    fn offer_loop_breaking_delay(
+        &mut self,
+        ui: &mut Ui,
+        grid: &EditGrid,
+        prefs: &Prefs,
+    ) {
+        let (coords, changes) = match grid.loop_breaking_delay() {
+            Some(suggestion) => suggestion,
+            None => return,
+        };
+        self.edit_grid.set_camera_goal(coords.as_f32() + vec2(0.5, 0.5));
+        let size = RectSize::new(self.width as i32, self.height as i32);
+        let format = "This loop of event wires can be broken by inserting \
+                      a $*Delay$* chip, which passes each event along one \
+                      cycle later.\n\nInsert a $*Delay$* chip here?";
+        let buttons = &[
+            (
+                prefs.strings().get(StringKey::ButtonCancel),
+                None,
+                Some(Keycode::Escape),
+            ),
+            ("Insert Delay", Some(changes), Some(Keycode::Return)),
+        ];
+        self.loop_breaking_dialog =
+            Some(ButtonDialogBox::new(size, prefs, format, buttons));
+        ui.request_redraw();
+    }
+
+    fn move_camera_to_interface_port(
+        &mut self,
+        ui: &mut Ui,
+        grid: &mut EditGrid,
+        iface_index: usize,
+        port_index: usize,
+    ) {
+        self.edit_grid.cancel_interaction(ui, grid);
+        let bounds = grid.bounds();
+        let coords = grid.interfaces().get(iface_index).and_then(|iface| {
+            iface.ports(bounds).get(port_index).map(|(_, port)| port.coords)
+        });
+        if let Some(coords) = coords {
+            self.edit_grid.set_camera_goal(coords.as_f32() + vec2(0.5, 0.5));
+        }
+    }
+
     pub fn show_export_image_success(
         &mut self,
         ui: &mut Ui,
@@ -774,6 +1238,27 @@ impl CircuitView {
         // TODO: Unfocus other views
     }
 
+    fn show_fabrication_failure(
+        &mut self,
+        prefs: &Prefs,
+        mismatch: &FabricationMismatch,
+    ) {
+        let size = RectSize::new(self.width as i32, self.height as i32);
+        let format = format!(
+            "$R$*FAILURE:$*$D Incorrect output for {} at time step {}.\n\n{}",
+            Paragraph::escape(mismatch.port_name),
+            mismatch.time_step,
+            Paragraph::escape(&mismatch.message)
+        );
+        let buttons = &[(
+            prefs.strings().get(StringKey::ButtonOk),
+            (),
+            Some(Keycode::Return),
+        )];
+        self.failure_dialog =
+            Some(ButtonDialogBox::new(size, prefs, &format, buttons));
+    }
+
     pub fn show_failed_to_save_error(
         &mut self,
         ui: &mut Ui,
@@ -805,25 +1290,274 @@ impl CircuitView {
         // TODO: Unfocus other views
     }
 
+    /// Shows a dialog for choosing one of the current puzzle's snapshot slots;
+    /// `slot_names` gives the name of the snapshot held in each slot, or
+    /// `None` for slots that are empty.
+    pub fn show_snapshot_slots_dialog(
+        &mut self,
+        ui: &mut Ui,
+        prefs: &Prefs,
+        slot_names: Vec<Option<String>>,
+    ) {
+        let size = RectSize::new(self.width as i32, self.height as i32);
+        let mut format =
+            "$*Snapshots$*\n\nChoose a snapshot slot:".to_string();
+        let mut labels = Vec::<String>::new();
+        for (slot, name) in slot_names.iter().enumerate() {
+            let status = match name {
+                Some(name) if !name.is_empty() => Paragraph::escape(name),
+                Some(_) => "saved".to_string(),
+                None => "empty".to_string(),
+            };
+            format.push_str(&format!("\n  Slot {}: {}", slot + 1, status));
+            labels.push(format!("Slot {}", slot + 1));
+        }
+        // Dialog buttons are laid out right-to-left, so add the slots in
+        // reverse order to have them read left-to-right.
+        let mut buttons = vec![(
+            prefs.strings().get(StringKey::ButtonCancel),
+            SnapshotSlotsDialogAction::ContinueEditing,
+            Some(Keycode::Escape),
+        )];
+        for slot in (0..slot_names.len()).rev() {
+            buttons.push((
+                labels[slot].as_str(),
+                SnapshotSlotsDialogAction::ChooseSlot(slot),
+                None,
+            ));
+        }
+        self.snapshot_slots_dialog =
+            Some(ButtonDialogBox::new(size, prefs, &format, &buttons));
+        self.snapshot_names = slot_names;
+        ui.request_redraw();
+    }
+
+    fn show_snapshot_dialog(
+        &mut self,
+        ui: &mut Ui,
+        prefs: &Prefs,
+        slot: usize,
+    ) {
+        let size = RectSize::new(self.width as i32, self.height as i32);
+        let mut buttons = vec![(
+            prefs.strings().get(StringKey::ButtonCancel),
+            SnapshotDialogAction::ContinueEditing,
+            Some(Keycode::Escape),
+        )];
+        let name = self.snapshot_names.get(slot).cloned().flatten();
+        let format = if let Some(name) = name {
+            buttons.push(("Load", SnapshotDialogAction::Load(slot), None));
+            buttons.push((
+                "Overwrite",
+                SnapshotDialogAction::Save(slot),
+                None,
+            ));
+            let title = if name.is_empty() {
+                format!("Snapshot slot {}", slot + 1)
+            } else {
+                format!("$*{}$*", Paragraph::escape(&name))
+            };
+            format!(
+                "{} is in use.\n\nLoading it will replace the current \
+                 circuit; you can undo this afterwards.",
+                title
+            )
+        } else {
+            buttons.push((
+                "Save",
+                SnapshotDialogAction::Save(slot),
+                Some(Keycode::Return),
+            ));
+            format!(
+                "Snapshot slot {} is empty.\n\nSave the current circuit \
+                 into it?",
+                slot + 1
+            )
+        };
+        self.snapshot_dialog =
+            Some(ButtonDialogBox::new(size, prefs, &format, &buttons));
+        ui.request_redraw();
+    }
+
+    fn show_snapshot_name_dialog(
+        &mut self,
+        ui: &mut Ui,
+        prefs: &Prefs,
+        slot: usize,
+    ) {
+        let size = RectSize::new(self.width as i32, self.height as i32);
+        let initial = match self.snapshot_names.get(slot) {
+            Some(Some(name)) if !name.is_empty() => name.clone(),
+            _ => format!("Snapshot {}", slot + 1),
+        };
+        let dialog = TextDialogBox::new(
+            size,
+            prefs,
+            "Choose a name for this snapshot:",
+            &initial,
+            SNAPSHOT_NAME_MAX_CHARS,
+        );
+        self.snapshot_name_dialog = Some((dialog, slot));
+        ui.request_redraw();
+    }
+
+    pub fn show_export_recording_success(
+        &mut self,
+        ui: &mut Ui,
+        prefs: &Prefs,
+        path: &str,
+    ) {
+        let format = format!(
+            "Saved input recording to:\n\n{}\n\nPlease attach this file \
+             when filing a bug about this puzzle.",
+            Paragraph::escape(path)
+        );
+        self.show_recording_dialog(ui, prefs, &format);
+    }
+
+    pub fn show_export_recording_error(
+        &mut self,
+        ui: &mut Ui,
+        prefs: &Prefs,
+        error: &str,
+    ) {
+        debug_warn!("Failed to export input recording: {}", error);
+        let format = format!(
+            "$R$*ERROR:$*$D Unable to export input recording!\n\n{}",
+            Paragraph::escape(error)
+        );
+        self.show_recording_dialog(ui, prefs, &format);
+    }
+
+    fn show_recording_dialog(
+        &mut self,
+        ui: &mut Ui,
+        prefs: &Prefs,
+        format: &str,
+    ) {
+        let size = RectSize::new(self.width as i32, self.height as i32);
+        let ok = prefs.strings().get(StringKey::ButtonOk);
+        let buttons = &[(ok, (), Some(Keycode::Return))];
+        self.recording_dialog =
+            Some(ButtonDialogBox::new(size, prefs, format, buttons));
+        ui.request_redraw();
+    }
+
+    pub fn show_reference_results(
+        &mut self,
+        ui: &mut Ui,
+        prefs: &Prefs,
+        result: Result<Vec<ReferenceMismatch>, Vec<String>>,
+    ) {
+        let format = match result {
+            Ok(ref mismatches) if mismatches.is_empty() => {
+                "$*Reference check passed$*\n\nThe circuit's output \
+                 matched the reference for every input tried."
+                    .to_string()
+            }
+            Ok(mismatches) => {
+                let mut format = format!(
+                    "$R$*Reference check failed$*$D\n\n\
+                     The circuit's output differed from the reference on \
+                     {} time step(s):",
+                    mismatches.len()
+                );
+                for mismatch in
+                    mismatches.iter().take(MAX_REFERENCE_MISMATCHES_LISTED)
+                {
+                    format.push_str(&format!(
+                        "\n  Time step {}: A={}, B={}; expected {}, got {}",
+                        mismatch.time_step,
+                        mismatch.inputs.0,
+                        mismatch.inputs.1,
+                        mismatch.expected,
+                        mismatch.actual
+                    ));
+                }
+                if mismatches.len() > MAX_REFERENCE_MISMATCHES_LISTED {
+                    format.push_str(&format!(
+                        "\n  ...and {} more",
+                        mismatches.len() - MAX_REFERENCE_MISMATCHES_LISTED
+                    ));
+                }
+                format
+            }
+            Err(errors) => {
+                debug_warn!("Reference check failed: {:?}", errors);
+                format!(
+                    "$R$*ERROR:$*$D Unable to check against reference!\n\n{}",
+                    Paragraph::escape(&errors.join("\n"))
+                )
+            }
+        };
+        let size = RectSize::new(self.width as i32, self.height as i32);
+        let ok = prefs.strings().get(StringKey::ButtonOk);
+        let buttons = &[(ok, (), Some(Keycode::Return))];
+        self.reference_dialog =
+            Some(ButtonDialogBox::new(size, prefs, &format, buttons));
+        ui.request_redraw();
+    }
+
+    pub fn show_snapshot_error(
+        &mut self,
+        ui: &mut Ui,
+        prefs: &Prefs,
+        error: &str,
+    ) {
+        debug_warn!("Snapshot failed: {}", error);
+        let size = RectSize::new(self.width as i32, self.height as i32);
+        let format = format!(
+            "$R$*ERROR:$*$D Unable to use snapshot!\n\n{}",
+            Paragraph::escape(error)
+        );
+        let buttons = &[(
+            "Continue editing",
+            SnapshotDialogAction::ContinueEditing,
+            Some(Keycode::Escape),
+        )];
+        self.snapshot_dialog =
+            Some(ButtonDialogBox::new(size, prefs, &format, buttons));
+        ui.request_redraw();
+    }
+
     pub fn show_victory_dialog(
         &mut self,
         ui: &mut Ui,
         prefs: &Prefs,
         puzzle: Puzzle,
-        area: i32,
+        stats: CircuitStats,
         score: u32,
         local_scores: &ScoreCurve,
     ) {
         // TODO: Play sound for victory.
         let window_size = RectSize::new(self.width as i32, self.height as i32);
-        let format = format!("Task \"{}\" completed!", puzzle.title());
+        let format = format!(
+            "$=Task \"{}\" completed!\n\
+             $=$*{}: {}$*\n\
+             $=$CChips:$D {}   $CWire length:$D {}   $CArea:$D {}",
+            puzzle.title(),
+            puzzle.score_units().label(),
+            score,
+            stats.num_chips,
+            stats.wire_length,
+            stats.area
+        );
         let buttons = &[
             (
                 "Continue editing",
                 VictoryDialogAction::ContinueEditing,
                 Some(Keycode::Escape),
             ),
-            ("Export image", VictoryDialogAction::ExportImage(score), None),
+            (
+                "Export image",
+                VictoryDialogAction::ExportImage(score, false),
+                None,
+            ),
+            (
+                "Export transparent",
+                VictoryDialogAction::ExportImage(score, true),
+                None,
+            ),
             (
                 "Back to menu",
                 VictoryDialogAction::BackToMenu,
@@ -836,7 +1570,7 @@ impl CircuitView {
             &format,
             puzzle,
             local_scores,
-            (area, score),
+            (stats.area, score),
             buttons,
         ));
         ui.request_redraw();
@@ -848,6 +1582,10 @@ fn is_valid_const(text: &str) -> bool {
     text.parse::<u8>().is_ok()
 }
 
+fn is_valid_snapshot_name(text: &str) -> bool {
+    !text.trim().is_empty()
+}
+
 fn is_valid_voltage(text: &str) -> bool {
     match text.parse::<f64>() {
         Ok(value) => value >= -1.0 && value <= 1.0,
@@ -855,6 +1593,70 @@ fn is_valid_voltage(text: &str) -> bool {
     }
 }
 
+/// Returns a dialog box warning about chips that couldn't be loaded into the
+/// grid, or `None` if there weren't any.
+fn rejected_chips_dialog(
+    window_size: RectSize<i32>,
+    prefs: &Prefs,
+    rejected: &[RejectedChip],
+) -> Option<ButtonDialogBox<()>> {
+    if rejected.is_empty() {
+        return None;
+    }
+    let mut format = "$O$*WARNING:$*$D Some chips in this circuit could not \
+                      be loaded, and have been removed:\n"
+        .to_string();
+    for chip in rejected.iter().take(MAX_REJECTED_CHIPS_LISTED) {
+        let coords = chip.coords();
+        let line = match chip {
+            RejectedChip::Unknown(_, name) => {
+                format!("{} (unknown chip type)", name)
+            }
+            RejectedChip::NotAllowed(_, ctype) => {
+                format!("{} (not allowed in this puzzle)", ctype)
+            }
+        };
+        format.push_str(&format!(
+            "\n  ({}, {}): {}",
+            coords.x,
+            coords.y,
+            Paragraph::escape(&line)
+        ));
+    }
+    if rejected.len() > MAX_REJECTED_CHIPS_LISTED {
+        format.push_str(&format!(
+            "\n  ...and {} more",
+            rejected.len() - MAX_REJECTED_CHIPS_LISTED
+        ));
+    }
+    let ok = prefs.strings().get(StringKey::ButtonOk);
+    let buttons = &[(ok, (), Some(Keycode::Return))];
+    Some(ButtonDialogBox::new(window_size, prefs, &format, buttons))
+}
+
+/// Captures the circuit and the inputs recorded so far by the current
+/// evaluation run.  The run need not have ended in victory, so that the
+/// recording can also be used to reproduce bugs.
+fn current_run_data(
+    grid: &EditGrid,
+    prefs: &Prefs,
+    score: u32,
+) -> SolutionData {
+    let top_left = grid.bounds().top_left();
+    let (time_steps, inputs) = match grid.eval() {
+        Some(eval) => (eval.time_step(), eval.recorded_inputs(top_left)),
+        None => (0, None),
+    };
+    SolutionData {
+        install_id: prefs.install_id(),
+        puzzle: grid.puzzle(),
+        score,
+        time_steps,
+        circuit: grid.to_circuit_data(),
+        inputs,
+    }
+}
+
 fn change_button_chip_hotkey(
     ui: &mut Ui,
     grid: &mut EditGrid,
@@ -907,11 +1709,8 @@ fn change_comment_chip_value(
         grid.chip_at(coords)
     {
         let mut new_bytes = [b' '; MAX_COMMENT_CHARS];
-        for (index, byte) in new_string
-            .chars()
-            .map(|chr| chr as u8)
-            .take(MAX_COMMENT_CHARS)
-            .enumerate()
+        for (index, byte) in
+            text_to_glyphs(new_string).take(MAX_COMMENT_CHARS).enumerate()
         {
             new_bytes[index] = byte;
         }
@@ -952,6 +1751,26 @@ fn change_const_chip_value(
     }
 }
 
+fn change_wire_label(
+    ui: &mut Ui,
+    grid: &mut EditGrid,
+    loc: (Coords, Direction),
+    new_label: &str,
+) {
+    let new_label: String =
+        new_label.trim().chars().take(MAX_WIRE_LABEL_CHARS).collect();
+    let old_label = grid.wire_label_at(loc.0, loc.1).unwrap_or("").to_string();
+    if new_label == old_label {
+        return;
+    }
+    let changes = vec![GridChange::SetWireLabel(loc, old_label, new_label)];
+    if grid.try_mutate(changes) {
+        ui.request_redraw();
+    } else {
+        debug_warn!("change_wire_label mutation failed");
+    }
+}
+
 fn change_vref_chip_value(
     ui: &mut Ui,
     grid: &mut EditGrid,
@@ -199,6 +199,22 @@ impl PartsTray {
         }
     }
 
+    /// Returns the chip type in this tray whose name matches the given string
+    /// (ignoring case), if any.  For chip types with a parameter (such as
+    /// `Const`), the name alone matches the variant shown in the tray.
+    pub fn find_part(&self, name: &str) -> Option<ChipType> {
+        let name = name.trim();
+        if name.is_empty() {
+            return None;
+        }
+        self.parts.iter().map(|&(_, ctype)| ctype).find(|ctype| {
+            let full_name = ctype.to_string();
+            let base_name = full_name.split('(').next().unwrap_or("");
+            full_name.eq_ignore_ascii_case(name)
+                || base_name.eq_ignore_ascii_case(name)
+        })
+    }
+
     fn slid_rect(&self) -> Rect<i32> {
         self.rect - vec2(self.slide.distance(), 0)
     }
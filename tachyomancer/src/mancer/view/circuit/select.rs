@@ -24,8 +24,8 @@ use crate::mancer::gui::{Clipboard, Resources, Sound, Ui};
 use cgmath::{vec2, Matrix4, MetricSpace, Point2, Vector2};
 use std::collections::{HashMap, HashSet};
 use tachy::geom::{
-    AsFloat, AsInt, Color3, Color4, Coords, CoordsDelta, CoordsRect,
-    CoordsSize, Direction, MatrixExt, Orientation, Rect,
+    AsFloat, AsInt, Color3, Color4, Coords, CoordsDelta, CoordsExt,
+    CoordsRect, CoordsSize, Direction, MatrixExt, Orientation, Rect,
 };
 use tachy::save::{ChipSet, ChipType, CircuitData, WireShape, WireSize};
 use tachy::state::{ChipExt, EditGrid, GridChange, WireColor, WireId};
@@ -179,11 +179,11 @@ impl SelectionDrag {
                 WireModel::draw_fragment(
                     resources,
                     &grid_matrix,
-                    coords,
-                    dir,
+                    (coords, dir),
                     shape,
                     color,
                     size,
+                    false,
                     hilight,
                 );
             }
@@ -388,36 +388,61 @@ fn draw_selection_box(
 
 //===========================================================================//
 
-pub fn flip_horz(grid: &mut EditGrid, selected_rect: CoordsRect) {
-    reorient(grid, selected_rect, Orientation::default().flip_horz());
+pub fn flip_horz(
+    grid: &mut EditGrid,
+    selected_rect: CoordsRect,
+) -> Option<CoordsRect> {
+    reorient(grid, selected_rect, Orientation::default().flip_horz())
 }
 
-pub fn flip_vert(grid: &mut EditGrid, selected_rect: CoordsRect) {
-    reorient(grid, selected_rect, Orientation::default().flip_vert());
+pub fn flip_vert(
+    grid: &mut EditGrid,
+    selected_rect: CoordsRect,
+) -> Option<CoordsRect> {
+    reorient(grid, selected_rect, Orientation::default().flip_vert())
 }
 
-pub fn rotate_ccw(grid: &mut EditGrid, selected_rect: CoordsRect) {
-    reorient(grid, selected_rect, Orientation::default().rotate_ccw());
+pub fn rotate_ccw(
+    grid: &mut EditGrid,
+    selected_rect: CoordsRect,
+) -> Option<CoordsRect> {
+    reorient(grid, selected_rect, Orientation::default().rotate_ccw())
 }
 
-pub fn rotate_cw(grid: &mut EditGrid, selected_rect: CoordsRect) {
-    reorient(grid, selected_rect, Orientation::default().rotate_cw());
+pub fn rotate_cw(
+    grid: &mut EditGrid,
+    selected_rect: CoordsRect,
+) -> Option<CoordsRect> {
+    reorient(grid, selected_rect, Orientation::default().rotate_cw())
 }
 
+/// Reorients the selected part of the grid in place (keeping the top-left
+/// corner fixed) as a single undoable change.  Returns the new selected rect,
+/// or `None` (leaving the grid unchanged) if the reoriented selection would
+/// leave the grid bounds or collide with something outside the selection.
 fn reorient(
     grid: &mut EditGrid,
     selected_rect: CoordsRect,
     orient: Orientation,
-) {
+) -> Option<CoordsRect> {
+    let new_rect = Rect::with_size(
+        selected_rect.top_left(),
+        orient * selected_rect.size(),
+    );
+    if !grid.bounds().contains_rect(new_rect) {
+        return None;
+    }
     let mut selection = cut_provisionally(grid, selected_rect);
     selection.reorient(orient);
     let changes =
         changes_for_paste(grid, &selection, selected_rect.top_left());
     if grid.try_mutate_provisionally(changes) {
         grid.commit_provisional_changes();
+        Some(new_rect)
     } else {
         debug_warn!("reorient paste mutation failed");
         grid.roll_back_provisional_changes();
+        None
     }
 }
 
@@ -451,14 +476,22 @@ pub fn delete(grid: &mut EditGrid, selected_rect: CoordsRect) {
     }
 }
 
-pub fn delete_wire(grid: &mut EditGrid, wire_id: WireId) {
+/// Removes every fragment of the given wire as a single undoable change.
+/// Returns false if the grid can't currently be edited (e.g. because it is
+/// being evaluated).
+pub fn delete_wire(grid: &mut EditGrid, wire_id: WireId) -> bool {
+    if grid.eval().is_some() {
+        return false;
+    }
     let old_wires: HashMap<(Coords, Direction), WireShape> =
         grid.wire_fragments_for_wire_id(wire_id).collect();
     let new_wires = HashMap::<(Coords, Direction), WireShape>::new();
     let changes = vec![GridChange::ReplaceWires(old_wires, new_wires)];
     if !grid.try_mutate(changes) {
         debug_warn!("delete_wire mutation failed");
+        return false;
     }
+    true
 }
 
 pub fn cut_provisionally(
@@ -496,17 +529,17 @@ fn changes_for_cut(
                 }
             }
         }
-        for dir in Direction::all() {
+        for (dir, coords2) in coords.neighbors() {
             if let Some(shape) = grid.wire_shape_at(coords, dir) {
-                if selected_rect.contains_point(coords + dir) {
+                if selected_rect.contains_point(coords2) {
                     selection_wires.insert((coords, dir), shape);
                     old_wires.insert((coords, dir), shape);
-                } else if grid.wire_shape_at(coords + dir, -dir)
+                } else if grid.wire_shape_at(coords2, -dir)
                     == Some(WireShape::Stub)
                 {
                     selection_wires.insert((coords, dir), shape);
                     old_wires.insert((coords, dir), shape);
-                    old_wires.insert((coords + dir, -dir), WireShape::Stub);
+                    old_wires.insert((coords2, -dir), WireShape::Stub);
                 } else if shape != WireShape::Stub {
                     selection_wires.insert((coords, dir), shape);
                     old_wires.insert((coords, dir), shape);
@@ -635,7 +668,10 @@ fn changes_for_paste(
 
 #[cfg(test)]
 mod tests {
-    use super::{changes_for_cut, changes_for_paste, Selection};
+    use super::{
+        changes_for_cut, changes_for_paste, delete_wire, flip_horz, rotate_cw,
+        Selection,
+    };
     use cgmath::vec2;
     use std::collections::HashMap;
     use tachy::geom::{
@@ -644,6 +680,45 @@ mod tests {
     use tachy::save::{ChipType, CircuitData, Puzzle, PuzzleSet, WireShape};
     use tachy::state::EditGrid;
 
+    #[test]
+    fn delete_wire_removes_whole_net_in_one_undo_step() {
+        let mut data = CircuitData::new(10, 10);
+        data.wires.insert(
+            CoordsDelta::new(3, 5),
+            Direction::East,
+            WireShape::Stub,
+        );
+        data.wires.insert(
+            CoordsDelta::new(4, 5),
+            Direction::West,
+            WireShape::TurnLeft,
+        );
+        data.wires.insert(
+            CoordsDelta::new(4, 5),
+            Direction::North,
+            WireShape::TurnRight,
+        );
+        data.wires.insert(
+            CoordsDelta::new(4, 4),
+            Direction::South,
+            WireShape::Stub,
+        );
+        let mut grid = EditGrid::from_circuit_data(
+            Puzzle::TutorialOr,
+            &PuzzleSet::new(),
+            &data,
+        );
+        let wire = grid.wire_id_at(Coords::new(4, 4), Direction::South);
+        assert!(delete_wire(&mut grid, wire.unwrap()));
+        assert_eq!(grid.wire_fragments().count(), 0);
+        assert!(grid.undo());
+        assert_eq!(grid.wire_fragments().count(), 4);
+        assert_eq!(
+            grid.wire_shape_at(Coords::new(4, 5), Direction::West),
+            Some(WireShape::TurnLeft)
+        );
+    }
+
     #[test]
     fn cut_removes_edge_stub() {
         let mut data = CircuitData::new(10, 10);
@@ -825,6 +900,127 @@ mod tests {
             None
         );
     }
+
+    fn grid_with_counter_and_not(
+        counter_delta: CoordsDelta,
+        not_delta: CoordsDelta,
+    ) -> EditGrid {
+        let mut data = CircuitData::new(10, 10);
+        data.chips.insert(
+            counter_delta,
+            ChipType::Counter,
+            Orientation::default(),
+        );
+        data.chips.insert(not_delta, ChipType::Not, Orientation::default());
+        EditGrid::from_circuit_data(
+            Puzzle::SandboxEvent,
+            &PuzzleSet::with_everything_solved(),
+            &data,
+        )
+    }
+
+    #[test]
+    fn rotate_multi_chip_selection() {
+        let mut grid = grid_with_counter_and_not(
+            CoordsDelta::new(2, 2),
+            CoordsDelta::new(2, 3),
+        );
+        let rect = CoordsRect::new(2, 2, 2, 2);
+        assert_eq!(rotate_cw(&mut grid, rect), Some(rect));
+        assert_eq!(
+            grid.chip_at(Coords::new(3, 3)),
+            Some((
+                Coords::new(3, 2),
+                ChipType::Counter,
+                Orientation::default().rotate_cw()
+            ))
+        );
+        assert_eq!(
+            grid.chip_at(Coords::new(2, 2)),
+            Some((
+                Coords::new(2, 2),
+                ChipType::Not,
+                Orientation::default().rotate_cw()
+            ))
+        );
+        assert_eq!(grid.chip_at(Coords::new(2, 3)), None);
+        assert!(grid.undo());
+        assert_eq!(
+            grid.chip_at(Coords::new(3, 2)),
+            Some((
+                Coords::new(2, 2),
+                ChipType::Counter,
+                Orientation::default()
+            ))
+        );
+    }
+
+    #[test]
+    fn mirror_multi_chip_selection() {
+        let mut grid = grid_with_counter_and_not(
+            CoordsDelta::new(2, 2),
+            CoordsDelta::new(4, 2),
+        );
+        let rect = CoordsRect::new(2, 2, 3, 1);
+        assert_eq!(flip_horz(&mut grid, rect), Some(rect));
+        assert_eq!(
+            grid.chip_at(Coords::new(4, 2)),
+            Some((
+                Coords::new(3, 2),
+                ChipType::Counter,
+                Orientation::default().flip_horz()
+            ))
+        );
+        assert_eq!(
+            grid.chip_at(Coords::new(2, 2)),
+            Some((
+                Coords::new(2, 2),
+                ChipType::Not,
+                Orientation::default().flip_horz()
+            ))
+        );
+    }
+
+    #[test]
+    fn rotate_selection_rejects_collision() {
+        let mut grid = grid_with_counter_and_not(
+            CoordsDelta::new(2, 2),
+            CoordsDelta::new(2, 3),
+        );
+        let rect = CoordsRect::new(2, 2, 2, 1);
+        assert_eq!(rotate_cw(&mut grid, rect), None);
+        assert_eq!(
+            grid.chip_at(Coords::new(3, 2)),
+            Some((
+                Coords::new(2, 2),
+                ChipType::Counter,
+                Orientation::default()
+            ))
+        );
+        assert_eq!(
+            grid.chip_at(Coords::new(2, 3)),
+            Some((Coords::new(2, 3), ChipType::Not, Orientation::default()))
+        );
+    }
+
+    #[test]
+    fn rotate_selection_rejects_leaving_bounds() {
+        let mut grid = grid_with_counter_and_not(
+            CoordsDelta::new(2, 9),
+            CoordsDelta::new(5, 5),
+        );
+        assert_eq!(grid.bounds().bottom(), 10);
+        let rect = CoordsRect::new(2, 9, 2, 1);
+        assert_eq!(rotate_cw(&mut grid, rect), None);
+        assert_eq!(
+            grid.chip_at(Coords::new(3, 9)),
+            Some((
+                Coords::new(2, 9),
+                ChipType::Counter,
+                Orientation::default()
+            ))
+        );
+    }
 }
 
 //===========================================================================//
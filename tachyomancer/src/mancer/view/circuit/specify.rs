@@ -17,6 +17,7 @@
 // | with Tachyomancer.  If not, see <http://www.gnu.org/licenses/>.          |
 // +--------------------------------------------------------------------------+
 
+use super::super::button::TextButton;
 use super::super::paragraph::Paragraph;
 use super::super::tooltip::TooltipSink;
 use super::tray::TraySlide;
@@ -24,9 +25,10 @@ use crate::mancer::font::Align;
 use crate::mancer::gui::{Cursor, Event, Resources, Ui};
 use crate::mancer::save::Prefs;
 use crate::mancer::shader::UiShader;
-use cgmath::{vec2, Deg, Matrix4};
+use cgmath::{vec2, Deg, Matrix4, Point2};
 use tachy::geom::{AsFloat, Color4, MatrixExt, Rect, RectSize};
 use tachy::save::Puzzle;
+use tachy::state::PuzzleExt;
 
 //===========================================================================//
 
@@ -35,6 +37,12 @@ const PARAGRAPH_LINE_HEIGHT: f32 = 20.0;
 const PARAGRAPH_MAX_WIDTH: f32 = 320.0;
 const PARAGRAPH_MIN_HEIGHT: i32 = 150;
 
+const HINT_BUTTON_LABEL: &str = "Show hint";
+
+const PORT_BUTTON_COLUMNS: i32 = 2;
+const PORT_BUTTON_HEIGHT: i32 = 26;
+const PORT_BUTTON_SPACING: i32 = 6;
+
 const TRAY_EXTRA_HIDDEN_HEIGHT: i32 = 8;
 const TRAY_FLIP_HORZ: bool = true;
 const TRAY_INNER_MARGIN: i32 = 20;
@@ -44,9 +52,21 @@ const TRAY_TAB_TEXT: &str = "SPEC";
 
 //===========================================================================//
 
+/// Asks the circuit view to center the camera on the given port, identified
+/// by its interface index and its port index within that interface.
+pub enum SpecificationAction {
+    CenterOnPort(usize, usize),
+}
+
+//===========================================================================//
+
 pub struct SpecificationTray {
     rect: Rect<i32>,
     paragraph: Paragraph,
+    port_buttons: Vec<TextButton<(usize, usize)>>,
+    hint_button: Option<TextButton<()>>,
+    hint_paragraph: Option<(Paragraph, i32)>,
+    hint_revealed: bool,
     slide: TraySlide,
 }
 
@@ -54,6 +74,7 @@ impl SpecificationTray {
     pub fn new(
         window_size: RectSize<i32>,
         current_puzzle: Puzzle,
+        puzzle_solved: bool,
         prefs: &Prefs,
     ) -> SpecificationTray {
         let paragraph = Paragraph::compile(
@@ -61,26 +82,80 @@ impl SpecificationTray {
             PARAGRAPH_LINE_HEIGHT,
             PARAGRAPH_MAX_WIDTH,
             prefs,
-            current_puzzle.instructions(),
+            &specification_format(current_puzzle),
         );
         let tray_width =
             (paragraph.width().ceil() as i32) + 2 * TRAY_INNER_MARGIN;
-        let tray_height = (paragraph.height().ceil() as i32)
-            .max(PARAGRAPH_MIN_HEIGHT)
-            + 2 * TRAY_INNER_MARGIN;
-        let rect = Rect::new(
-            window_size.width - tray_width,
-            -TRAY_EXTRA_HIDDEN_HEIGHT,
-            tray_width,
-            tray_height,
+        let paragraph_height =
+            (paragraph.height().ceil() as i32).max(PARAGRAPH_MIN_HEIGHT);
+        let left = window_size.width - tray_width;
+        let top = -TRAY_EXTRA_HIDDEN_HEIGHT;
+        let port_buttons = port_buttons(
+            current_puzzle,
+            Point2::new(
+                left + TRAY_INNER_MARGIN,
+                top + TRAY_INNER_MARGIN + paragraph_height,
+            ),
+            tray_width - 2 * TRAY_INNER_MARGIN,
         );
+        let mut content_bottom = port_buttons
+            .last()
+            .map(|button| button.rect().bottom())
+            .unwrap_or(top + TRAY_INNER_MARGIN + paragraph_height);
+        // Hints are only offered for puzzles that the player hasn't solved
+        // yet.
+        let hint = if puzzle_solved { None } else { current_puzzle.hint() };
+        let hint_button = hint.map(|_| {
+            let rect = Rect::new(
+                left + TRAY_INNER_MARGIN,
+                content_bottom + PORT_BUTTON_SPACING,
+                tray_width - 2 * TRAY_INNER_MARGIN,
+                PORT_BUTTON_HEIGHT,
+            );
+            TextButton::new(rect, HINT_BUTTON_LABEL, ())
+        });
+        let hint_paragraph = hint.map(|format| {
+            let paragraph = Paragraph::compile(
+                PARAGRAPH_FONT_SIZE,
+                PARAGRAPH_LINE_HEIGHT,
+                (tray_width - 2 * TRAY_INNER_MARGIN) as f32,
+                prefs,
+                &format!("$*Hint:$* {}", format),
+            );
+            (paragraph, content_bottom + PORT_BUTTON_SPACING)
+        });
+        if let Some(ref button) = hint_button {
+            content_bottom = button.rect().bottom();
+        }
+        let tray_height = content_bottom + TRAY_INNER_MARGIN - top;
+        let rect = Rect::new(left, top, tray_width, tray_height);
         SpecificationTray {
             rect,
             paragraph,
+            port_buttons,
+            hint_button,
+            hint_paragraph,
+            hint_revealed: false,
             slide: TraySlide::new(rect.width),
         }
     }
 
+    /// Replaces the hint button with the hint itself, growing the tray to
+    /// fit it.
+    fn reveal_hint(&mut self, ui: &mut Ui) {
+        if let Some((ref paragraph, hint_top)) = self.hint_paragraph {
+            let hint_bottom = hint_top + (paragraph.height().ceil() as i32);
+            self.rect.height = hint_bottom + TRAY_INNER_MARGIN - self.rect.y;
+            self.hint_button = None;
+            self.hint_revealed = true;
+            ui.request_redraw();
+        }
+    }
+
+    pub fn toggle(&mut self) {
+        self.slide.toggle();
+    }
+
     fn slid_rect(&self) -> Rect<i32> {
         self.rect + vec2(self.slide.distance(), 0)
     }
@@ -125,6 +200,21 @@ impl SpecificationTray {
                 rect.y + TRAY_INNER_MARGIN as f32,
             ),
         );
+        for button in self.port_buttons.iter() {
+            button.draw(resources, &matrix, true);
+        }
+        if let Some(ref button) = self.hint_button {
+            button.draw(resources, &matrix, true);
+        }
+        if self.hint_revealed {
+            if let Some((ref paragraph, hint_top)) = self.hint_paragraph {
+                paragraph.draw(
+                    resources,
+                    &matrix,
+                    (rect.x + TRAY_INNER_MARGIN as f32, hint_top as f32),
+                );
+            }
+        }
     }
 
     pub fn on_event(
@@ -132,7 +222,23 @@ impl SpecificationTray {
         event: &Event,
         ui: &mut Ui,
         tooltip: &mut dyn TooltipSink<()>,
-    ) -> bool {
+    ) -> Option<Option<SpecificationAction>> {
+        let rel_event =
+            event.relative_to(Point2::new(-self.slide.distance(), 0));
+        for button in self.port_buttons.iter_mut() {
+            if let Some((iface, port)) = button.on_event(&rel_event, ui, true)
+            {
+                return Some(Some(SpecificationAction::CenterOnPort(
+                    iface, port,
+                )));
+            }
+        }
+        if let Some(ref mut button) = self.hint_button {
+            if button.on_event(&rel_event, ui, true).is_some() {
+                self.reveal_hint(ui);
+                return Some(None);
+            }
+        }
         match event {
             Event::ClockTick(tick) => self.slide.on_clock_tick(tick, ui),
             Event::MouseDown(mouse) => {
@@ -144,9 +250,9 @@ impl SpecificationTray {
                 );
                 if tab_rect.contains_point(rel_mouse_pt.as_f32()) {
                     self.slide.toggle();
-                    return true;
+                    return Some(None);
                 } else if self.rect.contains_point(rel_mouse_pt) {
-                    return true;
+                    return Some(None);
                 }
             }
             Event::MouseMove(mouse) | Event::MouseUp(mouse) => {
@@ -166,17 +272,69 @@ impl SpecificationTray {
             Event::Multitouch(touch)
                 if self.slid_rect().contains_point(touch.pt) =>
             {
-                return true;
+                return Some(None);
             }
             Event::Scroll(scroll)
                 if self.slid_rect().contains_point(scroll.pt) =>
             {
-                return true;
+                return Some(None);
             }
             _ => {}
         }
-        return false;
+        return None;
+    }
+}
+
+/// Returns the paragraph format for the tray: the puzzle's instructions,
+/// followed by a reference listing of each of its interfaces' ports.
+fn specification_format(puzzle: Puzzle) -> String {
+    let mut format = puzzle.instructions().to_string();
+    for interface in puzzle.interfaces() {
+        format.push_str("\n\n");
+        format.push_str(&interface.spec_format());
+    }
+    format
+}
+
+/// Returns one button per interface port, laid out in rows below `top_left`,
+/// for jumping the camera to that port on the board.
+fn port_buttons(
+    puzzle: Puzzle,
+    top_left: Point2<i32>,
+    width: i32,
+) -> Vec<TextButton<(usize, usize)>> {
+    let button_width = (width
+        - (PORT_BUTTON_COLUMNS - 1) * PORT_BUTTON_SPACING)
+        / PORT_BUTTON_COLUMNS;
+    let mut buttons = Vec::new();
+    for (iface_index, interface) in puzzle.interfaces().iter().enumerate() {
+        let num_ports = interface.port_names().count();
+        for (port_index, port_name) in interface.port_names().enumerate() {
+            let label = if num_ports == 1 {
+                interface.label().to_string()
+            } else {
+                format!("{} {}", interface.label(), port_name)
+            };
+            let index = buttons.len() as i32;
+            let rect = Rect::new(
+                top_left.x
+                    + (index % PORT_BUTTON_COLUMNS)
+                        * (button_width + PORT_BUTTON_SPACING),
+                top_left.y
+                    + PORT_BUTTON_SPACING
+                    + (index / PORT_BUTTON_COLUMNS)
+                        * (PORT_BUTTON_HEIGHT + PORT_BUTTON_SPACING),
+                button_width,
+                PORT_BUTTON_HEIGHT,
+            );
+            buttons.push(TextButton::new(
+                rect,
+                &label,
+                (iface_index, port_index),
+            ));
+        }
     }
+    buttons
 }
 
 //===========================================================================//
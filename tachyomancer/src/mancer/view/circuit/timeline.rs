@@ -0,0 +1,209 @@
+// +--------------------------------------------------------------------------+
+// | Copyright 2018 Matthew D. Steele <mdsteele@alum.mit.edu>                 |
+// |                                                                          |
+// | This file is part of Tachyomancer.                                       |
+// |                                                                          |
+// | Tachyomancer is free software: you can redistribute it and/or modify it  |
+// | under the terms of the GNU General Public License as published by the    |
+// | Free Software Foundation, either version 3 of the License, or (at your   |
+// | option) any later version.                                               |
+// |                                                                          |
+// | Tachyomancer is distributed in the hope that it will be useful, but      |
+// | WITHOUT ANY WARRANTY; without even the implied warranty of               |
+// | MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU        |
+// | General Public License for details.                                      |
+// |                                                                          |
+// | You should have received a copy of the GNU General Public License along  |
+// | with Tachyomancer.  If not, see <http://www.gnu.org/licenses/>.          |
+// +--------------------------------------------------------------------------+
+
+use super::super::button::{Slider, SliderAction};
+use crate::mancer::gui::{Event, Resources, Ui};
+use cgmath::Matrix4;
+use std::collections::HashSet;
+use tachy::geom::{AsFloat, Color4, Rect, RectSize};
+use tachy::save::InputsData;
+use tachy::state::{replay_inputs_to_time_step, EditGrid, EvalResult};
+
+//===========================================================================//
+
+const SCRUBBER_HEIGHT: i32 = 24;
+const SCRUBBER_LABEL_WIDTH: i32 = 60;
+const SCRUBBER_VALUE_WIDTH: i32 = 50;
+const SCRUBBER_WIDTH: i32 = 360;
+
+const TRAY_INNER_MARGIN: i32 = 12;
+
+//===========================================================================//
+
+/// A slider shown while the circuit is being evaluated, which lets the
+/// player seek back (and forward again) to any time step that the current
+/// run has already reached.  Seeking restarts the evaluation and replays the
+/// run's recorded button presses up to the chosen time step.
+pub struct TimeScrubber {
+    rect: Rect<i32>,
+    slider: Slider,
+    end_time_step: u32,
+    inputs: Option<InputsData>,
+    last_position: Option<(u32, u32, usize)>,
+    drag_time_step: Option<u32>,
+}
+
+impl TimeScrubber {
+    pub fn new(window_size: RectSize<i32>) -> TimeScrubber {
+        let rect = Rect::new(
+            (window_size.width - SCRUBBER_WIDTH) / 2,
+            window_size.height - (SCRUBBER_HEIGHT + 2 * TRAY_INNER_MARGIN),
+            SCRUBBER_WIDTH,
+            SCRUBBER_HEIGHT + 2 * TRAY_INNER_MARGIN,
+        );
+        let slider = Slider::with_range(
+            Rect::new(
+                rect.x + TRAY_INNER_MARGIN + SCRUBBER_LABEL_WIDTH,
+                rect.y + TRAY_INNER_MARGIN,
+                rect.width
+                    - (2 * TRAY_INNER_MARGIN
+                        + SCRUBBER_LABEL_WIDTH
+                        + SCRUBBER_VALUE_WIDTH),
+                SCRUBBER_HEIGHT,
+            ),
+            0,
+            1,
+            "Time".to_string(),
+            time_step_label,
+        );
+        TimeScrubber {
+            rect,
+            slider,
+            end_time_step: 0,
+            inputs: None,
+            last_position: None,
+            drag_time_step: None,
+        }
+    }
+
+    fn is_visible(&self) -> bool {
+        self.last_position.is_some() && self.end_time_step > 0
+    }
+
+    pub fn draw(&self, resources: &Resources, matrix: &Matrix4<f32>) {
+        if !self.is_visible() {
+            return;
+        }
+        resources.shaders().ui().draw_box2(
+            matrix,
+            &self.rect.as_f32(),
+            &Color4::ORANGE2,
+            &Color4::CYAN2,
+            &Color4::PURPLE0_TRANSLUCENT,
+        );
+        self.slider.draw(resources, matrix);
+    }
+
+    /// Returns `Some(Some(result))` if the player seeked to a new time step
+    /// (with `result` being the result of the last cycle replayed),
+    /// `Some(None)` if the event was otherwise absorbed by the scrubber, or
+    /// `None` if the event should be passed on.
+    pub fn on_event(
+        &mut self,
+        event: &Event,
+        ui: &mut Ui,
+        grid: &mut EditGrid,
+    ) -> Option<Option<EvalResult>> {
+        self.update_extent(grid);
+        if !self.is_visible() {
+            return None;
+        }
+        match self.slider.on_event(event, ui) {
+            Some(SliderAction::Update(value)) => {
+                self.drag_time_step = Some(value as u32);
+                return Some(None);
+            }
+            Some(SliderAction::Release) => {
+                if let Some(time_step) = self.drag_time_step.take() {
+                    let result = replay_inputs_to_time_step(
+                        grid,
+                        self.inputs.as_ref(),
+                        time_step,
+                    );
+                    self.update_extent(grid);
+                    ui.request_redraw();
+                    return Some(result);
+                }
+                return Some(None);
+            }
+            None => {}
+        }
+        if self.slider.is_dragging() {
+            match event {
+                Event::MouseDown(_) | Event::MouseMove(_) => {
+                    return Some(None);
+                }
+                _ => {}
+            }
+        }
+        match event {
+            Event::MouseDown(mouse) if self.rect.contains_point(mouse.pt) => {
+                Some(None)
+            }
+            Event::Multitouch(touch) if self.rect.contains_point(touch.pt) => {
+                Some(None)
+            }
+            Event::Scroll(scroll) if self.rect.contains_point(scroll.pt) => {
+                Some(None)
+            }
+            _ => None,
+        }
+    }
+
+    /// Keeps track of the furthest time step reached by the current run (and
+    /// the button presses made along the way), so that the player can seek
+    /// back and then forward again.  The recorded run is replaced once the
+    /// evaluation moves past its end, or once the player makes a button press
+    /// that the recorded run didn't have.
+    fn update_extent(&mut self, grid: &EditGrid) {
+        let eval = match grid.eval() {
+            Some(eval) => eval,
+            None => {
+                self.end_time_step = 0;
+                self.inputs = None;
+                self.last_position = None;
+                self.drag_time_step = None;
+                return;
+            }
+        };
+        let position = (eval.time_step(), eval.cycle(), eval.subcycle());
+        if self.last_position == Some(position) {
+            return;
+        }
+        self.last_position = Some(position);
+        let recorded = eval.recorded_inputs(grid.bounds().top_left());
+        if position.0 >= self.end_time_step
+            || !is_subset(recorded.as_ref(), self.inputs.as_ref())
+        {
+            self.end_time_step = position.0;
+            self.inputs = recorded;
+        }
+        if !self.slider.is_dragging() {
+            self.slider.set_range(
+                position.0 as i32,
+                self.end_time_step.max(1) as i32,
+            );
+        }
+    }
+}
+
+fn is_subset(inputs: Option<&InputsData>, of: Option<&InputsData>) -> bool {
+    let inputs = match inputs {
+        Some(inputs) => inputs,
+        None => return true,
+    };
+    let of: HashSet<_> = of.iter().flat_map(|of| of.iter()).collect();
+    inputs.iter().all(|entry| of.contains(&entry))
+}
+
+fn time_step_label(value: i32) -> String {
+    format!("{}", value)
+}
+
+//===========================================================================//
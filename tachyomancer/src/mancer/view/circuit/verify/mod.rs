@@ -74,6 +74,9 @@ pub struct VerificationTray {
 }
 
 impl VerificationTray {
+    // Every puzzle must be listed explicitly here (even if it just uses a
+    // `NullVerifyView` for now), so that new puzzles aren't forgotten.
+    #[deny(clippy::wildcard_enum_match_arm)]
     pub fn new(
         window_size: RectSize<i32>,
         current_puzzle: Puzzle,
@@ -248,6 +251,12 @@ impl VerificationTray {
         VerificationTray { rect, subview, slide: TraySlide::new(rect.width) }
     }
 
+    pub fn toggle(&mut self) {
+        if !self.rect.is_empty() {
+            self.slide.toggle();
+        }
+    }
+
     fn slid_rect(&self) -> Rect<i32> {
         self.rect + vec2(self.slide.distance(), 0)
     }
@@ -25,8 +25,8 @@ use std::u32;
 use tachy::geom::{AsFloat, Color3, Fixed, Rect, RectSize};
 use tachy::save::WireSize;
 use tachy::state::{
-    CircuitEval, EvalError, FabricationData, FabricationEval, InterfacePort,
-    PortColor,
+    CircuitEval, EvalError, FabricationData, FabricationEval,
+    FabricationMismatch, InterfacePort, PortColor,
 };
 
 //===========================================================================//
@@ -98,13 +98,25 @@ impl PuzzleVerifyView for FabricationVerifyView {
         matrix: &Matrix4<f32>,
         circuit_eval: Option<&CircuitEval>,
     ) {
-        let (time_step, values, errors) = if let Some(eval) = circuit_eval {
-            let puzzle = eval.puzzle_eval::<FabricationEval>();
-            (Some(eval.time_step()), puzzle.table_values(), eval.errors())
-        } else {
-            (None, self.data.expected_table_values(), &[] as &[EvalError])
-        };
-        self.table.draw(resources, matrix, time_step, values, errors);
+        let (time_step, values, errors, mismatch) =
+            if let Some(eval) = circuit_eval {
+                let puzzle = eval.puzzle_eval::<FabricationEval>();
+                (
+                    Some(eval.time_step()),
+                    puzzle.table_values(),
+                    eval.errors(),
+                    puzzle.first_mismatch(),
+                )
+            } else {
+                (
+                    None,
+                    self.data.expected_table_values(),
+                    &[] as &[EvalError],
+                    None,
+                )
+            };
+        self.table
+            .draw(resources, matrix, time_step, values, errors, mismatch);
     }
 }
 
@@ -151,6 +163,7 @@ impl FabricationTable {
         time_step: Option<u32>,
         values: &[u32],
         errors: &[EvalError],
+        mismatch: Option<&FabricationMismatch>,
     ) {
         let rect = self.rect.as_f32();
         let column_width = rect.width / (self.column_ports.len() as f32);
@@ -176,8 +189,13 @@ impl FabricationTable {
         }
 
         for row in 0..self.num_rows {
+            let is_mismatch_row = mismatch
+                .map(|mismatch| mismatch.time_step as usize)
+                == Some(row);
             let color = if Some(row as u32) == time_step {
                 Color3::new(0.7, 0.7, 1.0)
+            } else if is_mismatch_row {
+                Color3::new(0.9, 0.2, 0.2)
             } else if error_rows.contains(&row) {
                 Color3::new(0.7, 0.3, 0.3)
             } else {
@@ -187,6 +205,19 @@ impl FabricationTable {
             let rect = Rect::new(rect.x, row_top, rect.width, row_height);
             resources.shaders().solid().fill_rect(matrix, color, rect);
             let row_center = row_top + 0.5 * row_height;
+            if let Some(mismatch) = mismatch.filter(|_| is_mismatch_row) {
+                let cell_rect = Rect::new(
+                    rect.x + (mismatch.column as f32) * column_width,
+                    row_top,
+                    column_width,
+                    row_height,
+                );
+                resources.shaders().solid().fill_rect(
+                    matrix,
+                    Color3::new(1.0, 0.6, 0.1),
+                    cell_rect,
+                );
+            }
             for col in 0..num_columns {
                 let value = values[row * num_columns + col];
                 if value == u32::MAX {
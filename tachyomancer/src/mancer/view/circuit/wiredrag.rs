@@ -38,6 +38,15 @@ enum Zone {
 }
 
 impl Zone {
+    /// Returns the cell side that this zone lies on, if it is an edge zone.
+    fn edge(self) -> Option<(Coords, Direction)> {
+        match self {
+            Zone::Center(_) => None,
+            Zone::East(coords) => Some((coords, Direction::East)),
+            Zone::South(coords) => Some((coords, Direction::South)),
+        }
+    }
+
     fn from_grid_pt(grid_pt: Point2<f32>) -> Zone {
         let coords: Coords = grid_pt.as_i32_floor();
         let x = grid_pt.x - (coords.x as f32) - 0.5;
@@ -222,11 +231,22 @@ pub struct WireDrag {
     curr: Option<Zone>,
     changed: bool,
     half_wire: Option<Direction>,
+    auto_route: bool,
+    first_edge: Option<(Coords, Direction)>,
+    last_edge: Option<(Coords, Direction)>,
 }
 
 impl WireDrag {
-    pub fn new() -> WireDrag {
-        WireDrag { last_pt: None, curr: None, changed: false, half_wire: None }
+    pub fn new(auto_route: bool) -> WireDrag {
+        WireDrag {
+            last_pt: None,
+            curr: None,
+            changed: false,
+            half_wire: None,
+            auto_route,
+            first_edge: None,
+            last_edge: None,
+        }
     }
 
     pub fn half_wire(&self) -> Option<(Coords, Direction)> {
@@ -260,6 +280,9 @@ impl WireDrag {
             ui.audio().play_sound(Sound::DragWire);
             ui.request_redraw();
         }
+        if drag_result == DragResult::Stop {
+            self.try_auto_route(ui, grid);
+        }
         drag_result != DragResult::Stop
     }
 
@@ -399,10 +422,19 @@ impl WireDrag {
             }
         };
         self.curr = Some(zone);
+        if drag_result != DragResult::Stop {
+            if let Some(edge) = zone.edge() {
+                self.first_edge = self.first_edge.or(Some(edge));
+                self.last_edge = Some(edge);
+            }
+        }
         drag_result
     }
 
     pub fn finish(mut self, ui: &mut Ui, grid: &mut EditGrid) {
+        if self.try_auto_route(ui, grid) {
+            return;
+        }
         let drag_result = match (self.changed, self.half_wire, self.curr) {
             (false, None, Some(Zone::Center(coords))) => {
                 WireDrag::toggle_cross(coords, grid)
@@ -438,6 +470,32 @@ impl WireDrag {
         grid.commit_provisional_changes();
     }
 
+    /// If auto-routing is enabled and this drag went from one port to
+    /// another, replaces the dragged wire with an automatically-routed one.
+    /// Returns false (leaving the dragged wire in place) if no route could
+    /// be found.
+    fn try_auto_route(&mut self, ui: &mut Ui, grid: &mut EditGrid) -> bool {
+        if !self.auto_route || !self.changed {
+            return false;
+        }
+        let start = self.first_edge.and_then(|e| port_at_edge(e, grid));
+        let end = self.last_edge.and_then(|e| port_at_edge(e, grid));
+        match (start, end) {
+            (Some(start), Some(end)) if start != end => {
+                if !grid.try_auto_route_wire(start, end) {
+                    debug_log!("no auto-route from {:?} to {:?}", start, end);
+                    return false;
+                }
+            }
+            _ => return false,
+        }
+        debug_log!("auto-routed wire from {:?} to {:?}", start, end);
+        self.half_wire = None;
+        ui.audio().play_sound(Sound::DragWire);
+        ui.request_redraw();
+        true
+    }
+
     pub fn try_toggle_cross(coords: Coords, grid: &mut EditGrid) -> bool {
         WireDrag::toggle_cross(coords, grid) == DragResult::Changed
     }
@@ -789,6 +847,21 @@ fn stub(
     }
 }
 
+/// Returns the location of the chip or interface port on either side of the
+/// given cell edge, if any.
+fn port_at_edge(
+    (coords, dir): (Coords, Direction),
+    grid: &EditGrid,
+) -> Option<(Coords, Direction)> {
+    if grid.has_port_at((coords, dir)) {
+        Some((coords, dir))
+    } else if grid.has_port_at((coords + dir, -dir)) {
+        Some((coords + dir, -dir))
+    } else {
+        None
+    }
+}
+
 //===========================================================================//
 
 #[cfg(test)]
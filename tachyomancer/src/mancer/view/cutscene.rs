@@ -66,6 +66,7 @@ pub enum CutsceneAction {
 
 pub struct CutsceneView {
     size: RectSize<f32>,
+    allow_skip: bool,
     skip_clicks: i32,
     skip_click_time: f64,
     bg_color: Color3,
@@ -77,6 +78,7 @@ impl CutsceneView {
     pub fn new(window_size: RectSize<i32>) -> CutsceneView {
         CutsceneView {
             size: window_size.as_f32(),
+            allow_skip: true,
             skip_clicks: 0,
             skip_click_time: 0.0,
             bg_color: Color3::BLACK,
@@ -90,6 +92,7 @@ impl CutsceneView {
         ui: &'a mut Ui<'a>,
         (cutscene, prefs): (&mut CutsceneScript, &'a Prefs),
     ) {
+        self.allow_skip = prefs.allow_skip_cutscenes();
         cutscene.tick(0.0, &mut TheaterImpl::new(self, ui, prefs));
     }
 
@@ -117,7 +120,7 @@ impl CutsceneView {
                 CONTINUE_MESSAGE,
             );
         }
-        if self.skip_clicks >= CLICKS_TO_SHOW_SKIP {
+        if self.allow_skip && self.skip_clicks >= CLICKS_TO_SHOW_SKIP {
             self.draw_message(
                 resources,
                 &matrix,
@@ -190,7 +193,7 @@ impl CutsceneView {
                 }
             }
             Event::KeyDown(key) if key.code == Keycode::Escape => {
-                self.maybe_skip(ui, prefs, cutscene);
+                return self.maybe_skip(ui, prefs, cutscene);
             }
             Event::KeyDown(key) if key.code == Keycode::Return => {
                 self.unpause(ui, cutscene);
@@ -201,7 +204,7 @@ impl CutsceneView {
                 } else if mouse.right
                     && cfg!(any(target_os = "android", target_os = "ios"))
                 {
-                    self.maybe_skip(ui, prefs, cutscene);
+                    return self.maybe_skip(ui, prefs, cutscene);
                 }
             }
             _ => {}
@@ -209,21 +212,28 @@ impl CutsceneView {
         return None;
     }
 
+    /// Shows the skip message, or if it's already showing, skips to the end
+    /// of the cutscene (in which case the cutscene is finished).
     fn maybe_skip<'a>(
         &'a mut self,
         ui: &'a mut Ui<'a>,
         prefs: &'a Prefs,
         cutscene: &mut CutsceneScript,
-    ) {
+    ) -> Option<CutsceneAction> {
+        if !self.allow_skip {
+            return None;
+        }
         if self.skip_clicks >= CLICKS_TO_SHOW_SKIP {
             self.skip_clicks = 0;
             self.skip_click_time = 0.0;
             ui.request_redraw();
             cutscene.skip(&mut TheaterImpl::new(self, ui, prefs));
+            Some(CutsceneAction::Finished)
         } else {
             self.skip_clicks = CLICKS_TO_SHOW_SKIP;
             self.skip_click_time = TIME_TO_HIDE_SKIP;
             ui.request_redraw();
+            None
         }
     }
 
@@ -25,7 +25,7 @@ use super::button::{
 use super::graph::ScoreGraph;
 use super::paragraph::Paragraph;
 use crate::mancer::gui::{Cursor, Event, Keycode, Resources, Ui};
-use crate::mancer::save::Prefs;
+use crate::mancer::save::{Prefs, StringKey};
 use cgmath::{Matrix4, Point2};
 use tachy::geom::{AsFloat, Color3, Color4, Rect, RectSize};
 use tachy::save::{HotkeyCode, Puzzle, ScoreCurve, WireSize};
@@ -219,7 +219,7 @@ impl HotkeyDialogBox {
         );
         let ok_button = TextButton::new_with_key(
             ok_button_rect,
-            "OK",
+            prefs.strings().get(StringKey::ButtonOk),
             (),
             Some(Keycode::Return),
         );
@@ -232,7 +232,7 @@ impl HotkeyDialogBox {
         );
         let cancel_button = TextButton::new_with_key(
             cancel_button_rect,
-            "Cancel",
+            prefs.strings().get(StringKey::ButtonCancel),
             (),
             Some(Keycode::Escape),
         );
@@ -463,7 +463,7 @@ impl TextDialogBox {
         );
         let ok_button = TextButton::new_with_key(
             ok_button_rect,
-            "OK",
+            prefs.strings().get(StringKey::ButtonOk),
             (),
             Some(Keycode::Return),
         );
@@ -476,7 +476,7 @@ impl TextDialogBox {
         );
         let cancel_button = TextButton::new_with_key(
             cancel_button_rect,
-            "Cancel",
+            prefs.strings().get(StringKey::ButtonCancel),
             (),
             Some(Keycode::Escape),
         );
@@ -621,7 +621,7 @@ impl WireSizeDialogBox {
         );
         let ok_button = TextButton::new_with_key(
             ok_button_rect,
-            "OK",
+            prefs.strings().get(StringKey::ButtonOk),
             (),
             Some(Keycode::Return),
         );
@@ -634,7 +634,7 @@ impl WireSizeDialogBox {
         );
         let cancel_button = TextButton::new_with_key(
             cancel_button_rect,
-            "Cancel",
+            prefs.strings().get(StringKey::ButtonCancel),
             (),
             Some(Keycode::Escape),
         );
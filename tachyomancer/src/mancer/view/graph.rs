@@ -36,6 +36,8 @@ const TICK_LENGTH: f32 = 4.0;
 const TICK_THICKNESS: f32 = 2.0;
 const TICK_LABEL_FONT_SIZE: f32 = 16.0;
 const SCORE_HILIGHT_RADIUS: f32 = 4.0;
+const TARGET_MARKER_RADIUS: f32 = 6.0;
+const TARGET_MARKER_THICKNESS: f32 = 2.0;
 
 fn tick_step_for_maximum(max: i32) -> i32 {
     if max >= 4000 {
@@ -164,6 +166,9 @@ impl ScoreGraph {
             self.puzzle,
             &self.local_scores,
             global_scores,
+            self.puzzle
+                .target_score()
+                .or_else(|| global_scores.best_area_and_score()),
         );
         fbo.unbind(resources.window_size());
         fbo
@@ -237,6 +242,7 @@ impl ScoreGraph {
         puzzle: Puzzle,
         local_scores: &ScoreCurve,
         global_scores: &ScoreCurve,
+        target_score: Option<(i32, u32)>,
     ) {
         // Draw the graph data:
         let graph_bounds = puzzle.graph_bounds();
@@ -259,6 +265,41 @@ impl ScoreGraph {
             Color3::ORANGE1,
         );
 
+        // Draw target score marker (clamped to the graph, since a point
+        // taken from the global frontier can lie past the graph bounds):
+        if let Some((area, score)) = target_score {
+            let cx = (graph_rect.x
+                + graph_rect.width
+                    * ((area as f32) / (graph_bounds.0 as f32)))
+                .min(graph_rect.right());
+            let cy = (graph_rect.bottom()
+                - graph_rect.height
+                    * ((score as f32) / (graph_bounds.1 as f32)))
+                .max(graph_rect.y);
+            let solid = resources.shaders().solid();
+            let color = Color3::GREEN3;
+            solid.fill_rect(
+                matrix,
+                color,
+                Rect::new(
+                    cx - TARGET_MARKER_RADIUS,
+                    cy - 0.5 * TARGET_MARKER_THICKNESS,
+                    2.0 * TARGET_MARKER_RADIUS,
+                    TARGET_MARKER_THICKNESS,
+                ),
+            );
+            solid.fill_rect(
+                matrix,
+                color,
+                Rect::new(
+                    cx - 0.5 * TARGET_MARKER_THICKNESS,
+                    cy - TARGET_MARKER_RADIUS,
+                    TARGET_MARKER_THICKNESS,
+                    2.0 * TARGET_MARKER_RADIUS,
+                ),
+            );
+        }
+
         // Draw axes:
         let color = Color3::PURPLE3;
         let axis_rect = Rect::new(
@@ -23,7 +23,7 @@ use crate::mancer::gui::{Event, Keycode, Resources, Sound, Ui};
 use crate::mancer::save::Prefs;
 use crate::mancer::state::{Cutscene, Portrait};
 use cgmath::{Matrix4, Point2};
-use tachy::geom::{AsFloat, Color4, Rect};
+use tachy::geom::{AsFloat, Color3, Color4, Rect};
 use tachy::save::{Puzzle, PuzzleKind};
 
 //===========================================================================//
@@ -95,6 +95,11 @@ pub trait BubbleView {
         false
     }
 
+    /// Returns true if this bubble is in the middle of typing out text.
+    fn is_streaming(&self) -> bool {
+        false
+    }
+
     fn is_finished(&self) -> bool;
 }
 
@@ -400,6 +405,10 @@ impl BubbleView for SpeechBubbleView {
         self.pause_after
     }
 
+    fn is_streaming(&self) -> bool {
+        !self.paragraph.is_done()
+    }
+
     fn is_finished(&self) -> bool {
         self.sent_finished
     }
@@ -421,25 +430,8 @@ impl YouChoiceBubbleView {
         key: String,
         choices: Vec<(String, String)>,
     ) -> Box<dyn BubbleView> {
-        debug_assert!(!choices.is_empty());
-        let mut bottom = top - CHOICE_BUTTON_SPACING;
-        let num_choices = choices.len();
-        let choices = choices
-            .into_iter()
-            .enumerate()
-            .map(|(index, (value, format))| {
-                let choice = ChoiceButton::new(
-                    width,
-                    bottom + CHOICE_BUTTON_SPACING,
-                    ChoicePosition::from_index_and_count(index, num_choices),
-                    prefs,
-                    value,
-                    &format,
-                );
-                bottom = choice.rect.bottom();
-                choice
-            })
-            .collect();
+        let (choices, bottom) =
+            ChoiceButton::stack(width, top, prefs, choices);
         Box::new(YouChoiceBubbleView {
             rect: Rect::new(0, top, width, bottom - top),
             key,
@@ -486,6 +478,85 @@ impl BubbleView for YouChoiceBubbleView {
 
 //===========================================================================//
 
+/// Shows a choice that the player has already made, with the chosen option
+/// highlighted and the other options dimmed.
+pub struct PastChoiceBubbleView {
+    rect: Rect<i32>,
+    choices: Vec<ChoiceButton>,
+    chosen: String,
+    pause_after: bool,
+    sent_finished: bool,
+}
+
+impl PastChoiceBubbleView {
+    pub fn new(
+        width: i32,
+        top: i32,
+        prefs: &Prefs,
+        choices: Vec<(String, String)>,
+        chosen: String,
+        pause_after: bool,
+    ) -> Box<dyn BubbleView> {
+        let (choices, bottom) =
+            ChoiceButton::stack(width, top, prefs, choices);
+        Box::new(PastChoiceBubbleView {
+            rect: Rect::new(0, top, width, bottom - top),
+            choices,
+            chosen,
+            pause_after,
+            sent_finished: false,
+        })
+    }
+}
+
+impl BubbleView for PastChoiceBubbleView {
+    fn kind(&self) -> BubbleKind {
+        BubbleKind::Speech
+    }
+
+    fn rect(&self) -> Rect<i32> {
+        self.rect
+    }
+
+    fn draw(&self, resources: &Resources, matrix: &Matrix4<f32>) {
+        for choice in self.choices.iter() {
+            choice.draw_resolved(
+                resources,
+                matrix,
+                choice.value == self.chosen,
+            );
+        }
+    }
+
+    fn on_event(
+        &mut self,
+        event: &Event,
+        _ui: &mut Ui,
+    ) -> Option<BubbleAction> {
+        if let Event::ClockTick(_) = event {
+            if !self.sent_finished {
+                self.sent_finished = true;
+                return Some(BubbleAction::ParagraphFinished);
+            }
+        }
+        return None;
+    }
+
+    fn skip_paragraph(&mut self, _ui: &mut Ui) {
+        self.sent_finished = true;
+    }
+
+    fn should_pause_afterwards(&self) -> bool {
+        self.pause_after
+    }
+
+    fn is_finished(&self) -> bool {
+        self.sent_finished
+    }
+}
+
+//===========================================================================//
+
 #[derive(Clone, Copy)]
 enum ChoicePosition {
     First,
@@ -556,6 +627,34 @@ impl ChoiceButton {
         }
     }
 
+    fn stack(
+        width: i32,
+        top: i32,
+        prefs: &Prefs,
+        choices: Vec<(String, String)>,
+    ) -> (Vec<ChoiceButton>, i32) {
+        debug_assert!(!choices.is_empty());
+        let mut bottom = top - CHOICE_BUTTON_SPACING;
+        let num_choices = choices.len();
+        let choices = choices
+            .into_iter()
+            .enumerate()
+            .map(|(index, (value, format))| {
+                let choice = ChoiceButton::new(
+                    width,
+                    bottom + CHOICE_BUTTON_SPACING,
+                    ChoicePosition::from_index_and_count(index, num_choices),
+                    prefs,
+                    value,
+                    &format,
+                );
+                bottom = choice.rect.bottom();
+                choice
+            })
+            .collect();
+        (choices, bottom)
+    }
+
     fn draw(&self, resources: &Resources, matrix: &Matrix4<f32>) {
         let bg_color = Color4::CYAN0_TRANSLUCENT
             .mix(Color4::CYAN3_TRANSLUCENT, self.hover_pulse.brightness());
@@ -593,6 +692,47 @@ impl ChoiceButton {
         }
         return None;
     }
+
+    fn draw_resolved(
+        &self,
+        resources: &Resources,
+        matrix: &Matrix4<f32>,
+        chosen: bool,
+    ) {
+        if chosen {
+            resources.shaders().ui().draw_bubble_kind(
+                matrix,
+                &self.rect.as_f32(),
+                self.position.bubble_kind(),
+                &Color4::CYAN4,
+                &Color4::CYAN3,
+                &Color4::CYAN0.mix(Color4::CYAN3, 0.5),
+            );
+            self.paragraph.draw(resources, matrix, self.paragraph_left_top);
+        } else {
+            resources.shaders().ui().draw_bubble_kind(
+                matrix,
+                &self.rect.as_f32(),
+                self.position.bubble_kind(),
+                &Color4::PURPLE2,
+                &Color4::PURPLE1,
+                &Color4::PURPLE0_TRANSLUCENT,
+            );
+            self.paragraph.draw(resources, matrix, self.paragraph_left_top);
+            let (left, top) = self.paragraph_left_top;
+            let text_rect = Rect::new(
+                left,
+                top,
+                self.paragraph.width(),
+                self.paragraph.height(),
+            );
+            resources.shaders().solid().tint_rect(
+                matrix,
+                Color3::PURPLE0.with_alpha(0.6),
+                text_rect,
+            );
+        }
+    }
 }
 
 //===========================================================================//
@@ -20,7 +20,8 @@
 use super::super::super::button::{HoverPulse, Scrollbar};
 use super::bubble::{
     BubbleAction, BubbleKind, BubbleView, CutsceneBubbleView,
-    PuzzleBubbleView, ReachedAction, SpeechBubbleView, YouChoiceBubbleView,
+    PastChoiceBubbleView, PuzzleBubbleView, ReachedAction, SpeechBubbleView,
+    YouChoiceBubbleView,
 };
 use crate::mancer::font::Align;
 use crate::mancer::gl::Stencil;
@@ -137,6 +138,20 @@ impl BubbleSequenceView {
         ui: &mut Ui,
         state: &mut GameState,
     ) -> Option<SequenceAction> {
+        // Lower the music while text is being typed out:
+        if let Event::ClockTick(_) = event {
+            if state.prefs().duck_music() && self.is_streaming_text() {
+                ui.audio().duck_music();
+            }
+        }
+
+        // Shift+Enter reveals everything up to the next interactive bubble:
+        if let Event::KeyDown(key) = event {
+            if key.code == Keycode::Return && key.shift {
+                return self.reveal_all(ui, state);
+            }
+        }
+
         // Handle scrollbar events:
         self.scrollbar.on_event(event, ui);
         if let Event::Scroll(scroll) = event {
@@ -226,6 +241,61 @@ impl BubbleSequenceView {
         return None;
     }
 
+    /// Instantly finishes typing out all visible speech bubbles, and keeps
+    /// revealing further speech bubbles until reaching a choice, puzzle, or
+    /// cutscene bubble (or the end of the conversation).
+    fn reveal_all(
+        &mut self,
+        ui: &mut Ui,
+        state: &mut GameState,
+    ) -> Option<SequenceAction> {
+        loop {
+            for bubble in self.bubbles.iter_mut().take(self.num_bubbles_shown)
+            {
+                bubble.skip_paragraph(ui);
+            }
+            let last_is_speech = self.num_bubbles_shown > 0
+                && self.bubbles[self.num_bubbles_shown - 1].kind()
+                    == BubbleKind::Speech;
+            if !last_is_speech {
+                return None;
+            }
+            if self.num_bubbles_shown >= self.bubbles.len()
+                && self.bubbles_are_complete
+            {
+                state.mark_current_conversation_complete();
+                let action = self.advance(ui, state);
+                debug_assert!(action.is_none());
+                return Some(SequenceAction::ConversationCompleted);
+            }
+            state.set_current_conversation_progress(self.num_bubbles_shown);
+            match self.bubbles.get(self.num_bubbles_shown).map(|b| b.kind()) {
+                Some(BubbleKind::Cutscene) | Some(BubbleKind::Puzzle) => {
+                    if let Some(ref mut button) = self.more_button {
+                        button.make_visible(ui);
+                    }
+                    return None;
+                }
+                Some(BubbleKind::Choice) => return self.advance(ui, state),
+                Some(BubbleKind::Speech) | None => {
+                    let num_shown = self.num_bubbles_shown;
+                    let action = self.advance(ui, state);
+                    if action.is_some() || self.num_bubbles_shown == num_shown
+                    {
+                        return action;
+                    }
+                }
+            }
+        }
+    }
+
+    fn is_streaming_text(&self) -> bool {
+        self.bubbles
+            .iter()
+            .take(self.num_bubbles_shown)
+            .any(|bubble| bubble.is_streaming())
+    }
+
     pub fn reset(
         &mut self,
         ui: &mut Ui,
@@ -400,6 +470,16 @@ impl BubbleSequenceView {
                         choices,
                     )
                 }
+                ConversationBubble::YouChoiceMade(choices, chosen) => {
+                    PastChoiceBubbleView::new(
+                        bubble_width,
+                        bubble_top,
+                        prefs,
+                        choices,
+                        chosen,
+                        !is_last,
+                    )
+                }
                 ConversationBubble::YouSpeech(format) => {
                     SpeechBubbleView::new(
                         bubble_width,
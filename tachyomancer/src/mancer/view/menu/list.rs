@@ -92,6 +92,22 @@ impl<T: Clone + Eq> ListView<T> {
     ) where
         Q: PartialEq + ?Sized,
         T: Borrow<Q>,
+    {
+        self.draw_with_badges(resources, matrix, current, |_| false);
+    }
+
+    /// Like `draw`, but also draws a gold badge at the right end of each
+    /// item for which `has_badge` returns true.
+    pub fn draw_with_badges<Q, F>(
+        &self,
+        resources: &Resources,
+        matrix: &Matrix4<f32>,
+        current: &Q,
+        has_badge: F,
+    ) where
+        Q: PartialEq + ?Sized,
+        T: Borrow<Q>,
+        F: Fn(&T) -> bool,
     {
         resources.shaders().shadow().rect_shadow_basic(
             matrix,
@@ -102,7 +118,7 @@ impl<T: Clone + Eq> ListView<T> {
             let stencil = Stencil::new();
             self.draw_background(resources, matrix);
             stencil.enable_clipping();
-            self.draw_items(resources, matrix, current);
+            self.draw_items(resources, matrix, current, has_badge);
         }
         self.draw_frame(resources, matrix);
         self.scrollbar.draw(resources, matrix);
@@ -116,14 +132,16 @@ impl<T: Clone + Eq> ListView<T> {
         solid.tint_rect(matrix, Color4::PURPLE0_TRANSLUCENT, rect);
     }
 
-    fn draw_items<Q>(
+    fn draw_items<Q, F>(
         &self,
         resources: &Resources,
         matrix: &Matrix4<f32>,
         current: &Q,
+        has_badge: F,
     ) where
         Q: PartialEq + ?Sized,
         T: Borrow<Q>,
+        F: Fn(&T) -> bool,
     {
         let item_width = self.item_width() as f32;
         let ui = resources.shaders().ui();
@@ -171,6 +189,25 @@ impl<T: Clone + Eq> ListView<T> {
                 shader.draw(matrix, icon_rect, icon as u32, color, texture);
             }
 
+            // Badge:
+            if has_badge(value) {
+                let badge_rect = Rect::new(
+                    rect.right() - ((ITEM_INNER_MARGIN + ICON_WIDTH) as f32),
+                    rect.y + 0.5 * (ITEM_HEIGHT - ICON_HEIGHT) as f32,
+                    ICON_WIDTH as f32,
+                    ICON_HEIGHT as f32,
+                );
+                let texture = resources.textures().list_icons();
+                let shader = resources.shaders().icon();
+                shader.draw(
+                    matrix,
+                    badge_rect,
+                    ListIcon::Optimal as u32,
+                    &Color4::YELLOW3,
+                    texture,
+                );
+            }
+
             // Label:
             let text_offset = if opt_icon.is_some() {
                 ICON_WIDTH + ITEM_INNER_MARGIN
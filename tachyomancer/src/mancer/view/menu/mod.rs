@@ -22,8 +22,11 @@ mod credits;
 mod list;
 mod nav;
 mod prefs;
+mod preload;
 mod puzzle;
 
+pub use self::preload::AssetPreloader;
+
 use self::converse::{ConverseAction, ConverseView};
 use self::nav::NavigationView;
 use self::prefs::{PrefsAction, PrefsView};
@@ -32,15 +35,19 @@ use super::background::{background_for_chapter, BackgroundView};
 use super::button::RadioButton;
 use super::dialog::{ButtonDialogBox, DialogAction, TextDialogBox};
 use super::paragraph::Paragraph;
+use crate::mancer::font::Align;
 use crate::mancer::gui::{
-    ClockEventData, Cursor, Event, Keycode, Music, Resources, Ui, Window,
-    WindowOptions,
+    ClockEventData, Cursor, Event, Keycode, Music, MusicRepeat, Resources, Ui,
+    Window, WindowOptions,
+};
+use crate::mancer::save::{
+    CircuitCopyKind, MenuSection, StringKey, CIRCUIT_NAME_MAX_CHARS,
 };
-use crate::mancer::save::{MenuSection, CIRCUIT_NAME_MAX_CHARS};
 use crate::mancer::state::{Cutscene, GameState};
 use cgmath::{self, Matrix4};
+use std::path::Path;
 use tachy::geom::{AsFloat, MatrixExt, Rect, RectSize};
-use tachy::save::{Chapter, Conversation, Puzzle};
+use tachy::save::{Chapter, CircuitNotes, Conversation, Puzzle};
 
 //===========================================================================//
 
@@ -55,6 +62,10 @@ const SECTION_MARGIN_HORZ: i32 = SECTION_BUTTON_MARGIN_HORZ;
 const SECTION_TOP: i32 =
     SECTION_BUTTON_MARGIN_TOP + SECTION_BUTTON_HEIGHT + SECTION_MARGIN_TOP;
 
+const UPDATE_NOTICE_FONT_SIZE: f32 = 16.0;
+
+const SAVE_DIR_PATH_MAX_CHARS: usize = 500;
+
 //===========================================================================//
 
 #[derive(Clone)]
@@ -62,14 +73,16 @@ pub enum MenuAction {
     GoToPuzzle(Puzzle),
     PlayCutscene(Cutscene),
     UnlockPuzzles(Vec<Puzzle>),
-    CopyCircuit,
+    CopyCircuit(CircuitCopyKind),
     DeleteCircuit,
     EditCircuit,
+    ImportCircuit(String),
     RenameCircuit(String),
     RebootWindow(WindowOptions),
     NewProfile,
     SwitchProfile(String),
     DeleteProfile(String),
+    ResetAllSettings,
     QuitGame,
 }
 
@@ -87,6 +100,7 @@ pub struct MenuView {
 
     confirmation_dialog: Option<ButtonDialogBox<Option<MenuAction>>>,
     rename_dialog: Option<TextDialogBox>,
+    save_dir_dialog: Option<TextDialogBox>,
 
     left_section: MenuSection,
     right_section: MenuSection,
@@ -94,13 +108,38 @@ pub struct MenuView {
 }
 
 impl MenuView {
-    pub fn new(window: &mut Window, state: &GameState) -> MenuView {
+    pub fn new(
+        window: &mut Window,
+        state: &GameState,
+        preloader: &mut AssetPreloader,
+    ) -> MenuView {
         let size = window.size();
+        let strings = state.prefs().strings();
         let section_buttons = vec![
-            section_button(size, 0, "Navigation", MenuSection::Navigation),
-            section_button(size, 1, "Messages", MenuSection::Messages),
-            section_button(size, 2, "Tasks", MenuSection::Puzzles),
-            section_button(size, 3, "Settings", MenuSection::Prefs),
+            section_button(
+                size,
+                0,
+                strings.get(StringKey::MenuNavigation),
+                MenuSection::Navigation,
+            ),
+            section_button(
+                size,
+                1,
+                strings.get(StringKey::MenuMessages),
+                MenuSection::Messages,
+            ),
+            section_button(
+                size,
+                2,
+                strings.get(StringKey::MenuTasks),
+                MenuSection::Puzzles,
+            ),
+            section_button(
+                size,
+                3,
+                strings.get(StringKey::MenuSettings),
+                MenuSection::Prefs,
+            ),
         ];
         let section_rect = Rect::new(
             SECTION_MARGIN_HORZ,
@@ -118,11 +157,13 @@ impl MenuView {
         let converse_view = ConverseView::new(section_rect, &mut ui, state);
         let puzzles_view = PuzzlesView::new(section_rect, &mut ui, state);
 
-        ui.audio().play_music(music_for_chapter(latest_chapter));
+        ui.audio()
+            .play_music(music_for_chapter(latest_chapter), MusicRepeat::Loop);
 
         MenuView {
             size,
-            background: background_for_chapter(latest_chapter, size.as_f32()),
+            background: preloader
+                .take_background(latest_chapter, size.as_f32()),
             section_buttons,
             navigation_view,
             converse_view,
@@ -130,6 +171,7 @@ impl MenuView {
             puzzles_view,
             confirmation_dialog: None,
             rename_dialog: None,
+            save_dir_dialog: None,
             left_section: state.menu_section(),
             right_section: state.menu_section(),
             section_anim: 0.0,
@@ -166,11 +208,26 @@ impl MenuView {
                 enabled,
             );
         }
+        if let Some(version) = resources.newer_version() {
+            resources.fonts().roman().draw(
+                &projection,
+                UPDATE_NOTICE_FONT_SIZE,
+                Align::MidRight,
+                (
+                    size.width - SECTION_MARGIN_HORZ as f32,
+                    size.height - 0.5 * SECTION_MARGIN_BOTTOM as f32,
+                ),
+                &format!("A new version ({}) is available.", version),
+            );
+        }
         if let Some(ref dialog) = self.rename_dialog {
             dialog.draw(resources, &projection, |name| {
                 state.is_valid_circuit_rename(name)
             });
         }
+        if let Some(ref dialog) = self.save_dir_dialog {
+            dialog.draw(resources, &projection, is_valid_save_dir_path);
+        }
         if let Some(ref dialog) = self.confirmation_dialog {
             dialog.draw(resources, &projection);
         }
@@ -220,6 +277,14 @@ impl MenuView {
                         self.section_anim = 0.0;
                     }
                 }
+                if self.left_section != self.right_section
+                    && state.prefs().reduce_motion()
+                {
+                    self.left_section = goal_section;
+                    self.right_section = goal_section;
+                    self.section_anim = 0.0;
+                    ui.request_redraw();
+                }
                 if self.left_section != self.right_section {
                     let mut anim_goal = if goal_section == self.left_section {
                         0.0
@@ -290,6 +355,11 @@ impl MenuView {
                 self.puzzles_view.clear_score_graph_cache();
                 ui.request_redraw();
             }
+            Event::Debug(key, value) if key == "PlayMusic" => {
+                if let Ok(music) = value.parse::<Music>() {
+                    ui.audio().play_music(vec![music], MusicRepeat::Once);
+                }
+            }
             Event::Debug(key, value) if key == "UnlockPuzzle" => {
                 if let Ok(puzzle) = value.parse::<Puzzle>() {
                     return Some(MenuAction::GoToPuzzle(puzzle));
@@ -324,6 +394,19 @@ impl MenuView {
             }
         }
 
+        if let Some(mut dialog) = self.save_dir_dialog.take() {
+            match dialog.on_event(event, ui, is_valid_save_dir_path) {
+                Some(DialogAction::Value(path)) => {
+                    self.move_save_dir(ui, state, Path::new(&path));
+                }
+                Some(DialogAction::Cancel) => {}
+                None => self.save_dir_dialog = Some(dialog),
+            }
+            if !event.is_clock_tick() {
+                return None;
+            }
+        }
+
         if self.left_section == self.right_section || event.is_clock_tick() {
             if let Some(action) = self.on_section_event(event, ui, state) {
                 return Some(action);
@@ -380,7 +463,40 @@ impl MenuView {
                         return None;
                     }
                     Some(PuzzlesAction::Copy) => {
-                        return Some(MenuAction::CopyCircuit);
+                        self.unfocus(ui, state);
+                        let format = format!(
+                            "Copy {} with or without its wires?",
+                            Paragraph::escape(state.circuit_name())
+                        );
+                        let strings = state.prefs().strings();
+                        let cancel_button = (
+                            strings.get(StringKey::ButtonCancel),
+                            None,
+                            Some(Keycode::Escape),
+                        );
+                        let chips_button = (
+                            strings.get(StringKey::ButtonChipsOnly),
+                            Some(MenuAction::CopyCircuit(
+                                CircuitCopyKind::ChipsOnly,
+                            )),
+                            None,
+                        );
+                        let full_button = (
+                            strings.get(StringKey::ButtonFullCopy),
+                            Some(MenuAction::CopyCircuit(
+                                CircuitCopyKind::Full,
+                            )),
+                            Some(Keycode::Return),
+                        );
+                        let buttons =
+                            &[cancel_button, chips_button, full_button];
+                        self.confirmation_dialog = Some(ButtonDialogBox::new(
+                            self.size,
+                            state.prefs(),
+                            &format,
+                            buttons,
+                        ));
+                        return None;
                     }
                     Some(PuzzlesAction::Delete) => {
                         self.unfocus(ui, state);
@@ -388,10 +504,17 @@ impl MenuView {
                             "Really delete {}?",
                             Paragraph::escape(state.circuit_name())
                         );
-                        let cancel_button =
-                            ("Cancel", None, Some(Keycode::Escape));
-                        let delete_button =
-                            ("Delete", Some(MenuAction::DeleteCircuit), None);
+                        let strings = state.prefs().strings();
+                        let cancel_button = (
+                            strings.get(StringKey::ButtonCancel),
+                            None,
+                            Some(Keycode::Escape),
+                        );
+                        let delete_button = (
+                            strings.get(StringKey::ButtonDelete),
+                            Some(MenuAction::DeleteCircuit),
+                            None,
+                        );
                         let buttons = &[cancel_button, delete_button];
                         self.confirmation_dialog = Some(ButtonDialogBox::new(
                             self.size,
@@ -404,6 +527,61 @@ impl MenuView {
                     Some(PuzzlesAction::Edit) => {
                         return Some(MenuAction::EditCircuit);
                     }
+                    Some(PuzzlesAction::Import) => {
+                        match ui.clipboard().get() {
+                            Some(code) => {
+                                return Some(MenuAction::ImportCircuit(code));
+                            }
+                            None => {
+                                self.show_error(
+                                    ui,
+                                    state,
+                                    "import circuit",
+                                    "The clipboard is empty.  Copy a circuit \
+                                 share code first.",
+                                );
+                                return None;
+                            }
+                        }
+                    }
+                    Some(PuzzlesAction::Share) => {
+                        match state.current_circuit_share_code() {
+                            Ok(code) => {
+                                ui.clipboard().set(&code);
+                                self.unfocus(ui, state);
+                                let format = format!(
+                                    "A share code for {} has been copied to \
+                                     the clipboard ({} characters).\n\n\
+                                     Other players can paste it with the \
+                                     $*Import$* button.",
+                                    Paragraph::escape(state.circuit_name()),
+                                    code.len()
+                                );
+                                let ok = state
+                                    .prefs()
+                                    .strings()
+                                    .get(StringKey::ButtonOk);
+                                let buttons =
+                                    &[(ok, None, Some(Keycode::Return))];
+                                self.confirmation_dialog =
+                                    Some(ButtonDialogBox::new(
+                                        self.size,
+                                        state.prefs(),
+                                        &format,
+                                        buttons,
+                                    ));
+                            }
+                            Err(err) => {
+                                self.show_error(
+                                    ui,
+                                    state,
+                                    "share circuit",
+                                    &err,
+                                );
+                            }
+                        }
+                        return None;
+                    }
                     Some(PuzzlesAction::Rename) => {
                         self.unfocus(ui, state);
                         let dialog = TextDialogBox::new(
@@ -439,10 +617,14 @@ impl MenuView {
                              This cannot be undone!",
                             Paragraph::escape(&name)
                         );
-                        let cancel_button =
-                            ("Cancel", None, Some(Keycode::Escape));
+                        let strings = state.prefs().strings();
+                        let cancel_button = (
+                            strings.get(StringKey::ButtonCancel),
+                            None,
+                            Some(Keycode::Escape),
+                        );
                         let delete_button = (
-                            "Delete",
+                            strings.get(StringKey::ButtonDelete),
                             Some(MenuAction::DeleteProfile(name)),
                             None,
                         );
@@ -455,6 +637,84 @@ impl MenuView {
                         ));
                         return None;
                     }
+                    Some(PrefsAction::MoveSaveData) => {
+                        self.unfocus(ui, state);
+                        let dialog = TextDialogBox::new(
+                            self.size,
+                            state.prefs(),
+                            "Choose a new location for your save data.  \
+                             This must be an empty directory.",
+                            &state.save_dir_path().to_string_lossy(),
+                            SAVE_DIR_PATH_MAX_CHARS,
+                        );
+                        self.save_dir_dialog = Some(dialog);
+                        return None;
+                    }
+                    Some(PrefsAction::ReportIssue) => {
+                        match state.write_issue_report() {
+                            Ok(path) => {
+                                self.unfocus(ui, state);
+                                let format = format!(
+                                    "An issue report has been written to:\n\n\
+                                     {}\n\n\
+                                     Please attach this file when filing a \
+                                     bug at\n\
+                                     https://github.com/mdsteele/\
+                                     tachyomancer/issues",
+                                    Paragraph::escape(&path.to_string_lossy())
+                                );
+                                let ok = state
+                                    .prefs()
+                                    .strings()
+                                    .get(StringKey::ButtonOk);
+                                let buttons =
+                                    &[(ok, None, Some(Keycode::Return))];
+                                self.confirmation_dialog =
+                                    Some(ButtonDialogBox::new(
+                                        self.size,
+                                        state.prefs(),
+                                        &format,
+                                        buttons,
+                                    ));
+                            }
+                            Err(err) => {
+                                self.show_error(
+                                    ui,
+                                    state,
+                                    "write issue report",
+                                    &err,
+                                );
+                            }
+                        }
+                        return None;
+                    }
+                    Some(PrefsAction::ResetAllSettings) => {
+                        self.unfocus(ui, state);
+                        let format = "Are you sure you want to reset all \
+                                      settings (including hotkeys) to their \
+                                      defaults?\n\n\
+                                      Your profiles and progress will not \
+                                      be affected.";
+                        let strings = state.prefs().strings();
+                        let cancel_button = (
+                            strings.get(StringKey::ButtonCancel),
+                            None,
+                            Some(Keycode::Escape),
+                        );
+                        let reset_button = (
+                            strings.get(StringKey::ButtonReset),
+                            Some(MenuAction::ResetAllSettings),
+                            None,
+                        );
+                        let buttons = &[cancel_button, reset_button];
+                        self.confirmation_dialog = Some(ButtonDialogBox::new(
+                            self.size,
+                            state.prefs(),
+                            format,
+                            buttons,
+                        ));
+                        return None;
+                    }
                     Some(PrefsAction::QuitGame) => {
                         return Some(MenuAction::QuitGame);
                     }
@@ -480,12 +740,125 @@ impl MenuView {
             unable,
             Paragraph::escape(error)
         );
-        let buttons = &[("OK", None, Some(Keycode::Return))];
+        let ok = state.prefs().strings().get(StringKey::ButtonOk);
+        let buttons = &[(ok, None, Some(Keycode::Return))];
+        let dialog =
+            ButtonDialogBox::new(self.size, state.prefs(), &format, buttons);
+        self.confirmation_dialog = Some(dialog);
+    }
+
+    /// Shows the description and author of a circuit that was just imported,
+    /// and offers to open it in the editor.
+    pub fn show_imported_circuit_notes(
+        &mut self,
+        ui: &mut Ui,
+        state: &mut GameState,
+        notes: &CircuitNotes,
+    ) {
+        self.unfocus(ui, state);
+        let mut format = format!(
+            "Imported circuit $*{}$*",
+            Paragraph::escape(state.circuit_name())
+        );
+        if !notes.author.is_empty() {
+            format.push_str(&format!(
+                " by $*{}$*",
+                Paragraph::escape(&notes.author)
+            ));
+        }
+        format.push('.');
+        if !notes.description.is_empty() {
+            format.push_str(&format!(
+                "\n\n{}",
+                Paragraph::escape(&notes.description)
+            ));
+        }
+        let ok = state.prefs().strings().get(StringKey::ButtonOk);
+        let buttons = &[
+            (ok, None, Some(Keycode::Escape)),
+            ("Edit", Some(MenuAction::EditCircuit), Some(Keycode::Return)),
+        ];
+        let dialog =
+            ButtonDialogBox::new(self.size, state.prefs(), &format, buttons);
+        self.confirmation_dialog = Some(dialog);
+    }
+
+    /// Warns the player that the directory they moved their save data to
+    /// couldn't be found, and so the default save data directory is being
+    /// used instead.
+    pub fn show_missing_location_warning(
+        &mut self,
+        ui: &mut Ui,
+        state: &mut GameState,
+        path: &Path,
+    ) {
+        self.unfocus(ui, state);
+        let format = format!(
+            "$Y$*WARNING:$*$D Unable to find your save data directory \
+             ({}).\n\nUntil it is available again, the default save data \
+             directory ({}) is being used instead.",
+            Paragraph::escape(&path.to_string_lossy()),
+            Paragraph::escape(&state.save_dir_path().to_string_lossy())
+        );
+        let ok = state.prefs().strings().get(StringKey::ButtonOk);
+        let buttons = &[(ok, None, Some(Keycode::Return))];
+        let dialog =
+            ButtonDialogBox::new(self.size, state.prefs(), &format, buttons);
+        self.confirmation_dialog = Some(dialog);
+    }
+
+    /// Warns the player that their save data directory isn't writable, and
+    /// so their progress won't be kept after they quit.
+    pub fn show_read_only_warning(
+        &mut self,
+        ui: &mut Ui,
+        state: &mut GameState,
+        path: &Path,
+    ) {
+        self.unfocus(ui, state);
+        let format = format!(
+            "$Y$*WARNING:$*$D Unable to write to the save data directory \
+             ({}).\n\nYou can keep playing, but $*your progress will not be \
+             saved$* after you quit.",
+            Paragraph::escape(&path.to_string_lossy())
+        );
+        let ok = state.prefs().strings().get(StringKey::ButtonOk);
+        let buttons = &[(ok, None, Some(Keycode::Return))];
         let dialog =
             ButtonDialogBox::new(self.size, state.prefs(), &format, buttons);
         self.confirmation_dialog = Some(dialog);
     }
 
+    fn move_save_dir(
+        &mut self,
+        ui: &mut Ui,
+        state: &mut GameState,
+        new_path: &Path,
+    ) {
+        if new_path == state.save_dir_path() {
+            return;
+        }
+        match state.move_save_dir(new_path) {
+            Ok(()) => {
+                let format = format!(
+                    "Your save data has been copied to:\n\n{}\n\n\
+                     From now on, it will be saved there instead.  The old \
+                     copy has been left in place, and can be deleted.",
+                    Paragraph::escape(&new_path.to_string_lossy())
+                );
+                let ok = state.prefs().strings().get(StringKey::ButtonOk);
+                let buttons = &[(ok, None, Some(Keycode::Return))];
+                self.confirmation_dialog = Some(ButtonDialogBox::new(
+                    self.size,
+                    state.prefs(),
+                    &format,
+                    buttons,
+                ));
+            }
+            Err(err) => self.show_error(ui, state, "move save data", &err),
+        }
+    }
+
     fn go_back_to_conversation(
         &mut self,
         conv: Conversation,
@@ -509,6 +882,7 @@ impl MenuView {
     ) {
         self.unfocus(ui, state);
         state.set_menu_section(MenuSection::Puzzles);
+        self.update_puzzle_list(ui, state);
         self.update_circuit_list(ui, state);
     }
 
@@ -530,6 +904,10 @@ impl MenuView {
 
 //===========================================================================//
 
+fn is_valid_save_dir_path(path: &str) -> bool {
+    Path::new(path).is_absolute()
+}
+
 fn music_for_chapter(chapter: Chapter) -> Vec<Music> {
     match chapter {
         Chapter::Odyssey => vec![Music::PitchBlack],
@@ -27,7 +27,11 @@ use super::list::{ListIcon, ListView};
 use crate::mancer::font::Align;
 use crate::mancer::gl::Stencil;
 use crate::mancer::gui::{Event, Resources, Sound, Ui, Window, WindowOptions};
-use crate::mancer::save::{Hotkey, Prefs, Profile, HOTKEY_CATEGORIES};
+use crate::mancer::save::{
+    Hotkey, PrefKey, PrefValue, Prefs, Profile, Pronouns, HOTKEY_CATEGORIES,
+    MAX_DOUBLE_CLICK_MS, MAX_SNAPSHOT_SLOTS, MIN_DOUBLE_CLICK_MS,
+    MIN_SNAPSHOT_SLOTS,
+};
 use crate::mancer::state::GameState;
 use cgmath::{Matrix4, Point2};
 use num_integer::Roots;
@@ -47,6 +51,10 @@ const AV_RESOLUTION_ROW_SPACING: i32 = 6;
 const AV_BUTTON_WIDTH: i32 = 200;
 const AV_BUTTON_HEIGHT: i32 = 40;
 const AV_BUTTON_SPACING: i32 = 24;
+const AV_RESET_HINT_FONT_SIZE: f32 = 16.0;
+const AV_ANTIALIAS_FONT_SIZE: f32 = 20.0;
+const AV_ANTIALIAS_LABEL_WIDTH: i32 = 140;
+const AV_ANTIALIAS_COLUMN_WIDTH: i32 = 100;
 
 const HOTKEY_FRAME_PADDING: i32 = AV_CATEGORY_FRAME_PADDING;
 const HOTKEY_CATEGORY_LABEL_FONT_SIZE: f32 = 22.0;
@@ -56,6 +64,13 @@ const HOTKEY_CATEGORY_SPACING: i32 = 32;
 const HOTKEY_BUTTON_WIDTH: i32 = 200;
 const HOTKEY_BUTTON_HEIGHT: i32 = 40;
 
+const GAMEPLAY_FRAME_PADDING: i32 = AV_CATEGORY_FRAME_PADDING;
+const GAMEPLAY_CHECKBOX_SPACING: i32 = 16;
+const GAMEPLAY_SLIDER_LABEL_WIDTH: i32 = 140;
+const GAMEPLAY_SLIDER_VALUE_WIDTH: i32 = 90;
+
+const DOUBLE_CLICK_STEP_MS: u32 = 50;
+
 const PROFILES_LIST_WIDTH: i32 = 240;
 const PROFILES_BUTTON_WIDTH: i32 = 180;
 const PROFILES_BUTTON_HEIGHT: i32 = 40;
@@ -65,6 +80,10 @@ const PROFILES_TITLE_FONT_SIZE: f32 = 26.0;
 const PROFILES_TITLE_MARGIN_BOTTOM: f32 = 14.0;
 const PROFILES_PARAGRAPH_FONT_SIZE: f32 = 20.0;
 const PROFILES_PARAGRAPH_LINE_HEIGHT: f32 = 22.0;
+const PROFILES_PRONOUNS_FONT_SIZE: f32 = 20.0;
+const PROFILES_PRONOUNS_LABEL_WIDTH: i32 = 110;
+const PROFILES_PRONOUNS_COLUMN_WIDTH: i32 = 140;
+const PROFILES_PRONOUNS_MARGIN_BOTTOM: i32 = 20;
 
 const CREDITS_FRAME_PADDING: i32 = AV_CATEGORY_FRAME_PADDING;
 const CREDITS_PARAGRAPH_SPACING: i32 = 50;
@@ -82,6 +101,9 @@ pub enum PrefsAction {
     NewProfile,
     SwitchProfile(String),
     DeleteProfile(String),
+    MoveSaveData,
+    ReportIssue,
+    ResetAllSettings,
     QuitGame,
 }
 
@@ -90,6 +112,7 @@ pub enum PrefsAction {
 #[derive(Clone, Copy, Eq, PartialEq)]
 pub enum PrefsPane {
     AudioVideo,
+    Gameplay,
     Hotkeys,
     Profiles,
     Credits,
@@ -97,6 +120,7 @@ pub enum PrefsPane {
 
 const PANES: &[(PrefsPane, &str)] = &[
     (PrefsPane::AudioVideo, "Audio/Video"),
+    (PrefsPane::Gameplay, "Gameplay"),
     (PrefsPane::Hotkeys, "Controls"),
     (PrefsPane::Profiles, "Profiles"),
     (PrefsPane::Credits, "Credits"),
@@ -107,8 +131,10 @@ const PANES: &[(PrefsPane, &str)] = &[
 pub struct PrefsView {
     current_pane: PrefsPane,
     pane_buttons: Vec<RadioButton<PrefsPane>>,
+    reset_button: TextButton<PrefsAction>,
     quit_button: TextButton<PrefsAction>,
     audio_video_pane: AudioVideoPane,
+    gameplay_pane: GameplayPane,
     hotkeys_pane: HotkeysPane,
     profiles_pane: ProfilesPane,
     credits_pane: CreditsPane,
@@ -122,7 +148,7 @@ impl PrefsView {
     ) -> PrefsView {
         let num_panes = PANES.len() as i32;
         let pane_button_height = (rect.height + PANE_BUTTON_SPACING)
-            / (num_panes + 1)
+            / (num_panes + 2)
             - PANE_BUTTON_SPACING;
         let pane_buttons = PANES
             .iter()
@@ -141,8 +167,20 @@ impl PrefsView {
             })
             .collect();
 
-        let quit_button_top =
+        let reset_button_top =
             rect.y + num_panes * (pane_button_height + PANE_BUTTON_SPACING);
+        let reset_button = TextButton::new(
+            Rect::new(
+                rect.x,
+                reset_button_top,
+                PANE_BUTTON_WIDTH,
+                pane_button_height,
+            ),
+            "Reset Settings",
+            PrefsAction::ResetAllSettings,
+        );
+        let quit_button_top =
+            reset_button_top + pane_button_height + PANE_BUTTON_SPACING;
         let quit_button_height = rect.height - (quit_button_top - rect.y);
         let quit_button = TextButton::new(
             Rect::new(
@@ -164,6 +202,7 @@ impl PrefsView {
         );
         let audio_video_pane = AudioVideoPane::new(pane_rect, window, state);
         let mut ui = window.ui();
+        let gameplay_pane = GameplayPane::new(pane_rect, state.prefs());
         let hotkeys_pane = HotkeysPane::new(pane_rect);
         let profiles_pane = ProfilesPane::new(pane_rect, &mut ui, state);
         let credits_pane = CreditsPane::new(pane_rect, state.prefs());
@@ -171,8 +210,10 @@ impl PrefsView {
         PrefsView {
             current_pane: PrefsPane::AudioVideo,
             pane_buttons,
+            reset_button,
             quit_button,
             audio_video_pane,
+            gameplay_pane,
             hotkeys_pane,
             profiles_pane,
             credits_pane,
@@ -189,11 +230,16 @@ impl PrefsView {
         for button in self.pane_buttons.iter() {
             button.draw(resources, matrix, &self.current_pane, true);
         }
+        let enabled = !state.prefs().are_all_defaults();
+        self.reset_button.draw(resources, matrix, enabled);
         self.quit_button.draw(resources, matrix, true);
 
         match self.current_pane {
             PrefsPane::AudioVideo => {
-                self.audio_video_pane.draw(resources, matrix);
+                self.audio_video_pane.draw(resources, matrix, state);
+            }
+            PrefsPane::Gameplay => {
+                self.gameplay_pane.draw(resources, matrix, state);
             }
             PrefsPane::Hotkeys => {
                 self.hotkeys_pane.draw(resources, matrix, state);
@@ -232,6 +278,10 @@ impl PrefsView {
             self.current_pane = pane;
             ui.request_redraw();
         }
+        let enabled = !state.prefs().are_all_defaults();
+        if let Some(action) = self.reset_button.on_event(event, ui, enabled) {
+            return Some(action);
+        }
         if let Some(action) = self.quit_button.on_event(event, ui, true) {
             return Some(action);
         }
@@ -249,6 +299,9 @@ impl PrefsView {
             PrefsPane::AudioVideo => {
                 self.audio_video_pane.on_event(event, ui, state)
             }
+            PrefsPane::Gameplay => {
+                self.gameplay_pane.on_event(event, ui, state)
+            }
             PrefsPane::Hotkeys => self.hotkeys_pane.on_event(event, ui, state),
             PrefsPane::Profiles => {
                 self.profiles_pane.on_event(event, ui, state)
@@ -262,13 +315,19 @@ impl PrefsView {
 
 pub struct AudioVideoPane {
     category_frames: Vec<Rect<f32>>,
-    antialias_checkbox: Checkbox,
+    reset_hint_position: (f32, f32),
+    antialias_label_position: (f32, f32),
+    antialias_checkboxes: Vec<RadioCheckbox<u8>>,
     fullscreen_checkbox: Checkbox,
+    reduce_motion_checkbox: Checkbox,
+    duck_music_checkbox: Checkbox,
+    check_updates_checkbox: Checkbox,
     resolution_checkboxes: Vec<RadioCheckbox<Option<RectSize<i32>>>>,
     sound_volume_slider: Slider,
     music_volume_slider: Slider,
     apply_button: TextButton<()>,
     revert_button: TextButton<()>,
+    report_button: TextButton<()>,
     current_window_options: WindowOptions,
     new_window_options: WindowOptions,
 }
@@ -307,7 +366,16 @@ impl AudioVideoPane {
             state.prefs().sound_volume_percent(),
             "Sound".to_string(),
         );
-        top += AV_SLIDER_HEIGHT + AV_CATEGORY_FRAME_PADDING;
+        top += AV_SLIDER_HEIGHT + AV_SLIDER_SPACING;
+        let duck_music_checkbox = Checkbox::new(
+            Point2::new(left + AV_SLIDER_MARGIN, top),
+            "Lower music during conversations".to_string(),
+        );
+        let reset_hint_position = (
+            (right - AV_SLIDER_MARGIN) as f32,
+            (top + CHECKBOX_HEIGHT / 2) as f32,
+        );
+        top += CHECKBOX_HEIGHT + AV_CATEGORY_FRAME_PADDING;
         category_frames.push(
             Rect::new(rect.x, frame_top, rect.width, top - frame_top).as_f32(),
         );
@@ -318,10 +386,29 @@ impl AudioVideoPane {
         top += AV_CATEGORY_FRAME_PADDING;
         let fullscreen_checkbox =
             Checkbox::new(Point2::new(left, top), "Fullscreen".to_string());
-        let antialias_checkbox = Checkbox::new(
+        let reduce_motion_checkbox = Checkbox::new(
             Point2::new(left + AV_BUTTON_WIDTH + AV_BUTTON_SPACING, top),
-            "Antialiasing".to_string(),
+            "Reduce motion".to_string(),
         );
+        top += CHECKBOX_HEIGHT + AV_RESOLUTION_ROW_SPACING;
+        let antialias_label_position =
+            (left as f32, (top + CHECKBOX_HEIGHT / 2) as f32);
+        let antialias_checkboxes = window
+            .supported_antialiasing_levels()
+            .iter()
+            .enumerate()
+            .map(|(index, &level)| {
+                let label = if level == 0 {
+                    "Off".to_string()
+                } else {
+                    format!("{}x", level)
+                };
+                let x = left
+                    + AV_ANTIALIAS_LABEL_WIDTH
+                    + (index as i32) * AV_ANTIALIAS_COLUMN_WIDTH;
+                RadioCheckbox::new(Point2::new(x, top), label, level)
+            })
+            .collect();
         top += CHECKBOX_HEIGHT;
         let button_top =
             rect.bottom() - AV_CATEGORY_FRAME_PADDING - AV_BUTTON_HEIGHT;
@@ -370,6 +457,21 @@ impl AudioVideoPane {
             AV_BUTTON_HEIGHT,
         );
         let revert_button = TextButton::new(revert_button_rect, "Revert", ());
+        let report_button_rect = Rect::new(
+            right - AV_BUTTON_WIDTH,
+            button_top,
+            AV_BUTTON_WIDTH,
+            AV_BUTTON_HEIGHT,
+        );
+        let report_button =
+            TextButton::new(report_button_rect, "Report Issue", ());
+        let check_updates_checkbox = Checkbox::new(
+            Point2::new(
+                left + 2 * (AV_BUTTON_WIDTH + AV_BUTTON_SPACING),
+                button_top + (AV_BUTTON_HEIGHT - CHECKBOX_HEIGHT) / 2,
+            ),
+            "Check for updates".to_string(),
+        );
         category_frames.push(
             Rect::new(
                 rect.x,
@@ -382,19 +484,30 @@ impl AudioVideoPane {
 
         AudioVideoPane {
             category_frames,
-            antialias_checkbox,
+            reset_hint_position,
+            antialias_label_position,
+            antialias_checkboxes,
             fullscreen_checkbox,
+            reduce_motion_checkbox,
+            duck_music_checkbox,
+            check_updates_checkbox,
             resolution_checkboxes,
             sound_volume_slider,
             music_volume_slider,
             apply_button,
             revert_button,
+            report_button,
             current_window_options: window.options().clone(),
             new_window_options: window.options().clone(),
         }
     }
 
-    pub fn draw(&self, resources: &Resources, matrix: &Matrix4<f32>) {
+    pub fn draw(
+        &self,
+        resources: &Resources,
+        matrix: &Matrix4<f32>,
+        state: &GameState,
+    ) {
         for rect in self.category_frames.iter() {
             resources.shaders().shadow().rect_shadow_basic(
                 matrix,
@@ -410,18 +523,32 @@ impl AudioVideoPane {
             );
         }
 
-        self.antialias_checkbox.draw(
-            resources,
+        resources.fonts().roman().draw(
             matrix,
-            self.new_window_options.antialiasing,
-            true,
+            AV_ANTIALIAS_FONT_SIZE,
+            Align::MidLeft,
+            self.antialias_label_position,
+            "Antialiasing:",
         );
+        for button in self.antialias_checkboxes.iter() {
+            button.draw(
+                resources,
+                matrix,
+                &self.new_window_options.antialiasing,
+            );
+        }
         self.fullscreen_checkbox.draw(
             resources,
             matrix,
             self.new_window_options.fullscreen,
             true,
         );
+        self.reduce_motion_checkbox.draw(
+            resources,
+            matrix,
+            state.prefs().reduce_motion(),
+            true,
+        );
         for button in self.resolution_checkboxes.iter() {
             button.draw(
                 resources,
@@ -431,10 +558,31 @@ impl AudioVideoPane {
         }
         self.sound_volume_slider.draw(resources, matrix);
         self.music_volume_slider.draw(resources, matrix);
+        self.duck_music_checkbox.draw(
+            resources,
+            matrix,
+            state.prefs().duck_music(),
+            true,
+        );
+        self.check_updates_checkbox.draw(
+            resources,
+            matrix,
+            state.prefs().check_for_updates(),
+            true,
+        );
+
+        resources.fonts().roman().draw(
+            matrix,
+            AV_RESET_HINT_FONT_SIZE,
+            Align::MidRight,
+            self.reset_hint_position,
+            "Right-click a setting to reset it",
+        );
 
         let enabled = self.new_window_options != self.current_window_options;
         self.apply_button.draw(resources, matrix, enabled);
         self.revert_button.draw(resources, matrix, enabled);
+        self.report_button.draw(resources, matrix, true);
     }
 
     pub fn on_event(
@@ -447,11 +595,59 @@ impl AudioVideoPane {
             self.new_window_options = self.current_window_options.clone();
         }
 
-        let antialiasing = self.new_window_options.antialiasing;
-        if let Some(checked) =
-            self.antialias_checkbox.on_event(event, ui, antialiasing, true)
+        if self
+            .antialias_checkboxes
+            .iter()
+            .any(|button| is_reset_click(event, ui, button.rect()))
+        {
+            if let PrefValue::Int(level) =
+                Prefs::default_value(PrefKey::Antialiasing)
+            {
+                self.new_window_options.antialiasing = level as u8;
+            }
+        }
+        if is_reset_click(event, ui, self.fullscreen_checkbox.rect()) {
+            self.new_window_options.fullscreen =
+                default_bool(PrefKey::Fullscreen);
+        }
+        if self
+            .resolution_checkboxes
+            .iter()
+            .any(|button| is_reset_click(event, ui, button.rect()))
         {
-            self.new_window_options.antialiasing = checked;
+            if let PrefValue::Resolution(resolution) =
+                Prefs::default_value(PrefKey::Resolution)
+            {
+                self.new_window_options.resolution = resolution;
+            }
+        }
+        if is_reset_click(event, ui, self.reduce_motion_checkbox.rect()) {
+            state.prefs_mut().reset(PrefKey::ReduceMotion);
+        }
+        if is_reset_click(event, ui, self.sound_volume_slider.rect()) {
+            state.prefs_mut().reset(PrefKey::SoundVolume);
+            let volume = state.prefs().sound_volume_percent();
+            self.sound_volume_slider.set_value(volume);
+            ui.audio().set_sound_volume_percent(volume);
+        }
+        if is_reset_click(event, ui, self.music_volume_slider.rect()) {
+            state.prefs_mut().reset(PrefKey::MusicVolume);
+            let volume = state.prefs().music_volume_percent();
+            self.music_volume_slider.set_value(volume);
+            ui.audio().set_music_volume_percent(volume);
+        }
+        if is_reset_click(event, ui, self.duck_music_checkbox.rect()) {
+            state.prefs_mut().reset(PrefKey::DuckMusic);
+        }
+        if is_reset_click(event, ui, self.check_updates_checkbox.rect()) {
+            state.prefs_mut().reset(PrefKey::CheckForUpdates);
+        }
+
+        let antialiasing = self.new_window_options.antialiasing;
+        for button in self.antialias_checkboxes.iter_mut() {
+            if let Some(level) = button.on_event(event, ui, &antialiasing) {
+                self.new_window_options.antialiasing = level;
+            }
         }
 
         if let Some(checked) = self.fullscreen_checkbox.on_event(
@@ -463,6 +659,15 @@ impl AudioVideoPane {
             self.new_window_options.fullscreen = checked;
         }
 
+        if let Some(checked) = self.reduce_motion_checkbox.on_event(
+            event,
+            ui,
+            state.prefs().reduce_motion(),
+            true,
+        ) {
+            state.prefs_mut().set_reduce_motion(checked);
+        }
+
         let resolution = self.new_window_options.resolution;
         for button in self.resolution_checkboxes.iter_mut() {
             if let Some(new_res) = button.on_event(event, ui, &resolution) {
@@ -490,6 +695,24 @@ impl AudioVideoPane {
             None => {}
         }
 
+        if let Some(checked) = self.duck_music_checkbox.on_event(
+            event,
+            ui,
+            state.prefs().duck_music(),
+            true,
+        ) {
+            state.prefs_mut().set_duck_music(checked);
+        }
+
+        if let Some(checked) = self.check_updates_checkbox.on_event(
+            event,
+            ui,
+            state.prefs().check_for_updates(),
+            true,
+        ) {
+            state.prefs_mut().set_check_for_updates(checked);
+        }
+
         let enabled = self.new_window_options != self.current_window_options;
         if let Some(()) = self.revert_button.on_event(event, ui, enabled) {
             self.new_window_options = self.current_window_options.clone();
@@ -502,6 +725,9 @@ impl AudioVideoPane {
             let options = self.new_window_options.clone();
             return Some(PrefsAction::RebootWindow(options));
         }
+        if let Some(()) = self.report_button.on_event(event, ui, true) {
+            return Some(PrefsAction::ReportIssue);
+        }
         return None;
     }
 }
@@ -524,7 +750,10 @@ impl HotkeysPane {
         for &(name, hotkeys) in HOTKEY_CATEGORIES.iter() {
             let section_height = HOTKEY_CATEGORY_LABEL_STRIDE
                 + HOTKEY_BOX_STRIDE * (hotkeys.len() as i32);
-            if rect.bottom() - HOTKEY_FRAME_PADDING - top < section_height {
+            // Leave room at the bottom for the defaults button.
+            let bottom =
+                rect.bottom() - HOTKEY_FRAME_PADDING - HOTKEY_BUTTON_HEIGHT;
+            if bottom - top < section_height {
                 left = rect.x + rect.width / 2;
                 top = rect.y + HOTKEY_FRAME_PADDING;
             }
@@ -630,6 +859,305 @@ impl HotkeysPane {
 
 //===========================================================================//
 
+pub struct GameplayPane {
+    rect: Rect<i32>,
+    auto_route_checkbox: Checkbox,
+    unconnected_ports_checkbox: Checkbox,
+    wire_sizes_checkbox: Checkbox,
+    jump_to_failures_checkbox: Checkbox,
+    idle_pause_checkbox: Checkbox,
+    label_images_checkbox: Checkbox,
+    skip_cutscenes_checkbox: Checkbox,
+    double_click_slider: Slider,
+    snapshot_slots_slider: Slider,
+}
+
+impl GameplayPane {
+    pub fn new(rect: Rect<i32>, prefs: &Prefs) -> GameplayPane {
+        let left = rect.x + GAMEPLAY_FRAME_PADDING;
+        let top = rect.y + GAMEPLAY_FRAME_PADDING;
+        let stride = CHECKBOX_HEIGHT + GAMEPLAY_CHECKBOX_SPACING;
+        let checkbox = |row: i32, label: &str| {
+            Checkbox::new(
+                Point2::new(left, top + row * stride),
+                label.to_string(),
+            )
+        };
+        let auto_route_checkbox =
+            checkbox(0, "Auto-route wires between ports");
+        let unconnected_ports_checkbox =
+            checkbox(1, "Highlight unconnected ports");
+        let wire_sizes_checkbox = checkbox(2, "Emphasize wire sizes");
+        let jump_to_failures_checkbox = checkbox(3, "Move camera to failures");
+        let idle_pause_checkbox = checkbox(4, "Pause runs when idle");
+        let label_images_checkbox = checkbox(5, "Label exported images");
+        let skip_cutscenes_checkbox = checkbox(6, "Allow skipping cutscenes");
+        let slider_left =
+            rect.x + rect.width / 2 + GAMEPLAY_SLIDER_LABEL_WIDTH;
+        let slider_width = rect.right()
+            - GAMEPLAY_FRAME_PADDING
+            - GAMEPLAY_SLIDER_VALUE_WIDTH
+            - slider_left;
+        let double_click_slider = Slider::with_range(
+            Rect::new(slider_left, top, slider_width, CHECKBOX_HEIGHT),
+            step_for_double_click_ms(prefs.double_click_ms()),
+            step_for_double_click_ms(MAX_DOUBLE_CLICK_MS),
+            "Double-click".to_string(),
+            double_click_step_label,
+        );
+        let snapshot_slots_slider = Slider::with_range(
+            Rect::new(
+                slider_left,
+                top + stride,
+                slider_width,
+                CHECKBOX_HEIGHT,
+            ),
+            step_for_snapshot_slots(prefs.snapshot_slots()),
+            step_for_snapshot_slots(MAX_SNAPSHOT_SLOTS),
+            "Snapshots".to_string(),
+            snapshot_slots_step_label,
+        );
+        GameplayPane {
+            rect,
+            auto_route_checkbox,
+            unconnected_ports_checkbox,
+            wire_sizes_checkbox,
+            jump_to_failures_checkbox,
+            idle_pause_checkbox,
+            label_images_checkbox,
+            skip_cutscenes_checkbox,
+            double_click_slider,
+            snapshot_slots_slider,
+        }
+    }
+
+    pub fn draw(
+        &self,
+        resources: &Resources,
+        matrix: &Matrix4<f32>,
+        state: &GameState,
+    ) {
+        resources.shaders().shadow().rect_shadow_basic(
+            matrix,
+            self.rect.as_f32(),
+            Color3::CYAN1,
+        );
+        resources.shaders().ui().draw_bubble(
+            matrix,
+            &self.rect.as_f32(),
+            &Color4::CYAN1,
+            &Color4::ORANGE1,
+            &Color4::PURPLE0_TRANSLUCENT,
+        );
+        self.unconnected_ports_checkbox.draw(
+            resources,
+            matrix,
+            state.prefs().highlight_unconnected_ports(),
+            true,
+        );
+        self.skip_cutscenes_checkbox.draw(
+            resources,
+            matrix,
+            state.prefs().allow_skip_cutscenes(),
+            true,
+        );
+        self.jump_to_failures_checkbox.draw(
+            resources,
+            matrix,
+            state.prefs().jump_to_failures(),
+            true,
+        );
+        self.wire_sizes_checkbox.draw(
+            resources,
+            matrix,
+            state.prefs().emphasize_wire_sizes(),
+            true,
+        );
+        self.idle_pause_checkbox.draw(
+            resources,
+            matrix,
+            state.prefs().idle_pause_seconds() > 0,
+            true,
+        );
+        self.label_images_checkbox.draw(
+            resources,
+            matrix,
+            state.prefs().label_exported_images(),
+            true,
+        );
+        self.auto_route_checkbox.draw(
+            resources,
+            matrix,
+            state.prefs().auto_route_wires(),
+            true,
+        );
+        self.double_click_slider.draw(resources, matrix);
+        self.snapshot_slots_slider.draw(resources, matrix);
+    }
+
+    pub fn on_event(
+        &mut self,
+        event: &Event,
+        ui: &mut Ui,
+        state: &mut GameState,
+    ) -> Option<PrefsAction> {
+        let resets = [
+            (
+                self.unconnected_ports_checkbox.rect(),
+                PrefKey::HighlightUnconnectedPorts,
+            ),
+            (self.skip_cutscenes_checkbox.rect(), PrefKey::AllowSkipCutscenes),
+            (self.jump_to_failures_checkbox.rect(), PrefKey::JumpToFailures),
+            (self.wire_sizes_checkbox.rect(), PrefKey::EmphasizeWireSizes),
+            (self.idle_pause_checkbox.rect(), PrefKey::IdlePauseSeconds),
+            (self.label_images_checkbox.rect(), PrefKey::LabelExportedImages),
+            (self.auto_route_checkbox.rect(), PrefKey::AutoRouteWires),
+        ];
+        for &(rect, key) in resets.iter() {
+            if is_reset_click(event, ui, rect) {
+                state.prefs_mut().reset(key);
+            }
+        }
+        if is_reset_click(event, ui, self.double_click_slider.rect()) {
+            state.prefs_mut().reset(PrefKey::DoubleClickMs);
+            self.double_click_slider.set_value(step_for_double_click_ms(
+                state.prefs().double_click_ms(),
+            ));
+        }
+        if is_reset_click(event, ui, self.snapshot_slots_slider.rect()) {
+            state.prefs_mut().reset(PrefKey::SnapshotSlots);
+            self.snapshot_slots_slider.set_value(step_for_snapshot_slots(
+                state.prefs().snapshot_slots(),
+            ));
+        }
+
+        if let Some(checked) = self.unconnected_ports_checkbox.on_event(
+            event,
+            ui,
+            state.prefs().highlight_unconnected_ports(),
+            true,
+        ) {
+            state.prefs_mut().set_highlight_unconnected_ports(checked);
+        }
+        if let Some(checked) = self.skip_cutscenes_checkbox.on_event(
+            event,
+            ui,
+            state.prefs().allow_skip_cutscenes(),
+            true,
+        ) {
+            state.prefs_mut().set_allow_skip_cutscenes(checked);
+        }
+        if let Some(checked) = self.jump_to_failures_checkbox.on_event(
+            event,
+            ui,
+            state.prefs().jump_to_failures(),
+            true,
+        ) {
+            state.prefs_mut().set_jump_to_failures(checked);
+        }
+        if let Some(checked) = self.wire_sizes_checkbox.on_event(
+            event,
+            ui,
+            state.prefs().emphasize_wire_sizes(),
+            true,
+        ) {
+            state.prefs_mut().set_emphasize_wire_sizes(checked);
+        }
+        if let Some(checked) = self.idle_pause_checkbox.on_event(
+            event,
+            ui,
+            state.prefs().idle_pause_seconds() > 0,
+            true,
+        ) {
+            if checked {
+                state.prefs_mut().reset(PrefKey::IdlePauseSeconds);
+            } else {
+                state.prefs_mut().set_idle_pause_seconds(0);
+            }
+        }
+        if let Some(checked) = self.label_images_checkbox.on_event(
+            event,
+            ui,
+            state.prefs().label_exported_images(),
+            true,
+        ) {
+            state.prefs_mut().set_label_exported_images(checked);
+        }
+        if let Some(checked) = self.auto_route_checkbox.on_event(
+            event,
+            ui,
+            state.prefs().auto_route_wires(),
+            true,
+        ) {
+            state.prefs_mut().set_auto_route_wires(checked);
+        }
+        if let Some(SliderAction::Update(step)) =
+            self.double_click_slider.on_event(event, ui)
+        {
+            state
+                .prefs_mut()
+                .set_double_click_ms(double_click_ms_for_step(step));
+        }
+        if let Some(SliderAction::Update(step)) =
+            self.snapshot_slots_slider.on_event(event, ui)
+        {
+            state
+                .prefs_mut()
+                .set_snapshot_slots(snapshot_slots_for_step(step));
+        }
+        None
+    }
+}
+
+/// Returns true if the event is a right-click within the given rect.
+/// Right-clicking on the widget for a setting resets that setting to its
+/// default value.
+fn is_reset_click(event: &Event, ui: &mut Ui, rect: Rect<i32>) -> bool {
+    match event {
+        Event::MouseDown(mouse)
+            if mouse.right && rect.contains_point(mouse.pt) =>
+        {
+            ui.audio().play_sound(Sound::ButtonClick);
+            ui.request_redraw();
+            true
+        }
+        _ => false,
+    }
+}
+
+fn default_bool(key: PrefKey) -> bool {
+    match Prefs::default_value(key) {
+        PrefValue::Bool(value) => value,
+        value => panic!("{:?} is not a bool setting: {:?}", key, value),
+    }
+}
+
+fn step_for_double_click_ms(millis: u32) -> i32 {
+    ((millis - MIN_DOUBLE_CLICK_MS) / DOUBLE_CLICK_STEP_MS) as i32
+}
+
+fn double_click_ms_for_step(step: i32) -> u32 {
+    MIN_DOUBLE_CLICK_MS + (step.max(0) as u32) * DOUBLE_CLICK_STEP_MS
+}
+
+fn double_click_step_label(step: i32) -> String {
+    format!("{} ms", double_click_ms_for_step(step))
+}
+
+fn step_for_snapshot_slots(slots: usize) -> i32 {
+    (slots - MIN_SNAPSHOT_SLOTS) as i32
+}
+
+fn snapshot_slots_for_step(step: i32) -> usize {
+    MIN_SNAPSHOT_SLOTS + (step.max(0) as usize)
+}
+
+fn snapshot_slots_step_label(step: i32) -> String {
+    format!("{} slots", snapshot_slots_for_step(step))
+}
+
+//===========================================================================//
+
 pub struct ProfilesPane {
     profile_list: ListView<String>,
     frame_rect: Rect<f32>,
@@ -637,6 +1165,9 @@ pub struct ProfilesPane {
     summary_paragraph: Paragraph,
     switch_button: TextButton<()>,
     delete_button: TextButton<()>,
+    move_save_button: TextButton<()>,
+    pronouns_top: i32,
+    pronouns_checkboxes: Vec<RadioCheckbox<Pronouns>>,
 }
 
 impl ProfilesPane {
@@ -685,6 +1216,39 @@ impl ProfilesPane {
             "Delete Profile",
             (),
         );
+        let pronouns_top =
+            button_top - PROFILES_PRONOUNS_MARGIN_BOTTOM - CHECKBOX_HEIGHT;
+        let move_save_button = TextButton::new(
+            Rect::new(
+                frame_rect.x + PROFILES_FRAME_PADDING,
+                pronouns_top
+                    - PROFILES_PRONOUNS_MARGIN_BOTTOM
+                    - PROFILES_BUTTON_HEIGHT,
+                PROFILES_BUTTON_WIDTH,
+                PROFILES_BUTTON_HEIGHT,
+            ),
+            "Move Save Data",
+            (),
+        );
+        let pronouns_checkboxes = vec![
+            ("she/her", Pronouns::She),
+            ("he/him", Pronouns::He),
+            ("they/them", Pronouns::They),
+        ]
+        .into_iter()
+        .enumerate()
+        .map(|(index, (label, pronouns))| {
+            let left = frame_rect.x
+                + PROFILES_FRAME_PADDING
+                + PROFILES_PRONOUNS_LABEL_WIDTH
+                + (index as i32) * PROFILES_PRONOUNS_COLUMN_WIDTH;
+            RadioCheckbox::new(
+                Point2::new(left, pronouns_top),
+                label.to_string(),
+                pronouns,
+            )
+        })
+        .collect();
         let summary_paragraph = ProfilesPane::compile_summary_paragraph(
             &selected_profile_name,
             (frame_rect.width - 2 * PROFILES_FRAME_PADDING) as f32,
@@ -697,6 +1261,9 @@ impl ProfilesPane {
             summary_paragraph,
             switch_button,
             delete_button,
+            move_save_button,
+            pronouns_top,
+            pronouns_checkboxes,
         }
     }
 
@@ -760,6 +1327,21 @@ impl ProfilesPane {
             ),
         );
 
+        resources.fonts().roman().draw(
+            matrix,
+            PROFILES_PRONOUNS_FONT_SIZE,
+            Align::MidLeft,
+            (
+                title_left,
+                (self.pronouns_top as f32) + 0.5 * (CHECKBOX_HEIGHT as f32),
+            ),
+            "Pronouns:",
+        );
+        let pronouns = state.prefs().pronouns();
+        for checkbox in self.pronouns_checkboxes.iter() {
+            checkbox.draw(resources, matrix, &pronouns);
+        }
+
         self.switch_button.draw(
             resources,
             matrix,
@@ -770,6 +1352,7 @@ impl ProfilesPane {
             matrix,
             !self.selected_profile_name.is_empty(),
         );
+        self.move_save_button.draw(resources, matrix, true);
     }
 
     pub fn on_event(
@@ -778,6 +1361,13 @@ impl ProfilesPane {
         ui: &mut Ui,
         state: &mut GameState,
     ) -> Option<PrefsAction> {
+        let pronouns = state.prefs().pronouns();
+        for checkbox in self.pronouns_checkboxes.iter_mut() {
+            if let Some(new_pronouns) = checkbox.on_event(event, ui, &pronouns)
+            {
+                state.prefs_mut().set_pronouns(new_pronouns);
+            }
+        }
         debug_assert!(state.profile().is_some());
         let current_profile_name = state.profile().unwrap().name();
         if let Some(profile_name) =
@@ -813,6 +1403,9 @@ impl ProfilesPane {
                 self.selected_profile_name.clone(),
             ));
         }
+        if let Some(()) = self.move_save_button.on_event(event, ui, true) {
+            return Some(PrefsAction::MoveSaveData);
+        }
         return None;
     }
 
@@ -829,9 +1422,10 @@ impl ProfilesPane {
         } else {
             match state.load_profile(profile_name) {
                 Ok(profile) => ProfilesPane::profile_summary_format(&profile),
-                Err(error) => {
-                    format!("$R$*ERROR:$*$D {}", Paragraph::escape(&error))
-                }
+                Err(error) => format!(
+                    "$R$*ERROR:$*$D {}",
+                    Paragraph::escape(&error.to_string())
+                ),
             }
         };
         Paragraph::compile(
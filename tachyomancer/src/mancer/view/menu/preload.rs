@@ -0,0 +1,82 @@
+// +--------------------------------------------------------------------------+
+// | Copyright 2018 Matthew D. Steele <mdsteele@alum.mit.edu>                 |
+// |                                                                          |
+// | This file is part of Tachyomancer.                                       |
+// |                                                                          |
+// | Tachyomancer is free software: you can redistribute it and/or modify it  |
+// | under the terms of the GNU General Public License as published by the    |
+// | Free Software Foundation, either version 3 of the License, or (at your   |
+// | option) any later version.                                               |
+// |                                                                          |
+// | Tachyomancer is distributed in the hope that it will be useful, but      |
+// | WITHOUT ANY WARRANTY; without even the implied warranty of               |
+// | MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU        |
+// | General Public License for details.                                      |
+// |                                                                          |
+// | You should have received a copy of the GNU General Public License along  |
+// | with Tachyomancer.  If not, see <http://www.gnu.org/licenses/>.          |
+// +--------------------------------------------------------------------------+
+
+use super::super::background::{
+    background_for_chapter, BackgroundGeometry, BackgroundView,
+};
+use super::music_for_chapter;
+use crate::mancer::gui::Ui;
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+use tachy::geom::RectSize;
+use tachy::save::Chapter;
+
+//===========================================================================//
+
+/// Loads the background and music for a chapter ahead of time, so that
+/// switching to the menu for that chapter doesn't cause a frame hitch.
+pub struct AssetPreloader {
+    background: Option<(Chapter, Receiver<BackgroundGeometry>)>,
+}
+
+impl AssetPreloader {
+    pub fn new() -> AssetPreloader {
+        AssetPreloader { background: None }
+    }
+
+    /// Starts loading the assets for the given chapter on background
+    /// threads, unless they are already being preloaded.
+    pub fn preload_chapter(&mut self, chapter: Chapter, ui: &mut Ui) {
+        if let Some((preloading, _)) = self.background {
+            if preloading == chapter {
+                return;
+            }
+        }
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            let _ = sender.send(BackgroundGeometry::for_chapter(chapter));
+        });
+        self.background = Some((chapter, receiver));
+        ui.audio().preload_music(&music_for_chapter(chapter));
+    }
+
+    /// Creates the background view for the given chapter, using the
+    /// preloaded geometry if it's ready, or else generating it right now.
+    pub(super) fn take_background(
+        &mut self,
+        chapter: Chapter,
+        screen_size: RectSize<f32>,
+    ) -> Box<dyn BackgroundView> {
+        if let Some((preloading, receiver)) = self.background.take() {
+            if preloading == chapter {
+                if let Ok(geometry) = receiver.try_recv() {
+                    return geometry.into_view(screen_size);
+                }
+                debug_log!(
+                    "Background for {:?} isn't done preloading; \
+                     generating it now",
+                    chapter
+                );
+            }
+        }
+        background_for_chapter(chapter, screen_size)
+    }
+}
+
+//===========================================================================//
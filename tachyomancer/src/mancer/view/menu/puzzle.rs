@@ -31,7 +31,7 @@ use crate::mancer::state::GameState;
 use cgmath::{vec2, Matrix4};
 use std::cell::RefCell;
 use tachy::geom::{AsFloat, Color3, Color4, Rect, RectSize};
-use tachy::save::{Conversation, Puzzle, PuzzleKind};
+use tachy::save::{Conversation, Puzzle, PuzzleKind, PuzzleOptimality};
 use tachy::state::{EditGrid, PuzzleExt, WireColor};
 
 //===========================================================================//
@@ -41,12 +41,25 @@ const CIRCUIT_LIST_WIDTH: i32 = 240;
 const ELEMENT_SPACING: i32 = 18;
 const PUZZLE_LIST_WIDTH: i32 = 250;
 
+const FILTER_BUTTON_HEIGHT: i32 = 40;
+const NEXT_UNSOLVED_BUTTON_HEIGHT: i32 = 40;
+
+const FILTERS: &[(Option<PuzzleKind>, &str)] = &[
+    (None, "Filter: All"),
+    (Some(PuzzleKind::Tutorial), "Filter: Tutorial"),
+    (Some(PuzzleKind::Fabricate), "Filter: Fabricate"),
+    (Some(PuzzleKind::Automate), "Filter: Automate"),
+    (Some(PuzzleKind::Command), "Filter: Command"),
+    (Some(PuzzleKind::Sandbox), "Filter: Sandbox"),
+];
+
 const DESCRIPTION_FONT_SIZE: f32 = 20.0;
 const DESCRIPTION_INNER_MARGIN_HORZ: f32 = 14.0;
 const DESCRIPTION_INNER_MARGIN_VERT: f32 = 10.0;
 const DESCRIPTION_LINE_HEIGHT: f32 = 22.0;
 const DESCRIPTION_TITLE_FONT_SIZE: f32 = 26.0;
 const DESCRIPTION_TITLE_MARGIN_BOTTOM: f32 = 14.0;
+const TIME_SPENT_FONT_SIZE: f32 = 18.0;
 
 const GRAPH_INNER_MARGIN: f32 = 12.0;
 
@@ -61,13 +74,18 @@ pub enum PuzzlesAction {
     Copy,
     Delete,
     Edit,
+    Import,
     Rename,
+    Share,
 }
 
 //===========================================================================//
 
 pub struct PuzzlesView {
+    filter_buttons: Vec<TextButton<()>>,
     puzzle_list: ListView<Puzzle>,
+    next_unsolved_button: TextButton<()>,
+    all_solved_button: TextButton<()>,
     circuit_list: ListView<String>,
     back_button: TextButton<()>,
     description: DescriptionPanel,
@@ -77,6 +95,8 @@ pub struct PuzzlesView {
     rename_button: TextButton<PuzzlesAction>,
     copy_button: TextButton<PuzzlesAction>,
     delete_button: TextButton<PuzzlesAction>,
+    share_button: TextButton<PuzzlesAction>,
+    import_button: TextButton<PuzzlesAction>,
 }
 
 impl PuzzlesView {
@@ -86,12 +106,25 @@ impl PuzzlesView {
         state: &GameState,
     ) -> PuzzlesView {
         let semi_height = (rect.height - ELEMENT_SPACING) / 2;
-        let button_height = (semi_height - 3 * ELEMENT_SPACING) / 4;
+        let button_height = (semi_height - 5 * ELEMENT_SPACING) / 6;
         let buttons_left = rect.right() - BUTTON_WIDTH;
         let buttons_top = rect.y + semi_height + ELEMENT_SPACING;
 
-        let puzzle_list_rect =
-            Rect::new(rect.x, rect.y, PUZZLE_LIST_WIDTH, rect.height);
+        let filter_button_rect =
+            Rect::new(rect.x, rect.y, PUZZLE_LIST_WIDTH, FILTER_BUTTON_HEIGHT);
+        let next_button_rect = Rect::new(
+            rect.x,
+            rect.bottom() - NEXT_UNSOLVED_BUTTON_HEIGHT,
+            PUZZLE_LIST_WIDTH,
+            NEXT_UNSOLVED_BUTTON_HEIGHT,
+        );
+        let puzzle_list_top = filter_button_rect.bottom() + ELEMENT_SPACING;
+        let puzzle_list_rect = Rect::new(
+            rect.x,
+            puzzle_list_top,
+            PUZZLE_LIST_WIDTH,
+            next_button_rect.y - ELEMENT_SPACING - puzzle_list_top,
+        );
         let graph_rect = Rect::new(
             rect.right() - semi_height,
             rect.y,
@@ -136,14 +169,36 @@ impl PuzzlesView {
         button_rect.y += button_height + ELEMENT_SPACING;
         let delete_button =
             TextButton::new(button_rect, "Delete", PuzzlesAction::Delete);
+        button_rect.y += button_height + ELEMENT_SPACING;
+        let share_button =
+            TextButton::new(button_rect, "Share", PuzzlesAction::Share);
+        button_rect.y += button_height + ELEMENT_SPACING;
+        let import_button =
+            TextButton::new(button_rect, "Import", PuzzlesAction::Import);
 
         PuzzlesView {
+            filter_buttons: FILTERS
+                .iter()
+                .map(|&(_, label)| {
+                    TextButton::new(filter_button_rect, label, ())
+                })
+                .collect(),
             puzzle_list: ListView::new(
                 puzzle_list_rect,
                 ui,
                 puzzle_list_items(state),
                 &state.current_puzzle(),
             ),
+            next_unsolved_button: TextButton::new(
+                next_button_rect,
+                "Next Unsolved",
+                (),
+            ),
+            all_solved_button: TextButton::new(
+                next_button_rect,
+                "All Solved!",
+                (),
+            ),
             circuit_list: ListView::new(
                 circuit_list_rect,
                 ui,
@@ -157,6 +212,8 @@ impl PuzzlesView {
             rename_button,
             copy_button,
             delete_button,
+            share_button,
+            import_button,
             back_button: TextButton::new(back_button_rect, "Back", ()),
         }
     }
@@ -175,8 +232,25 @@ impl PuzzlesView {
         matrix: &Matrix4<f32>,
         state: &GameState,
     ) {
+        let filter_index = filter_index(state.puzzle_filter());
+        self.filter_buttons[filter_index].draw(resources, matrix, true);
         let puzzle = state.current_puzzle();
-        self.puzzle_list.draw(resources, matrix, &puzzle);
+        self.puzzle_list.draw_with_badges(
+            resources,
+            matrix,
+            &puzzle,
+            |&puzzle| {
+                let global_scores = resources.global_scores_for(puzzle);
+                state.puzzle_optimality(puzzle, &global_scores)
+                    == PuzzleOptimality::Optimal
+            },
+        );
+        if state.first_unsolved_puzzle().is_some() {
+            self.next_unsolved_button.draw(resources, matrix, true);
+        } else {
+            let enabled = state.first_incomplete_conversation().is_some();
+            self.all_solved_button.draw(resources, matrix, enabled);
+        }
         self.description.draw(resources, matrix, state);
         self.back_button.draw(resources, matrix, true);
         self.graph.draw(resources, matrix, state);
@@ -187,6 +261,8 @@ impl PuzzlesView {
         let enabled = self.copy_and_delete_enabled(state);
         self.copy_button.draw(resources, matrix, enabled);
         self.delete_button.draw(resources, matrix, enabled);
+        self.share_button.draw(resources, matrix, enabled);
+        self.import_button.draw(resources, matrix, true);
     }
 
     pub fn on_event(
@@ -195,6 +271,11 @@ impl PuzzlesView {
         ui: &mut Ui,
         state: &mut GameState,
     ) -> Option<PuzzlesAction> {
+        let filter_index = filter_index(state.puzzle_filter());
+        let filter_button = &mut self.filter_buttons[filter_index];
+        if let Some(()) = filter_button.on_event(event, ui, true) {
+            self.cycle_filter(ui, state);
+        }
         if let Some(puzzle) =
             self.puzzle_list.on_event(event, ui, &state.current_puzzle())
         {
@@ -208,6 +289,27 @@ impl PuzzlesView {
             state.set_circuit_name(circuit_name);
             ui.request_redraw();
         }
+        if let Some(puzzle) = state.first_unsolved_puzzle() {
+            if let Some(()) =
+                self.next_unsolved_button.on_event(event, ui, true)
+            {
+                state.set_current_puzzle(puzzle);
+                self.update_puzzle_list(ui, state);
+                self.update_circuit_list(ui, state);
+            }
+        } else {
+            // If every unlocked puzzle has been solved, point the player
+            // towards the next conversation, which may unlock more.
+            let next_conv = state.first_incomplete_conversation();
+            let enabled = next_conv.is_some();
+            if let Some(()) =
+                self.all_solved_button.on_event(event, ui, enabled)
+            {
+                if let Some(conv) = next_conv {
+                    return Some(PuzzlesAction::GoToConversation(conv));
+                }
+            }
+        }
         self.graph.on_event(event, ui);
         if let Some(()) = self.back_button.on_event(event, ui, true) {
             let puzzle = state.current_puzzle();
@@ -230,9 +332,43 @@ impl PuzzlesView {
         if let Some(action) = self.delete_button.on_event(event, ui, enabled) {
             return Some(action);
         }
+        if let Some(action) = self.share_button.on_event(event, ui, enabled) {
+            return Some(action);
+        }
+        if let Some(action) = self.import_button.on_event(event, ui, true) {
+            return Some(action);
+        }
         return None;
     }
 
+    /// Switches the puzzle list to the next filter that matches at least one
+    /// unlocked puzzle.
+    fn cycle_filter(&mut self, ui: &mut Ui, state: &mut GameState) {
+        let index = filter_index(state.puzzle_filter());
+        let mut next = None;
+        for offset in 1..FILTERS.len() {
+            let filter = FILTERS[(index + offset) % FILTERS.len()].0;
+            if Puzzle::all().any(|puzzle| {
+                state.is_puzzle_unlocked(puzzle)
+                    && passes_filter(puzzle, filter)
+            }) {
+                next = filter;
+                break;
+            }
+        }
+        state.set_puzzle_filter(next);
+        if !passes_filter(state.current_puzzle(), next) {
+            let first = Puzzle::all().find(|&puzzle| {
+                state.is_puzzle_unlocked(puzzle) && passes_filter(puzzle, next)
+            });
+            if let Some(puzzle) = first {
+                state.set_current_puzzle(puzzle);
+                self.update_circuit_list(ui, state);
+            }
+        }
+        self.update_puzzle_list(ui, state);
+    }
+
     pub fn update_circuit_list(&mut self, ui: &mut Ui, state: &GameState) {
         self.circuit_list.set_items(
             ui,
@@ -269,8 +405,11 @@ fn circuit_list_items(
 fn puzzle_list_items(
     state: &GameState,
 ) -> Vec<(Puzzle, String, bool, Option<ListIcon>)> {
+    let filter = state.puzzle_filter();
     Puzzle::all()
-        .filter(|&puzzle| state.is_puzzle_unlocked(puzzle))
+        .filter(|&puzzle| {
+            state.is_puzzle_unlocked(puzzle) && passes_filter(puzzle, filter)
+        })
         .map(|puzzle| {
             let label = puzzle.title().to_string();
             let icon = match puzzle.kind() {
@@ -285,6 +424,17 @@ fn puzzle_list_items(
         .collect()
 }
 
+fn passes_filter(puzzle: Puzzle, filter: Option<PuzzleKind>) -> bool {
+    match filter {
+        Some(kind) => puzzle.kind() == kind,
+        None => true,
+    }
+}
+
+fn filter_index(filter: Option<PuzzleKind>) -> usize {
+    FILTERS.iter().position(|&(kind, _)| kind == filter).unwrap_or(0)
+}
+
 //===========================================================================//
 
 struct DescriptionCache {
@@ -371,6 +521,7 @@ impl DescriptionPanel {
             resources,
             fbo_size,
             puzzle,
+            state.puzzle_seconds_spent(puzzle),
             state.prefs(),
         );
         fbo.unbind(resources.window_size());
@@ -381,6 +532,7 @@ impl DescriptionPanel {
         resources: &Resources,
         fbo_size: RectSize<f32>,
         puzzle: Puzzle,
+        seconds_spent: f64,
         prefs: &Prefs,
     ) {
         let matrix = cgmath::ortho(
@@ -420,6 +572,28 @@ impl DescriptionPanel {
         let paragraph_top =
             DESCRIPTION_TITLE_FONT_SIZE + DESCRIPTION_TITLE_MARGIN_BOTTOM;
         paragraph.draw(resources, &matrix, (0.0, paragraph_top));
+        if seconds_spent > 0.0 {
+            resources.fonts().roman().draw(
+                &matrix,
+                TIME_SPENT_FONT_SIZE,
+                Align::BottomRight,
+                (fbo_size.width, fbo_size.height),
+                &format!("Time spent: {}", format_time_spent(seconds_spent)),
+            );
+        }
+    }
+}
+
+/// Formats a duration for the "time spent" line of the description panel,
+/// e.g. "2h 05m", "17m", or "<1m".
+fn format_time_spent(seconds: f64) -> String {
+    let minutes = (seconds / 60.0).floor() as u64;
+    if minutes == 0 {
+        "<1m".to_string()
+    } else if minutes < 60 {
+        format!("{}m", minutes)
+    } else {
+        format!("{}h {:02}m", minutes / 60, minutes % 60)
     }
 }
 
@@ -695,11 +869,11 @@ impl CircuitPreviewPanel {
             WireModel::draw_fragment(
                 resources,
                 &grid_matrix,
-                coords,
-                dir,
+                (coords, dir),
                 shape,
                 color,
                 size,
+                false,
                 &Color4::TRANSPARENT,
             );
         }
@@ -33,6 +33,6 @@ mod wire;
 pub use self::begin::{BeginAction, BeginView};
 pub use self::circuit::{export_circuit_image, CircuitAction, CircuitView};
 pub use self::cutscene::{CutsceneAction, CutsceneView};
-pub use self::menu::{MenuAction, MenuView};
+pub use self::menu::{AssetPreloader, MenuAction, MenuView};
 
 //===========================================================================//
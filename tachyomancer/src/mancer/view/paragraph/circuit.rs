@@ -194,11 +194,11 @@ impl CompiledPiece for CompiledCircuitPiece {
             WireModel::draw_fragment(
                 resources,
                 &grid_matrix,
-                coords,
-                dir,
+                (coords, dir),
                 shape,
                 info.color,
                 size,
+                false,
                 &Color4::TRANSPARENT,
             );
         }
@@ -77,6 +77,8 @@ impl Paragraph {
     ///     * "Right-click", which turns into the equivalent action depending
     ///       on the platform (e.g. "Control-click" on MacOS).
     ///     * "YOURNAME", which turns into the name of the current profile.
+    ///     * "THEY", "THEM", and "THEIR", which turn into the player's chosen
+    ///       subject pronoun, object pronoun, and possessive determiner.
     /// * `$|o-c|`, where `o` is an orientation and `c` is a chip type, inserts
     ///   an image of that chip.
     /// * `$#t#`, where `t` is TOML for a circuit, inserts an image of that
@@ -273,7 +275,7 @@ impl StreamingParagraph {
 mod tests {
     use super::Paragraph;
     use crate::mancer::font::Font;
-    use crate::mancer::save::Prefs;
+    use crate::mancer::save::{Prefs, Pronouns};
 
     #[cfg_attr(rustfmt, rustfmt_skip)]
     fn get_lines(paragraph: &Paragraph) -> Vec<String> {
@@ -356,6 +358,31 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn pronoun_phrases() {
+        let size = 20.0;
+        let width = (60.0 * size * Font::Roman.ratio()).ceil();
+        let mut prefs = Prefs::for_testing();
+        let format = "Ask $'THEM' if $'THEY' brought $'THEIR' badge.";
+        let paragraph = Paragraph::compile(size, size, width, &prefs, format);
+        assert_eq!(
+            get_lines(&paragraph),
+            vec!["Ask them if they brought their badge."]
+        );
+        prefs.set_pronouns(Pronouns::She);
+        let paragraph = Paragraph::compile(size, size, width, &prefs, format);
+        assert_eq!(
+            get_lines(&paragraph),
+            vec!["Ask her if she brought her badge."]
+        );
+        prefs.set_pronouns(Pronouns::He);
+        let paragraph = Paragraph::compile(size, size, width, &prefs, format);
+        assert_eq!(
+            get_lines(&paragraph),
+            vec!["Ask him if he brought his badge."]
+        );
+    }
 }
 
 //===========================================================================//
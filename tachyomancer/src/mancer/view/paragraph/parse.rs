@@ -21,7 +21,7 @@ use super::circuit::ParserCircuitPiece;
 use super::compile::Compiler;
 use super::text::ParserTextPiece;
 use super::types::{CompiledLine, ParserAlign, ParserPiece, ParserPieceSplit};
-use crate::mancer::font::Font;
+use crate::mancer::font::{char_to_glyph, text_to_glyphs, Font};
 use crate::mancer::save::{Hotkey, Prefs};
 use std::mem;
 use std::str::FromStr;
@@ -63,11 +63,11 @@ impl Parser {
     }
 
     pub fn push_char(&mut self, chr: char) {
-        self.current_piece.push(chr as u8);
+        self.current_piece.extend(char_to_glyph(chr));
     }
 
     fn push_str(&mut self, string: &str) {
-        self.current_piece.extend(string.chars().map(|chr| chr as u8));
+        self.current_piece.extend(text_to_glyphs(string));
     }
 
     pub fn push_phrase(&mut self, phrase_name: &str, prefs: &Prefs) {
@@ -89,6 +89,9 @@ impl Parser {
                 }
             }
             "YOURNAME" => prefs.current_profile().unwrap_or("YOURNAME"),
+            "THEY" => prefs.pronouns().subject(),
+            "THEM" => prefs.pronouns().object(),
+            "THEIR" => prefs.pronouns().possessive(),
             _ => {
                 debug_warn!(
                     "Bad phrase name {:?} in paragraph format string",
@@ -19,9 +19,10 @@
 
 use super::paragraph::Paragraph;
 use crate::mancer::gui::{Event, Resources, Ui};
-use crate::mancer::save::Prefs;
+use crate::mancer::save::{Hotkey, HotkeyCodeExt, Prefs};
 use cgmath::{Matrix4, Point2};
 use tachy::geom::{Color3, Color4, Rect, RectSize};
+use tachy::save::HotkeyCode;
 
 //===========================================================================//
 
@@ -32,6 +33,10 @@ const TOOLTIP_INNER_MARGIN: f32 = 10.0;
 const TOOLTIP_MAX_WIDTH: f32 = 400.0;
 const TOOLTIP_OUTER_MARGIN: f32 = 14.0;
 
+// The maximum number of tooltips that can be pinned at once; pinning another
+// tooltip beyond this will unpin the oldest one:
+const MAX_PINNED_TOOLTIPS: usize = 4;
+
 //===========================================================================//
 
 pub trait TooltipSink<T> {
@@ -67,47 +72,72 @@ pub struct Tooltip<T> {
     window_size: RectSize<i32>,
     hover: Option<(T, Point2<i32>, f64)>,
     paragraph: Option<Paragraph>,
+    pinned: Vec<(T, Point2<i32>, Paragraph)>,
     locked: bool,
 }
 
 impl<T: PartialEq> Tooltip<T> {
     pub fn new(window_size: RectSize<i32>) -> Tooltip<T> {
-        Tooltip { window_size, hover: None, paragraph: None, locked: true }
+        Tooltip {
+            window_size,
+            hover: None,
+            paragraph: None,
+            pinned: Vec::new(),
+            locked: true,
+        }
+    }
+
+    /// Returns the tag whose tooltip is currently being displayed, if any.
+    pub fn active_tag(&self) -> Option<&T> {
+        match (&self.hover, &self.paragraph) {
+            (Some((tag, _, _)), Some(_)) => Some(tag),
+            _ => None,
+        }
     }
 
     pub fn draw(&self, resources: &Resources, matrix: &Matrix4<f32>) {
+        for (_, pt, paragraph) in self.pinned.iter() {
+            self.draw_paragraph(resources, matrix, *pt, paragraph);
+        }
         if let Some((_, pt, _)) = self.hover {
             if let Some(ref paragraph) = self.paragraph {
-                let width = paragraph.width() + 2.0 * TOOLTIP_INNER_MARGIN;
-                let height = paragraph.height() + 2.0 * TOOLTIP_INNER_MARGIN;
-                let left = (pt.x as f32)
-                    - width * (pt.x as f32) / (self.window_size.width as f32);
-                let top = if pt.y > self.window_size.height / 2 {
-                    (pt.y as f32) - (height + TOOLTIP_OUTER_MARGIN)
-                } else {
-                    (pt.y as f32) + TOOLTIP_OUTER_MARGIN
-                };
-                let rect = Rect::new(left, top, width, height);
-                let ui = resources.shaders().ui();
-                ui.draw_box2(
-                    matrix,
-                    &rect,
-                    &Color4::ORANGE2,
-                    &Color4::CYAN2,
-                    &Color3::PURPLE0.with_alpha(0.9),
-                );
-                paragraph.draw(
-                    resources,
-                    matrix,
-                    (
-                        rect.x + TOOLTIP_INNER_MARGIN,
-                        rect.y + TOOLTIP_INNER_MARGIN,
-                    ),
-                );
+                self.draw_paragraph(resources, matrix, pt, paragraph);
             }
         }
     }
 
+    fn draw_paragraph(
+        &self,
+        resources: &Resources,
+        matrix: &Matrix4<f32>,
+        pt: Point2<i32>,
+        paragraph: &Paragraph,
+    ) {
+        let width = paragraph.width() + 2.0 * TOOLTIP_INNER_MARGIN;
+        let height = paragraph.height() + 2.0 * TOOLTIP_INNER_MARGIN;
+        let left = (pt.x as f32)
+            - width * (pt.x as f32) / (self.window_size.width as f32);
+        let top = if pt.y > self.window_size.height / 2 {
+            (pt.y as f32) - (height + TOOLTIP_OUTER_MARGIN)
+        } else {
+            (pt.y as f32) + TOOLTIP_OUTER_MARGIN
+        };
+        let rect = Rect::new(left, top, width, height);
+        let ui = resources.shaders().ui();
+        ui.draw_box2(
+            matrix,
+            &rect,
+            &Color4::ORANGE2,
+            &Color4::CYAN2,
+            &Color3::PURPLE0.with_alpha(0.9),
+        );
+        paragraph.draw(
+            resources,
+            matrix,
+            (rect.x + TOOLTIP_INNER_MARGIN, rect.y + TOOLTIP_INNER_MARGIN),
+        );
+    }
+
     pub fn on_event<F>(
         &mut self,
         event: &Event,
@@ -120,8 +150,16 @@ impl<T: PartialEq> Tooltip<T> {
         self.locked = false;
         match event {
             Event::ClockTick(tick) => {
+                let pinned = &self.pinned;
                 if self.paragraph.is_none() {
                     if let Some((ref tag, _, ref mut time)) = self.hover {
+                        // Don't show a second copy of a pinned tooltip.
+                        if pinned
+                            .iter()
+                            .any(|(pinned_tag, _, _)| pinned_tag == tag)
+                        {
+                            return;
+                        }
                         *time = (*time + tick.elapsed).min(TOOLTIP_HOVER_TIME);
                         if *time >= TOOLTIP_HOVER_TIME {
                             self.paragraph = Some(Paragraph::compile(
@@ -136,6 +174,13 @@ impl<T: PartialEq> Tooltip<T> {
                     }
                 }
             }
+            Event::KeyDown(key)
+                if HotkeyCode::from_keycode(key.code)
+                    .and_then(|code| prefs.hotkey_for_code(code))
+                    == Some(Hotkey::PinTooltip) =>
+            {
+                self.toggle_pin(ui);
+            }
             Event::KeyDown(_) => self.hover_none(ui),
             Event::MouseDown(_) => self.hover_none(ui),
             Event::MouseMove(mouse) if mouse.left || mouse.right => {
@@ -148,6 +193,35 @@ impl<T: PartialEq> Tooltip<T> {
         }
     }
 
+    /// Pins the currently-displayed tooltip in place, so that it remains
+    /// visible while other things are hovered.  If the hovered tag's tooltip
+    /// is already pinned, unpins it instead; if nothing is hovered, unpins
+    /// all tooltips.
+    fn toggle_pin(&mut self, ui: &mut Ui) {
+        if let Some((tag, pt, time)) = self.hover.take() {
+            if let Some(index) = self
+                .pinned
+                .iter()
+                .position(|(pinned_tag, _, _)| pinned_tag == &tag)
+            {
+                self.pinned.remove(index);
+                self.hover = Some((tag, pt, time));
+                ui.request_redraw();
+            } else if let Some(paragraph) = self.paragraph.take() {
+                if self.pinned.len() >= MAX_PINNED_TOOLTIPS {
+                    self.pinned.remove(0);
+                }
+                self.pinned.push((tag, pt, paragraph));
+                ui.request_redraw();
+            } else {
+                self.hover = Some((tag, pt, time));
+            }
+        } else if !self.pinned.is_empty() {
+            self.pinned.clear();
+            ui.request_redraw();
+        }
+    }
+
     pub fn sink<A, F: Fn(A) -> T>(&mut self, func: F) -> TooltipRef<T, F> {
         TooltipRef { tooltip: self, func }
     }
@@ -18,6 +18,7 @@
 // +--------------------------------------------------------------------------+
 
 use crate::mancer::gui::Resources;
+use crate::mancer::shader::EMPHASIZED_SIZE_INDEX_OFFSET;
 use cgmath::Matrix4;
 use tachy::geom::{Color3, Color4, Coords, Direction, MatrixExt};
 use tachy::save::{WireShape, WireSize};
@@ -32,26 +33,29 @@ const WIRE_COLOR_UNKNOWN: Color3 = Color3::new(0.65, 0.65, 0.65);
 pub struct WireModel {}
 
 impl WireModel {
-    /// The matrix should go from grid space to GL clip space.
+    /// The matrix should go from grid space to GL clip space.  If
+    /// `emphasize_size` is true, the difference in thickness between wires of
+    /// different sizes will be exaggerated.
     pub fn draw_fragment(
         resources: &Resources,
         grid_matrix: &Matrix4<f32>,
-        coords: Coords,
-        dir: Direction,
+        (coords, dir): (Coords, Direction),
         shape: WireShape,
         color: WireColor,
         size: WireSize,
+        emphasize_size: bool,
         hilight: &Color4,
     ) {
         let shader = resources.shaders().wire();
         let matrix = grid_matrix * obj_to_grid(coords, dir);
+        let size_index = wire_size_index(size, emphasize_size);
         let texture = resources.textures().wire();
         // TODO: Tint wires grayer if not connected to a Source port.
         match (shape, dir) {
             (WireShape::Stub, _) => {
                 shader.draw_stub(
                     &matrix,
-                    wire_size_index(size),
+                    size_index,
                     wire_color(color),
                     hilight,
                     texture,
@@ -61,7 +65,7 @@ impl WireModel {
             | (WireShape::Straight, Direction::North) => {
                 shader.draw_straight(
                     &matrix,
-                    wire_size_index(size),
+                    size_index,
                     wire_color(color),
                     hilight,
                     texture,
@@ -70,7 +74,7 @@ impl WireModel {
             (WireShape::TurnLeft, _) => {
                 shader.draw_turn(
                     &matrix,
-                    wire_size_index(size),
+                    size_index,
                     wire_color(color),
                     hilight,
                     texture,
@@ -79,7 +83,7 @@ impl WireModel {
             (WireShape::SplitTee, _) => {
                 shader.draw_tee(
                     &matrix,
-                    wire_size_index(size),
+                    size_index,
                     wire_color(color),
                     hilight,
                     texture,
@@ -88,7 +92,7 @@ impl WireModel {
             (WireShape::Cross, Direction::East) => {
                 shader.draw_cross(
                     &matrix,
-                    wire_size_index(size),
+                    size_index,
                     wire_color(color),
                     hilight,
                     texture,
@@ -102,10 +106,10 @@ impl WireModel {
     pub fn draw_half_straight(
         resources: &Resources,
         grid_matrix: &Matrix4<f32>,
-        coords: Coords,
-        dir: Direction,
+        (coords, dir): (Coords, Direction),
         color: WireColor,
         size: WireSize,
+        emphasize_size: bool,
         hilight: &Color4,
     ) {
         let shader = resources.shaders().wire();
@@ -113,7 +117,7 @@ impl WireModel {
         let texture = resources.textures().wire();
         shader.draw_half_straight(
             &matrix,
-            wire_size_index(size),
+            wire_size_index(size, emphasize_size),
             wire_color(color),
             hilight,
             texture,
@@ -137,13 +141,18 @@ fn wire_color(color: WireColor) -> &'static Color3 {
     }
 }
 
-fn wire_size_index(size: WireSize) -> usize {
-    match size {
+fn wire_size_index(size: WireSize, emphasize: bool) -> usize {
+    let index = match size {
         WireSize::Zero => 0,
         WireSize::One => 1,
         WireSize::Two => 2,
         WireSize::Four => 3,
         WireSize::Eight => 4,
+    };
+    if emphasize {
+        index + EMPHASIZED_SIZE_INDEX_OFFSET
+    } else {
+        index
     }
 }
 
@@ -0,0 +1,88 @@
+// +--------------------------------------------------------------------------+
+// | Copyright 2018 Matthew D. Steele <mdsteele@alum.mit.edu>                 |
+// |                                                                          |
+// | This file is part of Tachyomancer.                                       |
+// |                                                                          |
+// | Tachyomancer is free software: you can redistribute it and/or modify it  |
+// | under the terms of the GNU General Public License as published by the    |
+// | Free Software Foundation, either version 3 of the License, or (at your   |
+// | option) any later version.                                               |
+// |                                                                          |
+// | Tachyomancer is distributed in the hope that it will be useful, but      |
+// | WITHOUT ANY WARRANTY; without even the implied warranty of               |
+// | MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU        |
+// | General Public License for details.                                      |
+// |                                                                          |
+// | You should have received a copy of the GNU General Public License along  |
+// | with Tachyomancer.  If not, see <http://www.gnu.org/licenses/>.          |
+// +--------------------------------------------------------------------------+
+
+use iron::{Handler, IronResult, Request, Response};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+//===========================================================================//
+
+pub const REQUEST_ID_HEADER: &str = "X-Request-Id";
+
+//===========================================================================//
+
+/// Wraps another handler, tagging each request with a unique ID (returned to
+/// the client in the `X-Request-Id` response header) and logging one line per
+/// request with its method, path, remote address, status, and elapsed time.
+pub struct AccessLogger<H> {
+    inner: H,
+    id_prefix: String,
+    next_id: AtomicUsize,
+}
+
+impl<H: Handler> AccessLogger<H> {
+    pub fn new(inner: H) -> AccessLogger<H> {
+        let start_secs = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or(0);
+        AccessLogger {
+            inner,
+            id_prefix: format!("{:x}", start_secs),
+            next_id: AtomicUsize::new(1),
+        }
+    }
+
+    fn generate_request_id(&self) -> String {
+        let index = self.next_id.fetch_add(1, Ordering::Relaxed);
+        format!("{}-{:06x}", self.id_prefix, index)
+    }
+}
+
+impl<H: Handler> Handler for AccessLogger<H> {
+    fn handle(&self, request: &mut Request) -> IronResult<Response> {
+        let request_id = self.generate_request_id();
+        let start = Instant::now();
+        let mut result = self.inner.handle(request);
+        let elapsed = start.elapsed();
+        let response = match result {
+            Ok(ref mut response) => response,
+            Err(ref mut error) => &mut error.response,
+        };
+        response
+            .headers
+            .set_raw(REQUEST_ID_HEADER, vec![request_id.clone().into_bytes()]);
+        let status = match response.status {
+            Some(status) => status.to_u16().to_string(),
+            None => "-".to_string(),
+        };
+        eprintln!(
+            "INFO [{}] {} /{} from {} -> {} in {:.3}ms",
+            request_id,
+            request.method,
+            request.url.path().join("/"),
+            request.remote_addr,
+            status,
+            1e3 * elapsed.as_secs_f64(),
+        );
+        result
+    }
+}
+
+//===========================================================================//
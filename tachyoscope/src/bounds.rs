@@ -0,0 +1,140 @@
+// +--------------------------------------------------------------------------+
+// | Copyright 2018 Matthew D. Steele <mdsteele@alum.mit.edu>                 |
+// |                                                                          |
+// | This file is part of Tachyomancer.                                       |
+// |                                                                          |
+// | Tachyomancer is free software: you can redistribute it and/or modify it  |
+// | under the terms of the GNU General Public License as published by the    |
+// | Free Software Foundation, either version 3 of the License, or (at your   |
+// | option) any later version.                                               |
+// |                                                                          |
+// | Tachyomancer is distributed in the hope that it will be useful, but      |
+// | WITHOUT ANY WARRANTY; without even the implied warranty of               |
+// | MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU        |
+// | General Public License for details.                                      |
+// |                                                                          |
+// | You should have received a copy of the GNU General Public License along  |
+// | with Tachyomancer.  If not, see <http://www.gnu.org/licenses/>.          |
+// +--------------------------------------------------------------------------+
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+use tachy::save::Puzzle;
+
+//===========================================================================//
+
+/// The largest max area that a bounds override may specify.
+const MAX_OVERRIDE_AREA: i32 = 10_000;
+
+/// The largest max score that a bounds override may specify.
+const MAX_OVERRIDE_SCORE: u32 = 1_000_000;
+
+//===========================================================================//
+
+/// The per-puzzle graph bounds used by this server: each puzzle's compiled-in
+/// `graph_bounds()`, with optional overrides loaded from a config file.
+#[derive(Debug, Default)]
+pub struct GraphBounds {
+    overrides: HashMap<Puzzle, (i32, u32)>,
+}
+
+impl GraphBounds {
+    /// Loads bounds overrides from a TOML file mapping puzzle names to
+    /// `[max_area, max_score]` pairs.
+    pub fn load(path: &Path) -> Result<GraphBounds, String> {
+        let string = fs::read_to_string(path).map_err(|err| {
+            format!("Could not read bounds file {:?}: {}", path, err)
+        })?;
+        GraphBounds::deserialize_from_string(&string)
+    }
+
+    pub fn deserialize_from_string(
+        string: &str,
+    ) -> Result<GraphBounds, String> {
+        let table: BTreeMap<String, (i32, u32)> = toml::from_str(string)
+            .map_err(|err| format!("Could not parse bounds: {}", err))?;
+        let mut overrides = HashMap::new();
+        for (key, (max_area, max_score)) in table.into_iter() {
+            let puzzle = Puzzle::from_str(&key)
+                .map_err(|_| format!("Invalid puzzle key: {:?}", key))?;
+            if max_area <= 0 || max_area > MAX_OVERRIDE_AREA {
+                return Err(format!(
+                    "Max area for {:?} must be between 1 and {} (was {})",
+                    puzzle, MAX_OVERRIDE_AREA, max_area
+                ));
+            }
+            if max_score == 0 || max_score > MAX_OVERRIDE_SCORE {
+                return Err(format!(
+                    "Max score for {:?} must be between 1 and {} (was {})",
+                    puzzle, MAX_OVERRIDE_SCORE, max_score
+                ));
+            }
+            overrides.insert(puzzle, (max_area, max_score));
+        }
+        Ok(GraphBounds { overrides })
+    }
+
+    /// Returns the `(max_area, max_score)` bounds for the given puzzle.
+    pub fn get(&self, puzzle: Puzzle) -> (i32, u32) {
+        match self.overrides.get(&puzzle) {
+            Some(&bounds) => bounds,
+            None => puzzle.graph_bounds(),
+        }
+    }
+
+    /// Serializes the bounds for every puzzle (not just the overridden ones).
+    pub fn serialize_to_string(&self) -> Result<String, String> {
+        let table: BTreeMap<String, (i32, u32)> = Puzzle::all()
+            .map(|puzzle| (format!("{:?}", puzzle), self.get(puzzle)))
+            .collect();
+        toml::to_string(&table)
+            .map_err(|err| format!("Could not serialize bounds: {}", err))
+    }
+}
+
+//===========================================================================//
+
+#[cfg(test)]
+mod tests {
+    use super::GraphBounds;
+    use tachy::save::Puzzle;
+
+    #[test]
+    fn default_bounds() {
+        let bounds = GraphBounds::default();
+        for puzzle in Puzzle::all() {
+            assert_eq!(bounds.get(puzzle), puzzle.graph_bounds());
+        }
+    }
+
+    #[test]
+    fn override_bounds() {
+        let bounds =
+            GraphBounds::deserialize_from_string("TutorialOr = [40, 100]\n")
+                .unwrap();
+        assert_eq!(bounds.get(Puzzle::TutorialOr), (40, 100));
+        assert_eq!(
+            bounds.get(Puzzle::TutorialMux),
+            Puzzle::TutorialMux.graph_bounds()
+        );
+    }
+
+    #[test]
+    fn invalid_bounds() {
+        for toml in &[
+            "Foobar = [40, 100]\n",
+            "TutorialOr = [0, 100]\n",
+            "TutorialOr = [-5, 100]\n",
+            "TutorialOr = [40, 0]\n",
+            "TutorialOr = [1000000, 100]\n",
+            "TutorialOr = [40, 1000000000]\n",
+            "TutorialOr = 40\n",
+        ] {
+            assert!(GraphBounds::deserialize_from_string(toml).is_err());
+        }
+    }
+}
+
+//===========================================================================//
@@ -0,0 +1,118 @@
+// +--------------------------------------------------------------------------+
+// | Copyright 2018 Matthew D. Steele <mdsteele@alum.mit.edu>                 |
+// |                                                                          |
+// | This file is part of Tachyomancer.                                       |
+// |                                                                          |
+// | Tachyomancer is free software: you can redistribute it and/or modify it  |
+// | under the terms of the GNU General Public License as published by the    |
+// | Free Software Foundation, either version 3 of the License, or (at your   |
+// | option) any later version.                                               |
+// |                                                                          |
+// | Tachyomancer is distributed in the hope that it will be useful, but      |
+// | WITHOUT ANY WARRANTY; without even the implied warranty of               |
+// | MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU        |
+// | General Public License for details.                                      |
+// |                                                                          |
+// | You should have received a copy of the GNU General Public License along  |
+// | with Tachyomancer.  If not, see <http://www.gnu.org/licenses/>.          |
+// +--------------------------------------------------------------------------+
+
+use iron::status;
+use iron::{Handler, IronResult, Request, Response};
+
+//===========================================================================//
+
+const ALLOWED_METHODS: &str = "GET, OPTIONS";
+const PREFLIGHT_MAX_AGE_SECS: u32 = 86400;
+
+//===========================================================================//
+
+/// Wraps a read-only handler, adding an `Access-Control-Allow-Origin` header
+/// to its responses so that they can be fetched from browser scripts.
+pub struct CorsHandler<H> {
+    inner: H,
+    allowed_origin: String,
+}
+
+impl<H: Handler> CorsHandler<H> {
+    pub fn new(inner: H, allowed_origin: String) -> CorsHandler<H> {
+        CorsHandler { inner, allowed_origin }
+    }
+}
+
+impl<H: Handler> Handler for CorsHandler<H> {
+    fn handle(&self, request: &mut Request) -> IronResult<Response> {
+        let mut result = self.inner.handle(request);
+        let response = match result {
+            Ok(ref mut response) => response,
+            Err(ref mut error) => &mut error.response,
+        };
+        set_allow_origin(response, &self.allowed_origin);
+        result
+    }
+}
+
+//===========================================================================//
+
+/// Responds to CORS preflight `OPTIONS` requests for read-only endpoints.
+pub struct CorsPreflightHandler {
+    allowed_origin: String,
+}
+
+impl CorsPreflightHandler {
+    pub fn new(allowed_origin: String) -> CorsPreflightHandler {
+        CorsPreflightHandler { allowed_origin }
+    }
+}
+
+impl Handler for CorsPreflightHandler {
+    fn handle(&self, _: &mut Request) -> IronResult<Response> {
+        let mut response = Response::with(status::NoContent);
+        set_allow_origin(&mut response, &self.allowed_origin);
+        response.headers.set_raw(
+            "Access-Control-Allow-Methods",
+            vec![ALLOWED_METHODS.as_bytes().to_vec()],
+        );
+        response.headers.set_raw(
+            "Access-Control-Max-Age",
+            vec![PREFLIGHT_MAX_AGE_SECS.to_string().into_bytes()],
+        );
+        Ok(response)
+    }
+}
+
+//===========================================================================//
+
+fn set_allow_origin(response: &mut Response, allowed_origin: &str) {
+    response.headers.set_raw(
+        "Access-Control-Allow-Origin",
+        vec![allowed_origin.as_bytes().to_vec()],
+    );
+    if allowed_origin != "*" {
+        add_vary(response, "Origin");
+    }
+}
+
+/// Adds the given header name to the response's `Vary` header, keeping any
+/// names that the wrapped handler already put there (e.g. for gzip).
+fn add_vary(response: &mut Response, name: &str) {
+    let mut names: Vec<String> = response
+        .headers
+        .get_raw("Vary")
+        .into_iter()
+        .flat_map(|lines| lines.iter())
+        .flat_map(|line| {
+            String::from_utf8_lossy(line)
+                .split(',')
+                .map(|part| part.trim().to_string())
+                .collect::<Vec<String>>()
+        })
+        .filter(|part| !part.is_empty())
+        .collect();
+    if !names.iter().any(|part| part.eq_ignore_ascii_case(name)) {
+        names.push(name.to_string());
+    }
+    response.headers.set_raw("Vary", vec![names.join(", ").into_bytes()]);
+}
+
+//===========================================================================//
@@ -17,15 +17,21 @@
 // | with Tachyomancer.  If not, see <http://www.gnu.org/licenses/>.          |
 // +--------------------------------------------------------------------------+
 
-use super::shared::{hash_circuit_data, ScoreDatabase};
-use std::collections::HashMap;
+use super::shared::{
+    hash_circuit_data, hash_solution_data, RetentionMode, ScoreDatabase,
+    SubmissionStatus,
+};
+use std::collections::{HashMap, HashSet};
 use std::sync::Mutex;
 use std::time::Instant;
-use tachy::save::{CircuitData, Puzzle, ScoreCurveMap, SolutionData};
+use tachy::save::{
+    CircuitData, Puzzle, ScoreCurve, ScoreCurveMap, SolutionData,
+};
 
 //===========================================================================//
 
 pub struct InMemoryScoreDatabase {
+    mode: RetentionMode,
     storage: Mutex<Storage>,
 }
 
@@ -33,20 +39,91 @@ struct Storage {
     circuits: HashMap<String, CircuitData>,
     submissions: HashMap<(u64, Puzzle, String), Instant>,
     verified: HashMap<(Puzzle, String), (i32, u32)>,
+    // Verified solutions that `BestPerInstall` retention chose not to keep
+    // (or has since discarded), so that resubmitting them doesn't require
+    // verifying them all over again:
+    unkept: HashMap<(Puzzle, String), (i32, u32)>,
+    best_per_install: HashMap<(u64, Puzzle), String>,
+    anonymous: HashSet<(Puzzle, String)>,
+    failed: HashMap<String, Vec<String>>,
 }
 
 impl InMemoryScoreDatabase {
-    pub fn new() -> InMemoryScoreDatabase {
+    pub fn new(mode: RetentionMode) -> InMemoryScoreDatabase {
         InMemoryScoreDatabase {
+            mode,
             storage: Mutex::new(Storage {
                 circuits: HashMap::new(),
                 submissions: HashMap::new(),
                 verified: HashMap::new(),
+                unkept: HashMap::new(),
+                best_per_install: HashMap::new(),
+                anonymous: HashSet::new(),
+                failed: HashMap::new(),
             }),
         }
     }
 }
 
+impl Storage {
+    /// Records a newly-verified solution under `BestPerInstall` retention.
+    /// The solution is kept only if it is the submitting install's best
+    /// solution for its puzzle so far (anonymous solutions are always kept),
+    /// in which case it replaces that install's previous best.
+    fn insert_best_per_install(
+        &mut self,
+        install_id: Option<u64>,
+        key: (Puzzle, String),
+        area: i32,
+        score: u32,
+    ) {
+        let id = match install_id {
+            Some(id) => id,
+            None => {
+                self.unkept.remove(&key);
+                self.verified.insert(key.clone(), (area, score));
+                self.anonymous.insert(key);
+                return;
+            }
+        };
+        let (puzzle, hash) = key;
+        if let Some(best_hash) = self.best_per_install.get(&(id, puzzle)) {
+            let (best_area, best_score) =
+                self.verified[&(puzzle, best_hash.clone())];
+            if (best_score, best_area) <= (score, area) {
+                let key = (puzzle, hash);
+                if !self.verified.contains_key(&key) {
+                    self.unkept.insert(key, (area, score));
+                }
+                return;
+            }
+        }
+        self.unkept.remove(&(puzzle, hash.clone()));
+        self.verified.insert((puzzle, hash.clone()), (area, score));
+        if let Some(old_hash) =
+            self.best_per_install.insert((id, puzzle), hash)
+        {
+            self.discard_if_unused(puzzle, old_hash);
+        }
+    }
+
+    /// Removes a superseded verified solution, unless it is still some other
+    /// install's best solution (or was also submitted anonymously).
+    fn discard_if_unused(&mut self, puzzle: Puzzle, hash: String) {
+        let still_used = self.best_per_install.iter().any(
+            |(&(_, other_puzzle), other_hash)| {
+                other_puzzle == puzzle && *other_hash == hash
+            },
+        );
+        let key = (puzzle, hash);
+        if !still_used && !self.anonymous.contains(&key) {
+            if let Some(area_and_score) = self.verified.remove(&key) {
+                self.unkept.insert(key, area_and_score);
+            }
+        }
+    }
+}
+
 impl ScoreDatabase for InMemoryScoreDatabase {
     fn load_num_verified_solutions(&self) -> Result<u64, String> {
         let storage = self.storage.lock().unwrap();
@@ -62,11 +139,23 @@ impl ScoreDatabase for InMemoryScoreDatabase {
         Ok(scores)
     }
 
+    fn load_frontier(&self, puzzle: Puzzle) -> Result<ScoreCurve, String> {
+        let storage = self.storage.lock().unwrap();
+        let points = storage
+            .verified
+            .iter()
+            .filter(|&(&(other, _), _)| other == puzzle)
+            .map(|(_, &(area, score))| (area, score))
+            .collect();
+        Ok(ScoreCurve::with_scores(points))
+    }
+
     fn store_new_solution(
         &self,
         solution: &SolutionData,
-    ) -> Result<Option<(Puzzle, String)>, String> {
+    ) -> Result<SubmissionStatus, String> {
         let hash = hash_circuit_data(&solution.circuit)?;
+        let solution_hash = hash_solution_data(solution)?;
         debug_log!("Solution hash is {}", hash);
         let mut storage = self.storage.lock().unwrap();
         if !storage.circuits.contains_key(&hash) {
@@ -79,20 +168,59 @@ impl ScoreDatabase for InMemoryScoreDatabase {
                 .or_insert_with(Instant::now);
         }
         let key = (solution.puzzle, hash);
-        if storage.verified.contains_key(&key) {
-            return Ok(None);
+        let known = storage
+            .verified
+            .get(&key)
+            .or_else(|| storage.unkept.get(&key))
+            .cloned();
+        if let Some((area, score)) = known {
+            if self.mode == RetentionMode::BestPerInstall {
+                storage.insert_best_per_install(
+                    solution.install_id,
+                    key,
+                    area,
+                    score,
+                );
+            }
+            return Ok(SubmissionStatus::AlreadyVerified);
         }
-        return Ok(Some(key));
+        if let Some(errors) = storage.failed.get(&solution_hash) {
+            return Ok(SubmissionStatus::AlreadyFailed(errors.clone()));
+        }
+        return Ok(SubmissionStatus::New(key));
     }
 
     fn store_verified_solution(
         &self,
         key: (Puzzle, String),
+        install_id: Option<u64>,
         area: i32,
         score: u32,
     ) -> Result<(), String> {
         let mut storage = self.storage.lock().unwrap();
-        storage.verified.insert(key, (area, score));
+        match self.mode {
+            RetentionMode::FullHistory => {
+                storage.verified.insert(key, (area, score));
+            }
+            RetentionMode::BestPerInstall => {
+                storage.insert_best_per_install(install_id, key, area, score);
+            }
+        }
+        Ok(())
+    }
+
+    fn store_failed_solution(
+        &self,
+        solution: &SolutionData,
+        errors: &[String],
+    ) -> Result<(), String> {
+        let solution_hash = hash_solution_data(solution)?;
+        let mut storage = self.storage.lock().unwrap();
+        storage.failed.insert(solution_hash, errors.to_vec());
+        Ok(())
+    }
+
+    fn close(&self) -> Result<(), String> {
         Ok(())
     }
 }
@@ -101,24 +229,29 @@ impl ScoreDatabase for InMemoryScoreDatabase {
 
 #[cfg(test)]
 mod tests {
-    use super::super::shared::ScoreDatabase;
+    use super::super::shared::{
+        RetentionMode, ScoreDatabase, SubmissionStatus,
+    };
     use super::InMemoryScoreDatabase;
-    use tachy::save::{Puzzle, SolutionData};
+    use tachy::save::{CircuitData, Puzzle, SolutionData};
 
     #[test]
     fn num_verified_solutions() {
-        let db = InMemoryScoreDatabase::new();
+        let db = InMemoryScoreDatabase::new(RetentionMode::FullHistory);
         assert_eq!(db.load_num_verified_solutions().unwrap(), 0);
 
         // Store a solution.  It should not count as verified until we have
         // actually verified it.
         let solution =
             SolutionData::load("tests/solutions/tutorial_or_1.toml").unwrap();
-        let key = db.store_new_solution(&solution).unwrap();
-        assert!(key.is_some());
+        let key = match db.store_new_solution(&solution).unwrap() {
+            SubmissionStatus::New(key) => key,
+            status => panic!("Unexpected status: {:?}", status),
+        };
         assert_eq!(db.load_num_verified_solutions().unwrap(), 0);
         db.store_verified_solution(
-            key.unwrap(),
+            key,
+            solution.install_id,
             solution.circuit.size.area(),
             solution.score,
         )
@@ -129,11 +262,14 @@ mod tests {
         // until we have actually verified it.
         let solution =
             SolutionData::load("tests/solutions/tutorial_or_2.toml").unwrap();
-        let key = db.store_new_solution(&solution).unwrap();
-        assert!(key.is_some());
+        let key = match db.store_new_solution(&solution).unwrap() {
+            SubmissionStatus::New(key) => key,
+            status => panic!("Unexpected status: {:?}", status),
+        };
         assert_eq!(db.load_num_verified_solutions().unwrap(), 1);
         db.store_verified_solution(
-            key.unwrap(),
+            key,
+            solution.install_id,
             solution.circuit.size.area(),
             solution.score,
         )
@@ -142,7 +278,10 @@ mod tests {
 
         // Try storing the same solution again.  It should be rejected as a
         // duplicate.
-        assert!(db.store_new_solution(&solution).unwrap().is_none());
+        assert_eq!(
+            db.store_new_solution(&solution).unwrap(),
+            SubmissionStatus::AlreadyVerified
+        );
         assert_eq!(db.load_num_verified_solutions().unwrap(), 2);
 
         // Load scores and check that we have both scores for TutorialOr.
@@ -150,6 +289,169 @@ mod tests {
         let score_curve = score_map.get(Puzzle::TutorialOr);
         assert_eq!(score_curve.scores(), &[(8, 16), (9, 12)]);
     }
+
+    fn make_solution(
+        install_id: Option<u64>,
+        width: i32,
+        height: i32,
+        score: u32,
+    ) -> SolutionData {
+        SolutionData {
+            install_id,
+            puzzle: Puzzle::TutorialOr,
+            score,
+            time_steps: 4,
+            circuit: CircuitData::new(width, height),
+            inputs: None,
+        }
+    }
+
+    fn submit(db: &InMemoryScoreDatabase, solution: &SolutionData) {
+        if let SubmissionStatus::New(key) =
+            db.store_new_solution(solution).unwrap()
+        {
+            db.store_verified_solution(
+                key,
+                solution.install_id,
+                solution.circuit.size.area(),
+                solution.score,
+            )
+            .unwrap();
+        }
+    }
+
+    fn scores(db: &InMemoryScoreDatabase) -> Vec<(i32, u32)> {
+        db.load_scores().unwrap().get(Puzzle::TutorialOr).scores().to_vec()
+    }
+
+    #[test]
+    fn failed_solutions_are_remembered() {
+        let db = InMemoryScoreDatabase::new(RetentionMode::FullHistory);
+        let mut solution = make_solution(Some(1), 3, 3, 20);
+        let status = db.store_new_solution(&solution).unwrap();
+        assert!(matches!(status, SubmissionStatus::New(_)));
+        let errors = vec!["Circuit is broken".to_string()];
+        db.store_failed_solution(&solution, &errors).unwrap();
+        // Resubmitting the same solution (even from another install) should
+        // return the previous errors.
+        solution.install_id = Some(2);
+        assert_eq!(
+            db.store_new_solution(&solution).unwrap(),
+            SubmissionStatus::AlreadyFailed(errors)
+        );
+        // But submitting the same circuit with a different claimed score
+        // should require verification again.
+        solution.score = 21;
+        let status = db.store_new_solution(&solution).unwrap();
+        assert!(matches!(status, SubmissionStatus::New(_)));
+        assert_eq!(db.load_num_verified_solutions().unwrap(), 0);
+    }
+
+    #[test]
+    fn best_per_install_with_differing_scores() {
+        let db = InMemoryScoreDatabase::new(RetentionMode::BestPerInstall);
+        submit(&db, &make_solution(Some(1), 3, 3, 20));
+        assert_eq!(scores(&db), vec![(9, 20)]);
+        // A better score from the same install replaces the old one, even if
+        // its area is larger.
+        submit(&db, &make_solution(Some(1), 4, 4, 10));
+        assert_eq!(scores(&db), vec![(16, 10)]);
+        // A worse score from the same install is ignored.
+        submit(&db, &make_solution(Some(1), 2, 2, 30));
+        assert_eq!(scores(&db), vec![(16, 10)]);
+        // Solutions from other installs are kept separately.
+        submit(&db, &make_solution(Some(2), 2, 2, 30));
+        assert_eq!(scores(&db), vec![(4, 30), (16, 10)]);
+        // Superseded and ignored solutions aren't kept at all.
+        assert_eq!(db.load_num_verified_solutions().unwrap(), 2);
+    }
+
+    #[test]
+    fn best_per_install_remembers_unkept_solutions() {
+        let db = InMemoryScoreDatabase::new(RetentionMode::BestPerInstall);
+        let first = make_solution(Some(1), 3, 3, 20);
+        let second = make_solution(Some(1), 4, 4, 10);
+        let worse = make_solution(Some(1), 2, 2, 30);
+        submit(&db, &first);
+        submit(&db, &second);
+        submit(&db, &worse);
+        assert_eq!(scores(&db), vec![(16, 10)]);
+        // Neither the superseded solution nor the ignored one should need
+        // to be verified again.
+        assert_eq!(
+            db.store_new_solution(&first).unwrap(),
+            SubmissionStatus::AlreadyVerified
+        );
+        assert_eq!(
+            db.store_new_solution(&worse).unwrap(),
+            SubmissionStatus::AlreadyVerified
+        );
+        assert_eq!(scores(&db), vec![(16, 10)]);
+        // But if another install submits one of them, it is kept again.
+        let other = make_solution(Some(2), 3, 3, 20);
+        assert_eq!(
+            db.store_new_solution(&other).unwrap(),
+            SubmissionStatus::AlreadyVerified
+        );
+        assert_eq!(scores(&db), vec![(9, 20), (16, 10)]);
+    }
+
+    #[test]
+    fn best_per_install_with_equal_scores() {
+        let db = InMemoryScoreDatabase::new(RetentionMode::BestPerInstall);
+        submit(&db, &make_solution(Some(1), 4, 4, 10));
+        // With equal scores, the smaller area wins.
+        submit(&db, &make_solution(Some(1), 3, 3, 10));
+        assert_eq!(scores(&db), vec![(9, 10)]);
+        submit(&db, &make_solution(Some(1), 4, 4, 10));
+        assert_eq!(scores(&db), vec![(9, 10)]);
+    }
+
+    #[test]
+    fn best_per_install_keeps_solutions_shared_with_other_installs() {
+        let db = InMemoryScoreDatabase::new(RetentionMode::BestPerInstall);
+        submit(&db, &make_solution(Some(1), 3, 3, 20));
+        submit(&db, &make_solution(Some(2), 3, 3, 20));
+        assert_eq!(db.load_num_verified_solutions().unwrap(), 1);
+        // Install 1 improving doesn't drop the solution install 2 still has.
+        submit(&db, &make_solution(Some(1), 4, 4, 10));
+        assert_eq!(scores(&db), vec![(9, 20), (16, 10)]);
+        submit(&db, &make_solution(Some(2), 4, 4, 10));
+        assert_eq!(scores(&db), vec![(16, 10)]);
+        assert_eq!(db.load_num_verified_solutions().unwrap(), 1);
+    }
+
+    #[test]
+    fn best_per_install_keeps_anonymous_solutions() {
+        let db = InMemoryScoreDatabase::new(RetentionMode::BestPerInstall);
+        submit(&db, &make_solution(None, 4, 4, 10));
+        submit(&db, &make_solution(None, 3, 3, 20));
+        assert_eq!(scores(&db), vec![(9, 20), (16, 10)]);
+    }
+
+    #[test]
+    fn frontier_of_known_points() {
+        let db = InMemoryScoreDatabase::new(RetentionMode::FullHistory);
+        assert!(db.load_frontier(Puzzle::TutorialOr).unwrap().is_empty());
+        let points = &[(4, 4, 50), (3, 5, 40), (4, 5, 45), (3, 3, 60)];
+        for &(width, height, score) in points {
+            submit(&db, &make_solution(None, width, height, score));
+        }
+        submit(&db, &make_solution(None, 3, 4, 60));
+        assert_eq!(
+            db.load_frontier(Puzzle::TutorialOr).unwrap().scores(),
+            &[(9, 60), (15, 40)]
+        );
+        assert!(db.load_frontier(Puzzle::TutorialMux).unwrap().is_empty());
+    }
+
+    #[test]
+    fn full_history_keeps_all_solutions() {
+        let db = InMemoryScoreDatabase::new(RetentionMode::FullHistory);
+        submit(&db, &make_solution(Some(1), 3, 3, 20));
+        submit(&db, &make_solution(Some(1), 4, 4, 10));
+        assert_eq!(scores(&db), vec![(9, 20), (16, 10)]);
+    }
 }
 
 //===========================================================================//
@@ -23,6 +23,6 @@ mod shared;
 //===========================================================================//
 
 pub use self::inmemory::InMemoryScoreDatabase;
-pub use self::shared::ScoreDatabase;
+pub use self::shared::{RetentionMode, ScoreDatabase, SubmissionStatus};
 
 //===========================================================================//
@@ -19,7 +19,40 @@
 
 use sha::sha256::Sha256;
 use sha::utils::{Digest, DigestExt};
-use tachy::save::{CircuitData, Puzzle, ScoreCurveMap, SolutionData};
+use tachy::save::{
+    CircuitData, CircuitNotes, CircuitWireLabelData, Puzzle, ScoreCurve,
+    ScoreCurveMap, SolutionData,
+};
+
+//===========================================================================//
+
+/// Determines which verified solutions count towards the global scores.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RetentionMode {
+    /// Every distinct verified solution counts.
+    FullHistory,
+    /// Only each install's best solution (lowest score, then smallest area)
+    /// for each puzzle is kept; a better solution replaces the install's
+    /// previous best when it is stored.  Solutions submitted without an
+    /// install ID are always kept.
+    BestPerInstall,
+}
+
+//===========================================================================//
+
+/// The result of recording a newly-submitted solution.
+#[derive(Debug, Eq, PartialEq)]
+pub enum SubmissionStatus {
+    /// The solution has not been processed before, and needs to be verified.
+    /// The key should be passed to `store_verified_solution` if verification
+    /// succeeds.
+    New((Puzzle, String)),
+    /// An identical circuit has already been verified.
+    AlreadyVerified,
+    /// An identical submission has already failed verification with the
+    /// given errors.
+    AlreadyFailed(Vec<String>),
+}
 
 //===========================================================================//
 
@@ -30,27 +63,69 @@ pub trait ScoreDatabase: Send + Sync {
     /// Returns the global scores.
     fn load_scores(&self) -> Result<ScoreCurveMap, String>;
 
-    /// Returns a key to be passed to `store_verified_solution` if the solution
-    /// is new and needs to be verified, otherwise returns `None`.
+    /// Returns the Pareto frontier (minimizing both area and score) of the
+    /// verified solutions for the given puzzle.
+    fn load_frontier(&self, puzzle: Puzzle) -> Result<ScoreCurve, String> {
+        Ok(self.load_scores()?.get(puzzle).clone())
+    }
+
+    /// Records the submission of a solution, and returns whether it still
+    /// needs to be verified or the result of processing it previously.
     fn store_new_solution(
         &self,
         solution: &SolutionData,
-    ) -> Result<Option<(Puzzle, String)>, String>;
+    ) -> Result<SubmissionStatus, String>;
 
-    /// Record that the specified solution was valid (and resulted in the given
-    /// score).
+    /// Record that the specified solution (submitted by the given install, if
+    /// any) was valid and resulted in the given score.
     fn store_verified_solution(
         &self,
         key: (Puzzle, String),
+        install_id: Option<u64>,
         area: i32,
         score: u32,
     ) -> Result<(), String>;
+
+    /// Record that the specified solution failed verification with the given
+    /// errors, so that resubmissions of it will not be verified again.
+    fn store_failed_solution(
+        &self,
+        solution: &SolutionData,
+        errors: &[String],
+    ) -> Result<(), String>;
+
+    /// Flushes any pending writes and releases the database's resources.  No
+    /// other methods should be called after this.
+    fn close(&self) -> Result<(), String>;
 }
 
 //===========================================================================//
 
+/// Returns a hash of the circuit, used to deduplicate submitted circuits.
+/// Freeform circuit notes and wire labels are ignored, since they don't affect
+/// the circuit's behavior.
 pub fn hash_circuit_data(circuit: &CircuitData) -> Result<String, String> {
-    let serialized = circuit.serialize_to_string()?;
+    let serialized = if circuit.notes.is_empty() && circuit.labels.is_empty() {
+        circuit.serialize_to_string()?
+    } else {
+        let mut circuit = circuit.clone();
+        circuit.notes = CircuitNotes::default();
+        circuit.labels = CircuitWireLabelData::default();
+        circuit.serialize_to_string()?
+    };
+    Ok(Sha256::default().digest(serialized.as_bytes()).to_hex())
+}
+
+/// Returns a hash of the entire submitted solution (not just its circuit),
+/// ignoring which install submitted it.
+pub fn hash_solution_data(solution: &SolutionData) -> Result<String, String> {
+    let mut solution = SolutionData::deserialize_from_string(
+        &solution.serialize_to_string()?,
+    )?;
+    solution.install_id = None;
+    solution.circuit.notes = CircuitNotes::default();
+    solution.circuit.labels = CircuitWireLabelData::default();
+    let serialized = solution.serialize_to_string()?;
     Ok(Sha256::default().digest(serialized.as_bytes()).to_hex())
 }
 
@@ -58,7 +133,7 @@ pub fn hash_circuit_data(circuit: &CircuitData) -> Result<String, String> {
 
 #[cfg(test)]
 mod tests {
-    use super::hash_circuit_data;
+    use super::{hash_circuit_data, hash_solution_data};
     use tachy::save::SolutionData;
 
     #[test]
@@ -68,8 +143,8 @@ mod tests {
         let hash = hash_circuit_data(&solution.circuit).unwrap();
         assert_eq!(
             hash,
-            "f38b0da4b7a01eeb75cfc2698efb7dbd\
-             895b03bd971cc5170aecb0220a0724d8"
+            "08223ba3a08d14e31c6deb1b0c013922\
+             b817ffa7a68cfcc6a20663f6964dbaca"
         );
     }
 
@@ -80,9 +155,50 @@ mod tests {
         let hash = hash_circuit_data(&solution.circuit).unwrap();
         assert_eq!(
             hash,
-            "9af25154810a935af30a2107019b4d5b\
-             c2a62b99e95c82607bbb8c574c22a2d2"
+            "74576830698b9cafdd6a307389516807\
+             a5a0c36136c3a10d54a750b2f4c0573a"
+        );
+    }
+
+    #[test]
+    fn solution_hash_ignores_install_id() {
+        let mut solution =
+            SolutionData::load("tests/solutions/tutorial_or_1.toml").unwrap();
+        let hash1 = hash_solution_data(&solution).unwrap();
+        solution.install_id = Some(12345);
+        assert_eq!(hash_solution_data(&solution).unwrap(), hash1);
+        solution.score += 1;
+        assert_ne!(hash_solution_data(&solution).unwrap(), hash1);
+    }
+
+    #[test]
+    fn hashes_ignore_circuit_notes() {
+        let mut solution =
+            SolutionData::load("tests/solutions/tutorial_or_1.toml").unwrap();
+        let circuit_hash = hash_circuit_data(&solution.circuit).unwrap();
+        let solution_hash = hash_solution_data(&solution).unwrap();
+        solution.circuit.notes.description = "My first OR gate".to_string();
+        solution.circuit.notes.author = "Jane".to_string();
+        assert_eq!(
+            hash_circuit_data(&solution.circuit).unwrap(),
+            circuit_hash
+        );
+        assert_eq!(hash_solution_data(&solution).unwrap(), solution_hash);
+    }
+
+    #[test]
+    fn hashes_ignore_wire_labels() {
+        let mut solution =
+            SolutionData::load("tests/solutions/tutorial_or_1.toml").unwrap();
+        let circuit_hash = hash_circuit_data(&solution.circuit).unwrap();
+        let solution_hash = hash_solution_data(&solution).unwrap();
+        let (delta, dir, _) = solution.circuit.wires.iter().next().unwrap();
+        solution.circuit.labels.insert(delta, dir, "Out".to_string());
+        assert_eq!(
+            hash_circuit_data(&solution.circuit).unwrap(),
+            circuit_hash
         );
+        assert_eq!(hash_solution_data(&solution).unwrap(), solution_hash);
     }
 }
 
@@ -0,0 +1,70 @@
+// +--------------------------------------------------------------------------+
+// | Copyright 2018 Matthew D. Steele <mdsteele@alum.mit.edu>                 |
+// |                                                                          |
+// | This file is part of Tachyomancer.                                       |
+// |                                                                          |
+// | Tachyomancer is free software: you can redistribute it and/or modify it  |
+// | under the terms of the GNU General Public License as published by the    |
+// | Free Software Foundation, either version 3 of the License, or (at your   |
+// | option) any later version.                                               |
+// |                                                                          |
+// | Tachyomancer is distributed in the hope that it will be useful, but      |
+// | WITHOUT ANY WARRANTY; without even the implied warranty of               |
+// | MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU        |
+// | General Public License for details.                                      |
+// |                                                                          |
+// | You should have received a copy of the GNU General Public License along  |
+// | with Tachyomancer.  If not, see <http://www.gnu.org/licenses/>.          |
+// +--------------------------------------------------------------------------+
+
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use iron::headers::{AcceptEncoding, Encoding, Headers};
+use std::io::Write;
+
+//===========================================================================//
+
+/// Returns true if the request headers indicate that the client will accept
+/// a gzip-encoded response.
+pub fn accepts_gzip(headers: &Headers) -> bool {
+    match headers.get::<AcceptEncoding>() {
+        Some(accept) => accept
+            .iter()
+            .any(|item| item.item == Encoding::Gzip && item.quality.0 > 0),
+        None => false,
+    }
+}
+
+/// Compresses the given data into gzip format.
+pub fn gzip_compress(data: &[u8]) -> Result<Vec<u8>, String> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder
+        .write_all(data)
+        .map_err(|err| format!("Failed to compress data: {}", err))?;
+    encoder.finish().map_err(|err| format!("Failed to compress data: {}", err))
+}
+
+//===========================================================================//
+
+#[cfg(test)]
+mod tests {
+    use super::accepts_gzip;
+    use iron::headers::Headers;
+
+    fn headers_with_accept_encoding(value: &str) -> Headers {
+        let mut headers = Headers::new();
+        headers.set_raw("Accept-Encoding", vec![value.as_bytes().to_vec()]);
+        headers
+    }
+
+    #[test]
+    fn accept_encoding_parsing() {
+        assert!(!accepts_gzip(&Headers::new()));
+        assert!(accepts_gzip(&headers_with_accept_encoding("gzip")));
+        assert!(accepts_gzip(&headers_with_accept_encoding("deflate, gzip")));
+        assert!(!accepts_gzip(&headers_with_accept_encoding("identity")));
+        assert!(!accepts_gzip(&headers_with_accept_encoding("gzip;q=0")));
+    }
+}
+
+//===========================================================================//
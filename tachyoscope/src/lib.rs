@@ -17,15 +17,31 @@
 // | with Tachyomancer.  If not, see <http://www.gnu.org/licenses/>.          |
 // +--------------------------------------------------------------------------+
 
+extern crate ctrlc;
+extern crate flate2;
 extern crate iron;
 extern crate router;
 extern crate sha;
 #[macro_use]
 extern crate tachy;
+extern crate toml;
 
+mod access;
+mod bounds;
+mod cors;
 mod db;
-
-use self::db::{InMemoryScoreDatabase, ScoreDatabase};
+mod gzip;
+mod shutdown;
+
+use self::access::AccessLogger;
+pub use self::access::REQUEST_ID_HEADER;
+use self::bounds::GraphBounds;
+use self::cors::{CorsHandler, CorsPreflightHandler};
+pub use self::db::RetentionMode;
+use self::db::{InMemoryScoreDatabase, ScoreDatabase, SubmissionStatus};
+use self::gzip::{accepts_gzip, gzip_compress};
+use self::shutdown::{ShutdownGate, ShutdownState};
+use iron::headers::{ContentEncoding, Encoding};
 use iron::status;
 use iron::{
     Handler, Iron, IronError, IronResult, Listening, Request, Response,
@@ -33,42 +49,184 @@ use iron::{
 use router::Router;
 use std::io::{self, Read};
 use std::net::SocketAddr;
-use std::sync::Arc;
-use tachy::save::SolutionData;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::{mpsc, Arc};
+use std::time::Duration;
+use tachy::save::{Puzzle, ScoreCurveMap, SolutionData};
 use tachy::state::verify_solution;
 
 //===========================================================================//
 
+/// The default limit on the size of a submitted solution's request body.
+/// Real solution files are only a few kilobytes, so this is very generous.
+pub const DEFAULT_MAX_BODY_BYTES: u64 = 1 << 20;
+
+//===========================================================================//
+
 #[derive(Debug)]
 pub struct StartupFlags {
     pub addr: SocketAddr,
+    /// If set, read-only endpoints will allow cross-origin requests from this
+    /// origin (which may be `*` to allow any origin).
+    pub cors_origin: Option<String>,
+    /// Determines which verified solutions count towards the global scores.
+    pub retention: RetentionMode,
+    /// If set, a TOML file of per-puzzle graph bounds overrides to use in
+    /// place of the compiled-in bounds.
+    pub graph_bounds_path: Option<PathBuf>,
+    /// Submitted solutions with request bodies larger than this many bytes
+    /// will be rejected without being parsed.
+    pub max_body_bytes: u64,
+    /// The newest released version of the game, reported to clients that
+    /// check for updates.
+    pub latest_version: String,
 }
 
 //===========================================================================//
 
-pub fn run_server(flags: &StartupFlags) -> Result<Listening, String> {
-    let server = Iron::new(make_router())
+pub fn run_server(flags: &StartupFlags) -> Result<RunningServer, String> {
+    let db: Arc<Box<dyn ScoreDatabase>> =
+        Arc::new(Box::new(InMemoryScoreDatabase::new(flags.retention)));
+    let bounds = match flags.graph_bounds_path {
+        Some(ref path) => GraphBounds::load(path)?,
+        None => GraphBounds::default(),
+    };
+    let shutdown = Arc::new(ShutdownState::new());
+    let handler = AccessLogger::new(ShutdownGate::new(
+        make_router(flags, &db, Arc::new(bounds)),
+        shutdown.clone(),
+    ));
+    let listening = Iron::new(handler)
         .http(flags.addr)
         .map_err(|err| format!("{:?}", err))?;
-    debug_log!("HTTP server now listening on {}", server.socket);
-    Ok(server)
+    debug_log!("HTTP server now listening on {}", listening.socket);
+    Ok(RunningServer { listening, shutdown, db })
 }
 
-fn make_router() -> Router {
-    let db: Arc<Box<dyn ScoreDatabase>> =
-        Arc::new(Box::new(InMemoryScoreDatabase::new()));
+fn make_router(
+    flags: &StartupFlags,
+    db: &Arc<Box<dyn ScoreDatabase>>,
+    bounds: Arc<GraphBounds>,
+) -> Router {
     let mut router = Router::new();
     router.get("/", LandingPageHandler { db: db.clone() }, "LandingPage");
-    router.get("/readiness_check", ReadinessHandler {}, "Readiness");
-    router.get("/scores", GetScoresHandler { db: db.clone() }, "GetScores");
+    add_read_only_route(
+        &mut router,
+        flags,
+        "/frontier",
+        GetFrontierHandler { db: db.clone() },
+        "GetFrontier",
+    );
+    router.get(
+        "/latest_version",
+        LatestVersionHandler { version: flags.latest_version.clone() },
+        "LatestVersion",
+    );
+    router.get("/livez", LivenessHandler {}, "Liveness");
+    add_read_only_route(
+        &mut router,
+        flags,
+        "/puzzles",
+        GetPuzzlesHandler { bounds: bounds.clone() },
+        "GetPuzzles",
+    );
+    router.get(
+        "/readiness_check",
+        ReadinessHandler { db: db.clone() },
+        "Readiness",
+    );
+    add_read_only_route(
+        &mut router,
+        flags,
+        "/scores",
+        GetScoresHandler { db: db.clone() },
+        "GetScores",
+    );
     router.post(
         "/submit_solution",
-        SubmitSolutionHandler { db },
+        SubmitSolutionHandler {
+            db: db.clone(),
+            bounds,
+            max_body_bytes: flags.max_body_bytes,
+        },
         "SubmitSolution",
     );
     router
 }
 
+/// Adds a GET route for a read-only endpoint, allowing cross-origin requests
+/// to it if the server is configured to do so.
+fn add_read_only_route<H: Handler>(
+    router: &mut Router,
+    flags: &StartupFlags,
+    path: &str,
+    handler: H,
+    name: &str,
+) {
+    if let Some(ref origin) = flags.cors_origin {
+        router.get(path, CorsHandler::new(handler, origin.clone()), name);
+        router.options(
+            path,
+            CorsPreflightHandler::new(origin.clone()),
+            format!("{}Preflight", name),
+        );
+    } else {
+        router.get(path, handler, name);
+    }
+}
+
+//===========================================================================//
+
+pub struct RunningServer {
+    listening: Listening,
+    shutdown: Arc<ShutdownState>,
+    db: Arc<Box<dyn ScoreDatabase>>,
+}
+
+impl RunningServer {
+    /// Blocks until the process receives SIGINT or SIGTERM, and then shuts
+    /// down the server, waiting up to `timeout` for in-flight requests to
+    /// finish.
+    pub fn run_until_signal(
+        mut self,
+        timeout: Duration,
+    ) -> Result<(), String> {
+        let (sender, receiver) = mpsc::channel();
+        ctrlc::set_handler(move || {
+            let _ = sender.send(());
+        })
+        .map_err(|err| format!("Failed to install signal handler: {}", err))?;
+        receiver
+            .recv()
+            .map_err(|err| format!("Signal handler failed: {}", err))?;
+        debug_log!("Received shutdown signal.");
+        self.shut_down(timeout)
+    }
+
+    /// Stops accepting new requests, waits up to `timeout` for in-flight
+    /// requests to finish, and then closes the database.
+    ///
+    /// Note that hyper 0.10's `Listening::close` doesn't actually stop the
+    /// listener threads, so the socket stays open until the process exits.  The `ShutdownGate` is therefore the only
+    /// thing rejecting requests after this is called; any that arrive are
+    /// answered with a 503 rather than refused at the socket.
+    pub fn shut_down(&mut self, timeout: Duration) -> Result<(), String> {
+        self.shutdown.begin_shutdown();
+        if !self.shutdown.wait_for_idle(timeout) {
+            debug_warn!("Timed out waiting for in-flight requests to finish.");
+        }
+        self.db.close()?;
+        // This is close to a no-op in hyper 0.10 (see above), but it's still
+        // the right thing to call should the listener ever learn to stop.
+        self.listening.close().map_err(|err| format!("{:?}", err))?;
+        debug_log!("HTTP server has shut down.");
+        Ok(())
+    }
+}
+
+//===========================================================================//
+
 fn internal_error(err: String) -> IronError {
     debug_warn!("{}", err);
     let msg = format!("{}\n", err);
@@ -78,17 +236,84 @@ fn internal_error(err: String) -> IronError {
 
 //===========================================================================//
 
+struct GetFrontierHandler {
+    db: Arc<Box<dyn ScoreDatabase>>,
+}
+
+impl Handler for GetFrontierHandler {
+    fn handle(&self, request: &mut Request) -> IronResult<Response> {
+        debug_log!("Received GetFrontierHandler request.");
+        let puzzle = query_param(request, "puzzle")
+            .ok_or_else(|| "Missing puzzle parameter.".to_string())
+            .and_then(|name| {
+                Puzzle::from_str(&name)
+                    .map_err(|_| format!("Invalid puzzle: {:?}", name))
+            })
+            .map_err(|err| {
+                let msg = format!("{}\n", err);
+                let io_err = io::Error::new(io::ErrorKind::InvalidInput, err);
+                IronError::new(io_err, (status::BadRequest, msg))
+            })?;
+        let frontier =
+            self.db.load_frontier(puzzle).map_err(internal_error)?;
+        let mut scores = ScoreCurveMap::new();
+        scores.set(puzzle, frontier);
+        let response = scores.serialize_to_string().map_err(internal_error)?;
+        debug_log!("Sending GetFrontierHandler response.");
+        Ok(Response::with((status::Ok, response)))
+    }
+}
+
+fn query_param(request: &Request, name: &str) -> Option<String> {
+    let query = request.url.query()?;
+    for pair in query.split('&') {
+        let mut parts = pair.splitn(2, '=');
+        if parts.next() == Some(name) {
+            return Some(parts.next().unwrap_or("").to_string());
+        }
+    }
+    None
+}
+
+//===========================================================================//
+
+struct GetPuzzlesHandler {
+    bounds: Arc<GraphBounds>,
+}
+
+impl Handler for GetPuzzlesHandler {
+    fn handle(&self, _: &mut Request) -> IronResult<Response> {
+        debug_log!("Received GetPuzzlesHandler request.");
+        let response =
+            self.bounds.serialize_to_string().map_err(internal_error)?;
+        debug_log!("Sending GetPuzzlesHandler response.");
+        Ok(Response::with((status::Ok, response)))
+    }
+}
+
+//===========================================================================//
+
 struct GetScoresHandler {
     db: Arc<Box<dyn ScoreDatabase>>,
 }
 
 impl Handler for GetScoresHandler {
-    fn handle(&self, _: &mut Request) -> IronResult<Response> {
+    fn handle(&self, request: &mut Request) -> IronResult<Response> {
         debug_log!("Received GetScoresHandler request.");
         let scores = self.db.load_scores().map_err(internal_error)?;
         let response = scores.serialize_to_string().map_err(internal_error)?;
+        let mut response = if accepts_gzip(&request.headers) {
+            let compressed =
+                gzip_compress(response.as_bytes()).map_err(internal_error)?;
+            let mut response = Response::with((status::Ok, compressed));
+            response.headers.set(ContentEncoding(vec![Encoding::Gzip]));
+            response
+        } else {
+            Response::with((status::Ok, response))
+        };
+        response.headers.set_raw("Vary", vec![b"Accept-Encoding".to_vec()]);
         debug_log!("Sending GetScoresHandler response.");
-        Ok(Response::with((status::Ok, response)))
+        Ok(response)
     }
 }
 
@@ -111,10 +336,41 @@ impl Handler for LandingPageHandler {
 
 //===========================================================================//
 
-struct ReadinessHandler {}
+struct LatestVersionHandler {
+    version: String,
+}
+
+impl Handler for LatestVersionHandler {
+    fn handle(&self, _: &mut Request) -> IronResult<Response> {
+        let response = format!("{}\n", self.version);
+        Ok(Response::with((status::Ok, response)))
+    }
+}
+
+//===========================================================================//
+
+struct LivenessHandler {}
+
+impl Handler for LivenessHandler {
+    fn handle(&self, _: &mut Request) -> IronResult<Response> {
+        Ok(Response::with((status::Ok, "alive\n")))
+    }
+}
+
+//===========================================================================//
+
+struct ReadinessHandler {
+    db: Arc<Box<dyn ScoreDatabase>>,
+}
 
 impl Handler for ReadinessHandler {
     fn handle(&self, _: &mut Request) -> IronResult<Response> {
+        // Make a cheap query to check that the database is reachable:
+        if let Err(err) = self.db.load_num_verified_solutions() {
+            debug_warn!("Readiness check failed: {}", err);
+            let response = format!("database unavailable: {}\n", err);
+            return Ok(Response::with((status::ServiceUnavailable, response)));
+        }
         Ok(Response::with((status::Ok, "ready\n")))
     }
 }
@@ -123,6 +379,8 @@ impl Handler for ReadinessHandler {
 
 struct SubmitSolutionHandler {
     db: Arc<Box<dyn ScoreDatabase>>,
+    bounds: Arc<GraphBounds>,
+    max_body_bytes: u64,
 }
 
 impl Handler for SubmitSolutionHandler {
@@ -132,9 +390,33 @@ impl Handler for SubmitSolutionHandler {
             request.remote_addr
         );
 
-        // Read the serialized solution from the client:
-        let mut body = String::new();
-        request.body.read_to_string(&mut body).map_err(|err| {
+        // Read the serialized solution from the client, reading at most one
+        // byte past the limit so that we can tell if the body was too large
+        // without buffering all of it:
+        let mut bytes = Vec::<u8>::new();
+        request
+            .body
+            .by_ref()
+            .take(self.max_body_bytes.saturating_add(1))
+            .read_to_end(&mut bytes)
+            .map_err(|err| {
+                let msg = format!("{}\n", err);
+                IronError::new(err, (status::BadRequest, msg))
+            })?;
+        if bytes.len() as u64 > self.max_body_bytes {
+            debug_log!("Request body is too large; rejecting it.");
+            let msg = format!(
+                "Request body exceeds limit of {} bytes.\n",
+                self.max_body_bytes
+            );
+            let err = io::Error::new(io::ErrorKind::InvalidData, msg.clone());
+            return Err(IronError::new(err, (status::PayloadTooLarge, msg)));
+        }
+        let body = String::from_utf8(bytes).map_err(|_| {
+            let err = io::Error::new(
+                io::ErrorKind::InvalidData,
+                "stream did not contain valid UTF-8",
+            );
             let msg = format!("{}\n", err);
             IronError::new(err, (status::BadRequest, msg))
         })?;
@@ -155,64 +437,88 @@ impl Handler for SubmitSolutionHandler {
         );
 
         // Ignore solutions that fall outside graph bounds:
-        if solution_is_out_of_bounds(&data) {
+        if solution_is_out_of_bounds(&data, &self.bounds) {
             debug_log!("This solution is outside the graph; ignoring it.");
             let response = "Solution is not within graph bounds.".to_string();
             debug_log!("Sending SubmitSolutionHandler response.");
             return Ok(Response::with((status::Ok, response)));
         }
 
-        // If we've never verified this solution before, verify it:
-        if let Some(key) =
-            self.db.store_new_solution(&data).map_err(internal_error)?
-        {
-            debug_log!("Verifying solution...");
-            let errors = verify_solution(&data);
-            if errors.is_empty() {
-                debug_log!("Solution successful.  Storing in DB...");
-                self.db
-                    .store_verified_solution(
-                        key,
-                        data.circuit.size.area(),
-                        data.score,
-                    )
-                    .map_err(internal_error)?;
-                debug_log!("Solution has been stored in the DB.");
-            } else {
-                debug_log!("Solution had errors.  Ignoring it.");
-                let mut response = "Circuit had errors:\n".to_string();
-                for error in errors.iter() {
-                    response.push_str(&format!("- {}\n", error));
+        // If we've never processed this solution before, verify it:
+        let status =
+            self.db.store_new_solution(&data).map_err(internal_error)?;
+        match status {
+            SubmissionStatus::New(key) => {
+                debug_log!("Verifying solution...");
+                let errors = verify_solution(&data);
+                if errors.is_empty() {
+                    debug_log!("Solution successful.  Storing in DB...");
+                    self.db
+                        .store_verified_solution(
+                            key,
+                            data.install_id,
+                            data.circuit.size.area(),
+                            data.score,
+                        )
+                        .map_err(internal_error)?;
+                    debug_log!("Solution has been stored in the DB.");
+                } else {
+                    debug_log!("Solution had errors.  Recording them.");
+                    self.db
+                        .store_failed_solution(&data, &errors)
+                        .map_err(internal_error)?;
+                    debug_log!("Sending SubmitSolutionHandler response.");
+                    return Ok(errors_response(&errors));
                 }
+            }
+            SubmissionStatus::AlreadyVerified => {
+                debug_log!(
+                    "We've verified this solution before; ignoring it."
+                );
+            }
+            SubmissionStatus::AlreadyFailed(errors) => {
+                debug_log!(
+                    "This solution failed before; not re-verifying it."
+                );
                 debug_log!("Sending SubmitSolutionHandler response.");
-                return Ok(Response::with((status::Ok, response)));
+                return Ok(errors_response(&errors));
             }
-        } else {
-            debug_log!("We've seen this solution before; ignoring it.");
-        };
+        }
 
         debug_log!("Sending SubmitSolutionHandler response.");
         return Ok(Response::with((status::Ok, "ok\n")));
     }
 }
 
-fn solution_is_out_of_bounds(solution: &SolutionData) -> bool {
-    let (max_area, max_score) = solution.puzzle.graph_bounds();
-    // Check for extreme size values before calculating area, to avoid crashing
-    // on overflow.
+fn errors_response(errors: &[String]) -> Response {
+    let mut response = "Circuit had errors:\n".to_string();
+    for error in errors.iter() {
+        response.push_str(&format!("- {}\n", error));
+    }
+    Response::with((status::Ok, response))
+}
+
+fn solution_is_out_of_bounds(
+    solution: &SolutionData,
+    bounds: &GraphBounds,
+) -> bool {
+    let (max_area, max_score) = bounds.get(solution.puzzle);
+    // Use a checked area, so that extreme size values (which would overflow)
+    // count as out of bounds rather than crashing the server.
     solution.score > max_score
         || solution.circuit.size.width <= 0
         || solution.circuit.size.height <= 0
-        || solution.circuit.size.width > max_area
-        || solution.circuit.size.height > max_area
-        || solution.circuit.size.area() > max_area
+        || match solution.circuit.size.checked_area() {
+            Some(area) => area > max_area,
+            None => true,
+        }
 }
 
 //===========================================================================//
 
 #[cfg(test)]
 mod tests {
-    use super::solution_is_out_of_bounds;
+    use super::{solution_is_out_of_bounds, GraphBounds};
     use tachy::save::{CircuitData, Puzzle, SolutionData};
 
     #[test]
@@ -225,20 +531,54 @@ mod tests {
             circuit: CircuitData::new(5, 5),
             inputs: None,
         };
-        assert!(!solution_is_out_of_bounds(&solution));
+        let bounds = GraphBounds::default();
+        assert!(!solution_is_out_of_bounds(&solution, &bounds));
 
         solution.score = 5000;
-        assert!(solution_is_out_of_bounds(&solution));
+        assert!(solution_is_out_of_bounds(&solution, &bounds));
 
         solution.score = 20;
         solution.circuit = CircuitData::new(1000, 1000);
-        assert!(solution_is_out_of_bounds(&solution));
+        assert!(solution_is_out_of_bounds(&solution, &bounds));
 
         solution.circuit = CircuitData::new(i32::MAX, i32::MAX);
-        assert!(solution_is_out_of_bounds(&solution));
+        assert!(solution_is_out_of_bounds(&solution, &bounds));
 
         solution.circuit = CircuitData::new(i32::MIN, i32::MIN);
-        assert!(solution_is_out_of_bounds(&solution));
+        assert!(solution_is_out_of_bounds(&solution, &bounds));
+
+        solution.circuit = CircuitData::new(i32::MAX, 1);
+        assert!(solution_is_out_of_bounds(&solution, &bounds));
+
+        solution.circuit = CircuitData::new(65536, 65536);
+        assert!(solution_is_out_of_bounds(&solution, &bounds));
+
+        solution.circuit = CircuitData::new(i32::MIN, -1);
+        assert!(solution_is_out_of_bounds(&solution, &bounds));
+    }
+
+    #[test]
+    fn out_of_overridden_bounds() {
+        let solution = SolutionData {
+            install_id: None,
+            puzzle: Puzzle::TutorialOr,
+            score: 20,
+            time_steps: 4,
+            circuit: CircuitData::new(5, 5),
+            inputs: None,
+        };
+        let bounds =
+            GraphBounds::deserialize_from_string("TutorialOr = [20, 100]\n")
+                .unwrap();
+        assert!(solution_is_out_of_bounds(&solution, &bounds));
+        let bounds =
+            GraphBounds::deserialize_from_string("TutorialOr = [25, 19]\n")
+                .unwrap();
+        assert!(solution_is_out_of_bounds(&solution, &bounds));
+        let bounds =
+            GraphBounds::deserialize_from_string("TutorialOr = [25, 20]\n")
+                .unwrap();
+        assert!(!solution_is_out_of_bounds(&solution, &bounds));
     }
 }
 
@@ -21,19 +21,56 @@ extern crate getopts;
 extern crate tachyoscope;
 
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
-use tachyoscope::{run_server, StartupFlags};
+use std::path::PathBuf;
+use std::time::Duration;
+use tachyoscope::{
+    run_server, RetentionMode, StartupFlags, DEFAULT_MAX_BODY_BYTES,
+};
+
+//===========================================================================//
+
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(30);
 
 //===========================================================================//
 
 fn main() -> Result<(), String> {
-    let _ = run_server(&parse_flags()?);
-    Ok(())
+    let server = run_server(&parse_flags()?)?;
+    server.run_until_signal(SHUTDOWN_TIMEOUT)
 }
 
 fn parse_flags() -> Result<StartupFlags, String> {
     let mut opts = getopts::Options::new();
     opts.optflag("h", "help", "print this help menu");
+    opts.optflag(
+        "",
+        "best_per_install",
+        "only count each install's best solution for each puzzle",
+    );
+    opts.optopt(
+        "",
+        "cors_origin",
+        "allow cross-origin reads of scores from this origin",
+        "ORIGIN",
+    );
+    opts.optopt(
+        "",
+        "graph_bounds",
+        "load per-puzzle graph bounds overrides from this TOML file",
+        "PATH",
+    );
     opts.optopt("", "host", "the IP to listen on", "HOST");
+    opts.optopt(
+        "",
+        "latest_version",
+        "report this as the newest game version (default: this server's)",
+        "VERSION",
+    );
+    opts.optopt(
+        "",
+        "max_body_size",
+        "reject submitted solutions larger than this many bytes",
+        "BYTES",
+    );
     opts.optopt("", "port", "the port to listen on", "PORT");
 
     let args: Vec<String> = std::env::args().collect();
@@ -52,7 +89,27 @@ fn parse_flags() -> Result<StartupFlags, String> {
     let port: u16 = matches
         .opt_get_default("port", 8080)
         .map_err(|err| format!("{:?}", err))?;
-    Ok(StartupFlags { addr: SocketAddr::new(host, port) })
+    let max_body_bytes: u64 = matches
+        .opt_get_default("max_body_size", DEFAULT_MAX_BODY_BYTES)
+        .map_err(|err| {
+            format!("{:?} for {:?}", err, matches.opt_str("max_body_size"))
+        })?;
+    let cors_origin = matches.opt_str("cors_origin");
+    let retention = if matches.opt_present("best_per_install") {
+        RetentionMode::BestPerInstall
+    } else {
+        RetentionMode::FullHistory
+    };
+    Ok(StartupFlags {
+        addr: SocketAddr::new(host, port),
+        cors_origin,
+        retention,
+        graph_bounds_path: matches.opt_str("graph_bounds").map(PathBuf::from),
+        max_body_bytes,
+        latest_version: matches
+            .opt_str("latest_version")
+            .unwrap_or_else(|| env!("CARGO_PKG_VERSION").to_string()),
+    })
 }
 
 //===========================================================================//
@@ -0,0 +1,147 @@
+// +--------------------------------------------------------------------------+
+// | Copyright 2018 Matthew D. Steele <mdsteele@alum.mit.edu>                 |
+// |                                                                          |
+// | This file is part of Tachyomancer.                                       |
+// |                                                                          |
+// | Tachyomancer is free software: you can redistribute it and/or modify it  |
+// | under the terms of the GNU General Public License as published by the    |
+// | Free Software Foundation, either version 3 of the License, or (at your   |
+// | option) any later version.                                               |
+// |                                                                          |
+// | Tachyomancer is distributed in the hope that it will be useful, but      |
+// | WITHOUT ANY WARRANTY; without even the implied warranty of               |
+// | MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU        |
+// | General Public License for details.                                      |
+// |                                                                          |
+// | You should have received a copy of the GNU General Public License along  |
+// | with Tachyomancer.  If not, see <http://www.gnu.org/licenses/>.          |
+// +--------------------------------------------------------------------------+
+
+use iron::status;
+use iron::{Handler, IronError, IronResult, Request, Response};
+use std::io;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::{Duration, Instant};
+
+//===========================================================================//
+
+/// Tracks whether the server is shutting down, and how many requests are
+/// still being handled.
+pub struct ShutdownState {
+    shutting_down: AtomicBool,
+    in_flight: Mutex<usize>,
+    idle: Condvar,
+}
+
+impl ShutdownState {
+    pub fn new() -> ShutdownState {
+        ShutdownState {
+            shutting_down: AtomicBool::new(false),
+            in_flight: Mutex::new(0),
+            idle: Condvar::new(),
+        }
+    }
+
+    /// Stops any new requests from being handled.
+    pub fn begin_shutdown(&self) {
+        self.shutting_down.store(true, Ordering::SeqCst);
+    }
+
+    /// Blocks until all in-flight requests have finished, or until the
+    /// timeout elapses.  Returns true if all requests finished.
+    pub fn wait_for_idle(&self, timeout: Duration) -> bool {
+        let deadline = Instant::now() + timeout;
+        let mut in_flight = self.in_flight.lock().unwrap();
+        while *in_flight > 0 {
+            let now = Instant::now();
+            if now >= deadline {
+                return false;
+            }
+            in_flight =
+                self.idle.wait_timeout(in_flight, deadline - now).unwrap().0;
+        }
+        true
+    }
+
+    fn begin_request(&self) -> Option<InFlightGuard<'_>> {
+        let mut in_flight = self.in_flight.lock().unwrap();
+        if self.shutting_down.load(Ordering::SeqCst) {
+            return None;
+        }
+        *in_flight += 1;
+        Some(InFlightGuard { state: self })
+    }
+
+    fn end_request(&self) {
+        let mut in_flight = self.in_flight.lock().unwrap();
+        *in_flight -= 1;
+        if *in_flight == 0 {
+            self.idle.notify_all();
+        }
+    }
+}
+
+struct InFlightGuard<'a> {
+    state: &'a ShutdownState,
+}
+
+impl<'a> Drop for InFlightGuard<'a> {
+    fn drop(&mut self) {
+        self.state.end_request();
+    }
+}
+
+//===========================================================================//
+
+/// Wraps another handler, counting in-flight requests and rejecting new
+/// requests with a 503 once the server has begun shutting down.
+pub struct ShutdownGate<H> {
+    inner: H,
+    state: Arc<ShutdownState>,
+}
+
+impl<H: Handler> ShutdownGate<H> {
+    pub fn new(inner: H, state: Arc<ShutdownState>) -> ShutdownGate<H> {
+        ShutdownGate { inner, state }
+    }
+}
+
+impl<H: Handler> Handler for ShutdownGate<H> {
+    fn handle(&self, request: &mut Request) -> IronResult<Response> {
+        let _guard = match self.state.begin_request() {
+            Some(guard) => guard,
+            None => {
+                let msg = "Server is shutting down.\n";
+                let io_err = io::Error::new(io::ErrorKind::Other, msg);
+                return Err(IronError::new(
+                    io_err,
+                    (status::ServiceUnavailable, msg),
+                ));
+            }
+        };
+        self.inner.handle(request)
+    }
+}
+
+//===========================================================================//
+
+#[cfg(test)]
+mod tests {
+    use super::ShutdownState;
+    use std::time::Duration;
+
+    #[test]
+    fn shutdown_waits_for_in_flight_requests() {
+        let state = ShutdownState::new();
+        let guard = state.begin_request();
+        assert!(guard.is_some());
+        state.begin_shutdown();
+        assert!(state.begin_request().is_none());
+        assert!(!state.wait_for_idle(Duration::from_millis(10)));
+        drop(guard);
+        assert!(state.wait_for_idle(Duration::from_millis(10)));
+    }
+}
+
+//===========================================================================//
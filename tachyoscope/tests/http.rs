@@ -17,14 +17,17 @@
 // | with Tachyomancer.  If not, see <http://www.gnu.org/licenses/>.          |
 // +--------------------------------------------------------------------------+
 
-extern crate iron;
+extern crate flate2;
 extern crate portpicker;
 extern crate tachy;
 extern crate tachyoscope;
 extern crate ureq;
 
+use flate2::read::GzDecoder;
 use std::io::Read;
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::path::PathBuf;
+use std::time::Duration;
 use tachy::save::{ScoreCurveMap, SolutionData};
 
 //===========================================================================//
@@ -36,12 +39,40 @@ const READ_TIMEOUT_MS: u64 = 1000;
 //===========================================================================//
 
 #[test]
-fn readiness_check() {
+fn puzzles_with_bounds_override() {
     let port = portpicker::pick_unused_port().unwrap();
     let flags = tachyoscope::StartupFlags {
-        addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), port),
+        graph_bounds_path: Some(PathBuf::from("tests/bounds/override.toml")),
+        ..test_flags(port)
     };
-    let _server = Server(tachyoscope::run_server(&flags).unwrap());
+    let _server = Server::start(&flags);
+
+    let response = http_get(&format!("http://localhost:{}/puzzles", port));
+    assert_eq!(response.status(), 200);
+    let mut payload = String::new();
+    response.into_reader().read_to_string(&mut payload).unwrap();
+    assert!(payload.contains("TutorialOr = [8, 100]\n"));
+    assert!(payload.contains("TutorialMux = "));
+
+    // A solution outside the overridden bounds should be ignored:
+    let solution =
+        SolutionData::load("tests/solutions/tutorial_or_1.toml").unwrap();
+    let response = http_post(
+        &format!("http://localhost:{}/submit_solution", port),
+        "application/toml",
+        solution.serialize_to_string().unwrap().as_bytes(),
+    );
+    assert_eq!(response.status(), 200);
+    let mut payload = String::new();
+    response.into_reader().read_to_string(&mut payload).unwrap();
+    assert_eq!(payload, "Solution is not within graph bounds.");
+}
+
+#[test]
+fn readiness_check() {
+    let port = portpicker::pick_unused_port().unwrap();
+    let flags = test_flags(port);
+    let _server = Server::start(&flags);
     let response =
         http_get(&format!("http://localhost:{}/readiness_check", port));
     assert_eq!(response.status(), 200);
@@ -51,13 +82,188 @@ fn readiness_check() {
 }
 
 #[test]
-fn submit_solution() {
-    // Start a score server with a fresh database:
+fn cors_headers() {
     let port = portpicker::pick_unused_port().unwrap();
     let flags = tachyoscope::StartupFlags {
-        addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), port),
+        cors_origin: Some("https://example.com".to_string()),
+        ..test_flags(port)
+    };
+    let _server = Server::start(&flags);
+
+    let response = http_get(&format!("http://localhost:{}/scores", port));
+    assert_eq!(response.status(), 200);
+    assert_eq!(
+        response.header("Access-Control-Allow-Origin"),
+        Some("https://example.com")
+    );
+    assert_eq!(response.header("Vary"), Some("Accept-Encoding, Origin"));
+
+    let response =
+        ureq::request("OPTIONS", &format!("http://localhost:{}/scores", port))
+            .timeout_connect(CONNECT_TIMEOUT_MS)
+            .timeout_read(READ_TIMEOUT_MS)
+            .call();
+    assert_eq!(response.status(), 204);
+    assert_eq!(
+        response.header("Access-Control-Allow-Origin"),
+        Some("https://example.com")
+    );
+
+    let response = http_post(
+        &format!("http://localhost:{}/submit_solution", port),
+        "application/toml",
+        b"",
+    );
+    assert_eq!(response.header("Access-Control-Allow-Origin"), None);
+}
+
+#[test]
+fn cors_headers_off_by_default() {
+    let port = portpicker::pick_unused_port().unwrap();
+    let flags = test_flags(port);
+    let _server = Server::start(&flags);
+    let response = http_get(&format!("http://localhost:{}/scores", port));
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.header("Access-Control-Allow-Origin"), None);
+}
+
+#[test]
+fn frontier() {
+    let port = portpicker::pick_unused_port().unwrap();
+    let flags = test_flags(port);
+    let _server = Server::start(&flags);
+
+    let solution =
+        SolutionData::load("tests/solutions/tutorial_or_1.toml").unwrap();
+    let response = http_post(
+        &format!("http://localhost:{}/submit_solution", port),
+        "application/toml",
+        solution.serialize_to_string().unwrap().as_bytes(),
+    );
+    assert_eq!(response.status(), 200);
+
+    let response = http_get(&format!(
+        "http://localhost:{}/frontier?puzzle=TutorialOr",
+        port
+    ));
+    assert_eq!(response.status(), 200);
+    let mut payload = String::new();
+    response.into_reader().read_to_string(&mut payload).unwrap();
+    let scores = ScoreCurveMap::deserialize_from_string(&payload).unwrap();
+    assert_eq!(
+        scores.get(solution.puzzle).scores(),
+        &[(solution.circuit.size.area(), solution.score)]
+    );
+
+    let response =
+        http_get(&format!("http://localhost:{}/frontier?puzzle=Foobar", port));
+    assert_eq!(response.status(), 400);
+    let response = http_get(&format!("http://localhost:{}/frontier", port));
+    assert_eq!(response.status(), 400);
+}
+
+#[test]
+fn gzip_scores() {
+    let port = portpicker::pick_unused_port().unwrap();
+    let flags = test_flags(port);
+    let _server = Server::start(&flags);
+    let url = format!("http://localhost:{}/scores", port);
+
+    let response = http_get(&url);
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.header("Content-Encoding"), None);
+    let mut identity_payload = String::new();
+    response.into_reader().read_to_string(&mut identity_payload).unwrap();
+
+    let response = ureq::get(&url)
+        .timeout_connect(CONNECT_TIMEOUT_MS)
+        .timeout_write(WRITE_TIMEOUT_MS)
+        .timeout_read(READ_TIMEOUT_MS)
+        .set("Accept-Encoding", "gzip")
+        .call();
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.header("Content-Encoding"), Some("gzip"));
+    let mut gzip_payload = String::new();
+    GzDecoder::new(response.into_reader())
+        .read_to_string(&mut gzip_payload)
+        .unwrap();
+    assert_eq!(gzip_payload, identity_payload);
+}
+
+#[test]
+fn liveness_check() {
+    let port = portpicker::pick_unused_port().unwrap();
+    let flags = test_flags(port);
+    let _server = Server::start(&flags);
+    let response = http_get(&format!("http://localhost:{}/livez", port));
+    assert_eq!(response.status(), 200);
+    let mut payload = String::new();
+    response.into_reader().read_to_string(&mut payload).unwrap();
+    assert_eq!(payload, "alive\n");
+}
+
+#[test]
+fn latest_version() {
+    let port = portpicker::pick_unused_port().unwrap();
+    let flags = tachyoscope::StartupFlags {
+        latest_version: "1.2.3".to_string(),
+        ..test_flags(port)
     };
-    let _server = Server(tachyoscope::run_server(&flags).unwrap());
+    let _server = Server::start(&flags);
+    let response = http_get(&format!(
+        "http://localhost:{}/latest_version?current=0.1.0",
+        port
+    ));
+    assert_eq!(response.status(), 200);
+    let mut payload = String::new();
+    response.into_reader().read_to_string(&mut payload).unwrap();
+    assert_eq!(payload, "1.2.3\n");
+}
+
+#[test]
+fn requests_rejected_after_shutdown() {
+    let port = portpicker::pick_unused_port().unwrap();
+    let flags = test_flags(port);
+    let mut server = Server::start(&flags);
+    let url = format!("http://localhost:{}/livez", port);
+    assert_eq!(http_get(&url).status(), 200);
+    server.shut_down();
+    // Depending on how quickly the listener stops accepting connections,
+    // a new request is either refused outright or rejected by the server.
+    let response = http_get(&url);
+    if let Some(err) = response.synthetic_error() {
+        match err {
+            ureq::Error::ConnectionFailed(_) => {}
+            other => panic!("Unexpected error: {}", other),
+        }
+    } else {
+        assert_eq!(response.status(), 503);
+        let mut payload = String::new();
+        response.into_reader().read_to_string(&mut payload).unwrap();
+        assert_eq!(payload, "Server is shutting down.\n");
+    }
+}
+
+#[test]
+fn responses_have_request_ids() {
+    let port = portpicker::pick_unused_port().unwrap();
+    let flags = test_flags(port);
+    let _server = Server::start(&flags);
+    let url = format!("http://localhost:{}/readiness_check", port);
+    let response1 = http_get(&url);
+    let id1 = response1.header(tachyoscope::REQUEST_ID_HEADER).unwrap();
+    assert!(!id1.is_empty());
+    let response2 = http_get(&url);
+    let id2 = response2.header(tachyoscope::REQUEST_ID_HEADER).unwrap();
+    assert_ne!(id1, id2);
+}
+
+#[test]
+fn submit_solution() {
+    // Start a score server with a fresh database:
+    let port = portpicker::pick_unused_port().unwrap();
+    let flags = test_flags(port);
+    let _server = Server::start(&flags);
 
     // Load a solution:
     let solution =
@@ -91,13 +297,35 @@ fn submit_solution() {
     );
 }
 
+#[test]
+fn submit_solution_twice() {
+    let port = portpicker::pick_unused_port().unwrap();
+    let flags = test_flags(port);
+    let _server = Server::start(&flags);
+    let url = format!("http://localhost:{}/submit_solution", port);
+
+    // Submitting a valid solution twice should count it only once:
+    let solution =
+        SolutionData::load("tests/solutions/tutorial_or_1.toml").unwrap();
+    let payload = solution.serialize_to_string().unwrap();
+    for _ in 0..2 {
+        let response = http_post(&url, "application/toml", payload.as_bytes());
+        assert_eq!(response.status(), 200);
+        let mut body = String::new();
+        response.into_reader().read_to_string(&mut body).unwrap();
+        assert_eq!(body, "ok\n");
+    }
+    let response = http_get(&format!("http://localhost:{}/", port));
+    let mut body = String::new();
+    response.into_reader().read_to_string(&mut body).unwrap();
+    assert_eq!(body, "There are 1 verified solutions.\n");
+}
+
 #[test]
 fn submit_invalid_utf8() {
     let port = portpicker::pick_unused_port().unwrap();
-    let flags = tachyoscope::StartupFlags {
-        addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), port),
-    };
-    let _server = Server(tachyoscope::run_server(&flags).unwrap());
+    let flags = test_flags(port);
+    let _server = Server::start(&flags);
     let response = http_post(
         &format!("http://localhost:{}/submit_solution", port),
         "application/toml",
@@ -109,13 +337,33 @@ fn submit_invalid_utf8() {
     assert_eq!(payload, "stream did not contain valid UTF-8\n");
 }
 
+#[test]
+fn submit_oversized_body() {
+    let port = portpicker::pick_unused_port().unwrap();
+    let flags =
+        tachyoscope::StartupFlags { max_body_bytes: 100, ..test_flags(port) };
+    let _server = Server::start(&flags);
+    let url = format!("http://localhost:{}/submit_solution", port);
+
+    let response = http_post(&url, "application/toml", &[b' '; 101]);
+    assert_eq!(response.status(), 413);
+    let mut payload = String::new();
+    response.into_reader().read_to_string(&mut payload).unwrap();
+    assert_eq!(payload, "Request body exceeds limit of 100 bytes.\n");
+
+    // A body right at the limit should still get parsed:
+    let response = http_post(&url, "application/toml", &[b' '; 100]);
+    assert_eq!(response.status(), 400);
+    let mut payload = String::new();
+    response.into_reader().read_to_string(&mut payload).unwrap();
+    assert!(payload.starts_with("Could not deserialize solution"));
+}
+
 #[test]
 fn submit_invalid_solution_toml() {
     let port = portpicker::pick_unused_port().unwrap();
-    let flags = tachyoscope::StartupFlags {
-        addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), port),
-    };
-    let _server = Server(tachyoscope::run_server(&flags).unwrap());
+    let flags = test_flags(port);
+    let _server = Server::start(&flags);
     let response = http_post(
         &format!("http://localhost:{}/submit_solution", port),
         "application/toml",
@@ -129,16 +377,43 @@ fn submit_invalid_solution_toml() {
 
 //===========================================================================//
 
-struct Server(iron::Listening);
+/// A running test server that is shut down when dropped (unless the test has
+/// already shut it down explicitly).
+struct Server(Option<tachyoscope::RunningServer>);
+
+impl Server {
+    fn start(flags: &tachyoscope::StartupFlags) -> Server {
+        Server(Some(tachyoscope::run_server(flags).unwrap()))
+    }
+
+    fn shut_down(&mut self) {
+        if let Some(mut server) = self.0.take() {
+            server.shut_down(Duration::from_secs(1)).unwrap();
+        }
+    }
+}
 
 impl Drop for Server {
     fn drop(&mut self) {
-        self.0.close().unwrap();
+        self.shut_down();
     }
 }
 
 //===========================================================================//
 
+/// Returns the server flags used by tests that don't care about a given
+/// setting; tests override just the fields they need.
+fn test_flags(port: u16) -> tachyoscope::StartupFlags {
+    tachyoscope::StartupFlags {
+        addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), port),
+        cors_origin: None,
+        retention: tachyoscope::RetentionMode::FullHistory,
+        graph_bounds_path: None,
+        max_body_bytes: tachyoscope::DEFAULT_MAX_BODY_BYTES,
+        latest_version: "0.1.0".to_string(),
+    }
+}
+
 fn http_get(url: &str) -> ureq::Response {
     ureq::get(url)
         .timeout_connect(CONNECT_TIMEOUT_MS)